@@ -1,13 +1,46 @@
 use opener;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::error;
 
+use crate::filter::{danger_pattern, read_exclude_file, MatchMode, NameFilter};
 use crate::fps_counter::FPSCounter;
-use crate::fs::{delete_file, delete_folder, DataStore, DataStoreKey, FolderEntryType, SortBy};
+#[cfg(feature = "archives")]
+use crate::fs::{archive_to_folders, is_archive};
+use crate::fs::{
+    deep_folders, delete_empty_folder, delete_file, delete_folder, disk_space, duplicate_names,
+    empty_folders, filesystems_usage, for_each_folder, freed_by_filesystem, is_git_repository,
+    normalize_path, owned_sizes, paths_to_folder, purge_trashed, recent_files, resolve_root,
+    restore_trashed, root_available, root_device, snapshot_path, with_own_sizes, ChangeCheck,
+    DataStore, DataStoreKey, DeletionStep, DiskChange, FilesystemUsage, Folder, FolderEntry,
+    FolderEntryType, Preview, RootCheck, ScanError, ScanOptions, ScanRoot, SizeChange, Snapshot,
+    SortBy, TrashedEntry, Verification,
+};
+use crate::notify::{ring_bell, scan_finished_message, send_notification};
+use crate::prompt::{load_history, save_history, PathPrompt};
+use crate::report::{Outcome, SelectionReport, Summary};
 use crate::spinner::Spinner;
-use crate::task_manager::TaskManager;
+use crate::task_manager::{TaskManager, LOW_PRIORITY_SUPPORTED};
+use crate::views::{
+    DeepView, DiffView, ErrorsView, FilesystemsView, NamesView, ProfileMenu, RecentView, SortMenu,
+    TrashView, VerifyView, View, SORT_MENU_KEYS,
+};
+use std::ffi::{OsStr, OsString};
 use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use crate::config::{InitConfig, UIConfig};
+use crate::config::{
+    with_builtin_profiles, ColorDepth, Column, EnterAction, IdleAction, InitConfig, Lens,
+    PathStyle, Profile, PromptKey, SizeForm, SpaceScale, UIConfig, DIFF_THRESHOLD, EVENT_INTERVAL,
+    HISTORY_DEPTH, LOG_HOLD, LOG_WIDTH, MAX_COUNT, MIN_DEPTH, NOTIFY_AFTER_SECS, PREVIEW_SIZE,
+    RECENT_ROOTS, RECENT_WINDOW, ROOT_CHECK_INTERVAL, SIZE_PRESETS, SPARSE_RATIO, STALE_SCAN_AGE,
+};
+use crate::ui::constants::{
+    TABLE_SPACE_WIDTH, TEXT_HIGHLIGHT_SYMBOL, TEXT_NONE, TEXT_VIRTUAL_ROOT,
+};
+use crate::ui::treemap::treemap_entry_at;
+use crate::ui::{format_file_size, format_size, join_paths, sort_column_at};
+use ratatui::layout::Rect;
 use std::env;
 
 use crate::logger::{Logger, MessageLevel};
@@ -19,6 +52,25 @@ enum DiffKind {
     Subtract,
 }
 
+/// Work handed to the terminal between two frames
+#[derive(Debug, Clone, PartialEq)]
+pub enum Launch {
+    /// Program with its arguments, run in the terminal while the UI is suspended
+    Command(Vec<String>),
+    /// Text put on clipboard through the terminal
+    Clipboard(String),
+}
+
+/// Navigation away from current folder, held back while discarding its marks is confirmed
+#[derive(Debug, Clone, PartialEq)]
+pub enum Leave {
+    Parent,
+    Child(OsString),
+    Root,
+    /// Entering biggest folders down to a file
+    LargestAll,
+}
+
 /// Application.
 #[derive(Debug)]
 pub struct App<S: DataStore<DataStoreKey>> {
@@ -36,22 +88,126 @@ pub struct App<S: DataStore<DataStoreKey>> {
     pub fps_counter: FPSCounter,
     /// Spinner
     pub spinner: Spinner,
+    /// Path the scan was started at, empty for the virtual root
+    pub root_path: PathBuf,
+    /// Currently shown view
+    pub view: View,
+    /// When current folder was last rescanned by auto-refresh
+    pub last_refresh: Instant,
+    /// Entries awaiting deletion confirmation, relative to current folder
+    pub pending_deletion: Vec<PathBuf>,
+    /// Empty folders awaiting deletion confirmation, set instead of marks by the empty folder sweep
+    pub pending_sweep: Vec<PathBuf>,
+    /// Extra caution notes shown in deletion confirmation prompt
+    pub deletion_warnings: Vec<String>,
+    /// Space pending deletion frees on every filesystem it spans
+    pub deletion_freed: Vec<FilesystemUsage>,
+    /// When deletion started waiting for confirmation, drives the confirmation flash
+    pub confirmation_started: Option<Instant>,
+    /// Last key press, pending confirmation times out counting from it
+    pub last_input: Instant,
+    /// Digits typed before a motion, repeat it that many times
+    pub pending_count: Option<usize>,
+    /// Folder path, scanned folder count and size that owned sizes were computed for
+    owned_sizes_key: Option<(PathBuf, usize, u64)>,
+    /// Current folder and number of scan errors error titles were computed for
+    error_names_key: Option<(PathBuf, usize)>,
+    /// Current folder, nodes, total size and own size mode second size column was computed for
+    other_sizes_key: Option<(PathBuf, usize, u64, bool)>,
+    /// Folder path and capacity of the filesystem holding it, looked up once per folder
+    disk_total: Option<(PathBuf, Option<u64>)>,
+    /// Paths that could not be read, as of their folder's latest scan
+    pub scan_errors: Vec<ScanError>,
+    /// Where sizes of finished scan are kept for the next run, None when scans are not cached
+    pub snapshot_path: Option<PathBuf>,
+    /// Sizes kept by previous scan of the same root
+    pub previous_snapshot: Option<Snapshot>,
+    /// Changes since previous scan, computed once a scan finishes
+    pub scan_changes: Option<Vec<SizeChange>>,
+    /// Sizes folders had at cached scans of current root, by path relative to it
+    pub size_history: BTreeMap<String, Vec<(u64, u64)>>,
+    /// Keep sizes of finished scans, also of roots opened later
+    cache_scan: bool,
+    /// Cached scans folder sizes are kept from
+    history_depth: usize,
+    /// Limits applied to every scan, also of roots opened later
+    scan_options: ScanOptions,
+    /// File exclude patterns are read from, again on every rescan
+    exclude_from: Option<PathBuf>,
+    /// Rescans skip folders whose modification time is the one they were read with
+    incremental_rescan: bool,
+    /// Input asking for a new root, keys go to it while it is open
+    pub path_prompt: Option<PathPrompt>,
+    /// Sort menu over the table, keys go to it while it is open
+    pub sort_menu: Option<SortMenu>,
+    /// Profile menu over the table, keys go to it while it is open
+    pub profile_menu: Option<ProfileMenu>,
+    /// Built-in and config file profiles
+    pub profiles: Vec<Profile>,
+    /// Table options wiper started with, profiles apply on top of them
+    pub lens: Lens,
+    pub active_profile: Option<String>,
+    /// Roots scanned before current one, most recent first
+    pub recent_roots: Vec<PathBuf>,
+    /// Where recent roots are kept between runs, together with current one
+    history_file: Option<PathBuf>,
+    /// Idle timeout passed, nothing is redrawn until next key press
+    pub idle: bool,
+    /// Current folder as it was when entered, with its path
+    pub entry_snapshot: Option<(PathBuf, Folder)>,
+    /// When last scan or rescan finished, None while the first one runs
+    pub scan_finished_at: Option<Instant>,
+    /// File given as scan root, listed alone under the virtual root
+    pub file_root: Option<PathBuf>,
+    /// Scan root could not be read at its last check, deleting waits until it is back
+    pub root_missing: bool,
+    pub last_root_check: Instant,
+    /// Check of scan root started on tick, still running
+    pub root_check: Option<RootCheck>,
+    /// Device each scan root was on when its scan started, by path
+    root_devices: HashMap<PathBuf, u64>,
+    /// Bytes and entries deleted since the app started
+    pub session_freed: u64,
+    pub session_deleted: usize,
+    /// Waiting for the terminal to run it, taken before next frame
+    pub launch: Option<Launch>,
+    /// Contents of selected entry with its path, kept while the preview pane is shown
+    pub preview: Option<(PathBuf, Preview)>,
+    /// Where folder table was last drawn, for telling which header was clicked
+    pub table_area: Option<Rect>,
+    /// Treemap cells as last drawn with index of the entry each one shows, for clicks
+    pub treemap_cells: Vec<(usize, Rect)>,
+    /// Entries that could not be deleted since the app started
+    pub session_failed_deletions: usize,
+    /// Entries moved to trash since the app started, until they are purged
+    pub session_trash: Vec<TrashedEntry>,
+    /// Deletions that can be undone, most recent last
+    pub undo_history: Vec<DeletionStep>,
+    /// Undone deletions that can be redone, most recently undone last
+    pub redo_history: Vec<DeletionStep>,
+    /// Text to type before pending deletion goes ahead, set when a target matches a danger pattern
+    pub confirm_phrase: Option<String>,
+    /// Typed so far towards the confirm phrase
+    pub confirm_input: String,
+    /// Navigation waiting for confirmation, with the number of marks it would discard
+    pub pending_leave: Option<(Leave, usize)>,
+    /// Set once navigation is confirmed, lets it past the guard one time
+    leave_confirmed: bool,
+    /// Verification of a folder against disk still running
+    pub verification: Option<Verification>,
+    /// Comparison of deletion targets with disk still running, its warnings join the prompt
+    pub change_check: Option<ChangeCheck>,
 }
 
 impl<S: DataStore<DataStoreKey>> App<S> {
     /// Constructs a new instance of [`App`].
     pub fn new(config: InitConfig) -> Self {
-        let current_path = match config.file_path {
-            Some(path) => {
-                let path_buf = PathBuf::from(&path);
-                if path_buf.is_absolute() {
-                    path_buf
-                } else {
-                    let current_dir = env::current_dir().unwrap();
-                    current_dir.join(&path_buf)
-                }
-            }
-            None => env::current_dir().unwrap(),
+        let root = match &config.file_path {
+            Some(path) => resolve_root(Path::new(path)),
+            None => ScanRoot::Folder(env::current_dir().unwrap()),
+        };
+        let current_path = match &root {
+            ScanRoot::Folder(path) | ScanRoot::File(path) => path.clone(),
         };
 
         let mut app = App {
@@ -59,24 +215,396 @@ impl<S: DataStore<DataStoreKey>> App<S> {
             ui_config: UIConfig {
                 colored: false,
                 confirming_deletion: false,
-                sort_by: SortBy::Title,
-                move_to_trash: true,
-                open_file: true,
+                sort_by: config.sort_by.unwrap_or_default(),
+                sort_reversed: false,
+                folders_first: config.folders_first,
+                move_to_trash: !config.no_trash,
+                read_only: config.read_only,
+                enter_action: config.enter_action.unwrap_or_default(),
+                copy_size: config.copy_size.unwrap_or_default(),
+                color_depth: config.color_depth.unwrap_or_else(ColorDepth::from_env),
+                preview: false,
+                preview_size: config.preview_size.unwrap_or(PREVIEW_SIZE),
+                growth: false,
+                overview: config.overview,
+                breadcrumb: config.breadcrumb,
+                danger_patterns: config.danger_patterns.clone(),
+                align_sizes: config.align_sizes,
+                only_extensions: config.scan_options.extensions.clone(),
+                size_unit: config.size_unit.unwrap_or_default(),
                 debug_enabled: false,
+                auto_refresh_secs: config.auto_refresh_secs,
+                highlight_symbol: TEXT_HIGHLIGHT_SYMBOL.to_string(),
+                sparse_ratio: match config.no_sparse_marker {
+                    true => None,
+                    false => Some(config.sparse_ratio.unwrap_or(SPARSE_RATIO)),
+                },
+                own_size: false,
+                both_sizes: config.both_sizes || config.lists_column(Column::OtherSize),
+                other_sizes: HashMap::new(),
+                treemap: false,
+                split_view: false,
+                show_owner: cfg!(unix) && config.lists_column(Column::Owner),
+                show_permissions: cfg!(unix) && config.lists_column(Column::Mode),
+                show_age: config.lists_column(Column::Age),
+                bell_on_finish: config.bell,
+                notify_on_finish: config.notify,
+                notify_after_secs: config.notify_after_secs.unwrap_or(NOTIFY_AFTER_SECS),
+                confirm_flash: config.confirm_flash.unwrap_or_default(),
+                confirm_bell: config.confirm_bell,
+                confirm_quit: config.confirm_quit,
+                confirm_leave: !config.no_confirm_leave,
+                confirming_quit: false,
+                confirm_timeout_secs: config.confirm_timeout_secs,
+                confirm_key: config.confirm_key.unwrap_or(PromptKey::Char('d')),
+                cancel_key: config.cancel_key.unwrap_or(PromptKey::Esc),
+                idle_timeout_secs: config.idle_timeout_secs,
+                idle_action: config.idle_action.unwrap_or_default(),
+                flashing: false,
+                no_animations: config.no_animations,
+                wrap_navigation: config.wrap_navigation,
+                name_filter: NameFilter {
+                    case: config.filter_case,
+                    mode: match config.filter_glob {
+                        true => MatchMode::Glob,
+                        false => MatchMode::Substring,
+                    },
+                    query: config.name_filter.clone().unwrap_or_default(),
+                },
+                filter_editing: false,
+                recent_window: config.recent_window.unwrap_or(RECENT_WINDOW),
+                min_depth: config.min_depth.unwrap_or(MIN_DEPTH),
+                owner_filter: config.owner,
+                owned_sizes: HashMap::new(),
+                errors_only: config.errors_only,
+                error_names: HashSet::new(),
+                log_width: config.log_width.unwrap_or(LOG_WIDTH),
+                diff_threshold: config.diff_threshold.unwrap_or(DIFF_THRESHOLD),
+                space_scale: config.space_scale.unwrap_or_default(),
+                space_total: None,
+                show_disk_percent: config.disk_percent || config.lists_column(Column::DiskShare),
+                disk_capacity: None,
+                scan_age: None,
+                min_size: config.min_size,
+                size_presets: config.size_presets.clone().unwrap_or(SIZE_PRESETS.to_vec()),
+                space_width: config.space_width.unwrap_or(TABLE_SPACE_WIDTH),
+                columns: config.table_columns(),
+                bar_style: config.bar_style.unwrap_or_default(),
             },
             task_manager: TaskManager::<S>::default(),
             store: S::new(),
-            logger: Logger::default(),
+            logger: Logger {
+                hold: config.log_hold.unwrap_or(LOG_HOLD),
+                pin_errors: config.pin_errors,
+                ..Logger::default()
+            },
             fps_counter: FPSCounter::default(),
             spinner: Spinner::default(),
+            root_path: current_path.clone(),
+            view: View::default(),
+            last_refresh: Instant::now(),
+            pending_deletion: vec![],
+            pending_sweep: vec![],
+            deletion_warnings: vec![],
+            deletion_freed: vec![],
+            confirmation_started: None,
+            last_input: Instant::now(),
+            pending_count: None,
+            owned_sizes_key: None,
+            error_names_key: None,
+            other_sizes_key: None,
+            disk_total: None,
+            scan_errors: vec![],
+            snapshot_path: None,
+            previous_snapshot: None,
+            scan_changes: None,
+            size_history: BTreeMap::new(),
+            cache_scan: config.cache_scan,
+            history_depth: config.history_depth.unwrap_or(HISTORY_DEPTH),
+            scan_options: config.scan_options.clone(),
+            exclude_from: config.exclude_from.clone(),
+            incremental_rescan: config.incremental_rescan,
+            path_prompt: None,
+            sort_menu: None,
+            profile_menu: None,
+            profiles: with_builtin_profiles(&config.profiles),
+            lens: Lens::default(),
+            active_profile: None,
+            recent_roots: config
+                .history_file
+                .as_deref()
+                .map(load_history)
+                .unwrap_or_default(),
+            history_file: config.history_file.clone(),
+            scan_finished_at: None,
+            idle: false,
+            entry_snapshot: None,
+            file_root: None,
+            root_missing: false,
+            last_root_check: Instant::now(),
+            root_check: None,
+            root_devices: HashMap::new(),
+            session_freed: 0,
+            session_deleted: 0,
+            launch: None,
+            preview: None,
+            table_area: None,
+            treemap_cells: vec![],
+            session_failed_deletions: 0,
+            session_trash: vec![],
+            undo_history: vec![],
+            redo_history: vec![],
+            confirm_phrase: None,
+            confirm_input: String::new(),
+            pending_leave: None,
+            leave_confirmed: false,
+            verification: None,
+            change_check: None,
         };
 
         app.store.set_current_path(&current_path);
+        app.load_exclude_file();
+        if config.low_priority {
+            app.task_manager.set_low_priority(true);
+            if !LOW_PRIORITY_SUPPORTED {
+                app.logger.log(
+                    "Low priority scanning is not supported on this platform".to_string(),
+                    MessageLevel::Info,
+                );
+            }
+        }
+        if let Some(symbol) = &config.highlight_symbol {
+            app.ui_config.set_highlight_symbol(symbol);
+        }
+        app.lens = Lens::of(&app.ui_config);
+        if let Some(name) = &config.profile {
+            app.apply_profile(Some(name));
+        }
 
+        if !config.root_paths.is_empty() {
+            let roots: Vec<PathBuf> = config.root_paths.iter().map(normalize_path).collect();
+            let (folder, errors) = paths_to_folder(TEXT_VIRTUAL_ROOT, &roots);
+            for err in &errors {
+                app.logger.log_keyed(
+                    format!("{}: {}", err.path.to_string_lossy(), err.kind),
+                    err.kind.to_string(),
+                    MessageLevel::Error,
+                );
+            }
+            app.scan_errors = errors;
+            app.store.set_virtual_root(folder, roots);
+            app.root_path = app.store.get_current_path().clone();
+        } else if let ScanRoot::File(path) = root {
+            app.show_file_root(path);
+        }
+
+        app.load_snapshot();
+        app.save_recent_roots();
         app
     }
 
+    /// What is scanned and how, one line per parameter, so a session explains itself later
+    pub fn scan_parameters(&self) -> Vec<String> {
+        let roots = match self.root_path.as_os_str().is_empty() {
+            false => self.root_path.to_string_lossy().to_string(),
+            true => self
+                .store
+                .get_folder(&self.root_path)
+                .map(|folder| {
+                    let titles: Vec<&str> = folder
+                        .entries()
+                        .iter()
+                        .filter(|entry| entry.kind != FolderEntryType::Parent)
+                        .map(|entry| entry.title.as_str())
+                        .collect();
+                    titles.join(", ")
+                })
+                .unwrap_or_default(),
+        };
+
+        let options = &self.scan_options;
+        let mut files = vec![];
+        if let Some(min) = options.min_file_size {
+            files.push(format!("from {}", format_file_size(min)));
+        }
+        if let Some(max) = options.max_file_size {
+            files.push(format!("up to {}", format_file_size(max)));
+        }
+        if !options.extensions.is_empty() {
+            files.push(format!("only {}", options.extensions.join(",")));
+        }
+        let files = match files.is_empty() {
+            true => "all".to_string(),
+            false => files.join(", "),
+        };
+
+        let mut excludes = options.exclude.clone();
+        if let Some(path) = &self.exclude_from {
+            excludes.push(format!(
+                "{} from {}",
+                options.exclude_from_file.len(),
+                path.to_string_lossy()
+            ));
+        }
+        let excludes = match excludes.is_empty() {
+            true => TEXT_NONE.to_string(),
+            false => excludes.join(", "),
+        };
+
+        let priority = match *self.task_manager.low_priority.lock().unwrap() {
+            true => "low",
+            false => "normal",
+        };
+        vec![
+            format!("Root: {roots}"),
+            format!("Files: {files}"),
+            format!("Exclude: {excludes}"),
+            format!("Priority: {priority}"),
+        ]
+    }
+
+    /// Writes current root followed by previous ones to history file, if there is one
+    fn save_recent_roots(&mut self) {
+        let root_path = self.root_path.clone();
+        self.recent_roots.retain(|root| *root != root_path);
+        let Some(history_file) = &self.history_file else {
+            return;
+        };
+        let mut roots: Vec<PathBuf> = match root_path.as_os_str().is_empty() {
+            true => vec![],
+            false => vec![root_path],
+        };
+        roots.extend(self.recent_roots.iter().cloned());
+        roots.truncate(RECENT_ROOTS);
+        if let Err(err) = save_history(history_file, &roots) {
+            self.logger.log(
+                format!("{}: {}", history_file.to_string_lossy(), err),
+                MessageLevel::Error,
+            );
+        }
+    }
+
+    /// Lists file alone under the virtual root, with its parent folder one level up
+    fn show_file_root(&mut self, path: PathBuf) {
+        let (folder, errors) =
+            paths_to_folder(&path.to_string_lossy(), std::slice::from_ref(&path));
+        self.scan_errors = errors;
+        self.store.set_virtual_root(folder, vec![path.clone()]);
+        self.root_path = PathBuf::new();
+        self.file_root = Some(path);
+    }
+
+    /// Loads sizes kept by previous scan of current root when scans are cached
+    fn load_snapshot(&mut self) {
+        self.scan_changes = None;
+        self.size_history = BTreeMap::new();
+        // Virtual root is put together from arguments, there is nothing to compare it with
+        if !self.cache_scan || self.root_path.as_os_str().is_empty() {
+            self.snapshot_path = None;
+            self.previous_snapshot = None;
+            return;
+        }
+        self.snapshot_path = snapshot_path(&self.root_path);
+        self.previous_snapshot = self
+            .snapshot_path
+            .as_ref()
+            .and_then(|path| Snapshot::load(path).ok());
+        if let Some(previous) = &self.previous_snapshot {
+            self.size_history = previous.history.clone();
+        }
+    }
+
+    /// Asks for a folder to scan instead of current root
+    pub fn on_start_open_root(&mut self) {
+        let current_path = self.store.get_current_path().clone();
+        self.path_prompt = Some(PathPrompt::with_history(&current_path, &self.recent_roots));
+        self.ui_config.confirming_deletion = false;
+    }
+
+    pub fn on_cancel_open_root(&mut self) {
+        self.path_prompt = None;
+    }
+
+    /// Scans prompt path as new root, nothing is kept from the current one
+    pub fn on_submit_open_root(&mut self) {
+        let Some(prompt) = &mut self.path_prompt else {
+            return;
+        };
+        let Some(path) = prompt.submit() else {
+            // Picked recent root that no longer exists was dropped from prompt history
+            if prompt.history.len() < self.recent_roots.len() {
+                let history = prompt.history.clone();
+                self.recent_roots
+                    .retain(|root| history.contains(&root.to_string_lossy().to_string()));
+                self.save_recent_roots();
+            }
+            return;
+        };
+        self.path_prompt = None;
+        self.open_root(path);
+    }
+
+    pub fn open_root(&mut self, path: PathBuf) {
+        self.task_manager.discard_tasks();
+        let root = resolve_root(&path);
+        let path = match &root {
+            ScanRoot::Folder(path) | ScanRoot::File(path) => path.clone(),
+        };
+        let previous_root = std::mem::replace(&mut self.root_path, path.clone());
+        self.recent_roots
+            .retain(|root| *root != path && *root != previous_root);
+        if !previous_root.as_os_str().is_empty() && previous_root != path {
+            self.recent_roots.insert(0, previous_root);
+        }
+        self.recent_roots.truncate(RECENT_ROOTS);
+
+        self.store = S::new();
+        self.store.set_current_path(&path);
+        self.store.set_scan_options(self.scan_options.clone());
+        self.view = View::Folder;
+        self.ui_config.confirming_deletion = false;
+        self.pending_deletion.clear();
+        self.pending_sweep.clear();
+        self.pending_count = None;
+        self.scan_errors.clear();
+        self.owned_sizes_key = None;
+        self.disk_total = None;
+        self.scan_finished_at = None;
+        self.entry_snapshot = None;
+        self.file_root = None;
+        self.root_missing = false;
+        self.last_root_check = Instant::now();
+        self.root_check = None;
+        self.root_devices.clear();
+        if let ScanRoot::File(file) = root {
+            self.show_file_root(file);
+        }
+        self.save_recent_roots();
+        self.clear_filter();
+        self.load_snapshot();
+        self.logger.log(
+            format!("Opened {}", path.to_string_lossy()),
+            MessageLevel::Info,
+        );
+        self.init();
+    }
+
     pub fn init(&mut self) {
+        for (path, _) in self.root_targets().unwrap_or_default() {
+            if let Some(device) = root_device(&path) {
+                self.root_devices.insert(path, device);
+            }
+        }
+        if let Some(folder) = self.store.get_current_folder().cloned() {
+            // Virtual root is already stored, only its folders need to be scanned
+            for entry in folder.entries() {
+                if entry.kind == FolderEntryType::Folder {
+                    self.task_manager
+                        .maybe_add_task(&self.store, &PathBuf::from(&entry.name));
+                }
+            }
+            return;
+        }
         let path_buf = self.store.get_current_path().clone();
         self.logger
             .log(path_buf.to_string_lossy().to_string(), MessageLevel::Info);
@@ -85,186 +613,2162 @@ impl<S: DataStore<DataStoreKey>> App<S> {
 
     /// Handles the tick event of the terminal.
     pub fn tick(&mut self) {
-        self.task_manager.handle_results(&mut self.store);
+        let was_finished = self.task_manager.time_taken().is_some();
+        for (path, errors) in self.task_manager.handle_results(&mut self.store) {
+            // Rescanned folder replaces errors of its previous scan
+            self.scan_errors
+                .retain(|err| err.path != path && err.path.parent() != Some(path.as_path()));
+            for err in &errors {
+                self.logger.log_keyed(
+                    format!("{}: {}", err.path.to_string_lossy(), err.kind),
+                    err.kind.to_string(),
+                    MessageLevel::Error,
+                );
+            }
+            self.scan_errors.extend(errors);
+        }
+        if !was_finished {
+            if let Some(time_taken) = self.task_manager.time_taken() {
+                self.on_scan_finished(time_taken);
+            }
+        }
+        self.maybe_finish_verification();
+        self.maybe_finish_change_check();
+        if self.idle {
+            return;
+        }
+        self.maybe_finish_root_check();
+        if self.root_check.is_none() && self.last_root_check.elapsed() >= ROOT_CHECK_INTERVAL {
+            self.last_root_check = Instant::now();
+            self.root_check = self.root_targets().map(RootCheck::start);
+        }
+        self.maybe_auto_refresh();
+        self.maybe_abandon_deletion();
+        self.maybe_go_idle();
     }
 
-    /// Set running to false to quit the application.
-    pub fn quit(&mut self) {
-        self.running = false;
+    /// Time left before deletion waiting for confirmation is cancelled, None without timeout
+    pub fn confirmation_time_left(&self) -> Option<Duration> {
+        if !self.ui_config.confirming_deletion {
+            return None;
+        }
+        let timeout = Duration::from_secs(self.ui_config.confirm_timeout_secs?);
+        Some(timeout.saturating_sub(self.last_input.elapsed()))
     }
 
-    pub fn on_escape(&mut self) {
+    /// Read errors of latest scans and deletion failures of the whole session
+    pub fn outcome(&self) -> Outcome {
+        Outcome {
+            read_errors: !self.scan_errors.is_empty(),
+            deletion_failed: self.session_failed_deletions > 0,
+            deletion_refused: false,
+        }
+    }
+
+    /// Restarts confirmation timeout, called on every key press
+    pub fn on_input(&mut self) {
+        self.last_input = Instant::now();
+    }
+
+    fn maybe_abandon_deletion(&mut self) {
+        if self.confirmation_time_left() != Some(Duration::ZERO) {
+            return;
+        }
         self.ui_config.confirming_deletion = false;
+        self.logger.log(
+            format!(
+                "Deletion of {} abandoned after {}s without input",
+                join_paths(&self.pending_deletion),
+                self.ui_config.confirm_timeout_secs.unwrap_or(0)
+            ),
+            MessageLevel::Info,
+        );
     }
 
-    pub fn on_toggle_coloring(&mut self) {
-        self.ui_config.colored = !self.ui_config.colored;
+    /// Quits or stops redrawing once idle timeout passes, pending deletion is cancelled first
+    fn maybe_go_idle(&mut self) {
+        let Some(secs) = self.ui_config.idle_timeout_secs else {
+            return;
+        };
+        if self.last_input.elapsed() < Duration::from_secs(secs) {
+            return;
+        }
+        if self.ui_config.confirming_deletion {
+            self.ui_config.confirming_deletion = false;
+            self.pending_sweep.clear();
+            self.logger.log(
+                format!(
+                    "Deletion of {} cancelled, idle for {}s",
+                    join_paths(&self.pending_deletion),
+                    secs
+                ),
+                MessageLevel::Info,
+            );
+        }
+        match self.ui_config.idle_action {
+            IdleAction::Quit => self.quit(),
+            IdleAction::Sleep => self.idle = true,
+        }
     }
 
-    pub fn on_toggle_sorting(&mut self) {
-        match self.ui_config.sort_by {
-            SortBy::Title => {
-                self.ui_config.sort_by = SortBy::Size;
+    /// Leaves idle state, key that woke the app is not handled otherwise
+    pub fn on_wake(&mut self) {
+        self.idle = false;
+    }
+
+    /// Lets user know a long scan is done, quick ones finish silently
+    fn on_scan_finished(&mut self, time_taken: u128) {
+        self.scan_finished_at = Some(Instant::now());
+        self.update_snapshot();
+        if time_taken < self.ui_config.notify_after_secs as u128 * 1000 {
+            return;
+        }
+        if self.ui_config.bell_on_finish {
+            ring_bell();
+        }
+        if self.ui_config.notify_on_finish {
+            let summary = Summary::build(&self.store, &self.root_path, PathStyle::Absolute);
+            if let Err(err) = send_notification("wiper", &scan_finished_message(&summary)) {
+                self.logger.log(err, MessageLevel::Error);
             }
-            SortBy::Size => {
-                self.ui_config.sort_by = SortBy::Title;
+        }
+    }
+
+    /// Compares finished scan with previous one and keeps it for the next run
+    pub fn update_snapshot(&mut self) {
+        if self.snapshot_path.is_none() && self.previous_snapshot.is_none() {
+            return;
+        }
+        let mut snapshot = Snapshot::build(&self.store, &self.root_path);
+        if let Some(previous) = &self.previous_snapshot {
+            self.scan_changes = Some(snapshot.changes_since(previous));
+            snapshot.keep_history(previous, self.history_depth);
+        }
+        self.size_history = snapshot.history.clone();
+        if let Some(path) = &self.snapshot_path {
+            if let Err(err) = snapshot.save(path) {
+                self.logger.log(
+                    format!(
+                        "Could not cache scan in {}: {}",
+                        path.to_string_lossy(),
+                        err
+                    ),
+                    MessageLevel::Error,
+                );
             }
         }
     }
 
-    fn sort_current_folder(&mut self) {
-        self.store
-            .sort_current_folder(self.ui_config.sort_by.clone());
+    fn maybe_auto_refresh(&mut self) {
+        if let Some(secs) = self.ui_config.auto_refresh_secs {
+            if self.last_refresh.elapsed() >= Duration::from_secs(secs)
+                && self.task_manager.is_done()
+            {
+                self.refresh_current_folder_only();
+            }
+        }
     }
 
-    // MIGRATE: DONE
-    pub fn on_toggle_move_to_trash(&mut self) {
-        self.ui_config.move_to_trash = !self.ui_config.move_to_trash;
+    /// Whether scan root can still be read. Going away is logged once and cancels pending
+    /// deletion, coming back is logged too.
+    pub fn check_root(&mut self) -> bool {
+        self.last_root_check = Instant::now();
+        // Answer of a background check started before would be older than this one
+        self.root_check = None;
+        let available = self.root_targets().is_none_or(|roots| {
+            roots
+                .iter()
+                .any(|(path, device)| root_available(path, *device))
+        });
+        self.update_root_missing(available)
     }
 
-    pub fn on_cursor_up(&mut self) {
-        if let Some(folder) = self.store.get_current_folder_mut() {
-            if folder.cursor_index > 0 {
-                folder.cursor_index -= 1;
+    /// Applies result of root check running in background once it is done
+    fn maybe_finish_root_check(&mut self) {
+        let Some(available) = self
+            .root_check
+            .as_ref()
+            .and_then(|check| check.try_finish())
+        else {
+            return;
+        };
+        self.root_check = None;
+        self.update_root_missing(available);
+    }
+
+    /// Paths scan root is there while any of them is, with devices they were scanned on. None when
+    /// there is nothing on disk to check.
+    fn root_targets(&self) -> Option<Vec<(PathBuf, Option<u64>)>> {
+        let paths = match (&self.file_root, self.root_path.as_os_str().is_empty()) {
+            (Some(file), _) => vec![file.clone()],
+            (None, false) => vec![self.root_path.clone()],
+            // Virtual root is there while any of its roots is
+            (None, true) => self
+                .store
+                .get_folder(&self.root_path)?
+                .entries()
+                .iter()
+                .filter(|entry| entry.kind == FolderEntryType::Folder)
+                .map(|entry| PathBuf::from(&entry.name))
+                .collect(),
+        };
+        Some(
+            paths
+                .into_iter()
+                .map(|path| {
+                    let device = self.root_devices.get(&path).copied();
+                    (path, device)
+                })
+                .collect(),
+        )
+    }
+
+    /// Logs root going away or coming back, returns whether it is available
+    fn update_root_missing(&mut self, available: bool) -> bool {
+        if available != self.root_missing {
+            return available;
+        }
+        self.root_missing = !available;
+        let root = self.root_title();
+        match available {
+            true => self.logger.log(
+                format!("Scan root {} is available again, R rescans it", root),
+                MessageLevel::Info,
+            ),
+            false => {
+                self.ui_config.confirming_deletion = false;
+                self.pending_sweep.clear();
+                self.logger.log(
+                    format!("Scan root {} is no longer available", root),
+                    MessageLevel::Error,
+                );
             }
         }
-        self.ui_config.confirming_deletion = false;
+        available
     }
 
-    pub fn on_cursor_down(&mut self) {
-        if let Some(folder) = self.store.get_current_folder_mut() {
-            if folder.cursor_index < folder.entries.len() - 1 {
-                folder.cursor_index += 1;
+    /// Scan root as shown to the user, file roots by their own path
+    fn root_title(&self) -> String {
+        match (&self.file_root, self.root_path.as_os_str().is_empty()) {
+            (Some(file), _) => file.to_string_lossy().to_string(),
+            (None, false) => self.root_path.to_string_lossy().to_string(),
+            (None, true) => TEXT_VIRTUAL_ROOT.to_string(),
+        }
+    }
+
+    /// Logs why deleting is refused in read-only mode or while scan root is gone, true when it is
+    fn refuse_deletion(&mut self) -> bool {
+        if self.ui_config.read_only {
+            self.logger.log(
+                "Read-only mode, nothing can be deleted. Restart without --read-only to delete"
+                    .to_string(),
+                MessageLevel::Info,
+            );
+            return true;
+        }
+        if !self.check_root() {
+            self.logger.log(
+                "Scan root is not available, nothing deleted".to_string(),
+                MessageLevel::Error,
+            );
+        }
+        self.root_missing
+    }
+
+    /// Queues current folder and all its stored subfolders for a rescan
+    pub fn refresh_current_folder(&mut self) {
+        // Rescanning a vanished root would only turn the tree into errors
+        if self.root_missing {
+            return;
+        }
+        let current_path = self.store.get_current_path().clone();
+        let task_manager = &mut self.task_manager;
+        let incremental_rescan = self.incremental_rescan;
+        for_each_folder(&self.store, &current_path, |path, folder| {
+            // Virtual root and archive contents are not on disk
+            if path.as_os_str().is_empty() || folder.read_only {
+                return;
+            }
+            match incremental_rescan {
+                true => task_manager.add_rescan_task(path, folder),
+                false => task_manager.add_task(path),
             }
+        });
+        self.last_refresh = Instant::now();
+    }
+
+    /// Queues current folder alone for a rescan, folders below it keep what was read before and
+    /// new ones are scanned as they are found
+    fn refresh_current_folder_only(&mut self) {
+        let current_path = self.store.get_current_path().clone();
+        let Some(folder) = self.store.get_folder(&current_path) else {
+            return;
+        };
+        if self.root_missing || current_path.as_os_str().is_empty() || folder.read_only {
+            return;
+        }
+        match self.incremental_rescan {
+            true => self.task_manager.add_rescan_task(&current_path, folder),
+            false => self.task_manager.add_task(&current_path),
         }
+        self.last_refresh = Instant::now();
+    }
+
+    /// Rescans current folder on request, e.g. when it changed on disk since the scan
+    pub fn on_rescan(&mut self) {
         self.ui_config.confirming_deletion = false;
+        if !self.check_root() {
+            self.logger.log(
+                "Scan root is not available, pick another one with O".to_string(),
+                MessageLevel::Info,
+            );
+            return;
+        }
+        self.load_exclude_file();
+        self.refresh_current_folder();
     }
 
-    // MIGRATE: DONE
-    fn navigate_to_parent(&mut self) {
-        let to_process_subfolders = self.store.move_to_parent();
+    /// Reads patterns of `--exclude-from` file into scan options and hands them to the scanner.
+    /// Patterns read before are kept when the file can't be read.
+    fn load_exclude_file(&mut self) {
+        if let Some(path) = &self.exclude_from {
+            match read_exclude_file(path) {
+                Ok(patterns) => self.scan_options.exclude_from_file = patterns,
+                Err(err) => self.logger.log(
+                    format!("Could not read exclude file {}: {}", path.display(), err),
+                    MessageLevel::Error,
+                ),
+            }
+        }
+        self.store.set_scan_options(self.scan_options.clone());
+        self.task_manager
+            .set_scan_options(self.scan_options.clone());
+    }
 
-        self.logger.log(
-            self.store.get_current_path().to_string_lossy().to_string(),
-            MessageLevel::Info,
-        );
-        for subfolder in to_process_subfolders {
-            self.task_manager.maybe_add_task(&self.store, &subfolder);
+    /// Blocks until every queued folder is scanned, for non-interactive modes.
+    pub fn wait_for_tasks(&mut self) {
+        while !self.task_manager.is_done() {
+            self.tick();
+            thread::sleep(Duration::from_millis(EVENT_INTERVAL / 10));
         }
+        self.tick();
     }
 
-    fn navigate_to_child(&mut self, title: &str) {
-        let child_path = self.store.move_to_child(title);
-        self.logger
-            .log(child_path.to_string_lossy().to_string(), MessageLevel::Info);
-        self.task_manager.maybe_add_task(&self.store, &child_path);
+    /// Text for terminal window title, with scan progress while scanning
+    pub fn window_title(&self) -> String {
+        let path = match self.root_path.as_os_str().is_empty() {
+            true => TEXT_VIRTUAL_ROOT.to_string(),
+            false => self.root_path.to_string_lossy().to_string(),
+        };
+        match self.task_manager.progress() {
+            Some(percent) => format!("wiper — scanning… {}% · {}", percent, path),
+            None => format!("wiper · {}", path),
+        }
     }
 
-    pub fn on_backspace(&mut self) {
-        self.navigate_to_parent();
+    /// Set running to false to quit the application.
+    pub fn quit(&mut self) {
+        self.running = false;
     }
 
-    pub fn on_enter(&mut self) {
-        if let Some(folder) = self.store.get_current_folder().cloned() {
-            let entry = folder.get_selected_entry();
+    /// Quits, after one more confirmation when asked for and something was deleted
+    pub fn on_quit(&mut self) {
+        if self.ui_config.confirm_quit
+            && self.session_deleted > 0
+            && !self.ui_config.confirming_quit
+        {
+            self.ui_config.confirming_quit = true;
+            self.ui_config.confirming_deletion = false;
+            return;
+        }
+        self.quit();
+    }
 
-            match entry.kind {
-                FolderEntryType::Parent => {
-                    self.navigate_to_parent();
-                }
-                FolderEntryType::Folder => {
-                    self.navigate_to_child(&entry.title);
-                }
-                FolderEntryType::File => {
-                    if self.ui_config.open_file {
-                        let mut file_name = self.store.get_current_path().clone();
-                        file_name.push(entry.title.clone());
-                        let _ = opener::open(file_name);
-                    }
-                }
-            }
+    /// Stays in the app, quit has to be asked for again
+    pub fn on_cancel_quit(&mut self) {
+        self.ui_config.confirming_quit = false;
+    }
+
+    pub fn on_escape(&mut self) {
+        if self.ui_config.confirming_deletion {
+            self.ui_config.confirming_deletion = false;
+        } else if self.ui_config.name_filter.is_active() {
+            self.clear_filter();
+        } else if let Some(folder) = self.store.get_current_folder_mut() {
+            folder.marked.clear();
         }
+    }
+
+    pub fn on_start_filter(&mut self) {
+        self.ui_config.filter_editing = true;
         self.ui_config.confirming_deletion = false;
     }
 
-    pub fn on_delete(&mut self) {
-        if let Some(mut folder) = self.store.get_current_folder().cloned() {
-            let entry = folder.get_selected_entry();
+    pub fn on_filter_input(&mut self, c: char) {
+        self.ui_config.name_filter.query.push(c);
+        self.select_visible_entry();
+    }
 
-            let mut to_delete_path = PathBuf::from(&self.store.get_current_path());
-            to_delete_path.push(&entry.title);
+    pub fn on_filter_backspace(&mut self) {
+        self.ui_config.name_filter.query.pop();
+        self.select_visible_entry();
+    }
 
-            match entry.kind {
-                FolderEntryType::Parent => {}
-                FolderEntryType::Folder => {
-                    if !self.ui_config.confirming_deletion {
-                        self.ui_config.confirming_deletion = true;
-                    } else if delete_folder(&to_delete_path, &self.ui_config).is_ok() {
-                        if let Some(subfolder_size) = entry.size {
-                            self.propagate_size_update_upwards(
-                                &to_delete_path,
-                                subfolder_size,
-                                DiffKind::Subtract,
-                            );
-                        }
-                        folder.remove_selected();
-                        self.store.remove_path(&to_delete_path);
-                        self.store.set_current_folder(folder.clone());
-                        self.ui_config.confirming_deletion = false;
-                    }
-                }
-                FolderEntryType::File => {
-                    if !self.ui_config.confirming_deletion {
-                        self.ui_config.confirming_deletion = true;
-                    } else if delete_file(&to_delete_path, &self.ui_config).is_ok() {
-                        if let Some(subfile_size) = entry.size {
-                            let parent_folder = PathBuf::from(to_delete_path.parent().unwrap());
-                            self.propagate_size_update_upwards(
-                                &parent_folder,
-                                subfile_size,
-                                DiffKind::Subtract,
-                            );
-                        }
-                        folder.remove_selected();
-                        self.store.set_current_folder(folder.clone());
-                        self.ui_config.confirming_deletion = false;
-                    }
-                }
+    /// Stops typing into filter, keeping it applied
+    pub fn on_filter_submit(&mut self) {
+        self.ui_config.filter_editing = false;
+    }
+
+    pub fn on_toggle_filter_mode(&mut self) {
+        self.ui_config.name_filter.toggle_mode();
+        self.select_visible_entry();
+    }
+
+    pub fn clear_filter(&mut self) {
+        self.ui_config.name_filter.query.clear();
+        self.ui_config.filter_editing = false;
+    }
+
+    /// Moves cursor off an entry hidden by filters, onto the first shown one
+    fn select_visible_entry(&mut self) {
+        if let Some(folder) = self.store.get_current_folder_mut() {
+            let selected = folder.get_selected_entry();
+            if self.ui_config.is_visible(selected) && selected.kind != FolderEntryType::Parent {
+                return;
             }
+            folder.cursor_index = folder
+                .entries()
+                .iter()
+                .position(|entry| {
+                    entry.kind != FolderEntryType::Parent && self.ui_config.is_visible(entry)
+                })
+                .unwrap_or(0);
         }
     }
 
-    fn propagate_size_update_upwards(
-        &mut self,
-        to_delete_path: &Path,
-        entry_diff: u64,
-        diff_kind: DiffKind,
-    ) {
-        let mut parent_path = to_delete_path.to_path_buf();
-        while let Some(parent) = parent_path.parent() {
-            if let Some(parent_folder) = self.store.get_folder_mut(&parent_path) {
-                if let Some(parent_folder_entry) =
-                    parent_folder.entries.get_mut(parent_folder.cursor_index)
-                {
-                    if let Some(size) = parent_folder_entry.size.as_mut() {
-                        match diff_kind {
-                            DiffKind::Subtract => *size -= entry_diff,
-                        }
-                    }
-                }
-                parent_folder.sorted_by = None;
-                parent_path = parent.to_path_buf();
-            } else {
-                break;
+    pub fn on_toggle_mark(&mut self) {
+        if let Some(folder) = self.store.get_current_folder_mut() {
+            folder.toggle_mark_selected();
+        }
+        self.on_cursor_down();
+    }
+
+    /// Copies sizes of marked entries, or of selected one, as a table. Without a terminal that could
+    /// take it to the clipboard the report is logged instead.
+    pub fn on_copy_size_report(&mut self) {
+        let path = self.store.get_current_path().clone();
+        let Some(folder) = self.store.get_current_folder() else {
+            return;
+        };
+        let report = SelectionReport::build(folder, &path);
+        if report.entries.is_empty() {
+            self.logger
+                .log("Nothing to report".to_string(), MessageLevel::Info);
+            return;
+        }
+        let unit = self.ui_config.size_unit;
+        if !has_clipboard() {
+            self.logger.log(report.to_line(unit), MessageLevel::Info);
+            return;
+        }
+        self.logger.log(
+            format!("Copied size report of {} entries", report.entries.len()),
+            MessageLevel::Info,
+        );
+        self.launch = Some(Launch::Clipboard(report.to_table(unit)));
+    }
+
+    /// Copies size of selected entry, of its whole subtree for folders. `other_form` copies it
+    /// the way the config doesn't ask for.
+    pub fn on_copy_size(&mut self, other_form: bool) {
+        let Some(folder) = self.store.get_current_folder() else {
+            return;
+        };
+        let entry = folder.get_selected_entry();
+        let Some(size) = entry.size.filter(|_| entry.kind != FolderEntryType::Parent) else {
+            self.logger
+                .log("No size to copy".to_string(), MessageLevel::Info);
+            return;
+        };
+        let form = match other_form {
+            true => self.ui_config.copy_size.other(),
+            false => self.ui_config.copy_size,
+        };
+        let text = match form {
+            SizeForm::Formatted => format_size(size, self.ui_config.size_unit),
+            SizeForm::Bytes => size.to_string(),
+        };
+        let title = entry.title.clone();
+        if !has_clipboard() {
+            self.logger
+                .log(format!("Size of {title}: {text}"), MessageLevel::Info);
+            return;
+        }
+        self.logger.log(
+            format!("Copied size of {title}: {text}"),
+            MessageLevel::Info,
+        );
+        self.launch = Some(Launch::Clipboard(text));
+    }
+
+    pub fn on_toggle_coloring(&mut self) {
+        self.ui_config.colored = !self.ui_config.colored;
+    }
+
+    pub fn on_toggle_sorting(&mut self) {
+        self.ui_config.sort_reversed = false;
+        match self.ui_config.sort_by {
+            SortBy::Title => {
+                self.ui_config.sort_by = SortBy::Size;
+            }
+            _ => {
+                self.ui_config.sort_by = SortBy::Title;
             }
         }
     }
 
-    pub fn toggle_debug(&mut self) {
-        self.ui_config.debug_enabled = !self.ui_config.debug_enabled;
+    pub fn on_open_sort_menu(&mut self) {
+        self.ui_config.confirming_deletion = false;
+        self.sort_menu = Some(SortMenu::new(
+            self.ui_config.sort_by,
+            self.ui_config.sort_reversed,
+            self.ui_config.folders_first,
+        ));
     }
 
-    pub fn pre_render(&mut self) {
-        self.sort_current_folder();
+    pub fn on_close_sort_menu(&mut self) {
+        self.sort_menu = None;
+    }
+
+    /// Applies sorting picked in the menu, a sort key under cursor is picked first
+    pub fn on_apply_sort_menu(&mut self) {
+        let Some(mut menu) = self.sort_menu.take() else {
+            return;
+        };
+        if menu.cursor_index < SORT_MENU_KEYS.len() {
+            menu.toggle();
+        }
+        self.ui_config.sort_by = menu.sort_by;
+        self.ui_config.sort_reversed = menu.reversed;
+        self.ui_config.folders_first = menu.folders_first;
+    }
+
+    pub fn on_open_profile_menu(&mut self) {
+        self.ui_config.confirming_deletion = false;
+        let names = self
+            .profiles
+            .iter()
+            .map(|profile| profile.name.clone())
+            .collect();
+        self.profile_menu = Some(ProfileMenu::new(names, self.active_profile.as_deref()));
+    }
+
+    pub fn on_close_profile_menu(&mut self) {
+        self.profile_menu = None;
     }
+
+    pub fn on_apply_profile_menu(&mut self) {
+        let Some(menu) = self.profile_menu.take() else {
+            return;
+        };
+        self.apply_profile(menu.selected());
+    }
+
+    /// Switches table options to profile with provided name, None goes back to the options
+    /// wiper started with. Scanned data is only shown differently, nothing is rescanned.
+    pub fn apply_profile(&mut self, name: Option<&str>) {
+        let profile = match name {
+            None => None,
+            Some(name) => match self.profiles.iter().find(|profile| profile.name == name) {
+                Some(profile) => match profile.config() {
+                    Ok(config) => Some(config),
+                    Err(err) => {
+                        self.logger.log(err, MessageLevel::Error);
+                        return;
+                    }
+                },
+                None => {
+                    self.logger
+                        .log(format!("Unknown profile {name}"), MessageLevel::Error);
+                    return;
+                }
+            },
+        };
+        self.ui_config.apply_profile(&self.lens, profile.as_ref());
+        self.active_profile = name.map(str::to_string);
+        self.error_names_key = None;
+        self.update_error_names();
+        self.select_visible_entry();
+        let message = match name {
+            Some(name) => format!("Profile {name} applied"),
+            None => "Back to options wiper started with".to_string(),
+        };
+        self.logger.log(message, MessageLevel::Info);
+    }
+
+    /// Sorts by provided column, picking the column already sorted by reverses it
+    pub fn on_sort_by(&mut self, sort_by: SortBy) {
+        if self.ui_config.sort_by == sort_by {
+            self.ui_config.sort_reversed = !self.ui_config.sort_reversed;
+        } else {
+            self.ui_config.sort_by = sort_by;
+            self.ui_config.sort_reversed = false;
+        }
+    }
+
+    /// Clicking a header cell of the table sorts by its column
+    pub fn on_click(&mut self, column: u16, row: u16) {
+        if self.path_prompt.is_some() || !matches!(self.view, View::Folder) {
+            return;
+        }
+        if let Some(index) = treemap_entry_at(&self.treemap_cells, column, row) {
+            if let Some(folder) = self.store.get_current_folder_mut() {
+                if index < folder.entries().len() {
+                    folder.cursor_index = index;
+                }
+            }
+            return;
+        }
+        let Some(area) = self.table_area else {
+            return;
+        };
+        if row != area.y + 1 {
+            return;
+        }
+        if let Some(sort_by) = sort_column_at(area, &self.ui_config, column) {
+            self.on_sort_by(sort_by);
+        }
+    }
+
+    pub fn on_toggle_own_size(&mut self) {
+        self.ui_config.own_size = !self.ui_config.own_size;
+    }
+
+    pub fn on_toggle_both_sizes(&mut self) {
+        self.ui_config.both_sizes = !self.ui_config.both_sizes;
+        Column::OtherSize.insert_into(&mut self.ui_config.columns);
+    }
+
+    /// Hides entries below preset size, preset 0 shows everything again
+    pub fn on_size_preset(&mut self, preset: usize) {
+        self.ui_config.min_size = match preset {
+            0 => None,
+            _ => match self.ui_config.size_presets.get(preset - 1) {
+                Some(size) => Some(*size),
+                None => return,
+            },
+        };
+        self.select_visible_entry();
+    }
+
+    /// Switches Space bars between parent, root and disk scale
+    pub fn on_cycle_space_scale(&mut self) {
+        self.ui_config.space_scale = self.ui_config.space_scale.next();
+    }
+
+    /// Hides entries scanned without errors, down to the branches that need another look
+    pub fn on_toggle_errors_only(&mut self) {
+        self.ui_config.errors_only = !self.ui_config.errors_only;
+        self.error_names_key = None;
+        self.update_error_names();
+        self.select_visible_entry();
+    }
+
+    pub fn on_toggle_owner_column(&mut self) {
+        self.ui_config.show_owner = cfg!(unix) && !self.ui_config.show_owner;
+        Column::Owner.insert_into(&mut self.ui_config.columns);
+    }
+
+    pub fn on_toggle_permissions_column(&mut self) {
+        self.ui_config.show_permissions = cfg!(unix) && !self.ui_config.show_permissions;
+        Column::Mode.insert_into(&mut self.ui_config.columns);
+    }
+
+    pub fn on_toggle_age_column(&mut self) {
+        self.ui_config.show_age = !self.ui_config.show_age;
+        Column::Age.insert_into(&mut self.ui_config.columns);
+    }
+
+    pub fn on_toggle_treemap(&mut self) {
+        self.ui_config.treemap = !self.ui_config.treemap;
+    }
+
+    pub fn on_toggle_overview(&mut self) {
+        self.ui_config.overview = !self.ui_config.overview;
+    }
+
+    pub fn on_toggle_breadcrumb(&mut self) {
+        self.ui_config.breadcrumb = !self.ui_config.breadcrumb;
+    }
+
+    /// Unpins error kept at the top of debug panel logs
+    pub fn on_acknowledge_error(&mut self) {
+        self.logger.acknowledge();
+    }
+
+    /// Titles, sizes and entry counts of folders from root down to provided one, None for
+    /// folders not scanned yet
+    pub fn breadcrumb(&self, current_path: &Path) -> Vec<(String, Option<u64>, Option<usize>)> {
+        let mut crumbs = vec![];
+        let mut path = current_path.to_path_buf();
+        loop {
+            let parent = self.store.get_parent(&path);
+            let crumb = match self.store.get_folder(&path) {
+                Some(folder) => {
+                    let count = folder
+                        .entries()
+                        .iter()
+                        .filter(|entry| entry.kind != FolderEntryType::Parent)
+                        .count();
+                    (folder.title.clone(), Some(folder.total_size()), Some(count))
+                }
+                None => {
+                    let name = parent.as_ref().map(|(_, name)| name.to_string_lossy());
+                    (name.unwrap_or_default().to_string(), None, None)
+                }
+            };
+            crumbs.push(crumb);
+            match parent {
+                Some((parent, _)) if path != self.root_path => path = parent,
+                _ => break,
+            }
+        }
+        crumbs.reverse();
+        crumbs
+    }
+
+    pub fn on_toggle_preview(&mut self) {
+        self.ui_config.preview = !self.ui_config.preview;
+    }
+
+    pub fn on_toggle_growth(&mut self) {
+        self.ui_config.growth = !self.ui_config.growth;
+    }
+
+    /// Title of selected folder with sizes it had at cached scans, current folder when parent
+    /// entry is selected. History is empty for files and folders not seen by earlier scans.
+    pub fn selected_history(&mut self) -> Option<(String, Vec<(u64, u64)>)> {
+        let current_path = self.store.get_current_path().clone();
+        let folder = self.store.get_current_folder()?;
+        let entry = folder.get_selected_entry();
+        let (title, path) = match entry.kind {
+            FolderEntryType::Parent => (folder.title.clone(), current_path),
+            _ => (entry.title.clone(), current_path.join(&entry.name)),
+        };
+        let relative = path.strip_prefix(&self.root_path).ok()?;
+        let history = match entry.kind {
+            FolderEntryType::File => None,
+            _ => self.size_history.get(relative.to_string_lossy().as_ref()),
+        };
+        Some((title, history.cloned().unwrap_or_default()))
+    }
+
+    /// Reads selected file once it gets selected, folders are summed up from the store every frame
+    fn update_preview(&mut self) {
+        if !self.ui_config.preview {
+            self.preview = None;
+            return;
+        }
+        let current_path = self.store.get_current_path().clone();
+        let Some(folder) = self.store.get_current_folder() else {
+            self.preview = None;
+            return;
+        };
+        let Some(entry) = folder.entries().get(folder.cursor_index) else {
+            self.preview = None;
+            return;
+        };
+        let path = current_path.join(&entry.name);
+        self.preview = match entry.kind {
+            FolderEntryType::Parent => None,
+            FolderEntryType::Folder => {
+                let children = self.store.get_folder(&path).map(|folder| {
+                    folder
+                        .entries()
+                        .iter()
+                        .filter(|entry| entry.kind != FolderEntryType::Parent)
+                        .count()
+                });
+                let size = entry.size;
+                Some((path, Preview::Folder { children, size }))
+            }
+            FolderEntryType::File if folder.read_only => Some((
+                path,
+                Preview::Error("Archive contents are not extracted".to_string()),
+            )),
+            FolderEntryType::File => match self.preview.take() {
+                Some((previewed, preview)) if previewed == path => Some((previewed, preview)),
+                _ => {
+                    let preview = Preview::read_file(&path, self.ui_config.preview_size);
+                    Some((path, preview))
+                }
+            },
+        };
+    }
+
+    pub fn on_toggle_split_view(&mut self) {
+        self.ui_config.split_view = !self.ui_config.split_view;
+    }
+
+    /// Keeps a copy of current folder taken when it was entered.
+    ///
+    /// Sizes still follow the folder while a scan runs, so the copy ends up as the folder
+    /// was before anything in it was deleted rather than half scanned.
+    fn update_entry_snapshot(&mut self) {
+        let current_path = self.store.get_current_path().clone();
+        let Some(folder) = self.store.get_current_folder() else {
+            return;
+        };
+        match &mut self.entry_snapshot {
+            Some((path, snapshot)) if *path == current_path => {
+                if self.task_manager.is_done() {
+                    return;
+                }
+                let current: HashMap<&OsStr, &FolderEntry> = folder
+                    .entries()
+                    .iter()
+                    .map(|entry| (entry.name.as_os_str(), entry))
+                    .collect();
+                for index in 0..snapshot.entries().len() {
+                    if let Some(entry) = current.get(snapshot.entries()[index].name.as_os_str()) {
+                        snapshot.update_entry(index, |shown| {
+                            shown.is_loaded = entry.is_loaded;
+                            shown.size = entry.size;
+                        });
+                    }
+                }
+            }
+            _ => self.entry_snapshot = Some((current_path, folder.clone())),
+        }
+    }
+
+    fn sort_current_folder(&mut self) {
+        self.store.sort_current_folder(
+            self.ui_config.sort_by,
+            self.ui_config.sort_reversed,
+            self.ui_config.folders_first,
+        );
+    }
+
+    // MIGRATE: DONE
+    pub fn on_toggle_move_to_trash(&mut self) {
+        self.ui_config.move_to_trash = !self.ui_config.move_to_trash;
+    }
+
+    /// Adds digit to pending count, returns false when it does not start or continue one
+    pub fn on_count_digit(&mut self, digit: char) -> bool {
+        let Some(digit) = digit.to_digit(10) else {
+            return false;
+        };
+        let count = match self.pending_count {
+            None if digit == 0 => return false,
+            None => digit as usize,
+            Some(count) => count * 10 + digit as usize,
+        };
+        self.pending_count = Some(count.min(MAX_COUNT));
+        true
+    }
+
+    pub fn on_cursor_up(&mut self) {
+        if let Some(folder) = self.store.get_current_folder_mut() {
+            let wrapped = match self.ui_config.wrap_navigation {
+                true => folder.cursor_index + 1..folder.entries().len(),
+                false => 0..0,
+            };
+            if let Some(index) = (0..folder.cursor_index)
+                .rev()
+                .chain(wrapped.rev())
+                .find(|index| self.ui_config.is_visible(&folder.entries()[*index]))
+            {
+                folder.cursor_index = index;
+            }
+        }
+        self.ui_config.confirming_deletion = false;
+    }
+
+    pub fn on_cursor_down(&mut self) {
+        if let Some(folder) = self.store.get_current_folder_mut() {
+            let wrapped = match self.ui_config.wrap_navigation {
+                true => 0..folder.cursor_index,
+                false => 0..0,
+            };
+            if let Some(index) = (folder.cursor_index + 1..folder.entries().len())
+                .chain(wrapped)
+                .find(|index| self.ui_config.is_visible(&folder.entries()[*index]))
+            {
+                folder.cursor_index = index;
+            }
+        }
+        self.ui_config.confirming_deletion = false;
+    }
+
+    /// Selects the next entry at or above a path that could not be read, wrapping around
+    pub fn on_next_error_entry(&mut self) {
+        self.select_error_entry(true);
+    }
+
+    /// Selects the previous entry at or above a path that could not be read, wrapping around
+    pub fn on_previous_error_entry(&mut self) {
+        self.select_error_entry(false);
+    }
+
+    fn select_error_entry(&mut self, forward: bool) {
+        self.ui_config.confirming_deletion = false;
+        let current_path = self.store.get_current_path().clone();
+        let (errors, ui_config) = (&self.scan_errors, &self.ui_config);
+        let Some(folder) = self.store.get_current_folder_mut() else {
+            return;
+        };
+        let cursor = folder.cursor_index;
+        let len = folder.entries().len();
+        // Entries after cursor first, then from the other end up to and including it
+        let order: Vec<usize> = match forward {
+            true => (cursor + 1..len).chain(0..=cursor).collect(),
+            false => (0..cursor).rev().chain((cursor..len).rev()).collect(),
+        };
+        let found = order
+            .into_iter()
+            .filter(|index| *index < len)
+            .find(|index| {
+                let entry = &folder.entries()[*index];
+                let path = current_path.join(&entry.name);
+                entry.kind != FolderEntryType::Parent
+                    && ui_config.is_visible(entry)
+                    && errors.iter().any(|err| err.path.starts_with(&path))
+            });
+        match found {
+            Some(index) => folder.cursor_index = index,
+            None => self.logger.log(
+                "No entries with read errors here".to_string(),
+                MessageLevel::Info,
+            ),
+        }
+    }
+
+    /// Marks and filter belong to the folder view they were made in
+    fn clear_current_marks(&mut self) {
+        if let Some(folder) = self.store.get_current_folder_mut() {
+            folder.marked.clear();
+        }
+        self.clear_filter();
+    }
+
+    /// Holds navigation back when it would discard marks, true when it did
+    fn guard_leave(&mut self, leave: Leave) -> bool {
+        if !self.ui_config.confirm_leave || std::mem::take(&mut self.leave_confirmed) {
+            return false;
+        }
+        let marked = self
+            .store
+            .get_current_folder()
+            .map_or(0, |folder| folder.marked.len());
+        if marked == 0 {
+            return false;
+        }
+        self.ui_config.confirming_deletion = false;
+        self.pending_leave = Some((leave, marked));
+        true
+    }
+
+    /// Goes where navigation waiting for confirmation was headed, discarding marks
+    pub fn on_confirm_leave(&mut self) {
+        let Some((leave, _)) = self.pending_leave.take() else {
+            return;
+        };
+        self.leave_confirmed = true;
+        match leave {
+            Leave::Parent => self.navigate_to_parent(),
+            Leave::Child(name) => self.navigate_to_child(&name),
+            Leave::Root => self.on_go_to_root(),
+            Leave::LargestAll => self.on_follow_largest_all(),
+        }
+        // Navigation may stop before reaching the guard
+        self.leave_confirmed = false;
+    }
+
+    /// Stays in current folder with its marks
+    pub fn on_cancel_leave(&mut self) {
+        self.pending_leave = None;
+    }
+
+    // MIGRATE: DONE
+    fn navigate_to_parent(&mut self) {
+        if self.guard_leave(Leave::Parent) {
+            return;
+        }
+        self.check_root();
+        // File root has no folder of its own, its parent is opened as the new root
+        if self.store.get_current_path().as_os_str().is_empty() {
+            if let Some(parent) = self.file_root.as_ref().and_then(|file| file.parent()) {
+                self.open_root(parent.to_path_buf());
+                return;
+            }
+        }
+        self.clear_current_marks();
+        let to_process_subfolders = self.store.move_to_parent();
+
+        self.logger.log(
+            self.store.get_current_path().to_string_lossy().to_string(),
+            MessageLevel::Info,
+        );
+        for subfolder in to_process_subfolders {
+            self.task_manager.maybe_add_task(&self.store, &subfolder);
+        }
+    }
+
+    fn navigate_to_child(&mut self, name: &OsStr) {
+        if self.guard_leave(Leave::Child(name.to_os_string())) {
+            return;
+        }
+        self.check_root();
+        self.clear_current_marks();
+        let child_path = self.store.move_to_child(name);
+        self.logger
+            .log(child_path.to_string_lossy().to_string(), MessageLevel::Info);
+        self.task_manager.maybe_add_task(&self.store, &child_path);
+    }
+
+    /// Jumps back to the scan root, or to the virtual root listing several roots
+    pub fn on_go_to_root(&mut self) {
+        self.ui_config.confirming_deletion = false;
+        if self.store.get_current_path() == &self.root_path {
+            return;
+        }
+        if self.guard_leave(Leave::Root) {
+            return;
+        }
+        self.clear_current_marks();
+        let root_path = self.root_path.clone();
+        self.store.set_current_path(&root_path);
+        self.logger
+            .log(root_path.to_string_lossy().to_string(), MessageLevel::Info);
+        self.task_manager.maybe_add_task(&self.store, &root_path);
+    }
+
+    pub fn on_backspace(&mut self) {
+        self.navigate_to_parent();
+    }
+
+    pub fn on_enter(&mut self) {
+        if let Some(folder) = self.store.get_current_folder().cloned() {
+            let entry = folder.get_selected_entry();
+
+            match entry.kind {
+                FolderEntryType::Parent => {
+                    self.navigate_to_parent();
+                }
+                FolderEntryType::Folder => {
+                    self.navigate_to_child(&entry.name);
+                }
+                FolderEntryType::File => {
+                    let mut file_name = self.store.get_current_path().clone();
+                    file_name.push(&entry.name);
+                    if folder.read_only {
+                        // Archive contents only exist inside the archive
+                    } else if self.open_archive(&file_name, &entry.name) {
+                        // Entered archive as a folder
+                    } else {
+                        self.on_enter_file(file_name);
+                    }
+                }
+            }
+        }
+        self.ui_config.confirming_deletion = false;
+    }
+
+    /// Runs action configured for Enter on a file
+    fn on_enter_file(&mut self, path: PathBuf) {
+        match self.ui_config.enter_action {
+            EnterAction::Open => {
+                let _ = opener::open(path);
+            }
+            EnterAction::Pager => {
+                self.launch = Some(Launch::Command(external_command(&["PAGER"], "less", &path)));
+            }
+            EnterAction::Editor => {
+                self.launch = Some(Launch::Command(external_command(
+                    &["VISUAL", "EDITOR"],
+                    "vi",
+                    &path,
+                )));
+            }
+            EnterAction::CopyPath => {
+                let path = path.to_string_lossy().to_string();
+                self.logger
+                    .log(format!("Copied {path}"), MessageLevel::Info);
+                self.launch = Some(Launch::Clipboard(path));
+            }
+            EnterAction::Nothing => {}
+        }
+    }
+
+    /// What Enter does on selected entry when it is a file, shown in footer
+    pub fn enter_hint(&self) -> Option<&'static str> {
+        let folder = self.store.get_current_folder()?;
+        let entry = folder.entries().get(folder.cursor_index)?;
+        if entry.kind != FolderEntryType::File || folder.read_only {
+            return None;
+        }
+        #[cfg(feature = "archives")]
+        if is_archive(Path::new(&entry.name)) {
+            return Some("browse");
+        }
+        self.ui_config.enter_action.hint()
+    }
+
+    /// Moves cursor to the biggest entry, enters it when it is an already selected folder
+    pub fn on_follow_largest(&mut self) {
+        self.ui_config.confirming_deletion = false;
+        if let Some(folder) = self.store.get_current_folder_mut() {
+            if let Some(index) = folder.get_largest_entry_index() {
+                if folder.cursor_index != index {
+                    folder.cursor_index = index;
+                    return;
+                }
+                let entry = folder.entries()[index].clone();
+                if entry.kind == FolderEntryType::Folder {
+                    self.navigate_to_child(&entry.name);
+                }
+            }
+        }
+    }
+
+    /// Keeps entering the biggest folder until the biggest entry is a file or is not scanned yet
+    pub fn on_follow_largest_all(&mut self) {
+        self.ui_config.confirming_deletion = false;
+        let enters = self.store.get_current_folder().is_some_and(|folder| {
+            folder
+                .get_largest_entry_index()
+                .is_some_and(|index| folder.entries()[index].kind == FolderEntryType::Folder)
+        });
+        if enters && self.guard_leave(Leave::LargestAll) {
+            return;
+        }
+        // Folders entered below have no marks, only the first step is guarded
+        self.leave_confirmed = true;
+        while let Some(folder) = self.store.get_current_folder_mut() {
+            let Some(index) = folder.get_largest_entry_index() else {
+                break;
+            };
+            folder.cursor_index = index;
+            let entry = folder.entries()[index].clone();
+            if entry.kind != FolderEntryType::Folder {
+                break;
+            }
+            self.navigate_to_child(&entry.name);
+        }
+        self.leave_confirmed = false;
+    }
+
+    pub fn on_delete(&mut self) {
+        if self.refuse_deletion() {
+            return;
+        }
+        if self.ui_config.confirming_deletion && !self.pending_sweep.is_empty() {
+            self.delete_empty_folders();
+            return;
+        }
+        if let Some(mut folder) = self.store.get_current_folder().cloned() {
+            if folder.read_only {
+                self.logger.log(
+                    "Archive contents can't be deleted".to_string(),
+                    MessageLevel::Error,
+                );
+                return;
+            }
+            let names = folder.get_names_to_delete();
+            if names.is_empty() {
+                return;
+            }
+            if !self.ui_config.confirming_deletion {
+                let mut warnings = self.get_deletion_warnings(&folder, &names);
+                let dangerous = self.get_danger_warnings(&folder, &names);
+                let needs_phrase = !dangerous.is_empty();
+                warnings.extend(dangerous);
+                self.deletion_warnings = warnings;
+                let current_path = self.store.get_current_path().clone();
+                let entries: Vec<&FolderEntry> = folder
+                    .entries()
+                    .iter()
+                    .filter(|entry| names.contains(&entry.name))
+                    .collect();
+                self.deletion_freed = freed_by_filesystem(&self.store, &current_path, &entries);
+                let phrase = match entries.as_slice() {
+                    [entry] => entry.title.clone(),
+                    _ => format!("{} entries", names.len()),
+                };
+                self.arm_deletion(names.into_iter().map(PathBuf::from).collect());
+                if needs_phrase {
+                    self.confirm_phrase = Some(phrase);
+                }
+                return;
+            }
+            // Typed confirmation goes through `on_confirm_submit`
+            if self.is_typing_confirmation() {
+                return;
+            }
+
+            let current_path = self.store.get_current_path().clone();
+            let mut removed = vec![];
+            for name in names {
+                if let Some(index) = folder.entries().iter().position(|e| e.name == name) {
+                    if let Some(entry) = self.delete_entry(&current_path, &folder.entries()[index])
+                    {
+                        folder.remove_entry(index);
+                        removed.push(entry);
+                    }
+                }
+            }
+            self.store.set_current_folder(folder);
+            self.record_deletion(removed);
+            self.ui_config.confirming_deletion = false;
+        }
+    }
+
+    /// Starts waiting for confirmation of deleting empty folders below the current one
+    pub fn on_sweep_empty_folders(&mut self) {
+        if self.refuse_deletion() {
+            return;
+        }
+        if self.ui_config.confirming_deletion && !self.pending_sweep.is_empty() {
+            self.delete_empty_folders();
+            return;
+        }
+        let current_path = self.store.get_current_path().clone();
+        // Folders listed under virtual root are the scanned roots themselves
+        if current_path.as_os_str().is_empty() {
+            return;
+        }
+        let paths = empty_folders(&self.store, &current_path);
+        if paths.is_empty() {
+            self.logger
+                .log("No empty folders".to_string(), MessageLevel::Info);
+            return;
+        }
+        let relative: Vec<PathBuf> = paths
+            .iter()
+            .map(|path| {
+                path.strip_prefix(&current_path)
+                    .unwrap_or(path)
+                    .to_path_buf()
+            })
+            .collect();
+        // Same protection as deleting them one by one
+        let dangerous: Vec<String> = paths
+            .iter()
+            .zip(&relative)
+            .filter_map(|(path, relative)| {
+                danger_pattern(path, &self.ui_config.danger_patterns).map(|pattern| {
+                    format!(
+                        "{} matches danger pattern {}",
+                        relative.to_string_lossy(),
+                        pattern
+                    )
+                })
+            })
+            .collect();
+        let phrase = match relative.as_slice() {
+            [path] => path.to_string_lossy().to_string(),
+            _ => format!("{} entries", relative.len()),
+        };
+        self.deletion_freed = vec![];
+        self.arm_deletion(relative);
+        if !dangerous.is_empty() {
+            self.confirm_phrase = Some(phrase);
+        }
+        self.deletion_warnings = dangerous;
+        self.pending_sweep = paths;
+    }
+
+    /// Whether pending deletion waits for its confirm phrase to be typed
+    pub fn is_typing_confirmation(&self) -> bool {
+        self.ui_config.confirming_deletion && self.confirm_phrase.is_some()
+    }
+
+    pub fn on_confirm_input(&mut self, c: char) {
+        self.confirm_input.push(c);
+    }
+
+    pub fn on_confirm_backspace(&mut self) {
+        self.confirm_input.pop();
+    }
+
+    /// Deletes pending entries when typed text matches the phrase, cancels deletion otherwise
+    pub fn on_confirm_submit(&mut self) {
+        let matched = self.confirm_phrase.as_ref() == Some(&self.confirm_input);
+        self.confirm_phrase = None;
+        self.confirm_input.clear();
+        if matched {
+            self.on_delete();
+        } else {
+            self.ui_config.confirming_deletion = false;
+            self.logger.log(
+                "Typed text did not match, deletion cancelled".to_string(),
+                MessageLevel::Info,
+            );
+        }
+    }
+
+    fn arm_deletion(&mut self, targets: Vec<PathBuf>) {
+        self.confirm_phrase = None;
+        self.confirm_input.clear();
+        self.ui_config.confirming_deletion = true;
+        self.confirmation_started = Some(Instant::now());
+        if self.ui_config.confirm_bell {
+            ring_bell();
+        }
+        self.pending_deletion = targets;
+        self.pending_sweep.clear();
+    }
+
+    fn delete_empty_folders(&mut self) {
+        let mut removed = vec![];
+        for path in std::mem::take(&mut self.pending_sweep) {
+            let Some((parent, name)) = self.store.get_parent(&path) else {
+                continue;
+            };
+            let Some(entry) = self
+                .store
+                .get_folder(&parent)
+                .and_then(|folder| folder.entries().iter().find(|e| e.name == name).cloned())
+            else {
+                continue;
+            };
+            let result = delete_empty_folder(&path, &self.ui_config);
+            if let Some(entry) = self.finish_deletion(&parent, &entry, result) {
+                if let Some(folder) = self.store.get_folder_mut(&parent) {
+                    if let Some(index) = folder.entries().iter().position(|e| e.name == name) {
+                        folder.remove_entry(index);
+                    }
+                }
+                removed.push(entry);
+            }
+        }
+        self.logger.log(
+            format!("Removed {} empty folders", removed.len()),
+            MessageLevel::Info,
+        );
+        self.record_deletion(removed);
+        self.ui_config.confirming_deletion = false;
+    }
+
+    /// Warnings known right away, comparing targets with disk is started in background and
+    /// adds its warnings once done
+    fn get_deletion_warnings(&mut self, folder: &Folder, names: &[OsString]) -> Vec<String> {
+        let current_path = self.store.get_current_path().clone();
+        let mut warnings = vec![];
+        let mut targets = vec![];
+        for name in names {
+            let Some(entry) = folder.entries().iter().find(|e| &e.name == name) else {
+                continue;
+            };
+            let path = current_path.join(&entry.name);
+            if entry.kind == FolderEntryType::Folder && is_git_repository(&path) {
+                warnings.push(format!("{} contains a git repository", entry.title));
+            }
+            let scanned = self.store.get_folder(&path).cloned();
+            targets.push((path, entry.clone(), scanned));
+        }
+        self.change_check = Some(ChangeCheck::start(targets, &self.scan_options));
+        warnings
+    }
+
+    /// Adds warnings about targets changed on disk once their comparison is done, dropped when
+    /// the deletion was answered meanwhile
+    fn maybe_finish_change_check(&mut self) {
+        let Some(changes) = self
+            .change_check
+            .as_ref()
+            .and_then(|check| check.try_finish())
+        else {
+            return;
+        };
+        self.change_check = None;
+        if !self.ui_config.confirming_deletion {
+            return;
+        }
+        for (title, change) in changes {
+            self.deletion_warnings.push(match change {
+                DiskChange::Missing => format!("{} no longer exists on disk", title),
+                DiskChange::Modified => {
+                    format!(
+                        "{} changed on disk since scan, rescan recommended (R)",
+                        title
+                    )
+                }
+            });
+        }
+    }
+
+    /// Targets matching a danger pattern, compared by their canonical path
+    fn get_danger_warnings(&mut self, folder: &Folder, names: &[OsString]) -> Vec<String> {
+        if self.ui_config.danger_patterns.is_empty() {
+            return vec![];
+        }
+        let current_path = self.store.get_current_path().clone();
+        let mut warnings = vec![];
+        for name in names {
+            let Some(entry) = folder.entries().iter().find(|e| &e.name == name) else {
+                continue;
+            };
+            let path = current_path.join(&entry.name);
+            if let Some(pattern) = danger_pattern(&path, &self.ui_config.danger_patterns) {
+                warnings.push(format!(
+                    "{} matches danger pattern {}",
+                    entry.title, pattern
+                ));
+            }
+        }
+        warnings
+    }
+
+    /// Removes entry of provided folder from disk and store, returns it when it was removed
+    fn delete_entry(&mut self, folder_path: &Path, entry: &FolderEntry) -> Option<TrashedEntry> {
+        let to_delete_path = folder_path.join(&entry.name);
+        let result = match entry.kind {
+            FolderEntryType::Parent => return None,
+            FolderEntryType::Folder => delete_folder(&to_delete_path, &self.ui_config),
+            FolderEntryType::File => delete_file(&to_delete_path, &self.ui_config),
+        };
+        self.finish_deletion(folder_path, entry, result)
+    }
+
+    /// Drops deleted entry from the store and counts it, or logs why it could not be deleted
+    fn finish_deletion(
+        &mut self,
+        folder_path: &Path,
+        entry: &FolderEntry,
+        result: std::io::Result<()>,
+    ) -> Option<TrashedEntry> {
+        let to_delete_path = folder_path.join(&entry.name);
+        if let Err(err) = result {
+            self.logger.log(
+                format!("{}: {}", to_delete_path.to_string_lossy(), err),
+                MessageLevel::Error,
+            );
+            self.session_failed_deletions += 1;
+            return None;
+        }
+
+        if let Some(size) = entry.size {
+            self.propagate_size_update_upwards(folder_path, size, DiffKind::Subtract);
+        }
+        self.session_freed += entry.size.unwrap_or(0);
+        self.session_deleted += 1;
+        let removed = TrashedEntry::new(to_delete_path.clone(), entry.size.unwrap_or(0));
+        if self.ui_config.move_to_trash {
+            self.session_trash.push(removed.clone());
+        }
+        // Drops stored subtree, both for folders and for browsed archives
+        let mut to_remove = vec![];
+        for_each_folder(&self.store, &to_delete_path, |path, _| {
+            to_remove.push(path.to_path_buf());
+        });
+        for path in to_remove {
+            self.store.remove_path(&path);
+        }
+        Some(removed)
+    }
+
+    /// Adds confirmed deletion to undo history, nothing is left to redo after it
+    fn record_deletion(&mut self, entries: Vec<TrashedEntry>) {
+        if entries.is_empty() {
+            return;
+        }
+        self.undo_history.push(DeletionStep {
+            entries,
+            permanent: !self.ui_config.move_to_trash,
+        });
+        self.redo_history.clear();
+    }
+
+    /// Restores entries of the last deletion from trash
+    pub fn on_undo(&mut self) {
+        match self.undo_history.is_empty() {
+            true => self
+                .logger
+                .log("Nothing to undo".to_string(), MessageLevel::Info),
+            false => {
+                if self.undo_step() {
+                    self.log_history_steps("Undid", 1);
+                }
+            }
+        }
+    }
+
+    /// Deletes entries of the last undone deletion again
+    pub fn on_redo(&mut self) {
+        if self.refuse_deletion() {
+            return;
+        }
+        match self.redo_history.is_empty() {
+            true => self
+                .logger
+                .log("Nothing to redo".to_string(), MessageLevel::Info),
+            false => {
+                if self.redo_step() {
+                    self.log_history_steps("Redid", 1);
+                }
+            }
+        }
+    }
+
+    /// Undoes every deletion of this session, stops at the first one that can't be undone
+    pub fn on_undo_all(&mut self) {
+        if self.undo_history.is_empty() {
+            self.logger
+                .log("Nothing to undo".to_string(), MessageLevel::Info);
+            return;
+        }
+        let mut steps = 0;
+        while !self.undo_history.is_empty() && self.undo_step() {
+            steps += 1;
+        }
+        self.log_history_steps("Undid", steps);
+    }
+
+    /// Redoes every undone deletion, stops at the first one that can't be redone
+    pub fn on_redo_all(&mut self) {
+        if self.refuse_deletion() {
+            return;
+        }
+        if self.redo_history.is_empty() {
+            self.logger
+                .log("Nothing to redo".to_string(), MessageLevel::Info);
+            return;
+        }
+        let mut steps = 0;
+        while !self.redo_history.is_empty() && self.redo_step() {
+            steps += 1;
+        }
+        self.log_history_steps("Redid", steps);
+    }
+
+    fn log_history_steps(&mut self, done: &str, steps: usize) {
+        if steps == 0 {
+            return;
+        }
+        let left = self.undo_history.len();
+        self.logger.log(
+            format!(
+                "{} {} {}, {} left to undo, {} to redo",
+                done,
+                steps,
+                match steps {
+                    1 => "deletion",
+                    _ => "deletions",
+                },
+                left,
+                self.redo_history.len()
+            ),
+            MessageLevel::Info,
+        );
+    }
+
+    /// Restores last deletion and rescans folders it was in, returns whether it was undone.
+    /// A deletion that can't be undone stays in the history, so older ones stay behind it.
+    fn undo_step(&mut self) -> bool {
+        let Some(step) = self.undo_history.pop() else {
+            return false;
+        };
+        let result = match step.permanent {
+            true => Err(std::io::Error::other(format!(
+                "{} was deleted permanently",
+                step.entries[0].path.to_string_lossy()
+            ))),
+            false => restore_trashed(&step.entries),
+        };
+        if let Err(err) = result {
+            self.logger
+                .log(format!("Can't undo deletion: {}", err), MessageLevel::Error);
+            self.undo_history.push(step);
+            return false;
+        }
+
+        let mut parents: Vec<PathBuf> = vec![];
+        for entry in &step.entries {
+            self.session_trash.retain(|trashed| {
+                trashed.path != entry.path || trashed.trashed_at != entry.trashed_at
+            });
+            self.session_freed = self.session_freed.saturating_sub(entry.size);
+            self.session_deleted = self.session_deleted.saturating_sub(1);
+            // Restored scan roots are not listed again until the next run
+            if let Some((parent, _)) = self.store.get_parent(&entry.path) {
+                if !parent.as_os_str().is_empty() && !parents.contains(&parent) {
+                    parents.push(parent);
+                }
+            }
+        }
+        // Restored entries show up with their sizes once their folders are rescanned
+        for parent in parents {
+            self.task_manager.add_task(&parent);
+        }
+        self.redo_history.push(step);
+        true
+    }
+
+    /// Moves entries of last undone deletion to trash again, returns whether it was redone.
+    /// Entries have to be back in the scanned tree, which happens once rescan of their folders
+    /// finishes.
+    fn redo_step(&mut self) -> bool {
+        let Some(step) = self.redo_history.pop() else {
+            return false;
+        };
+        let mut targets = vec![];
+        for trashed in &step.entries {
+            let target = self
+                .store
+                .get_parent(&trashed.path)
+                .and_then(|(parent, name)| {
+                    let folder = self.store.get_folder(&parent)?;
+                    let entry = folder.entries().iter().find(|e| e.name == name)?.clone();
+                    Some((parent, entry))
+                });
+            match target {
+                Some(target) => targets.push(target),
+                None => {
+                    self.logger.log(
+                        format!(
+                            "Can't redo deletion: {} is not scanned yet",
+                            trashed.path.to_string_lossy()
+                        ),
+                        MessageLevel::Error,
+                    );
+                    self.redo_history.push(step);
+                    return false;
+                }
+            }
+        }
+
+        // Redone deletions go to trash like the undone ones did
+        let move_to_trash = std::mem::replace(&mut self.ui_config.move_to_trash, true);
+        let mut removed = vec![];
+        for (parent, entry) in targets {
+            if let Some(trashed) = self.delete_entry(&parent, &entry) {
+                if let Some(folder) = self.store.get_folder_mut(&parent) {
+                    if let Some(index) = folder.entries().iter().position(|e| e.name == entry.name)
+                    {
+                        folder.remove_entry(index);
+                    }
+                }
+                removed.push(trashed);
+            }
+        }
+        let redone = removed.len() == step.entries.len();
+        if !removed.is_empty() {
+            self.undo_history.push(DeletionStep {
+                entries: removed,
+                permanent: false,
+            });
+        }
+        self.ui_config.move_to_trash = move_to_trash;
+        redone
+    }
+
+    /// Enters archive as a read-only folder, returns whether path is a browsable archive
+    #[cfg(feature = "archives")]
+    fn open_archive(&mut self, path: &Path, name: &OsStr) -> bool {
+        if !is_archive(path) {
+            return false;
+        }
+        if !self.store.has_path(&path.to_path_buf()) {
+            match archive_to_folders(path) {
+                Ok(folders) => {
+                    for (inner_path, folder) in folders {
+                        let key = match inner_path.as_os_str().is_empty() {
+                            true => path.to_path_buf(),
+                            false => path.join(inner_path),
+                        };
+                        self.store.set_folder(&key, folder);
+                    }
+                }
+                Err(err) => {
+                    self.logger.log(
+                        format!("{}: {}", path.to_string_lossy(), err),
+                        MessageLevel::Error,
+                    );
+                    self.scan_errors
+                        .push(ScanError::new(path.to_path_buf(), &err));
+                    return false;
+                }
+            }
+        }
+        self.navigate_to_child(name);
+        true
+    }
+
+    #[cfg(not(feature = "archives"))]
+    fn open_archive(&mut self, _path: &Path, _name: &OsStr) -> bool {
+        false
+    }
+
+    /// Updates entries leading to provided folder in all of its ancestors
+    fn propagate_size_update_upwards(
+        &mut self,
+        folder_path: &Path,
+        entry_diff: u64,
+        diff_kind: DiffKind,
+    ) {
+        let mut path = folder_path.to_path_buf();
+        while let Some((parent, name)) = self.store.get_parent(&path) {
+            if let Some(parent_folder) = self.store.get_folder_mut(&parent) {
+                if let Some(index) = parent_folder.entries().iter().position(|e| e.name == name) {
+                    if let Some(size) = parent_folder.entries()[index].size {
+                        let size = match diff_kind {
+                            DiffKind::Subtract => size.checked_sub(entry_diff),
+                        };
+                        debug_assert!(size.is_some(), "entry smaller than what it holds");
+                        let size = size.unwrap_or(0);
+                        parent_folder.set_entry_size(index, Some(size));
+                    }
+                }
+                parent_folder.sorted_by = None;
+                path = parent;
+            } else {
+                break;
+            }
+        }
+    }
+
+    pub fn on_toggle_filesystems_view(&mut self) {
+        self.view = match self.view {
+            View::Filesystems(_) => View::Folder,
+            _ => View::Filesystems(FilesystemsView::new(filesystems_usage(
+                &self.store,
+                &self.root_path,
+            ))),
+        };
+    }
+
+    pub fn on_toggle_errors_view(&mut self) {
+        self.view = match self.view {
+            View::Errors(_) => View::Folder,
+            _ => View::Errors(ErrorsView::default()),
+        };
+    }
+
+    pub fn on_toggle_recent_view(&mut self) {
+        self.view = match self.view {
+            View::Recent(_) => View::Folder,
+            _ => View::Recent(RecentView::new(
+                recent_files(&self.store, &self.root_path),
+                self.ui_config.recent_window,
+            )),
+        };
+    }
+
+    pub fn on_toggle_deep_view(&mut self) {
+        self.view = match self.view {
+            View::Deep(_) => View::Folder,
+            _ => View::Deep(DeepView::new(
+                deep_folders(&self.store, &self.root_path),
+                self.ui_config.min_depth,
+            )),
+        };
+    }
+
+    /// Lists folders one level deeper or shallower, kept for the next time the view opens
+    pub fn on_change_min_depth(&mut self, deeper: bool) {
+        if let View::Deep(view) = &mut self.view {
+            match deeper {
+                true => view.deeper(),
+                false => view.shallower(),
+            }
+            self.ui_config.min_depth = view.min_depth;
+        }
+    }
+
+    /// Shows selected deep folder in its parent, where it can be opened or deleted
+    pub fn on_reveal_deep_folder(&mut self) {
+        let View::Deep(view) = &self.view else {
+            return;
+        };
+        let Some(folder) = view.selected() else {
+            return;
+        };
+        let path = folder.path.clone();
+        if self.reveal_path(&path) {
+            self.view = View::Folder;
+        }
+    }
+
+    /// Reads current folder and scanned folders below it from disk again, then lists where they
+    /// differ from the scan
+    pub fn on_toggle_verify_view(&mut self) {
+        if let View::Verify(_) = self.view {
+            self.view = View::Folder;
+            return;
+        }
+        if let Some(verification) = self.verification.take() {
+            verification.cancel();
+            self.logger.log(
+                format!("Stopped verifying {}", verification.path.display()),
+                MessageLevel::Info,
+            );
+            return;
+        }
+        if !self.task_manager.is_done() {
+            self.logger
+                .log("Scan is not finished yet".to_string(), MessageLevel::Info);
+            return;
+        }
+        if !self.check_root() {
+            self.logger.log(
+                "Scan root is not available, pick another one with O".to_string(),
+                MessageLevel::Info,
+            );
+            return;
+        }
+        let current_path = self.store.get_current_path().clone();
+        let on_disk = self
+            .store
+            .get_current_folder()
+            .is_some_and(|folder| !folder.read_only);
+        if current_path.as_os_str().is_empty() || !on_disk {
+            self.logger.log(
+                "Only folders on disk can be verified".to_string(),
+                MessageLevel::Info,
+            );
+            return;
+        }
+        self.logger.log(
+            format!("Verifying {}, V stops", current_path.display()),
+            MessageLevel::Info,
+        );
+        self.verification = Some(Verification::start(
+            &self.store,
+            &current_path,
+            &self.scan_options,
+        ));
+    }
+
+    /// Shows differences once verification running in background read every folder. They wait
+    /// while another view is open, so it isn't replaced under the user.
+    fn maybe_finish_verification(&mut self) {
+        if !matches!(self.view, View::Folder) {
+            return;
+        }
+        let Some(mismatches) = self
+            .verification
+            .as_ref()
+            .and_then(|verification| verification.try_finish())
+        else {
+            return;
+        };
+        let path = self.verification.take().unwrap().path;
+        let message = match mismatches.len() {
+            0 => format!("{} matches disk", path.display()),
+            count => format!(
+                "{} differs from disk in {} places, R rescans it",
+                path.display(),
+                count
+            ),
+        };
+        self.logger.log(message, MessageLevel::Info);
+        self.view = View::Verify(VerifyView::new(mismatches, path));
+    }
+
+    /// Shows entry of selected difference in its parent
+    pub fn on_reveal_mismatch(&mut self) {
+        let View::Verify(view) = &self.view else {
+            return;
+        };
+        let Some(mismatch) = view.selected() else {
+            return;
+        };
+        let path = mismatch.path.clone();
+        if self.reveal_path(&path) {
+            self.view = View::Folder;
+        }
+    }
+
+    /// Rescans verified folder, so the scan matches disk again
+    pub fn on_resync(&mut self) {
+        let View::Verify(view) = &self.view else {
+            return;
+        };
+        let path = view.path.clone();
+        self.view = View::Folder;
+        self.store.set_current_path(&path);
+        self.on_rescan();
+    }
+
+    pub fn on_toggle_diff_view(&mut self) {
+        if let View::Diff(_) = self.view {
+            self.view = View::Folder;
+            return;
+        }
+        match &self.scan_changes {
+            Some(changes) => {
+                self.view = View::Diff(DiffView::new(
+                    changes.clone(),
+                    self.ui_config.diff_threshold,
+                ))
+            }
+            None if self.previous_snapshot.is_some() => self
+                .logger
+                .log("Scan is not finished yet".to_string(), MessageLevel::Info),
+            None => self.logger.log(
+                "No previous scan to compare with".to_string(),
+                MessageLevel::Info,
+            ),
+        }
+    }
+
+    pub fn on_toggle_names_view(&mut self) {
+        self.view = match self.view {
+            View::Names(_) => View::Folder,
+            _ => View::Names(NamesView::new(duplicate_names(
+                &self.store,
+                &self.root_path,
+            ))),
+        };
+    }
+
+    pub fn on_toggle_trash_view(&mut self) {
+        self.view = match self.view {
+            View::Trash(_) => View::Folder,
+            _ => View::Trash(TrashView::default()),
+        };
+    }
+
+    /// Asks before the first press, purges entries trashed this session on the second one
+    pub fn on_purge_session_trash(&mut self) {
+        if self.refuse_deletion() {
+            return;
+        }
+        let View::Trash(view) = &mut self.view else {
+            return;
+        };
+        if self.session_trash.is_empty() {
+            self.logger.log(
+                "Nothing was moved to trash this session".to_string(),
+                MessageLevel::Info,
+            );
+            return;
+        }
+        if !view.confirming_purge {
+            view.confirming_purge = true;
+            return;
+        }
+        view.confirming_purge = false;
+        view.cursor_index = 0;
+        match purge_trashed(&self.session_trash) {
+            Ok(purged) => {
+                let size: u64 = self.session_trash.iter().map(|entry| entry.size).sum();
+                self.logger.log(
+                    format!(
+                        "Removed {} entries permanently from trash, {} reclaimed",
+                        purged,
+                        format_file_size(size)
+                    ),
+                    MessageLevel::Info,
+                );
+                self.session_trash.clear();
+            }
+            Err(err) => self.logger.log(
+                format!("Could not empty trash: {}", err),
+                MessageLevel::Error,
+            ),
+        }
+    }
+
+    /// Shows folder holding occurrence selected in names view, with the occurrence selected
+    pub fn on_reveal_name(&mut self) {
+        let View::Names(view) = &self.view else {
+            return;
+        };
+        let Some(file) = view.selected() else {
+            return;
+        };
+        let path = file.path.clone();
+        if self.reveal_path(&path) {
+            self.view = View::Folder;
+        }
+    }
+
+    /// Makes folder holding path current and selects path's entry, false when it is not scanned
+    pub fn reveal_path(&mut self, path: &Path) -> bool {
+        let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+            return false;
+        };
+        let parent = parent.to_path_buf();
+        if self.store.get_folder(&parent).is_none() {
+            return false;
+        }
+        self.ui_config.confirming_deletion = false;
+        self.clear_current_marks();
+        // Entry could be hidden by filters of the folder left behind
+        self.clear_filter();
+        self.ui_config.min_size = None;
+        self.store.set_current_path(&parent);
+        if let Some(folder) = self.store.get_current_folder_mut() {
+            if let Some(index) = folder.entries().iter().position(|entry| entry.name == name) {
+                folder.cursor_index = index;
+            }
+        }
+        true
+    }
+
+    /// Switches recent view to the next window preset, listed files come from the last scan
+    pub fn on_cycle_recent_window(&mut self) {
+        if let View::Recent(view) = &mut self.view {
+            view.cycle_window();
+            self.ui_config.recent_window = view.window;
+        }
+    }
+
+    pub fn close_view(&mut self) {
+        self.view = View::Folder;
+    }
+
+    pub fn toggle_debug(&mut self) {
+        self.ui_config.debug_enabled = !self.ui_config.debug_enabled;
+    }
+
+    fn update_space_total(&mut self) {
+        self.ui_config.space_total = match self.ui_config.space_scale {
+            SpaceScale::Parent => None,
+            SpaceScale::Root => self
+                .store
+                .get_folder(&self.root_path)
+                .map(|folder| folder.total_size()),
+            SpaceScale::Disk => self.current_disk_total(),
+        };
+        self.ui_config.disk_capacity = match self.ui_config.show_disk_percent {
+            true => self.current_disk_total(),
+            false => None,
+        };
+    }
+
+    /// Capacity of filesystem holding current folder
+    fn current_disk_total(&mut self) -> Option<u64> {
+        let current_path = self.store.get_current_path().clone();
+        if self.disk_total.as_ref().map(|(path, _)| path) != Some(&current_path) {
+            let total = disk_space(&current_path).map(|space| space.total);
+            self.disk_total = Some((current_path, total));
+        }
+        self.disk_total.as_ref().and_then(|(_, total)| *total)
+    }
+
+    /// Recomputes owned sizes once current folder or scanned data changes
+    fn update_owned_sizes(&mut self) {
+        let Some(uid) = self.ui_config.owner_filter else {
+            return;
+        };
+        let path = self.store.get_current_path().clone();
+        let nodes = self.store.get_nodes_len();
+        let Some(folder) = self.store.get_current_folder() else {
+            return;
+        };
+        let key = (path, nodes, folder.total_size());
+        if self.owned_sizes_key.as_ref() != Some(&key) {
+            self.ui_config.owned_sizes = owned_sizes(&self.store, &key.0, folder, uid);
+            self.owned_sizes_key = Some(key);
+        }
+        if self.ui_config.sort_by == SortBy::Size {
+            let sizes = &self.ui_config.owned_sizes;
+            if let Some(folder) = self.store.get_current_folder_mut() {
+                folder.sort_by_sizes(sizes, self.ui_config.sort_reversed);
+            }
+        }
+    }
+
+    /// Recomputes which entries hold read errors once current folder or errors change
+    fn update_error_names(&mut self) {
+        if !self.ui_config.errors_only {
+            return;
+        }
+        let path = self.store.get_current_path().clone();
+        let key = (path, self.scan_errors.len());
+        if self.error_names_key.as_ref() == Some(&key) {
+            return;
+        }
+        self.ui_config.error_names = match self.store.get_current_folder() {
+            Some(folder) => folder
+                .entries()
+                .iter()
+                .filter(|entry| entry.kind != FolderEntryType::Parent)
+                .filter(|entry| {
+                    let entry_path = key.0.join(&entry.name);
+                    self.scan_errors
+                        .iter()
+                        .any(|err| err.path.starts_with(&entry_path))
+                })
+                .map(|entry| entry.name.clone())
+                .collect(),
+            None => HashSet::new(),
+        };
+        self.error_names_key = Some(key);
+    }
+
+    /// Recomputes sizes of the second size column once current folder or scanned data changes
+    fn update_other_sizes(&mut self) {
+        if !self.ui_config.both_sizes {
+            return;
+        }
+        let path = self.store.get_current_path().clone();
+        let nodes = self.store.get_nodes_len();
+        let Some(folder) = self.store.get_current_folder() else {
+            return;
+        };
+        let own_size = self.ui_config.own_size;
+        let key = (path, nodes, folder.total_size(), own_size);
+        if self.other_sizes_key.as_ref() == Some(&key) {
+            return;
+        }
+        // Table shows own sizes in own size mode, so the second column shows the whole subtree
+        let shown = match own_size {
+            true => folder.clone(),
+            false => with_own_sizes(&self.store, &key.0, folder),
+        };
+        self.ui_config.other_sizes = shown
+            .entries()
+            .iter()
+            .filter(|entry| entry.kind != FolderEntryType::Parent)
+            .filter_map(|entry| entry.size.map(|size| (entry.name.clone(), size)))
+            .collect();
+        self.other_sizes_key = Some(key);
+    }
+
+    /// Recomputes what filters of current folder depend on, done before every render too
+    pub fn update_filters(&mut self) {
+        self.update_owned_sizes();
+        self.update_error_names();
+    }
+
+    pub fn pre_render(&mut self) {
+        self.sort_current_folder();
+        self.update_filters();
+        self.update_other_sizes();
+        self.update_space_total();
+        self.update_entry_snapshot();
+        self.update_preview();
+        self.ui_config.scan_age = self
+            .scan_finished_at
+            .map(|finished| finished.elapsed())
+            .filter(|age| *age >= STALE_SCAN_AGE);
+        self.ui_config.flashing = self.ui_config.confirming_deletion
+            && !self.ui_config.no_animations
+            && self
+                .confirmation_started
+                .is_some_and(|started| self.ui_config.confirm_flash.is_lit(started.elapsed()));
+    }
+}
+
+/// Whether there is a terminal to set the clipboard through, dumb ones ignore OSC 52
+fn has_clipboard() -> bool {
+    env::var("TERM").is_ok_and(|term| !term.is_empty() && term != "dumb")
+}
+
+/// Program named by the first set environment variable, fallback otherwise, with path appended
+fn external_command(vars: &[&str], fallback: &str, path: &Path) -> Vec<String> {
+    let program = vars
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or(fallback.to_string());
+    let mut command: Vec<String> = program.split_whitespace().map(String::from).collect();
+    command.push(path.to_string_lossy().to_string());
+    command
 }
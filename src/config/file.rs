@@ -0,0 +1,159 @@
+use crate::config::{with_builtin_profiles, InitConfig, Profile};
+use std::env;
+use std::fs::read_to_string;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Where options applied on every start are read from, None when home folder is unknown
+pub fn default_config_path() -> Option<PathBuf> {
+    let config_dir = match env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    Some(config_dir.join("wiper").join("config"))
+}
+
+/// Command line arguments held by config file contents.
+///
+/// Every line is an option written the way it is on the command line, e.g. `--size-unit mb`.
+/// Value is the rest of the line after the first whitespace, so it may contain spaces. Blank lines
+/// and lines starting with `#` are skipped, any other line not starting with `--` is an error.
+/// Lines from the first `[name]` section on belong to profiles, see [`config_file_profiles`].
+pub fn config_file_args(contents: &str) -> Result<Vec<String>, String> {
+    let lines = contents
+        .lines()
+        .take_while(|line| section_name(line).is_none());
+    lines_args(lines)
+}
+
+/// Profiles of config file, each one is a `[name]` line followed by its options
+pub fn config_file_profiles(contents: &str) -> Result<Vec<Profile>, String> {
+    let mut profiles: Vec<(String, Vec<&str>)> = vec![];
+    for line in contents.lines() {
+        match (section_name(line), profiles.last_mut()) {
+            (Some(name), _) => profiles.push((name.to_string(), vec![])),
+            (None, Some((_, lines))) => lines.push(line),
+            (None, None) => {}
+        }
+    }
+    profiles
+        .into_iter()
+        .map(|(name, lines)| {
+            let args = lines_args(lines.into_iter())?;
+            Ok(Profile { name, args })
+        })
+        .collect()
+}
+
+/// Name of `[name]` section starting at line
+fn section_name(line: &str) -> Option<&str> {
+    let name = line.trim().strip_prefix('[')?.strip_suffix(']')?.trim();
+    (!name.is_empty()).then_some(name)
+}
+
+/// Trimmed lines of a file holding one item per line, blank lines and `#` comments are skipped
+pub fn content_lines<'a>(lines: impl Iterator<Item = &'a str>) -> impl Iterator<Item = &'a str> {
+    lines
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+/// Option and value of each line, a line that isn't an option would turn into a scan root
+fn lines_args<'a>(lines: impl Iterator<Item = &'a str>) -> Result<Vec<String>, String> {
+    let mut args = vec![];
+    for line in content_lines(lines) {
+        if !line.starts_with("--") {
+            return Err(format!(
+                "Config file line `{line}` is not an option, options start with --"
+            ));
+        }
+        match line.split_once(char::is_whitespace) {
+            Some((option, value)) => {
+                args.extend([option.to_string(), value.trim_start().to_string()])
+            }
+            None => args.push(line.to_string()),
+        }
+    }
+    Ok(args)
+}
+
+/// Arguments from config file at provided path, or at the default one when None.
+///
+/// Provided file has to exist, a missing default file just means there is nothing to apply.
+pub fn load_config_args(path: Option<&Path>) -> io::Result<Vec<String>> {
+    let contents = read_config_file(path)?;
+    config_file_args(&contents).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Contents of config file, empty when the default one doesn't exist
+fn read_config_file(path: Option<&Path>) -> io::Result<String> {
+    let (path, required) = match path {
+        Some(path) => (path.to_path_buf(), true),
+        None => match default_config_path() {
+            Some(path) => (path, false),
+            None => return Ok(String::new()),
+        },
+    };
+    match read_to_string(&path) {
+        Ok(contents) => Ok(contents),
+        Err(err) if !required && err.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(err) => Err(io::Error::new(
+            err.kind(),
+            format!("{}: {err}", path.display()),
+        )),
+    }
+}
+
+impl InitConfig {
+    /// Builds config from command line arguments, with options from config file applied first,
+    /// so the same option on the command line wins
+    pub fn load(args: impl Iterator<Item = String>) -> Result<InitConfig, String> {
+        let args: Vec<String> = args.collect();
+        let config = InitConfig::build(args.iter().cloned()).map_err(str::to_string)?;
+        if config.no_config {
+            return config.check_profile();
+        }
+        let contents = read_config_file(config.config_file.as_deref())
+            .map_err(|err| format!("Could not read config file {err}"))?;
+        let file_args = config_file_args(&contents)?;
+        let profiles = config_file_profiles(&contents)?;
+        for profile in &profiles {
+            profile
+                .config()
+                .map_err(|err| format!("{err}, in config file"))?;
+        }
+        if file_args.is_empty() {
+            return InitConfig { profiles, ..config }.check_profile();
+        }
+        if file_args
+            .iter()
+            .any(|arg| arg == "--config" || arg == "--no-config")
+        {
+            return Err("--config and --no-config can't be used in a config file".to_string());
+        }
+        // Checked alone first, so an option missing its value doesn't take one from command line
+        let program = args.first().cloned().unwrap_or_default();
+        InitConfig::build(std::iter::once(program).chain(file_args.iter().cloned()))
+            .map_err(|err| format!("{err}, in config file"))?;
+
+        let mut merged = args;
+        let at = usize::from(!merged.is_empty());
+        merged.splice(at..at, file_args);
+        let config = InitConfig::build(merged.into_iter()).map_err(str::to_string)?;
+        InitConfig { profiles, ..config }.check_profile()
+    }
+
+    /// Fails when profile to start with is neither built in nor in config file
+    fn check_profile(self) -> Result<InitConfig, String> {
+        match &self.profile {
+            Some(name)
+                if with_builtin_profiles(&self.profiles)
+                    .iter()
+                    .all(|profile| &profile.name != name) =>
+            {
+                Err(format!("Unknown profile {name}"))
+            }
+            _ => Ok(self),
+        }
+    }
+}
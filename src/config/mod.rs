@@ -1,17 +1,969 @@
-use crate::fs::SortBy;
+use crate::filter::{CaseMode, NameFilter};
+use crate::fs::{user_id, Folder, FolderEntry, FolderEntryType, ScanOptions, SortBy};
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::time::Duration;
 
+mod file;
+mod profile;
+pub use file::{
+    config_file_args, config_file_profiles, content_lines, default_config_path, load_config_args,
+};
+pub use profile::{builtin_profiles, with_builtin_profiles, Lens, Profile, PROFILE_OPTIONS};
+
+#[derive(Debug, Default, Clone)]
 pub struct InitConfig {
     pub file_path: Option<String>,
+    /// Paths to show under a synthetic root instead of a single scan root
+    pub root_paths: Vec<String>,
+    /// Read newline-separated paths to scan from stdin
+    pub read_stdin: bool,
+    /// Print scan totals and exit without starting the UI
+    pub summary: bool,
+    /// Print chain of the biggest entries and exit without starting the UI
+    pub heaviest_path: bool,
+    /// Scan path several times and print how long it took, without starting the UI
+    pub bench_scan: Option<String>,
+    /// Times `--bench-scan` scans its path
+    pub bench_runs: Option<usize>,
+    /// Print non-interactive output as JSON
+    pub json: bool,
+    /// Rescan current folder every that many seconds
+    pub auto_refresh_secs: Option<u64>,
+    /// Symbol in front of selected row, empty to hide it
+    pub highlight_symbol: Option<String>,
+    /// Apparent to allocated size ratio above which files are marked as sparse
+    pub sparse_ratio: Option<f64>,
+    /// Do not mark sparse files
+    pub no_sparse_marker: bool,
+    /// Show scan progress in terminal window title
+    pub title_progress: bool,
+    /// Draw on the main screen, leaving the last frame in scrollback
+    pub inline: bool,
+    /// Replace spinners and flashes with static indicators
+    pub no_animations: bool,
+    /// Moving past the last entry selects the first one, and the other way around
+    pub wrap_navigation: bool,
+    /// Start with only entries that could not be read, or hold something that couldn't
+    pub errors_only: bool,
+    /// Folders go before files whatever entries are sorted by
+    pub folders_first: bool,
+    /// Ring terminal bell when a long scan finishes
+    pub bell: bool,
+    /// Send desktop notification when a long scan finishes
+    pub notify: bool,
+    /// Scans shorter than that many seconds finish silently
+    pub notify_after_secs: Option<u64>,
+    /// How strongly selection flashes when deletion waits for confirmation
+    pub confirm_flash: Option<FlashIntensity>,
+    /// Ring terminal bell when deletion waits for confirmation
+    pub confirm_bell: bool,
+    /// Cancel deletion waiting for confirmation after that many seconds without input
+    pub confirm_timeout_secs: Option<u64>,
+    /// Key confirming deletion, `d` pressed again when None
+    pub confirm_key: Option<PromptKey>,
+    /// Key cancelling deletion besides Esc
+    pub cancel_key: Option<PromptKey>,
+    /// Ask once more before quitting a session that deleted something
+    pub confirm_quit: bool,
+    /// Leave folders with marks without asking, marks are discarded
+    pub no_confirm_leave: bool,
+    /// How paths are written by reports and exports
+    pub path_style: PathStyle,
+    /// Quit or go idle after that many seconds without input
+    pub idle_timeout_secs: Option<u64>,
+    /// What happens once idle timeout passes
+    pub idle_action: Option<IdleAction>,
+    /// Limits on what the scan records
+    pub scan_options: ScanOptions,
+    /// File with more exclude patterns, read again on every rescan
+    pub exclude_from: Option<PathBuf>,
+    /// Rescans read again only folders whose modification time changed
+    pub incremental_rescan: bool,
+    /// Letter case rule for name filter
+    pub filter_case: CaseMode,
+    /// Match name filter as glob instead of substring
+    pub filter_glob: bool,
+    /// How far back recently modified view looks
+    pub recent_window: Option<Duration>,
+    /// Nesting level from which deep folders view lists folders
+    pub min_depth: Option<usize>,
+    /// Show only entries owned by user with that id
+    pub owner: Option<u32>,
+    /// Columns taken by a log message in debug panel before it is shortened
+    pub log_width: Option<usize>,
+    /// How long an error stays among debug panel logs before newer messages can push it out
+    pub log_hold: Option<Duration>,
+    /// Latest error stays at the top of debug panel logs until acknowledged
+    pub pin_errors: bool,
+    /// What the table is sorted by at start
+    pub sort_by: Option<SortBy>,
+    /// Hide entries smaller than that at start
+    pub min_size: Option<u64>,
+    /// Name filter query at start
+    pub name_filter: Option<String>,
+    /// Profile applied at start
+    pub profile: Option<String>,
+    /// Profiles of config file, built-in ones are not listed
+    pub profiles: Vec<Profile>,
+    /// Glob picking files to delete without starting the UI
+    pub delete_matching: Option<String>,
+    /// Pick only files not modified for that long for deletion without the UI
+    pub older_than: Option<Duration>,
+    /// Actually delete picked files instead of listing them
+    pub yes: bool,
+    /// Nothing can be deleted, for browsing without any risk
+    pub read_only: bool,
+    /// Delete permanently instead of moving to trash
+    pub no_trash: bool,
+    /// Keep sizes of this scan to compare the next scan of the same root with
+    pub cache_scan: bool,
+    /// Scan with idle I/O class and lowest CPU priority, where platform supports it
+    pub low_priority: bool,
+    /// Don't keep recently scanned roots between runs
+    pub no_history: bool,
+    /// Where recently scanned roots are kept between runs, not kept when None
+    pub history_file: Option<PathBuf>,
+    /// Changes smaller than that many bytes are hidden in comparison with previous scan
+    pub diff_threshold: Option<u64>,
+    /// What Space bars are relative to
+    pub space_scale: Option<SpaceScale>,
+    /// Show sizes as a share of filesystem capacity in their own column
+    pub disk_percent: bool,
+    /// Table columns in order, listed optional ones start switched on
+    pub columns: Option<Vec<Column>>,
+    /// Write scan totals in Prometheus text format there, scan runs without the UI
+    pub metrics_file: Option<String>,
+    /// Minimum sizes picked by `Alt` + number keys
+    pub size_presets: Option<Vec<u64>>,
+    /// Columns taken by Space bars, 0 hides them
+    pub space_width: Option<usize>,
+    /// Characters Space bars are drawn with
+    pub bar_style: Option<BarStyle>,
+    /// Format every scanned entry is exported in, scan runs without the UI
+    pub output_format: Option<ExportFormat>,
+    /// Where export is written, stdout when None or `-`
+    pub output: Option<String>,
+    /// Export holds only entries shown with filters given, instead of every scanned one
+    pub export_visible: bool,
+    /// What Enter does on a file
+    pub enter_action: Option<EnterAction>,
+    /// How `Y` copies size of selected entry
+    pub copy_size: Option<SizeForm>,
+    /// Colors terminal can show, detected from environment when None
+    pub color_depth: Option<ColorDepth>,
+    /// Bytes read from the start of a file for the preview pane
+    pub preview_size: Option<u64>,
+    /// Cached scans whose folder sizes are kept for the growth panel
+    pub history_depth: Option<usize>,
+    /// Show share of each top-level entry in a stacked bar above the table at the root
+    pub overview: bool,
+    /// Show path from root to current folder in the header, with size of each folder on it
+    pub breadcrumb: bool,
+    /// Globs of paths whose deletion needs the confirm phrase typed
+    pub danger_patterns: Vec<String>,
+    /// Right-align sizes in their column
+    pub align_sizes: bool,
+    /// Show own size of folders next to their whole subtree size
+    pub both_sizes: bool,
+    /// Unit sizes in the table are shown in
+    pub size_unit: Option<SizeUnit>,
+    /// Roots asked about before scanning, `/` and home folder when empty
+    pub broad_paths: Vec<String>,
+    /// Roots with more entries right below them than that are asked about before scanning
+    pub broad_entries: Option<usize>,
+    /// Scan broad roots without asking
+    pub no_broad_check: bool,
+    /// Config file read instead of the default one
+    pub config_file: Option<PathBuf>,
+    /// Ignore config files, only command line options apply
+    pub no_config: bool,
 }
 
 impl InitConfig {
     pub fn build(mut args: impl Iterator<Item = String>) -> Result<InitConfig, &'static str> {
         args.next();
 
-        Ok(InitConfig {
-            file_path: args.next(),
+        let mut config = InitConfig::default();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--stdin" => config.read_stdin = true,
+                "--summary" => config.summary = true,
+                "--bench-scan" => {
+                    let path = args.next().ok_or("Missing path for --bench-scan")?;
+                    config.bench_scan = Some(path);
+                }
+                "--bench-runs" => {
+                    let runs = args.next().ok_or("Missing runs for --bench-runs")?;
+                    let runs = runs
+                        .parse()
+                        .ok()
+                        .filter(|runs| *runs > 0)
+                        .ok_or("Invalid runs for --bench-runs")?;
+                    config.bench_runs = Some(runs);
+                }
+                "--heaviest-path" => config.heaviest_path = true,
+                "--json" => config.json = true,
+                "--auto-refresh" => {
+                    let secs = args.next().ok_or("Missing seconds for --auto-refresh")?;
+                    let secs = secs
+                        .parse()
+                        .map_err(|_| "Invalid seconds for --auto-refresh")?;
+                    if secs == 0 {
+                        return Err("Invalid seconds for --auto-refresh");
+                    }
+                    config.auto_refresh_secs = Some(secs);
+                }
+                "--highlight-symbol" => {
+                    let symbol = args.next().ok_or("Missing symbol for --highlight-symbol")?;
+                    config.highlight_symbol = Some(symbol);
+                }
+                "--sparse-ratio" => {
+                    let ratio = args.next().ok_or("Missing ratio for --sparse-ratio")?;
+                    let ratio = ratio
+                        .parse()
+                        .map_err(|_| "Invalid ratio for --sparse-ratio")?;
+                    config.sparse_ratio = Some(ratio);
+                }
+                "--no-sparse-marker" => config.no_sparse_marker = true,
+                "--title-progress" => config.title_progress = true,
+                "--inline" => config.inline = true,
+                "--no-animations" => config.no_animations = true,
+                "--wrap-navigation" => config.wrap_navigation = true,
+                "--errors-only" => config.errors_only = true,
+                "--folders-first" => config.folders_first = true,
+                "--config" => {
+                    let path = args.next().ok_or("Missing path for --config")?;
+                    config.config_file = Some(PathBuf::from(path));
+                }
+                "--no-config" => config.no_config = true,
+                "--exclude-smaller-than" => {
+                    let size = args
+                        .next()
+                        .ok_or("Missing size for --exclude-smaller-than")?;
+                    let size =
+                        parse_size(&size).ok_or("Invalid size for --exclude-smaller-than")?;
+                    config.scan_options.min_file_size = Some(size);
+                }
+                "--exclude-larger-than" => {
+                    let size = args
+                        .next()
+                        .ok_or("Missing size for --exclude-larger-than")?;
+                    let size = parse_size(&size).ok_or("Invalid size for --exclude-larger-than")?;
+                    config.scan_options.max_file_size = Some(size);
+                }
+                "--only" => {
+                    let extensions = args.next().ok_or("Missing extensions for --only")?;
+                    config.scan_options.extensions = extensions
+                        .split(',')
+                        .map(|extension| extension.trim().trim_start_matches('.').to_lowercase())
+                        .filter(|extension| !extension.is_empty())
+                        .collect();
+                }
+                "--exclude" => {
+                    let pattern = args.next().ok_or("Missing pattern for --exclude")?;
+                    config.scan_options.exclude.push(pattern);
+                }
+                "--incremental-rescan" => config.incremental_rescan = true,
+                "--exclude-from" => {
+                    let path = args.next().ok_or("Missing file for --exclude-from")?;
+                    config.exclude_from = Some(PathBuf::from(path));
+                }
+                "--case-sensitive" => config.filter_case = CaseMode::Sensitive,
+                "--ignore-case" => config.filter_case = CaseMode::Insensitive,
+                "--glob" => config.filter_glob = true,
+                "--confirm-flash" => {
+                    let intensity = args.next().ok_or("Missing value for --confirm-flash")?;
+                    config.confirm_flash = Some(match intensity.as_str() {
+                        "off" => FlashIntensity::Off,
+                        "subtle" => FlashIntensity::Subtle,
+                        "strong" => FlashIntensity::Strong,
+                        _ => return Err("Invalid value for --confirm-flash"),
+                    });
+                }
+                "--space-scale" => {
+                    let scale = args.next().ok_or("Missing value for --space-scale")?;
+                    config.space_scale = Some(match scale.as_str() {
+                        "parent" => SpaceScale::Parent,
+                        "root" => SpaceScale::Root,
+                        "disk" => SpaceScale::Disk,
+                        _ => return Err("Invalid value for --space-scale"),
+                    });
+                }
+                "--confirm-key" => {
+                    let key = args.next().ok_or("Missing key for --confirm-key")?;
+                    config.confirm_key =
+                        Some(PromptKey::parse(&key).ok_or("Invalid key for --confirm-key")?);
+                }
+                "--cancel-key" => {
+                    let key = args.next().ok_or("Missing key for --cancel-key")?;
+                    config.cancel_key =
+                        Some(PromptKey::parse(&key).ok_or("Invalid key for --cancel-key")?);
+                }
+                "--confirm-bell" => config.confirm_bell = true,
+                "--confirm-quit" => config.confirm_quit = true,
+                "--no-confirm-leave" => config.no_confirm_leave = true,
+                "--confirm-timeout" => {
+                    let secs = args.next().ok_or("Missing seconds for --confirm-timeout")?;
+                    let secs = secs
+                        .parse()
+                        .map_err(|_| "Invalid seconds for --confirm-timeout")?;
+                    config.confirm_timeout_secs = Some(secs);
+                }
+                "--path-style" => {
+                    let style = args.next().ok_or("Missing value for --path-style")?;
+                    config.path_style = match style.as_str() {
+                        "absolute" => PathStyle::Absolute,
+                        "relative" => PathStyle::Relative,
+                        _ => return Err("Invalid value for --path-style"),
+                    };
+                }
+                "--idle-timeout" => {
+                    let secs = args.next().ok_or("Missing seconds for --idle-timeout")?;
+                    let secs = secs
+                        .parse()
+                        .map_err(|_| "Invalid seconds for --idle-timeout")?;
+                    config.idle_timeout_secs = Some(secs);
+                }
+                "--preview-size" => {
+                    let size = args.next().ok_or("Missing size for --preview-size")?;
+                    let size = parse_size(&size)
+                        .filter(|size| *size > 0)
+                        .ok_or("Invalid size for --preview-size")?;
+                    config.preview_size = Some(size);
+                }
+                "--history-depth" => {
+                    let depth = args.next().ok_or("Missing value for --history-depth")?;
+                    let depth = depth
+                        .parse()
+                        .ok()
+                        .filter(|depth| *depth > 0)
+                        .ok_or("Invalid value for --history-depth")?;
+                    config.history_depth = Some(depth);
+                }
+                "--overview" => config.overview = true,
+                "--breadcrumb" => config.breadcrumb = true,
+                "--align-sizes" => config.align_sizes = true,
+                "--both-sizes" => config.both_sizes = true,
+                "--size-unit" => {
+                    let unit = args.next().ok_or("Missing value for --size-unit")?;
+                    config.size_unit = Some(match unit.to_ascii_lowercase().as_str() {
+                        "auto" => SizeUnit::Auto,
+                        "kb" | "kib" => SizeUnit::KB,
+                        "mb" | "mib" => SizeUnit::MB,
+                        "gb" | "gib" => SizeUnit::GB,
+                        _ => return Err("Invalid value for --size-unit"),
+                    });
+                }
+                "--broad-path" => {
+                    let path = args.next().ok_or("Missing path for --broad-path")?;
+                    config.broad_paths.push(path);
+                }
+                "--broad-entries" => {
+                    let entries = args.next().ok_or("Missing value for --broad-entries")?;
+                    let entries = entries
+                        .parse()
+                        .ok()
+                        .filter(|entries| *entries > 0)
+                        .ok_or("Invalid value for --broad-entries")?;
+                    config.broad_entries = Some(entries);
+                }
+                "--no-broad-check" => config.no_broad_check = true,
+                "--danger" => {
+                    let pattern = args.next().ok_or("Missing pattern for --danger")?;
+                    config.danger_patterns.push(pattern);
+                }
+                "--enter-action" => {
+                    let action = args.next().ok_or("Missing value for --enter-action")?;
+                    config.enter_action = Some(match action.as_str() {
+                        "open" => EnterAction::Open,
+                        "pager" => EnterAction::Pager,
+                        "editor" => EnterAction::Editor,
+                        "copy" => EnterAction::CopyPath,
+                        "none" => EnterAction::Nothing,
+                        _ => return Err("Invalid value for --enter-action"),
+                    });
+                }
+                "--copy-size" => {
+                    let form = args.next().ok_or("Missing value for --copy-size")?;
+                    config.copy_size = Some(match form.as_str() {
+                        "formatted" => SizeForm::Formatted,
+                        "bytes" => SizeForm::Bytes,
+                        _ => return Err("Invalid value for --copy-size"),
+                    });
+                }
+                "--colors" => {
+                    let depth = args.next().ok_or("Missing value for --colors")?;
+                    config.color_depth = match depth.as_str() {
+                        "auto" => None,
+                        "truecolor" => Some(ColorDepth::TrueColor),
+                        "256" => Some(ColorDepth::Ansi256),
+                        "16" => Some(ColorDepth::Ansi16),
+                        _ => return Err("Invalid value for --colors"),
+                    };
+                }
+                "--idle-action" => {
+                    let action = args.next().ok_or("Missing value for --idle-action")?;
+                    config.idle_action = Some(match action.as_str() {
+                        "quit" => IdleAction::Quit,
+                        "sleep" => IdleAction::Sleep,
+                        _ => return Err("Invalid value for --idle-action"),
+                    });
+                }
+                "--bell" => config.bell = true,
+                "--notify" => config.notify = true,
+                "--notify-after" => {
+                    let secs = args.next().ok_or("Missing seconds for --notify-after")?;
+                    let secs = secs
+                        .parse()
+                        .map_err(|_| "Invalid seconds for --notify-after")?;
+                    config.notify_after_secs = Some(secs);
+                }
+                "--recent-window" => {
+                    let window = args.next().ok_or("Missing duration for --recent-window")?;
+                    let window =
+                        parse_duration(&window).ok_or("Invalid duration for --recent-window")?;
+                    config.recent_window = Some(window);
+                }
+                "--sort" => {
+                    let key = args.next().ok_or("Missing key for --sort")?;
+                    config.sort_by = Some(match key.as_str() {
+                        "name" => SortBy::Title,
+                        "size" => SortBy::Size,
+                        "modified" => SortBy::Modified,
+                        "entries" => SortBy::Count,
+                        _ => return Err("Invalid key for --sort"),
+                    });
+                }
+                "--min-size" => {
+                    let size = args.next().ok_or("Missing size for --min-size")?;
+                    config.min_size = Some(parse_size(&size).ok_or("Invalid size for --min-size")?);
+                }
+                "--filter" => {
+                    let query = args.next().ok_or("Missing query for --filter")?;
+                    config.name_filter = Some(query);
+                }
+                "--profile" => {
+                    let name = args.next().ok_or("Missing name for --profile")?;
+                    config.profile = Some(name);
+                }
+                "--min-depth" => {
+                    let depth = args.next().ok_or("Missing depth for --min-depth")?;
+                    let depth = depth.parse().map_err(|_| "Invalid depth for --min-depth")?;
+                    if depth == 0 {
+                        return Err("Invalid depth for --min-depth");
+                    }
+                    config.min_depth = Some(depth);
+                }
+                "--log-width" => {
+                    let width = args.next().ok_or("Missing width for --log-width")?;
+                    let width = width.parse().map_err(|_| "Invalid width for --log-width")?;
+                    config.log_width = Some(width);
+                }
+                "--log-hold" => {
+                    let hold = args.next().ok_or("Missing duration for --log-hold")?;
+                    let hold = parse_duration(&hold).ok_or("Invalid duration for --log-hold")?;
+                    config.log_hold = Some(hold);
+                }
+                "--pin-errors" => config.pin_errors = true,
+                "--scan" => {
+                    let path = args.next().ok_or("Missing path for --scan")?;
+                    match config.file_path {
+                        None => config.file_path = Some(path),
+                        Some(_) => config.root_paths.push(path),
+                    }
+                }
+                "--delete-matching" => {
+                    let pattern = args.next().ok_or("Missing pattern for --delete-matching")?;
+                    config.delete_matching = Some(pattern);
+                }
+                "--older-than" => {
+                    let age = args.next().ok_or("Missing duration for --older-than")?;
+                    let age = parse_duration(&age).ok_or("Invalid duration for --older-than")?;
+                    config.older_than = Some(age);
+                }
+                "--yes" => config.yes = true,
+                "--read-only" => config.read_only = true,
+                "--no-trash" => config.no_trash = true,
+                "--size-presets" => {
+                    let sizes = args.next().ok_or("Missing sizes for --size-presets")?;
+                    let sizes: Option<Vec<u64>> = sizes.split(',').map(parse_size).collect();
+                    match sizes {
+                        Some(sizes) if (1..=9).contains(&sizes.len()) => {
+                            config.size_presets = Some(sizes)
+                        }
+                        _ => return Err("Invalid sizes for --size-presets"),
+                    }
+                }
+                "--space-width" => {
+                    let width = args.next().ok_or("Missing width for --space-width")?;
+                    let width = width
+                        .parse()
+                        .map_err(|_| "Invalid width for --space-width")?;
+                    config.space_width = Some(width);
+                }
+                "--no-space-column" => config.space_width = Some(0),
+                "--disk-percent" => config.disk_percent = true,
+                "--columns" => {
+                    let names = args.next().ok_or("Missing columns for --columns")?;
+                    let mut columns = vec![];
+                    for name in names.split(',') {
+                        let column =
+                            Column::parse(name.trim()).ok_or("Invalid column for --columns")?;
+                        if columns.contains(&column) {
+                            return Err("Column listed twice in --columns");
+                        }
+                        columns.push(column);
+                    }
+                    if !columns.contains(&Column::Name) {
+                        return Err("--columns must include name");
+                    }
+                    config.columns = Some(columns);
+                }
+                "--bar-style" => {
+                    let style = args.next().ok_or("Missing value for --bar-style")?;
+                    config.bar_style = Some(match style.as_str() {
+                        "blocks" => BarStyle::Blocks,
+                        "ascii" => BarStyle::Ascii,
+                        _ => return Err("Invalid value for --bar-style"),
+                    });
+                }
+                "--cache-scan" => config.cache_scan = true,
+                "--no-history" => config.no_history = true,
+                "--low-priority" => config.low_priority = true,
+                "--output-format" => {
+                    let format = args.next().ok_or("Missing value for --output-format")?;
+                    config.output_format = Some(match format.as_str() {
+                        "json" => ExportFormat::Json,
+                        "csv" => ExportFormat::Csv,
+                        "ncdu" => ExportFormat::Ncdu,
+                        "tree" => ExportFormat::Tree,
+                        _ => return Err("Invalid value for --output-format"),
+                    });
+                }
+                "--export-visible" => config.export_visible = true,
+                "--output" => {
+                    let path = args.next().ok_or("Missing path for --output")?;
+                    config.output = Some(path);
+                }
+                // Kept from before --output-format
+                "--csv" => {
+                    let path = args.next().ok_or("Missing path for --csv")?;
+                    config.output_format = Some(ExportFormat::Csv);
+                    config.output = Some(path);
+                }
+                "--metrics-file" => {
+                    let path = args.next().ok_or("Missing path for --metrics-file")?;
+                    config.metrics_file = Some(path);
+                }
+                "--diff-threshold" => {
+                    let size = args.next().ok_or("Missing size for --diff-threshold")?;
+                    let size = parse_size(&size).ok_or("Invalid size for --diff-threshold")?;
+                    config.diff_threshold = Some(size);
+                }
+                "--owner" => {
+                    let user = args.next().ok_or("Missing user for --owner")?;
+                    config.owner = Some(user_id(&user).ok_or("Unknown user for --owner")?);
+                }
+                _ if arg.starts_with("--") => return Err("Unknown option"),
+                _ => {
+                    if config.file_path.is_none() {
+                        config.file_path = Some(arg);
+                    } else {
+                        config.root_paths.push(arg);
+                    }
+                }
+            }
+        }
+
+        if config.confirm_key.is_some() && config.confirm_key == config.cancel_key {
+            return Err("--confirm-key and --cancel-key must differ");
+        }
+        if config.confirm_key == Some(PromptKey::Esc) {
+            return Err("Esc always cancels, it can't be --confirm-key");
+        }
+
+        if config.config_file.is_some() && config.no_config {
+            return Err("--config and --no-config can't be used together");
+        }
+
+        if config.read_only && config.yes {
+            return Err("--read-only can't be used with --yes");
+        }
+
+        if config.bench_runs.is_some() && config.bench_scan.is_none() {
+            return Err("--bench-runs needs --bench-scan");
+        }
+
+        if config.output.is_some() && config.output_format.is_none() {
+            return Err("--output needs --output-format");
+        }
+
+        if config.export_visible && config.output_format.is_none() {
+            return Err("--export-visible needs --output-format");
+        }
+
+        // Several positional paths are shown the same way as paths from stdin
+        if !config.root_paths.is_empty() {
+            if let Some(file_path) = config.file_path.take() {
+                config.root_paths.insert(0, file_path);
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Whether files are picked for deletion without starting the UI
+    pub fn is_batch_deletion(&self) -> bool {
+        self.delete_matching.is_some() || self.older_than.is_some()
+    }
+
+    /// Table columns in order, columns switched on by other options are added when missing
+    pub fn table_columns(&self) -> Vec<Column> {
+        let mut columns = self.columns.clone().unwrap_or(Column::DEFAULT.to_vec());
+        if self.both_sizes {
+            Column::OtherSize.insert_into(&mut columns);
+        }
+        if self.disk_percent {
+            Column::DiskShare.insert_into(&mut columns);
+        }
+        columns
+    }
+
+    /// Whether column was asked for with `--columns`
+    pub fn lists_column(&self, column: Column) -> bool {
+        self.columns
+            .as_ref()
+            .is_some_and(|columns| columns.contains(&column))
+    }
+}
+
+/// Parses size like "512", "100M" or "1.5GiB", units are powers of 1024
+pub fn parse_size(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let multiplier: u64 = match unit.to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1 << 10,
+        "M" | "MB" | "MIB" => 1 << 20,
+        "G" | "GB" | "GIB" => 1 << 30,
+        "T" | "TB" | "TIB" => 1 << 40,
+        _ => return None,
+    };
+    Some((number * multiplier as f64) as u64)
+}
+
+/// Parses duration like "90m", "24h" or "7d", plain number is seconds
+pub fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: u64 = number.parse().ok()?;
+    let multiplier: u64 = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return None,
+    };
+    Some(Duration::from_secs(number.checked_mul(multiplier)?))
+}
+
+/// Key answering the deletion prompt
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PromptKey {
+    Char(char),
+    Enter,
+    Esc,
+}
+
+impl PromptKey {
+    /// Single character, `enter` or `esc`
+    pub fn parse(key: &str) -> Option<PromptKey> {
+        match key.to_ascii_lowercase().as_str() {
+            "enter" => Some(PromptKey::Enter),
+            "esc" => Some(PromptKey::Esc),
+            _ => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if !c.is_whitespace() => Some(PromptKey::Char(c)),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// Name of the key as shown in prompts
+    pub fn label(&self) -> String {
+        match self {
+            PromptKey::Char(c) => c.to_string(),
+            PromptKey::Enter => "Enter".to_string(),
+            PromptKey::Esc => "Esc".to_string(),
+        }
+    }
+}
+
+/// One-shot flash shown when deletion starts waiting for confirmation
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum FlashIntensity {
+    Off,
+    /// Single short pulse
+    Subtle,
+    /// A few quick blinks
+    #[default]
+    Strong,
+}
+
+impl FlashIntensity {
+    /// Whether flash is lit after that much time since it started
+    pub fn is_lit(&self, elapsed: Duration) -> bool {
+        let millis = elapsed.as_millis();
+        match self {
+            FlashIntensity::Off => false,
+            FlashIntensity::Subtle => millis < 250,
+            FlashIntensity::Strong => millis < 900 && (millis / 150).is_multiple_of(2),
+        }
+    }
+}
+
+/// How paths are written by reports and exports
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum PathStyle {
+    #[default]
+    Absolute,
+    /// Relative to the scan root, which itself is `.`
+    Relative,
+}
+
+/// How scanned entries are written by `--output-format`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    /// Nested objects, one per entry
+    Json,
+    /// Row per entry, sorted by path
+    Csv,
+    /// ncdu export format, opened with `ncdu -f`
+    Ncdu,
+    /// Indented text tree, biggest entries first
+    Tree,
+}
+
+/// What happens after idle timeout passes without input
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum IdleAction {
+    #[default]
+    Quit,
+    /// Stop redrawing until a key arrives
+    Sleep,
+}
+
+/// Colors terminal can show
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ColorDepth {
+    /// Any RGB color
+    #[default]
+    TrueColor,
+    /// xterm palette of 256 colors
+    Ansi256,
+    /// Basic 8 colors and their bright variants
+    Ansi16,
+}
+
+impl ColorDepth {
+    /// Depth advertised by `$COLORTERM` and `$TERM`, terminals that don't tell get all colors
+    pub fn detect(colorterm: Option<&str>, term: Option<&str>) -> ColorDepth {
+        if matches!(colorterm, Some("truecolor" | "24bit")) {
+            return ColorDepth::TrueColor;
+        }
+        match term {
+            Some(term) if term.contains("256color") => ColorDepth::Ansi256,
+            Some(term)
+                if term.ends_with("16color")
+                    || matches!(term, "linux" | "vt100" | "vt220" | "ansi" | "dumb") =>
+            {
+                ColorDepth::Ansi16
+            }
+            _ => ColorDepth::TrueColor,
+        }
+    }
+
+    pub fn from_env() -> ColorDepth {
+        let colorterm = std::env::var("COLORTERM").ok();
+        let term = std::env::var("TERM").ok();
+        ColorDepth::detect(colorterm.as_deref(), term.as_deref())
+    }
+}
+
+/// How a size copied to clipboard is written
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SizeForm {
+    /// In size unit of the table, e.g. "1.40 GB"
+    #[default]
+    Formatted,
+    /// Exact number of bytes
+    Bytes,
+}
+
+impl SizeForm {
+    pub fn other(self) -> SizeForm {
+        match self {
+            SizeForm::Formatted => SizeForm::Bytes,
+            SizeForm::Bytes => SizeForm::Formatted,
+        }
+    }
+}
+
+/// What Enter does on a file
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum EnterAction {
+    /// Open with the application system associates with the file
+    #[default]
+    Open,
+    /// Show in `$PAGER`, `less` when unset
+    Pager,
+    /// Edit in `$VISUAL` or `$EDITOR`, `vi` when neither is set
+    Editor,
+    /// Copy absolute path to clipboard
+    CopyPath,
+    Nothing,
+}
+
+impl EnterAction {
+    /// Footer hint, None when Enter does nothing on files
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            EnterAction::Open => Some("open"),
+            EnterAction::Pager => Some("view"),
+            EnterAction::Editor => Some("edit"),
+            EnterAction::CopyPath => Some("copy path"),
+            EnterAction::Nothing => None,
+        }
+    }
+}
+
+/// Unit sizes in the table are shown in
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SizeUnit {
+    /// Biggest unit that keeps the number at least 1
+    #[default]
+    Auto,
+    KB,
+    MB,
+    GB,
+}
+
+/// What Space bars are relative to
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SpaceScale {
+    /// Biggest entry of current folder fills the bar
+    #[default]
+    Parent,
+    /// Whole scan fills the bar
+    Root,
+    /// Capacity of the filesystem holding current folder fills the bar
+    Disk,
+}
+
+impl SpaceScale {
+    pub fn next(&self) -> SpaceScale {
+        match self {
+            SpaceScale::Parent => SpaceScale::Root,
+            SpaceScale::Root => SpaceScale::Disk,
+            SpaceScale::Disk => SpaceScale::Parent,
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            SpaceScale::Parent => "parent",
+            SpaceScale::Root => "root",
+            SpaceScale::Disk => "disk",
+        }
+    }
+}
+
+/// Characters Space bars are drawn with
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum BarStyle {
+    #[default]
+    Blocks,
+    /// For terminals without block characters
+    Ascii,
+}
+
+impl BarStyle {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            BarStyle::Blocks => "█",
+            BarStyle::Ascii => "#",
+        }
+    }
+}
+
+/// Column of the table, besides the marker column in front of names
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    Name,
+    Size,
+    /// Own size next to the whole subtree size, or the other way round
+    OtherSize,
+    Owner,
+    Mode,
+    Age,
+    /// Size as a share of filesystem capacity
+    DiskShare,
+    /// Size bars
+    Space,
+}
+
+impl Column {
+    /// Every column in the default order, optional ones are shown once switched on
+    pub const DEFAULT: [Column; 8] = [
+        Column::Name,
+        Column::Size,
+        Column::OtherSize,
+        Column::Owner,
+        Column::Mode,
+        Column::Age,
+        Column::DiskShare,
+        Column::Space,
+    ];
+
+    /// Column by name used in `--columns`
+    pub fn parse(name: &str) -> Option<Column> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "name" => Column::Name,
+            "size" => Column::Size,
+            "other-size" => Column::OtherSize,
+            "owner" => Column::Owner,
+            "mode" | "permissions" => Column::Mode,
+            "age" => Column::Age,
+            "disk" => Column::DiskShare,
+            "space" => Column::Space,
+            _ => return None,
         })
     }
+
+    /// Adds column unless it is listed already, Space bars stay last
+    pub fn insert_into(self, columns: &mut Vec<Column>) {
+        if columns.contains(&self) {
+            return;
+        }
+        let at = match columns.last() {
+            Some(Column::Space) => columns.len() - 1,
+            _ => columns.len(),
+        };
+        columns.insert(at, self);
+    }
 }
 
 #[derive(Debug)]
@@ -19,9 +971,227 @@ pub struct UIConfig {
     pub colored: bool,
     pub confirming_deletion: bool,
     pub sort_by: SortBy,
+    /// Order of `sort_by` is flipped, e.g. smallest first
+    pub sort_reversed: bool,
+    /// Folders go before files whatever entries are sorted by, they always do by name
+    pub folders_first: bool,
     pub move_to_trash: bool,
-    pub open_file: bool,
+    /// Deleting, sweeping, redoing and purging trash are refused
+    pub read_only: bool,
+    /// What Enter does on a file
+    pub enter_action: EnterAction,
+    /// How `Y` copies size of selected entry, `Alt-y` copies it the other way
+    pub copy_size: SizeForm,
+    /// Colors of rendered frames are reduced to those terminal can show
+    pub color_depth: ColorDepth,
+    /// Show contents of selected entry next to the table
+    pub preview: bool,
+    /// Bytes read from the start of a file for the preview pane
+    pub preview_size: u64,
+    /// Show sizes selected folder had at cached scans next to the table
+    pub growth: bool,
+    /// Show share of each top-level entry in a stacked bar above the table at the root
+    pub overview: bool,
+    /// Header shows path from root to current folder, with size of each folder on it
+    pub breadcrumb: bool,
+    /// Globs of paths whose deletion needs the confirm phrase typed, matched against canonical paths
+    pub danger_patterns: Vec<String>,
+    /// Right-align sizes in their column, so digits line up
+    pub align_sizes: bool,
+    /// Unit sizes in the table are shown in
+    pub size_unit: SizeUnit,
     pub debug_enabled: bool,
+    /// Rescan current folder every that many seconds, off when None
+    pub auto_refresh_secs: Option<u64>,
+    /// Symbol in front of selected row, keeps a gap to the first column when not empty
+    pub highlight_symbol: String,
+    /// Mark files whose apparent size exceeds allocated one more than that many times
+    pub sparse_ratio: Option<f64>,
+    /// Show size of files directly in folders instead of whole subtree
+    pub own_size: bool,
+    /// Show a second size column, with whole subtree when own size is shown and own size otherwise
+    pub both_sizes: bool,
+    /// Sizes the second size column shows, by name of current folder entries
+    pub other_sizes: HashMap<OsString, u64>,
+    /// Show current folder as a treemap instead of a table
+    pub treemap: bool,
+    /// Show current folder as it was when entered next to its current state
+    pub split_view: bool,
+    /// Show owner column, only available on Unix
+    pub show_owner: bool,
+    /// Show permissions column, only available on Unix
+    pub show_permissions: bool,
+    /// Show how long ago entries were modified in their own column
+    pub show_age: bool,
+    /// Ring terminal bell when a long scan finishes
+    pub bell_on_finish: bool,
+    /// Send desktop notification when a long scan finishes
+    pub notify_on_finish: bool,
+    /// Scans shorter than that many seconds finish silently
+    pub notify_after_secs: u64,
+    /// How strongly selection flashes when deletion waits for confirmation
+    pub confirm_flash: FlashIntensity,
+    /// Ring terminal bell when deletion waits for confirmation
+    pub confirm_bell: bool,
+    /// Cancel deletion waiting for confirmation after that many seconds without input, off when None
+    pub confirm_timeout_secs: Option<u64>,
+    /// Key confirming pending deletion
+    pub confirm_key: PromptKey,
+    /// Key cancelling pending deletion, Esc cancels too
+    pub cancel_key: PromptKey,
+    /// Ask once more before quitting a session that deleted something
+    pub confirm_quit: bool,
+    /// Quit waits for confirmation, session summary is shown meanwhile
+    pub confirming_quit: bool,
+    /// Ask before leaving a folder with marks, leaving discards them
+    pub confirm_leave: bool,
+    /// Quit or go idle after that many seconds without input, off when None
+    pub idle_timeout_secs: Option<u64>,
+    pub idle_action: IdleAction,
+    /// Confirmation flash is lit in current frame
+    pub flashing: bool,
+    /// Spinners and flashes are replaced with static indicators
+    pub no_animations: bool,
+    /// Moving past the last entry selects the first one, and the other way around
+    pub wrap_navigation: bool,
+    /// Hides current folder entries whose titles don't match
+    pub name_filter: NameFilter,
+    /// Keys are typed into name filter query
+    pub filter_editing: bool,
+    /// How far back recently modified view looks, kept when the view is reopened
+    pub recent_window: Duration,
+    /// Nesting level from which deep folders view lists folders, kept when the view is reopened
+    pub min_depth: usize,
+    /// Shows only entries owned by user with that id, or holding something they own
+    pub owner_filter: Option<u32>,
+    /// Bytes owned by filtered user in current folder entries, by name
+    pub owned_sizes: HashMap<OsString, u64>,
+    /// Shows only entries that could not be read or hold something that couldn't
+    pub errors_only: bool,
+    /// Names of current folder entries with read errors at or below them
+    pub error_names: HashSet<OsString>,
+    /// Columns taken by a log message in debug panel before it is shortened
+    pub log_width: usize,
+    /// Changes smaller than that many bytes are hidden in comparison with previous scan
+    pub diff_threshold: u64,
+    /// What Space bars are relative to
+    pub space_scale: SpaceScale,
+    /// Size filling the whole Space bar, biggest entry of current folder when None
+    pub space_total: Option<u64>,
+    /// Show sizes as a share of filesystem capacity
+    pub show_disk_percent: bool,
+    /// Capacity of filesystem holding current folder, the column is hidden when None
+    pub disk_capacity: Option<u64>,
+    /// Time since last scan finished, only once it is older than [`STALE_SCAN_AGE`]
+    pub scan_age: Option<Duration>,
+    /// Hides entries smaller than that, off when None
+    pub min_size: Option<u64>,
+    /// Minimum sizes picked by `Alt` + number keys, first one is `Alt-1`
+    pub size_presets: Vec<u64>,
+    /// Columns taken by Space bars, the column is hidden when 0
+    pub space_width: usize,
+    /// Order of table columns, ones switched off are skipped
+    pub columns: Vec<Column>,
+    /// Characters Space bars are drawn with
+    pub bar_style: BarStyle,
+    /// Extensions scans are limited to, folders without any such file are hidden
+    pub only_extensions: Vec<String>,
+}
+
+impl UIConfig {
+    /// Columns the table shows, in order
+    pub fn visible_columns(&self) -> Vec<Column> {
+        self.columns
+            .iter()
+            .copied()
+            .filter(|column| match column {
+                Column::Name | Column::Size => true,
+                Column::OtherSize => self.both_sizes,
+                Column::Owner => self.show_owner,
+                Column::Mode => self.show_permissions,
+                Column::Age => self.show_age,
+                Column::DiskShare => self.disk_capacity.is_some(),
+                Column::Space => self.space_width > 0,
+            })
+            .collect()
+    }
+
+    /// Whether entry is shown with current filters, parent entry is always shown
+    pub fn is_visible(&self, entry: &FolderEntry) -> bool {
+        entry.kind == FolderEntryType::Parent
+            || (self.name_filter.matches(&entry.title)
+                && (self.owner_filter.is_none() || self.owned_sizes.contains_key(&entry.name))
+                && (!self.errors_only || self.error_names.contains(&entry.name))
+                && self.is_big_enough(entry)
+                && self.has_allowed_files(entry))
+    }
+
+    /// Entries of folder shown with current filters and their indexes, in folder order.
+    ///
+    /// Everything listing what is on screen goes through here, so it can't drift from the table.
+    pub fn visible_entries<'a>(
+        &'a self,
+        folder: &'a Folder,
+    ) -> impl Iterator<Item = (usize, &'a FolderEntry)> + 'a {
+        folder
+            .entries()
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| self.is_visible(entry))
+    }
+
+    /// Compares size shown for entry with minimum size, entries still being scanned are kept
+    fn is_big_enough(&self, entry: &FolderEntry) -> bool {
+        let Some(min_size) = self.min_size else {
+            return true;
+        };
+        let size = match self.owner_filter {
+            Some(_) => self.owned_sizes.get(&entry.name).cloned(),
+            None => entry.size,
+        };
+        size.is_none_or(|size| size >= min_size)
+    }
+
+    /// Folders scanned without a single file of allowed extensions are empty, so not shown
+    fn has_allowed_files(&self, entry: &FolderEntry) -> bool {
+        self.only_extensions.is_empty()
+            || entry.kind != FolderEntryType::Folder
+            || entry.size != Some(0)
+    }
+
+    /// Separates symbol from the first column so a bare arrow does not stick to it
+    pub fn set_highlight_symbol(&mut self, symbol: &str) {
+        self.highlight_symbol = if symbol.is_empty() || symbol.ends_with(char::is_whitespace) {
+            symbol.to_string()
+        } else {
+            format!("{} ", symbol)
+        };
+    }
 }
 
 pub const EVENT_INTERVAL: u64 = 100;
+pub const SPARSE_RATIO: f64 = 2.0;
+pub const NOTIFY_AFTER_SECS: u64 = 10;
+pub const LOG_WIDTH: usize = 40;
+pub const DIFF_THRESHOLD: u64 = 1 << 20;
+pub const MAX_COUNT: usize = 999;
+pub const RECENT_ROOTS: usize = 10;
+pub const PREVIEW_SIZE: u64 = 64 << 10;
+/// Cached scans folder sizes are kept from by default
+pub const HISTORY_DEPTH: usize = 12;
+/// Entries right below a root past which it is treated as broad
+pub const BROAD_ENTRIES: usize = 5000;
+/// Times `--bench-scan` scans its path by default
+pub const BENCH_RUNS: usize = 5;
+/// Share of total below which entries collapse into "other" in the overview bar
+pub const OVERVIEW_MIN_SHARE: f64 = 0.03;
+pub const SIZE_PRESETS: [u64; 4] = [1 << 20, 10 << 20, 100 << 20, 1 << 30];
+/// Scans older than that are marked in the header, files may have changed since
+pub const STALE_SCAN_AGE: Duration = Duration::from_secs(30 * 60);
+/// How often scan root is checked for going away, e.g. an unplugged drive
+pub const ROOT_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+/// How long errors are held among debug panel logs by default
+pub const LOG_HOLD: Duration = Duration::from_secs(5);
+pub const RECENT_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+/// Folders nested deeper than that are rarely made by hand
+pub const MIN_DEPTH: usize = 6;
@@ -0,0 +1,167 @@
+use crate::config::{Column, InitConfig, UIConfig};
+use crate::filter::{MatchMode, NameFilter};
+use crate::fs::SortBy;
+
+/// Options a profile may hold, the ones changing how the scanned tree is shown
+pub const PROFILE_OPTIONS: [&str; 7] = [
+    "--sort",
+    "--columns",
+    "--min-size",
+    "--filter",
+    "--glob",
+    "--folders-first",
+    "--errors-only",
+];
+
+/// Named bundle of table options, switched to at start with `--profile` or with `P`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile {
+    pub name: String,
+    /// Options the way they are written on the command line
+    pub args: Vec<String>,
+}
+
+impl Profile {
+    pub fn new(name: &str, args: &[&str]) -> Self {
+        Profile {
+            name: name.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+        }
+    }
+
+    /// Options of the profile parsed, anything besides [`PROFILE_OPTIONS`] is an error
+    pub fn config(&self) -> Result<InitConfig, String> {
+        if let Some(option) = self
+            .args
+            .iter()
+            .find(|arg| arg.starts_with("--") && !PROFILE_OPTIONS.contains(&arg.as_str()))
+        {
+            return Err(format!("{option} can't be used in profile {}", self.name));
+        }
+        let config = InitConfig::build(std::iter::once(String::new()).chain(self.args.clone()))
+            .map_err(|err| format!("{err}, in profile {}", self.name))?;
+        if config.file_path.is_some() {
+            return Err(format!("Paths can't be used in profile {}", self.name));
+        }
+        Ok(config)
+    }
+}
+
+/// Profiles shipped with wiper, config file ones with the same name replace them
+pub fn builtin_profiles() -> Vec<Profile> {
+    vec![
+        Profile::new(
+            "media",
+            &[
+                "--sort",
+                "size",
+                "--min-size",
+                "100M",
+                "--columns",
+                "name,size,age,space",
+            ],
+        ),
+        Profile::new(
+            "logs",
+            &[
+                "--sort",
+                "modified",
+                "--filter",
+                "*.log*",
+                "--glob",
+                "--columns",
+                "name,size,age",
+            ],
+        ),
+    ]
+}
+
+/// Config file profiles added to the built-in ones, replacing those of the same name
+pub fn with_builtin_profiles(profiles: &[Profile]) -> Vec<Profile> {
+    let mut all: Vec<Profile> = builtin_profiles()
+        .into_iter()
+        .filter(|builtin| profiles.iter().all(|profile| profile.name != builtin.name))
+        .collect();
+    all.extend(profiles.iter().cloned());
+    all
+}
+
+/// Table options as wiper started, profiles apply on top of them so switching between two
+/// doesn't keep anything from the first one
+#[derive(Debug, Clone, Default)]
+pub struct Lens {
+    sort_by: SortBy,
+    folders_first: bool,
+    columns: Vec<Column>,
+    both_sizes: bool,
+    show_owner: bool,
+    show_permissions: bool,
+    show_age: bool,
+    show_disk_percent: bool,
+    min_size: Option<u64>,
+    name_filter: NameFilter,
+    errors_only: bool,
+}
+
+impl Lens {
+    pub fn of(config: &UIConfig) -> Self {
+        Lens {
+            sort_by: config.sort_by,
+            folders_first: config.folders_first,
+            columns: config.columns.clone(),
+            both_sizes: config.both_sizes,
+            show_owner: config.show_owner,
+            show_permissions: config.show_permissions,
+            show_age: config.show_age,
+            show_disk_percent: config.show_disk_percent,
+            min_size: config.min_size,
+            name_filter: config.name_filter.clone(),
+            errors_only: config.errors_only,
+        }
+    }
+}
+
+impl UIConfig {
+    /// Sets table options to provided lens with options of profile on top, None restores the
+    /// lens alone
+    pub fn apply_profile(&mut self, lens: &Lens, profile: Option<&InitConfig>) {
+        let default = InitConfig::default();
+        let profile = profile.unwrap_or(&default);
+
+        self.sort_by = profile.sort_by.unwrap_or(lens.sort_by);
+        self.sort_reversed = false;
+        self.folders_first = lens.folders_first || profile.folders_first;
+        match &profile.columns {
+            Some(_) => {
+                self.columns = profile.table_columns();
+                self.both_sizes = profile.lists_column(Column::OtherSize);
+                self.show_owner = cfg!(unix) && profile.lists_column(Column::Owner);
+                self.show_permissions = cfg!(unix) && profile.lists_column(Column::Mode);
+                self.show_age = profile.lists_column(Column::Age);
+                self.show_disk_percent = profile.lists_column(Column::DiskShare);
+            }
+            None => {
+                self.columns = lens.columns.clone();
+                self.both_sizes = lens.both_sizes;
+                self.show_owner = lens.show_owner;
+                self.show_permissions = lens.show_permissions;
+                self.show_age = lens.show_age;
+                self.show_disk_percent = lens.show_disk_percent;
+            }
+        }
+        self.min_size = profile.min_size.or(lens.min_size);
+        self.name_filter = match &profile.name_filter {
+            Some(query) => NameFilter {
+                query: query.clone(),
+                case: lens.name_filter.case,
+                mode: match profile.filter_glob {
+                    true => MatchMode::Glob,
+                    false => MatchMode::Substring,
+                },
+            },
+            None => lens.name_filter.clone(),
+        };
+        self.filter_editing = false;
+        self.errors_only = lens.errors_only || profile.errors_only;
+    }
+}
@@ -1,23 +1,272 @@
 use crate::app::{App, AppResult};
-use crate::fs::{DataStore, DataStoreKey};
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crate::config::PromptKey;
+use crate::fs::{DataStore, DataStoreKey, SortBy};
+use crate::views::View;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 
 pub fn handle_key_events<S: DataStore<DataStoreKey>>(
     key_event: KeyEvent,
     app: &mut App<S>,
 ) -> AppResult<()> {
+    app.on_input();
+    if app.idle {
+        app.on_wake();
+        return Ok(());
+    }
+    // Any key but `q` keeps the app running
+    if app.ui_config.confirming_quit {
+        match key_event.code {
+            KeyCode::Char('q') => app.quit(),
+            KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => app.quit(),
+            _ => app.on_cancel_quit(),
+        }
+        return Ok(());
+    }
+    // Anything but `y` stays in the folder with its marks
+    if app.pending_leave.is_some() {
+        match key_event.code {
+            KeyCode::Char('y') => app.on_confirm_leave(),
+            KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => app.quit(),
+            _ => app.on_cancel_leave(),
+        }
+        return Ok(());
+    }
+    // Debug panel can be toggled from anywhere, including views and filter typing
+    if key_event.code == KeyCode::F(12) {
+        app.toggle_debug();
+        return Ok(());
+    }
+    if let Some(prompt) = &mut app.path_prompt {
+        match key_event.code {
+            KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => app.quit(),
+            KeyCode::Esc => app.on_cancel_open_root(),
+            KeyCode::Enter => app.on_submit_open_root(),
+            KeyCode::Tab => prompt.on_complete(),
+            KeyCode::Backspace => prompt.on_backspace(),
+            KeyCode::Up => prompt.on_history_up(),
+            KeyCode::Down => prompt.on_history_down(),
+            KeyCode::Char(c) => prompt.on_input(c),
+            _ => {}
+        }
+        return Ok(());
+    }
+    if let Some(menu) = &mut app.sort_menu {
+        match key_event.code {
+            KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => app.quit(),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('s') => app.on_close_sort_menu(),
+            KeyCode::Up | KeyCode::Char('k') => menu.cursor_up(),
+            KeyCode::Down | KeyCode::Char('j') => menu.cursor_down(),
+            KeyCode::Char(' ') => menu.toggle(),
+            KeyCode::Enter => app.on_apply_sort_menu(),
+            _ => {}
+        }
+        return Ok(());
+    }
+    if let Some(menu) = &mut app.profile_menu {
+        match key_event.code {
+            KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => app.quit(),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('P') => app.on_close_profile_menu(),
+            KeyCode::Up | KeyCode::Char('k') => menu.cursor_up(),
+            KeyCode::Down | KeyCode::Char('j') => menu.cursor_down(),
+            KeyCode::Enter => app.on_apply_profile_menu(),
+            _ => {}
+        }
+        return Ok(());
+    }
+    if let View::Filesystems(view) = &mut app.view {
+        match key_event.code {
+            KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => app.quit(),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('M') => app.close_view(),
+            KeyCode::Up | KeyCode::Char('k') => view.cursor_up(),
+            KeyCode::Down | KeyCode::Char('j') => view.cursor_down(),
+            KeyCode::Char('s') => view.toggle_sorting(),
+            _ => {}
+        }
+        return Ok(());
+    }
+    if let View::Errors(view) = &mut app.view {
+        match key_event.code {
+            KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => app.quit(),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('e') => app.close_view(),
+            KeyCode::Up | KeyCode::Char('k') => view.cursor_up(),
+            KeyCode::Down | KeyCode::Char('j') => view.cursor_down(app.scan_errors.len()),
+            _ => {}
+        }
+        return Ok(());
+    }
+    if let View::Recent(view) = &mut app.view {
+        match key_event.code {
+            KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => app.quit(),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('r') => app.close_view(),
+            KeyCode::Up | KeyCode::Char('k') => view.cursor_up(),
+            KeyCode::Down | KeyCode::Char('j') => view.cursor_down(),
+            KeyCode::Char('w') => app.on_cycle_recent_window(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    if let View::Verify(view) = &mut app.view {
+        match key_event.code {
+            KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => app.quit(),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('V') => app.close_view(),
+            KeyCode::Up | KeyCode::Char('k') => view.cursor_up(),
+            KeyCode::Down | KeyCode::Char('j') => view.cursor_down(),
+            KeyCode::Enter => app.on_reveal_mismatch(),
+            KeyCode::Char('R') => app.on_resync(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    if let View::Deep(view) = &mut app.view {
+        match key_event.code {
+            KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => app.quit(),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('L') => app.close_view(),
+            KeyCode::Up | KeyCode::Char('k') => view.cursor_up(),
+            KeyCode::Down | KeyCode::Char('j') => view.cursor_down(),
+            KeyCode::Char('+') | KeyCode::Char('=') => app.on_change_min_depth(true),
+            KeyCode::Char('-') => app.on_change_min_depth(false),
+            KeyCode::Enter => app.on_reveal_deep_folder(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    if let View::Diff(view) = &mut app.view {
+        match key_event.code {
+            KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => app.quit(),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('D') => app.close_view(),
+            KeyCode::Up | KeyCode::Char('k') => view.cursor_up(),
+            KeyCode::Down | KeyCode::Char('j') => view.cursor_down(),
+            KeyCode::Char('a') => view.toggle_show_all(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    if let View::Names(view) = &mut app.view {
+        match key_event.code {
+            KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => app.quit(),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('N') => app.close_view(),
+            KeyCode::Up | KeyCode::Char('k') => view.cursor_up(),
+            KeyCode::Down | KeyCode::Char('j') => view.cursor_down(),
+            KeyCode::Enter => app.on_reveal_name(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    let confirm_key = app.ui_config.confirm_key;
+    if let View::Trash(view) = &mut app.view {
+        match key_event.code {
+            _ if view.confirming_purge && is_prompt_key(confirm_key, &key_event) => {
+                app.on_purge_session_trash()
+            }
+            KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => app.quit(),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('T') => app.close_view(),
+            KeyCode::Up | KeyCode::Char('k') => view.cursor_up(),
+            KeyCode::Down | KeyCode::Char('j') => view.cursor_down(app.session_trash.len()),
+            KeyCode::Char('d') if !view.confirming_purge => app.on_purge_session_trash(),
+            _ => view.confirming_purge = false,
+        }
+        return Ok(());
+    }
+
+    if app.is_typing_confirmation() {
+        match key_event.code {
+            KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => app.quit(),
+            KeyCode::Esc => app.on_escape(),
+            KeyCode::Enter => app.on_confirm_submit(),
+            KeyCode::Backspace => app.on_confirm_backspace(),
+            KeyCode::Char(c) => app.on_confirm_input(c),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    if app.ui_config.filter_editing {
+        match key_event.code {
+            KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => app.quit(),
+            KeyCode::Char('g') if key_event.modifiers == KeyModifiers::CONTROL => {
+                app.on_toggle_filter_mode()
+            }
+            KeyCode::Esc => app.clear_filter(),
+            KeyCode::Enter => app.on_filter_submit(),
+            KeyCode::Backspace => app.on_filter_backspace(),
+            KeyCode::Up => app.on_cursor_up(),
+            KeyCode::Down => app.on_cursor_down(),
+            KeyCode::Char(c) => app.on_filter_input(c),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // Answers to the deletion prompt win over whatever else the key does
+    if app.ui_config.confirming_deletion {
+        if is_prompt_key(app.ui_config.confirm_key, &key_event) {
+            app.pending_count = None;
+            app.on_delete();
+            return Ok(());
+        }
+        if is_prompt_key(app.ui_config.cancel_key, &key_event) {
+            app.pending_count = None;
+            app.on_escape();
+            return Ok(());
+        }
+        // Pressing `d` again confirms nothing when another key does
+        if key_event.code == KeyCode::Char('d') && key_event.modifiers == KeyModifiers::NONE {
+            return Ok(());
+        }
+    }
+
+    if let KeyCode::Char(c) = key_event.code {
+        if key_event.modifiers == KeyModifiers::NONE && app.on_count_digit(c) {
+            return Ok(());
+        }
+        // Bare digits are counts, with `Alt` they pick a minimum size
+        if key_event.modifiers == KeyModifiers::ALT {
+            if let Some(preset) = c.to_digit(10) {
+                app.pending_count = None;
+                app.on_size_preset(preset as usize);
+                return Ok(());
+            }
+        }
+    }
+    // Any other key uses up pending count, only motions repeat
+    let pending_count = app.pending_count.take();
+    let count = pending_count.unwrap_or(1);
+
     match key_event.code {
+        KeyCode::Esc if pending_count.is_some() => {}
         KeyCode::Esc => {
             app.on_escape();
         }
+        KeyCode::Char('/') => {
+            app.on_start_filter();
+        }
         KeyCode::Char('q') => {
-            app.quit();
+            app.on_quit();
         }
         KeyCode::Up | KeyCode::Char('k') => {
-            app.on_cursor_up();
+            for _ in 0..count {
+                app.on_cursor_up();
+            }
         }
         KeyCode::Down | KeyCode::Char('j') => {
-            app.on_cursor_down();
+            for _ in 0..count {
+                app.on_cursor_down();
+            }
+        }
+        KeyCode::Char(']') => {
+            for _ in 0..count {
+                app.on_next_error_entry();
+            }
+        }
+        KeyCode::Char('[') => {
+            for _ in 0..count {
+                app.on_previous_error_entry();
+            }
         }
         KeyCode::Enter | KeyCode::Right | KeyCode::Char('l') => {
             app.on_enter();
@@ -25,6 +274,9 @@ pub fn handle_key_events<S: DataStore<DataStoreKey>>(
         KeyCode::Backspace | KeyCode::Left | KeyCode::Char('h') => {
             app.on_backspace();
         }
+        KeyCode::Home | KeyCode::Char('~') => {
+            app.on_go_to_root();
+        }
         // Exit application on `Ctrl-C`
         KeyCode::Char('c') | KeyCode::Char('C') => {
             if key_event.modifiers == KeyModifiers::CONTROL {
@@ -34,7 +286,22 @@ pub fn handle_key_events<S: DataStore<DataStoreKey>>(
             }
         }
         KeyCode::Char('s') => {
-            app.on_toggle_sorting();
+            app.on_open_sort_menu();
+        }
+        KeyCode::Char('P') => {
+            app.on_open_profile_menu();
+        }
+        KeyCode::Char('n') => {
+            app.on_sort_by(SortBy::Title);
+        }
+        KeyCode::Char('z') => {
+            app.on_sort_by(SortBy::Size);
+        }
+        KeyCode::Char('m') => {
+            app.on_sort_by(SortBy::Modified);
+        }
+        KeyCode::Char('#') => {
+            app.on_sort_by(SortBy::Count);
         }
         KeyCode::Char('d') => {
             if key_event.modifiers == KeyModifiers::CONTROL {
@@ -43,6 +310,118 @@ pub fn handle_key_events<S: DataStore<DataStoreKey>>(
                 app.on_delete();
             }
         }
+        KeyCode::Char('x') => {
+            app.on_sweep_empty_folders();
+        }
+        KeyCode::Char('y') => {
+            if key_event.modifiers == KeyModifiers::ALT {
+                app.on_copy_size(true);
+            } else {
+                app.on_copy_size_report();
+            }
+        }
+        KeyCode::Char('Y') => {
+            app.on_copy_size(false);
+        }
+        KeyCode::Char(' ') => {
+            app.on_toggle_mark();
+        }
+        KeyCode::Char('M') => {
+            app.on_toggle_filesystems_view();
+        }
+        KeyCode::Char('v') => {
+            app.on_toggle_treemap();
+        }
+        KeyCode::Char('S') => {
+            app.on_toggle_split_view();
+        }
+        KeyCode::Char('i') => {
+            app.on_toggle_preview();
+        }
+        KeyCode::Char('g') => {
+            app.on_toggle_growth();
+        }
+        KeyCode::Char('B') => {
+            app.on_toggle_overview();
+        }
+        KeyCode::Char('W') => {
+            app.on_toggle_breadcrumb();
+        }
+        KeyCode::Char('A') => {
+            app.on_acknowledge_error();
+        }
+        KeyCode::Char('o') => {
+            if key_event.modifiers == KeyModifiers::CONTROL {
+                app.on_toggle_both_sizes();
+            } else {
+                app.on_toggle_own_size();
+            }
+        }
+        KeyCode::Char('O') => {
+            app.on_start_open_root();
+        }
+        KeyCode::Char('b') => {
+            app.on_cycle_space_scale();
+        }
+        KeyCode::Char('u') => {
+            app.on_toggle_owner_column();
+        }
+        KeyCode::Char('p') => {
+            app.on_toggle_permissions_column();
+        }
+        KeyCode::Char('a') => {
+            app.on_toggle_age_column();
+        }
+        KeyCode::Char('f') => {
+            app.on_follow_largest();
+        }
+        KeyCode::Char('F') => {
+            app.on_follow_largest_all();
+        }
+        KeyCode::Char('e') => {
+            app.on_toggle_errors_view();
+        }
+        KeyCode::Char('E') => {
+            app.on_toggle_errors_only();
+        }
+        KeyCode::Char('T') => {
+            app.on_toggle_trash_view();
+        }
+        KeyCode::Char('r') => {
+            if key_event.modifiers == KeyModifiers::CONTROL {
+                for _ in 0..count {
+                    app.on_redo();
+                }
+            } else {
+                app.on_toggle_recent_view();
+            }
+        }
+        KeyCode::Char('U') => {
+            for _ in 0..count {
+                app.on_undo();
+            }
+        }
+        KeyCode::Char('<') => {
+            app.on_undo_all();
+        }
+        KeyCode::Char('>') => {
+            app.on_redo_all();
+        }
+        KeyCode::Char('R') => {
+            app.on_rescan();
+        }
+        KeyCode::Char('N') => {
+            app.on_toggle_names_view();
+        }
+        KeyCode::Char('L') => {
+            app.on_toggle_deep_view();
+        }
+        KeyCode::Char('V') => {
+            app.on_toggle_verify_view();
+        }
+        KeyCode::Char('D') => {
+            app.on_toggle_diff_view();
+        }
         KeyCode::Char('t') => {
             app.on_toggle_move_to_trash();
         }
@@ -50,3 +429,38 @@ pub fn handle_key_events<S: DataStore<DataStoreKey>>(
     }
     Ok(())
 }
+
+/// Whether key event is provided prompt key, letters may come with Shift
+fn is_prompt_key(key: PromptKey, key_event: &KeyEvent) -> bool {
+    let plain = matches!(
+        key_event.modifiers,
+        KeyModifiers::NONE | KeyModifiers::SHIFT
+    );
+    match key {
+        PromptKey::Char(c) => plain && key_event.code == KeyCode::Char(c),
+        PromptKey::Enter => plain && key_event.code == KeyCode::Enter,
+        PromptKey::Esc => key_event.code == KeyCode::Esc,
+    }
+}
+
+/// Only left clicks are handled, on table headers and treemap cells
+pub fn handle_mouse_events<S: DataStore<DataStoreKey>>(
+    mouse_event: MouseEvent,
+    app: &mut App<S>,
+) -> AppResult<()> {
+    if mouse_event.kind != MouseEventKind::Down(MouseButton::Left) {
+        return Ok(());
+    }
+    app.on_input();
+    if app.idle {
+        app.on_wake();
+        return Ok(());
+    }
+    if !app.ui_config.confirming_deletion
+        && !app.ui_config.confirming_quit
+        && app.pending_leave.is_none()
+    {
+        app.on_click(mouse_event.column, mouse_event.row);
+    }
+    Ok(())
+}
@@ -1,9 +1,11 @@
 mod handler;
 
-pub use handler::handle_key_events;
+pub use handler::{handle_key_events, handle_mouse_events};
 
 use crossterm::event::{Event as CrosstermEvent, KeyEvent, MouseEvent};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant};
 
@@ -23,6 +25,8 @@ pub enum Event {
 #[derive(Debug)]
 pub struct EventHandler {
     receiver: Receiver<Event>,
+    /// Terminal is left alone while set, e.g. while another program runs in it
+    paused: Arc<AtomicBool>,
 }
 
 impl EventHandler {
@@ -30,10 +34,16 @@ impl EventHandler {
         let tick_rate = Duration::from_millis(tick_rate);
         let (sender, receiver) = mpsc::channel();
         let sender_clone = sender.clone();
+        let paused = Arc::new(AtomicBool::new(false));
+        let paused_clone = paused.clone();
 
         thread::spawn(move || {
             let mut last_tick = Instant::now();
             loop {
+                if paused_clone.load(Ordering::Relaxed) {
+                    thread::sleep(Duration::from_millis(10));
+                    continue;
+                }
                 if last_tick.elapsed() >= tick_rate {
                     if sender_clone.send(Event::Tick).is_err() {
                         break; // Exit if the receiver has dropped
@@ -60,7 +70,16 @@ impl EventHandler {
             }
         });
 
-        Self { receiver }
+        Self { receiver, paused }
+    }
+
+    /// Stops reading terminal events until [`EventHandler::resume`]
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed);
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::Relaxed);
     }
 
     pub fn next(&self) -> Result<Event, mpsc::RecvError> {
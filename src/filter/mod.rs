@@ -0,0 +1,133 @@
+use crate::config::content_lines;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// How query letter case is compared with titles
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CaseMode {
+    /// Case-insensitive unless query has an uppercase letter
+    #[default]
+    Smart,
+    Sensitive,
+    Insensitive,
+}
+
+/// How query is compared with titles
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MatchMode {
+    /// Title contains query
+    #[default]
+    Substring,
+    /// Whole title matches query with `*` and `?` wildcards
+    Glob,
+}
+
+/// Query matched against entry titles, shared by every name based feature
+#[derive(Debug, Clone, Default)]
+pub struct NameFilter {
+    pub query: String,
+    pub case: CaseMode,
+    pub mode: MatchMode,
+}
+
+impl NameFilter {
+    pub fn is_active(&self) -> bool {
+        !self.query.is_empty()
+    }
+
+    pub fn is_case_sensitive(&self) -> bool {
+        match self.case {
+            CaseMode::Smart => self.query.chars().any(char::is_uppercase),
+            CaseMode::Sensitive => true,
+            CaseMode::Insensitive => false,
+        }
+    }
+
+    /// Empty query matches everything
+    pub fn matches(&self, title: &str) -> bool {
+        if !self.is_active() {
+            return true;
+        }
+        let (query, title) = match self.is_case_sensitive() {
+            true => (self.query.clone(), title.to_string()),
+            false => (self.query.to_lowercase(), title.to_lowercase()),
+        };
+        match self.mode {
+            MatchMode::Substring => title.contains(&query),
+            MatchMode::Glob => glob_match(&query, &title),
+        }
+    }
+
+    pub fn toggle_mode(&mut self) {
+        self.mode = match self.mode {
+            MatchMode::Substring => MatchMode::Glob,
+            MatchMode::Glob => MatchMode::Substring,
+        };
+    }
+
+    /// Short description of matching rules, e.g. "smart case, glob"
+    pub fn describe(&self) -> String {
+        let case = match self.case {
+            CaseMode::Smart => "smart case",
+            CaseMode::Sensitive => "case-sensitive",
+            CaseMode::Insensitive => "ignore case",
+        };
+        let mode = match self.mode {
+            MatchMode::Substring => "substring",
+            MatchMode::Glob => "glob",
+        };
+        format!("{}, {}", case, mode)
+    }
+}
+
+/// Glob patterns of an exclude file, one per line. Blank lines and `#` comments are skipped.
+pub fn exclude_patterns(contents: &str) -> Vec<String> {
+    content_lines(contents.lines()).map(String::from).collect()
+}
+
+/// Patterns of exclude file at path, read again on each rescan so edits apply without restart
+pub fn read_exclude_file(path: &Path) -> io::Result<Vec<String>> {
+    Ok(exclude_patterns(&fs::read_to_string(path)?))
+}
+
+/// First pattern canonical path matches, paths that can't be resolved are matched as they are
+pub fn danger_pattern<'a>(path: &Path, patterns: &'a [String]) -> Option<&'a str> {
+    if patterns.is_empty() {
+        return None;
+    }
+    let path = fs::canonicalize(path).unwrap_or(path.to_path_buf());
+    let path = path.to_string_lossy();
+    patterns
+        .iter()
+        .find(|pattern| glob_match(pattern, &path))
+        .map(String::as_str)
+}
+
+/// Matches whole text against pattern where `*` is any run of characters and `?` is one
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position of last `*` in pattern and text position it was tried at
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, star_t)) = backtrack {
+            // Let the last `*` take one more character
+            p = star + 1;
+            t = star_t + 1;
+            backtrack = Some((star, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
@@ -0,0 +1,169 @@
+use crate::fs::{Folder, FolderEntry, FolderEntryType};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Single file listed in archive headers
+struct ArchiveFile {
+    components: Vec<String>,
+    size: u64,
+    compressed: Option<u64>,
+}
+
+/// Whether file can be browsed as a folder
+pub fn is_archive(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("zip" | "jar" | "tar")
+    )
+}
+
+/// Lists archive contents as read-only folders keyed by their path inside the archive,
+/// archive root is keyed by empty path. Only headers are read, nothing is extracted.
+pub fn archive_to_folders(path: &Path) -> io::Result<Vec<(PathBuf, Folder)>> {
+    let (files, dirs) = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("tar") => read_tar(path)?,
+        _ => read_zip(path)?,
+    };
+
+    let title = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut folders: HashMap<PathBuf, Folder> = HashMap::new();
+    folders.insert(PathBuf::new(), Folder::new(title));
+
+    for components in dirs {
+        add_folders(&mut folders, &components);
+    }
+    for file in files {
+        let Some((title, parents)) = file.components.split_last() else {
+            continue;
+        };
+        let parent = add_folders(&mut folders, parents);
+        if let Some(folder) = folders.get_mut(&parent) {
+            folder.push_entry(FolderEntry {
+                kind: FolderEntryType::File,
+                title: title.clone(),
+                name: title.into(),
+                size: Some(file.size),
+                is_loaded: true,
+                device: None,
+                allocated: file.compressed,
+                modified: None,
+                owner: None,
+                mode: None,
+            });
+        }
+    }
+
+    // Deepest folders first, so every folder is complete before its size reaches the parent
+    let mut paths: Vec<PathBuf> = folders.keys().cloned().collect();
+    paths.sort_by_key(|path| std::cmp::Reverse(path.components().count()));
+    for path in paths {
+        let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+            continue;
+        };
+        let size = folders.get(&path).map(|folder| folder.total_size());
+        if let Some(parent_folder) = folders.get_mut(parent) {
+            if let Some(index) = parent_folder.entries().iter().position(|e| e.name == name) {
+                parent_folder.set_entry_size(index, size);
+            }
+        }
+    }
+
+    Ok(folders
+        .into_iter()
+        .map(|(path, mut folder)| {
+            folder.read_only = true;
+            (path, folder)
+        })
+        .collect())
+}
+
+/// Makes sure folder with provided components and all its ancestors exist, returns its key
+fn add_folders(folders: &mut HashMap<PathBuf, Folder>, components: &[String]) -> PathBuf {
+    let mut path = PathBuf::new();
+    for component in components {
+        let child = path.join(component);
+        if !folders.contains_key(&child) {
+            if let Some(parent) = folders.get_mut(&path) {
+                parent.push_entry(FolderEntry {
+                    kind: FolderEntryType::Folder,
+                    title: component.clone(),
+                    name: component.into(),
+                    size: Some(0),
+                    is_loaded: true,
+                    device: None,
+                    allocated: None,
+                    modified: None,
+                    owner: None,
+                    mode: None,
+                });
+            }
+            folders.insert(child.clone(), Folder::new(component.clone()));
+        }
+        path = child;
+    }
+    path
+}
+
+/// Splits archive entry name into plain components, dropping anything pointing outside of it
+fn name_to_components(name: &str) -> Vec<String> {
+    name.split(['/', '\\'])
+        .filter(|part| !part.is_empty() && *part != "." && *part != "..")
+        .map(String::from)
+        .collect()
+}
+
+fn read_zip(path: &Path) -> io::Result<(Vec<ArchiveFile>, Vec<Vec<String>>)> {
+    let mut archive = zip::ZipArchive::new(File::open(path)?).map_err(io::Error::other)?;
+    let mut files = vec![];
+    let mut dirs = vec![];
+
+    for index in 0..archive.len() {
+        let entry = archive.by_index_raw(index).map_err(io::Error::other)?;
+        let components = name_to_components(entry.name());
+        if components.is_empty() {
+            continue;
+        }
+        if entry.is_dir() {
+            dirs.push(components);
+        } else {
+            files.push(ArchiveFile {
+                components,
+                size: entry.size(),
+                compressed: Some(entry.compressed_size()),
+            });
+        }
+    }
+
+    Ok((files, dirs))
+}
+
+fn read_tar(path: &Path) -> io::Result<(Vec<ArchiveFile>, Vec<Vec<String>>)> {
+    let mut archive = tar::Archive::new(File::open(path)?);
+    let mut files = vec![];
+    let mut dirs = vec![];
+
+    for entry in archive.entries_with_seek()? {
+        let entry = entry?;
+        let header = entry.header();
+        let components = name_to_components(&entry.path()?.to_string_lossy());
+        if components.is_empty() {
+            continue;
+        }
+        if header.entry_type().is_dir() {
+            dirs.push(components);
+        } else if header.entry_type().is_file() {
+            files.push(ArchiveFile {
+                components,
+                size: header.size()?,
+                compressed: None,
+            });
+        }
+    }
+
+    Ok((files, dirs))
+}
@@ -0,0 +1,34 @@
+use crate::fs::{for_each_folder, DataStore, DataStoreKey, FolderEntryType};
+use std::path::{Path, PathBuf};
+
+/// Scanned folder with its depth below scan root, direct children of root are at depth 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeepFolder {
+    pub path: PathBuf,
+    pub depth: usize,
+    pub size: Option<u64>,
+}
+
+/// Lists every scanned folder below provided root, deepest first
+pub fn deep_folders<S: DataStore<DataStoreKey>>(store: &S, root: &Path) -> Vec<DeepFolder> {
+    let mut folders = vec![];
+
+    for_each_folder(store, root, |path, folder| {
+        let depth = path
+            .strip_prefix(root)
+            .map_or(0, |relative| relative.components().count())
+            + 1;
+        for entry in folder.entries() {
+            if entry.kind == FolderEntryType::Folder {
+                folders.push(DeepFolder {
+                    path: path.join(&entry.name),
+                    depth,
+                    size: entry.size,
+                });
+            }
+        }
+    });
+
+    folders.sort_by(|a, b| b.depth.cmp(&a.depth).then(a.path.cmp(&b.path)));
+    folders
+}
@@ -0,0 +1,196 @@
+use crate::fs::{for_each_folder, DataStore, DataStoreKey, FolderEntry, FolderEntryType};
+use std::collections::HashMap;
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+
+/// Capacity of the filesystem holding some path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiskSpace {
+    pub total: u64,
+    pub free: u64,
+}
+
+/// Returns capacity and free space of the filesystem holding provided path
+#[cfg(unix)]
+pub fn disk_space(path: &Path) -> Option<DiskSpace> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+
+    let block_size = stat.f_frsize as u64;
+    Some(DiskSpace {
+        total: stat.f_blocks as u64 * block_size,
+        free: stat.f_bavail as u64 * block_size,
+    })
+}
+
+/// Returns capacity and free space of the filesystem holding provided path
+#[cfg(not(unix))]
+pub fn disk_space(_path: &Path) -> Option<DiskSpace> {
+    None
+}
+
+/// Returns id of the filesystem holding entry with provided metadata
+#[cfg(unix)]
+pub fn device_id(metadata: &Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+
+    Some(metadata.dev())
+}
+
+/// Returns id of the filesystem holding entry with provided metadata
+#[cfg(not(unix))]
+pub fn device_id(_metadata: &Metadata) -> Option<u64> {
+    None
+}
+
+/// Returns id of the file within its filesystem
+#[cfg(unix)]
+pub fn inode_id(metadata: &Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+
+    Some(metadata.ino())
+}
+
+/// Returns id of the file within its filesystem
+#[cfg(not(unix))]
+pub fn inode_id(_metadata: &Metadata) -> Option<u64> {
+    None
+}
+
+/// Returns number of hard links to entry with provided metadata
+#[cfg(unix)]
+pub fn link_count(metadata: &Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+
+    metadata.nlink()
+}
+
+/// Returns number of hard links to entry with provided metadata
+#[cfg(not(unix))]
+pub fn link_count(_metadata: &Metadata) -> u64 {
+    1
+}
+
+/// Returns bytes taken on disk by entry with provided metadata
+#[cfg(unix)]
+pub fn allocated_size(metadata: &Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+
+    // Reported in 512-byte units regardless of filesystem block size
+    Some(metadata.blocks() * 512)
+}
+
+/// Returns bytes taken on disk by entry with provided metadata
+#[cfg(not(unix))]
+pub fn allocated_size(_metadata: &Metadata) -> Option<u64> {
+    None
+}
+
+/// Scanned usage of a single filesystem
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilesystemUsage {
+    pub device: Option<u64>,
+    pub mount_point: PathBuf,
+    pub size: u64,
+    pub files: u64,
+}
+
+/// Groups scanned files below provided root by the filesystem holding them
+pub fn filesystems_usage<S: DataStore<DataStoreKey>>(
+    store: &S,
+    root: &Path,
+) -> Vec<FilesystemUsage> {
+    let mut usage = HashMap::new();
+    for_each_folder(store, root, |path, folder| {
+        for entry in folder.entries() {
+            add_file(&mut usage, path, entry);
+        }
+    });
+    with_mount_points(usage)
+}
+
+/// Space provided entries of folder at `path` take on every filesystem they span, biggest first.
+///
+/// Files are counted on their own filesystem, so a folder with another disk mounted below it
+/// frees space on both.
+pub fn freed_by_filesystem<S: DataStore<DataStoreKey>>(
+    store: &S,
+    path: &Path,
+    entries: &[&FolderEntry],
+) -> Vec<FilesystemUsage> {
+    let mut usage = HashMap::new();
+    for entry in entries {
+        match entry.kind {
+            FolderEntryType::File => add_file(&mut usage, path, entry),
+            FolderEntryType::Folder => {
+                for_each_folder(store, &path.join(&entry.name), |path, folder| {
+                    for entry in folder.entries() {
+                        add_file(&mut usage, path, entry);
+                    }
+                });
+            }
+            FolderEntryType::Parent => {}
+        }
+    }
+    let mut usage = with_mount_points(usage);
+    usage.sort_by(|a, b| {
+        b.size
+            .cmp(&a.size)
+            .then_with(|| a.mount_point.cmp(&b.mount_point))
+    });
+    usage
+}
+
+/// Counts file found in folder at `path` towards its filesystem, other entries are skipped
+fn add_file(usage: &mut HashMap<Option<u64>, FilesystemUsage>, path: &Path, entry: &FolderEntry) {
+    if entry.kind != FolderEntryType::File {
+        return;
+    }
+    let filesystem = usage
+        .entry(entry.device)
+        .or_insert_with(|| FilesystemUsage {
+            device: entry.device,
+            mount_point: path.to_path_buf(),
+            size: 0,
+            files: 0,
+        });
+    filesystem.size += entry.size.unwrap_or(0);
+    filesystem.files += 1;
+    // Shallowest folder seen is the closest one to the mount point
+    if path.components().count() < filesystem.mount_point.components().count() {
+        filesystem.mount_point = path.to_path_buf();
+    }
+}
+
+/// Climbs from the shallowest folder of every filesystem up to where it is mounted
+fn with_mount_points(usage: HashMap<Option<u64>, FilesystemUsage>) -> Vec<FilesystemUsage> {
+    usage
+        .into_values()
+        .map(|mut filesystem| {
+            if let Some(device) = filesystem.device {
+                filesystem.mount_point = find_mount_point(&filesystem.mount_point, device);
+            }
+            filesystem
+        })
+        .collect()
+}
+
+/// Climbs up from provided path while parents stay on the same device
+fn find_mount_point(path: &Path, device: u64) -> PathBuf {
+    let mut mount_point = path.to_path_buf();
+    while let Some(parent) = mount_point.parent() {
+        match parent.metadata() {
+            Ok(metadata) if device_id(&metadata) == Some(device) => {
+                mount_point = parent.to_path_buf();
+            }
+            _ => break,
+        }
+    }
+    mount_point
+}
@@ -0,0 +1,505 @@
+use crate::config::{ExportFormat, PathStyle, UIConfig};
+use crate::fs::{
+    compare_names, for_each_folder, format_path, user_name, DataStore, DataStoreKey, Folder,
+    FolderEntry, FolderEntryType,
+};
+use crate::ui::constants::TEXT_UNKNOWN;
+use crate::ui::format_file_size;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Writes every scanned entry below root in provided format, the UI exports the same way.
+///
+/// With `visible`, only entries of root the table shows with its filters are written, along
+/// with everything below the folders among them.
+pub fn export<S: DataStore<DataStoreKey>>(
+    format: ExportFormat,
+    store: &S,
+    root: &Path,
+    paths: PathStyle,
+    visible: Option<&UIConfig>,
+    writer: &mut impl Write,
+) -> io::Result<()> {
+    match format {
+        ExportFormat::Json => json(store, root, paths, visible, writer),
+        ExportFormat::Csv => csv(store, root, paths, visible, writer),
+        ExportFormat::Ncdu => ncdu(store, root, visible, writer),
+        ExportFormat::Tree => tree(store, root, paths, visible, writer),
+    }
+}
+
+/// Entries below root an export writes
+struct Shown<'a> {
+    root: &'a Path,
+    /// Names of entries of root shown with filters, None writes everything
+    names: Option<HashSet<&'a OsStr>>,
+}
+
+impl<'a> Shown<'a> {
+    fn new<S: DataStore<DataStoreKey>>(
+        store: &'a S,
+        root: &'a Path,
+        visible: Option<&'a UIConfig>,
+    ) -> Shown<'a> {
+        let names = visible.map(|config| match store.get_folder(&root.to_path_buf()) {
+            Some(folder) => config
+                .visible_entries(folder)
+                .map(|(_, entry)| entry.name.as_os_str())
+                .collect(),
+            None => HashSet::new(),
+        });
+        Shown { root, names }
+    }
+
+    /// Whether entry at path is written, ones below a shown folder always are
+    fn keeps(&self, path: &Path) -> bool {
+        let Some(names) = &self.names else {
+            return true;
+        };
+        match path
+            .strip_prefix(self.root)
+            .ok()
+            .and_then(|path| path.components().next())
+        {
+            Some(first) => names.contains(first.as_os_str()),
+            None => true,
+        }
+    }
+}
+
+/// Writes every scanned file below root as CSV, one row each, sorted by path.
+///
+/// Columns are `path,name,type,size_bytes,modified,owner`. Sizes are plain bytes and
+/// modification times are UTC in ISO 8601, so both sort correctly in spreadsheets.
+pub fn csv<S: DataStore<DataStoreKey>>(
+    store: &S,
+    root: &Path,
+    paths: PathStyle,
+    visible: Option<&UIConfig>,
+    writer: &mut impl Write,
+) -> io::Result<()> {
+    let shown = Shown::new(store, root, visible);
+    let mut rows = vec![];
+    for_each_folder(store, root, |path, folder| {
+        // Folders are there through the paths of their files
+        for entry in folder.entries() {
+            if entry.kind != FolderEntryType::File {
+                continue;
+            }
+            let entry_path = path.join(&entry.name);
+            if !shown.keeps(&entry_path) {
+                continue;
+            }
+            rows.push([
+                format_path(&entry_path, root, paths),
+                entry.title.clone(),
+                "file".to_string(),
+                entry.size.map(|size| size.to_string()).unwrap_or_default(),
+                entry.modified.map(format_utc).unwrap_or_default(),
+                entry.owner.map(user_name).unwrap_or_default(),
+            ]);
+        }
+    });
+    rows.sort();
+
+    writeln!(writer, "path,name,type,size_bytes,modified,owner")?;
+    for row in rows {
+        let fields: Vec<String> = row.iter().map(|field| quote(field)).collect();
+        writeln!(writer, "{}", fields.join(","))?;
+    }
+    Ok(())
+}
+
+/// Version of the JSON export layout. Bumped whenever a field is added, removed, renamed or
+/// changes meaning, so tools reading exports can tell which layout they got.
+pub const JSON_FORMAT_VERSION: u32 = 1;
+
+/// JSON export as written, root folder with the layout version
+#[derive(Debug, Serialize, Deserialize)]
+struct JsonExport {
+    format_version: u32,
+    root: JsonEntry,
+}
+
+/// Entry of JSON export, folders hold their entries. Every field but `children` is always
+/// written, unknown values are null.
+#[derive(Debug, Serialize, Deserialize)]
+struct JsonEntry {
+    name: String,
+    path: String,
+    #[serde(rename = "type")]
+    kind: JsonEntryType,
+    bytes: Option<u64>,
+    /// Last modification time, UTC in ISO 8601 with nanoseconds when there are any
+    mtime: Option<String>,
+    /// Folder could not be read, only entries listed before the error are held
+    error: bool,
+    /// Entries of a folder, missing for files
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    children: Option<Vec<JsonEntry>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum JsonEntryType {
+    Directory,
+    File,
+}
+
+/// Writes root as a single object with entries nested by folder, sorted by name.
+///
+/// Layout is versioned by [`JSON_FORMAT_VERSION`] and described in the README.
+pub fn json<S: DataStore<DataStoreKey>>(
+    store: &S,
+    root: &Path,
+    paths: PathStyle,
+    visible: Option<&UIConfig>,
+    writer: &mut impl Write,
+) -> io::Result<()> {
+    let shown = Shown::new(store, root, visible);
+    let name = root
+        .file_name()
+        .unwrap_or(root.as_os_str())
+        .to_string_lossy()
+        .to_string();
+    let folder = store.get_folder(&root.to_path_buf());
+    let export = JsonExport {
+        format_version: JSON_FORMAT_VERSION,
+        root: JsonEntry {
+            name,
+            path: format_path(root, root, paths),
+            kind: JsonEntryType::Directory,
+            bytes: folder.map(|folder| folder.total_size()),
+            mtime: None,
+            error: folder.is_some_and(|folder| folder.has_error),
+            children: Some(json_children(store, root, &shown, paths)),
+        },
+    };
+    serde_json::to_writer(&mut *writer, &export)?;
+    writeln!(writer)
+}
+
+fn json_children<S: DataStore<DataStoreKey>>(
+    store: &S,
+    path: &Path,
+    shown: &Shown,
+    paths: PathStyle,
+) -> Vec<JsonEntry> {
+    children(store, path, shown)
+        .into_iter()
+        .map(|(child_path, entry)| {
+            let is_folder = entry.kind == FolderEntryType::Folder;
+            JsonEntry {
+                name: entry.title.clone(),
+                path: format_path(&child_path, shown.root, paths),
+                kind: match is_folder {
+                    true => JsonEntryType::Directory,
+                    false => JsonEntryType::File,
+                },
+                bytes: entry.size,
+                mtime: entry.modified.map(format_utc_precise),
+                error: is_folder
+                    && store
+                        .get_folder(&child_path)
+                        .is_some_and(|folder| folder.has_error),
+                children: is_folder.then(|| json_children(store, &child_path, shown, paths)),
+            }
+        })
+        .collect()
+}
+
+/// Reads folders back from a JSON export, keyed by the paths written in it.
+///
+/// Exports of another layout version are refused rather than read wrong. Only what the
+/// export holds comes back, names that were not valid UTF-8 keep their replaced characters.
+pub fn read_json(reader: impl Read) -> io::Result<Vec<(PathBuf, Folder)>> {
+    let value: Value = serde_json::from_reader(reader)?;
+    let version = value.get("format_version").and_then(Value::as_u64);
+    if version != Some(JSON_FORMAT_VERSION as u64) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            match version {
+                Some(version) => format!(
+                    "Unsupported export format version {}, expected {}",
+                    version, JSON_FORMAT_VERSION
+                ),
+                None => "Missing format_version, not a wiper JSON export".to_string(),
+            },
+        ));
+    }
+    let export: JsonExport = serde_json::from_value(value)?;
+    let mut folders = vec![];
+    collect_folders(&export.root, &mut folders)?;
+    Ok(folders)
+}
+
+fn collect_folders(entry: &JsonEntry, folders: &mut Vec<(PathBuf, Folder)>) -> io::Result<()> {
+    let mut folder = Folder::new(entry.name.clone());
+    folder.has_error = entry.error;
+    for child in entry.children.iter().flatten() {
+        let modified = match &child.mtime {
+            Some(mtime) => Some(parse_utc(mtime).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Invalid mtime {} of {}", mtime, child.path),
+                )
+            })?),
+            None => None,
+        };
+        folder.push_entry(FolderEntry {
+            title: child.name.clone(),
+            name: child.name.clone().into(),
+            size: child.bytes,
+            kind: match child.kind {
+                JsonEntryType::Directory => FolderEntryType::Folder,
+                JsonEntryType::File => FolderEntryType::File,
+            },
+            is_loaded: true,
+            device: None,
+            allocated: None,
+            modified,
+            owner: None,
+            mode: None,
+        });
+        if child.kind == JsonEntryType::Directory {
+            collect_folders(child, folders)?;
+        }
+    }
+    folders.push((PathBuf::from(&entry.path), folder));
+    Ok(())
+}
+
+/// Writes entries in ncdu export format, root keeps its absolute path as ncdu expects
+pub fn ncdu<S: DataStore<DataStoreKey>>(
+    store: &S,
+    root: &Path,
+    visible: Option<&UIConfig>,
+    writer: &mut impl Write,
+) -> io::Result<()> {
+    let shown = Shown::new(store, root, visible);
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let export = json!([
+        1,
+        0,
+        {
+            "progname": env!("CARGO_PKG_NAME"),
+            "progver": env!("CARGO_PKG_VERSION"),
+            "timestamp": timestamp,
+        },
+        ncdu_folder(store, root, &shown, root.to_string_lossy().to_string()),
+    ]);
+    serde_json::to_writer(&mut *writer, &export)?;
+    writeln!(writer)
+}
+
+/// Folder is an array of its own info followed by its entries, files are objects
+fn ncdu_folder<S: DataStore<DataStoreKey>>(
+    store: &S,
+    path: &Path,
+    shown: &Shown,
+    name: String,
+) -> Value {
+    let mut items = vec![json!({ "name": name })];
+    for (child_path, entry) in children(store, path, shown) {
+        items.push(match entry.kind {
+            FolderEntryType::Folder => ncdu_folder(store, &child_path, shown, entry.title.clone()),
+            _ => {
+                let size = entry.size.unwrap_or(0);
+                json!({
+                    "name": entry.title,
+                    "asize": size,
+                    "dsize": entry.allocated.unwrap_or(size),
+                })
+            }
+        });
+    }
+    Value::Array(items)
+}
+
+/// Writes entries as an indented tree with sizes, biggest first in each folder
+pub fn tree<S: DataStore<DataStoreKey>>(
+    store: &S,
+    root: &Path,
+    paths: PathStyle,
+    visible: Option<&UIConfig>,
+    writer: &mut impl Write,
+) -> io::Result<()> {
+    let shown = Shown::new(store, root, visible);
+    let size = store
+        .get_folder(&root.to_path_buf())
+        .map(|folder| format_file_size(folder.total_size()))
+        .unwrap_or(TEXT_UNKNOWN.to_string());
+    writeln!(writer, "{} ({})", format_path(root, root, paths), size)?;
+    tree_children(store, root, &shown, "", writer)
+}
+
+fn tree_children<S: DataStore<DataStoreKey>>(
+    store: &S,
+    path: &Path,
+    shown: &Shown,
+    prefix: &str,
+    writer: &mut impl Write,
+) -> io::Result<()> {
+    let mut entries = children(store, path, shown);
+    entries.sort_by(|(_, a), (_, b)| {
+        b.size
+            .cmp(&a.size)
+            .then_with(|| compare_names(&a.title, &b.title))
+    });
+    let count = entries.len();
+    for (index, (child_path, entry)) in entries.into_iter().enumerate() {
+        let (branch, indent) = match index + 1 == count {
+            true => ("└── ", "    "),
+            false => ("├── ", "│   "),
+        };
+        let is_folder = entry.kind == FolderEntryType::Folder;
+        let size = entry
+            .size
+            .map(format_file_size)
+            .unwrap_or(TEXT_UNKNOWN.to_string());
+        let slash = if is_folder { "/" } else { "" };
+        writeln!(writer, "{prefix}{branch}{}{slash} ({size})", entry.title)?;
+        if is_folder {
+            tree_children(
+                store,
+                &child_path,
+                shown,
+                &format!("{prefix}{indent}"),
+                writer,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Entries of folder at path that are written with their paths, sorted by name, parent entry
+/// left out
+fn children<'a, S: DataStore<DataStoreKey>>(
+    store: &'a S,
+    path: &Path,
+    shown: &Shown,
+) -> Vec<(PathBuf, &'a FolderEntry)> {
+    let Some(folder) = store.get_folder(&path.to_path_buf()) else {
+        return vec![];
+    };
+    let mut entries: Vec<(PathBuf, &FolderEntry)> = folder
+        .entries()
+        .iter()
+        .filter(|entry| entry.kind != FolderEntryType::Parent)
+        .map(|entry| (path.join(&entry.name), entry))
+        .filter(|(path, _)| shown.keeps(path))
+        .collect();
+    entries.sort_by(|(_, a), (_, b)| compare_names(&a.title, &b.title));
+    entries
+}
+
+/// Quotes field holding separators, quotes or line breaks, doubling quotes inside
+fn quote(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Formats time as `2024-01-31T12:00:00Z`, times before 1970 are clamped to it
+fn format_utc(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let (days, rest) = (secs / 86400, secs % 86400);
+    let (year, month, day) = civil_from_days(days as i64);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rest / 3600,
+        rest % 3600 / 60,
+        rest % 60
+    )
+}
+
+/// Formats time like [`format_utc`], with nanoseconds when there are any
+fn format_utc_precise(time: SystemTime) -> String {
+    let seconds = format_utc(time);
+    match time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.subsec_nanos())
+    {
+        0 => seconds,
+        nanos => format!("{}.{:09}Z", seconds.trim_end_matches('Z'), nanos),
+    }
+}
+
+/// Reads time written by [`format_utc_precise`], None for anything else
+fn parse_utc(text: &str) -> Option<SystemTime> {
+    let text = text.strip_suffix('Z')?;
+    let (date, time) = text.split_once('T')?;
+    let (time, nanos) = match time.split_once('.') {
+        Some((time, fraction)) if fraction.len() == 9 => (time, fraction.parse::<u32>().ok()?),
+        Some(_) => return None,
+        None => (time, 0),
+    };
+    let numbers = |text: &str| -> Option<Vec<u64>> {
+        text.split(['-', ':'])
+            .map(|part| part.parse().ok())
+            .collect()
+    };
+    let (date, time) = (numbers(date)?, numbers(time)?);
+    let (&[year, month, day], &[hours, minutes, seconds]) = (&date[..], &time[..]) else {
+        return None;
+    };
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    if hours >= 24 || minutes >= 60 || seconds >= 60 {
+        return None;
+    }
+    let days = days_from_civil(i64::try_from(year).ok()?, month as u32, day as u32)?;
+    let secs = u64::try_from(days)
+        .ok()?
+        .checked_mul(86400)?
+        .checked_add(hours * 3600 + minutes * 60 + seconds)?;
+    UNIX_EPOCH.checked_add(std::time::Duration::new(secs, nanos))
+}
+
+/// Days from 1970-01-01 to a calendar date, inverse of [`civil_from_days`], None when they
+/// don't fit
+fn days_from_civil(year: i64, month: u32, day: u32) -> Option<i64> {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month_index = if month > 2 { month - 3 } else { month + 9 } as i64;
+    let day_of_year = (153 * month_index + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era.checked_mul(146097)?
+        .checked_add(day_of_era)?
+        .checked_sub(719468)
+}
+
+/// Calendar date of a day counted from 1970-01-01, proleptic Gregorian calendar
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
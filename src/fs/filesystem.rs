@@ -0,0 +1,320 @@
+use crate::fs::{allocated_size, device_id, inode_id, link_count, owner_id, permission_mode};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::ffi::OsString;
+use std::fs::Metadata;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::time::SystemTime;
+
+/// Most symbolic links followed while resolving a single path, like Linux does
+const MAX_LINK_FOLLOWS: usize = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    File,
+    Folder,
+    Symlink,
+}
+
+/// Metadata the scanner reads, fields the platform doesn't provide are None
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntryMetadata {
+    pub kind: EntryKind,
+    pub len: u64,
+    pub device: Option<u64>,
+    pub inode: Option<u64>,
+    /// Number of hard links to the file
+    pub links: u64,
+    pub allocated: Option<u64>,
+    pub modified: Option<SystemTime>,
+    pub owner: Option<u32>,
+    pub mode: Option<u32>,
+}
+
+impl From<&Metadata> for EntryMetadata {
+    fn from(metadata: &Metadata) -> Self {
+        let file_type = metadata.file_type();
+        let kind = if file_type.is_symlink() {
+            EntryKind::Symlink
+        } else if file_type.is_dir() {
+            EntryKind::Folder
+        } else {
+            EntryKind::File
+        };
+        EntryMetadata {
+            kind,
+            len: metadata.len(),
+            device: device_id(metadata),
+            inode: inode_id(metadata),
+            links: link_count(metadata),
+            allocated: allocated_size(metadata),
+            modified: metadata.modified().ok(),
+            owner: owner_id(metadata),
+            mode: permission_mode(metadata),
+        }
+    }
+}
+
+/// Filesystem operations used by the scanner
+pub trait FileSystem {
+    /// Names of entries in folder, entries that could not be read are errors
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<io::Result<OsString>>>;
+    /// Metadata of the entry itself, symbolic links are not followed
+    fn symlink_metadata(&self, path: &Path) -> io::Result<EntryMetadata>;
+    /// Metadata of the entry, symbolic links are followed
+    fn metadata(&self, path: &Path) -> io::Result<EntryMetadata>;
+    /// Absolute path with every symbolic link resolved
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf>;
+}
+
+/// Disk as seen by the process
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RealFileSystem;
+
+impl FileSystem for RealFileSystem {
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<io::Result<OsString>>> {
+        Ok(std::fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.file_name()))
+            .collect())
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<EntryMetadata> {
+        path.symlink_metadata()
+            .map(|metadata| EntryMetadata::from(&metadata))
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<EntryMetadata> {
+        path.metadata()
+            .map(|metadata| EntryMetadata::from(&metadata))
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        std::fs::canonicalize(path)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum MemoryNode {
+    Folder,
+    File { inode: u64, len: u64 },
+    Symlink(PathBuf),
+}
+
+/// Filesystem kept in memory, for testing scans without fixtures on disk.
+///
+/// Paths are absolute, parents are created as needed. Relative link targets are resolved
+/// from the folder holding the link.
+#[derive(Debug, Clone)]
+pub struct MemoryFileSystem {
+    nodes: BTreeMap<PathBuf, MemoryNode>,
+    /// Errors returned instead of listing a folder
+    read_dir_errors: HashMap<PathBuf, io::ErrorKind>,
+    /// Errors returned instead of metadata of an entry
+    metadata_errors: HashMap<PathBuf, io::ErrorKind>,
+    next_inode: u64,
+}
+
+impl Default for MemoryFileSystem {
+    fn default() -> Self {
+        MemoryFileSystem {
+            nodes: BTreeMap::from([(PathBuf::from("/"), MemoryNode::Folder)]),
+            read_dir_errors: HashMap::new(),
+            metadata_errors: HashMap::new(),
+            next_inode: 1,
+        }
+    }
+}
+
+impl MemoryFileSystem {
+    pub fn new() -> Self {
+        MemoryFileSystem::default()
+    }
+
+    pub fn folder(&mut self, path: &str) -> &mut Self {
+        self.insert(Path::new(path), MemoryNode::Folder);
+        self
+    }
+
+    pub fn file(&mut self, path: &str, len: u64) -> &mut Self {
+        let inode = self.next_inode;
+        self.next_inode += 1;
+        self.insert(Path::new(path), MemoryNode::File { inode, len });
+        self
+    }
+
+    /// Adds another name for an existing file
+    pub fn hard_link(&mut self, path: &str, existing: &str) -> &mut Self {
+        let node = self.nodes.get(Path::new(existing)).cloned();
+        if let Some(node @ MemoryNode::File { .. }) = node {
+            self.insert(Path::new(path), node);
+        }
+        self
+    }
+
+    pub fn symlink(&mut self, path: &str, target: &str) -> &mut Self {
+        self.insert(Path::new(path), MemoryNode::Symlink(PathBuf::from(target)));
+        self
+    }
+
+    /// Removes entry, and everything below it for folders
+    pub fn remove(&mut self, path: &str) -> &mut Self {
+        let path = Path::new(path);
+        self.nodes.retain(|key, _| !key.starts_with(path));
+        self
+    }
+
+    /// Makes listing of folder fail
+    pub fn fail_read_dir(&mut self, path: &str, kind: io::ErrorKind) -> &mut Self {
+        self.read_dir_errors.insert(PathBuf::from(path), kind);
+        self
+    }
+
+    /// Makes reading metadata of entry fail
+    pub fn fail_metadata(&mut self, path: &str, kind: io::ErrorKind) -> &mut Self {
+        self.metadata_errors.insert(PathBuf::from(path), kind);
+        self
+    }
+
+    fn insert(&mut self, path: &Path, node: MemoryNode) {
+        for ancestor in path.ancestors().skip(1) {
+            self.nodes
+                .entry(ancestor.to_path_buf())
+                .or_insert(MemoryNode::Folder);
+        }
+        self.nodes.insert(path.to_path_buf(), node);
+    }
+
+    /// Resolves symbolic links on the way to path, and the last one when asked to
+    fn resolve(&self, path: &Path, follow_last: bool) -> io::Result<PathBuf> {
+        let mut pending: VecDeque<Component> = path.components().collect();
+        let mut current = PathBuf::from("/");
+        let mut follows = 0;
+
+        while let Some(component) = pending.pop_front() {
+            match component {
+                Component::RootDir | Component::Prefix(_) => current = PathBuf::from("/"),
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    current.pop();
+                }
+                Component::Normal(name) => {
+                    let next = current.join(name);
+                    match self.nodes.get(&next) {
+                        None => return Err(io::Error::from(io::ErrorKind::NotFound)),
+                        Some(MemoryNode::Symlink(target)) if follow_last || !pending.is_empty() => {
+                            follows += 1;
+                            if follows > MAX_LINK_FOLLOWS {
+                                return Err(io::Error::other("Too many levels of symbolic links"));
+                            }
+                            for component in target.components().rev() {
+                                pending.push_front(component);
+                            }
+                        }
+                        Some(_) => current = next,
+                    }
+                }
+            }
+        }
+        Ok(current)
+    }
+
+    fn node_metadata(&self, path: &Path) -> io::Result<EntryMetadata> {
+        if let Some(kind) = self.metadata_errors.get(path) {
+            return Err(io::Error::from(*kind));
+        }
+        let node = self
+            .nodes
+            .get(path)
+            .ok_or(io::Error::from(io::ErrorKind::NotFound))?;
+        let (kind, len, inode) = match node {
+            MemoryNode::Folder => (EntryKind::Folder, 0, None),
+            MemoryNode::File { inode, len } => (EntryKind::File, *len, Some(*inode)),
+            MemoryNode::Symlink(target) => {
+                (EntryKind::Symlink, target.as_os_str().len() as u64, None)
+            }
+        };
+        let links = match inode {
+            Some(inode) => self
+                .nodes
+                .values()
+                .filter(|node| matches!(node, MemoryNode::File { inode: i, .. } if *i == inode))
+                .count() as u64,
+            None => 1,
+        };
+        Ok(EntryMetadata {
+            kind,
+            len,
+            device: Some(0),
+            inode,
+            links,
+            allocated: Some(len),
+            modified: None,
+            owner: None,
+            mode: None,
+        })
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<io::Result<OsString>>> {
+        let path = self.resolve(path, true)?;
+        if let Some(kind) = self.read_dir_errors.get(&path) {
+            return Err(io::Error::from(*kind));
+        }
+        match self.nodes.get(&path) {
+            Some(MemoryNode::Folder) => Ok(self
+                .nodes
+                .keys()
+                .filter(|key| key.parent() == Some(path.as_path()))
+                .filter_map(|key| key.file_name())
+                .map(|name| Ok(name.to_os_string()))
+                .collect()),
+            _ => Err(io::Error::from(io::ErrorKind::NotADirectory)),
+        }
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<EntryMetadata> {
+        self.node_metadata(&self.resolve(path, false)?)
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<EntryMetadata> {
+        self.node_metadata(&self.resolve(path, true)?)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        self.resolve(path, true)
+    }
+}
+
+/// Files with several hard links seen during a scan, so their size is counted once
+#[derive(Debug, Default)]
+pub struct HardLinks {
+    /// Path each file was first counted at, by device and inode
+    owners: HashMap<(u64, u64), PathBuf>,
+}
+
+impl HardLinks {
+    /// Whether size of file at provided path should be counted. Files with several links are
+    /// counted at the first path they were seen at, for as long as that path still links to them.
+    pub fn claim<F: FileSystem>(&mut self, fs: &F, path: &Path, metadata: &EntryMetadata) -> bool {
+        let (Some(device), Some(inode)) = (metadata.device, metadata.inode) else {
+            return true;
+        };
+        if metadata.links < 2 {
+            return true;
+        }
+        if let Some(owner) = self.owners.get(&(device, inode)) {
+            if owner == path {
+                return true;
+            }
+            let still_linked = fs.symlink_metadata(owner).is_ok_and(|owner_metadata| {
+                owner_metadata.device == Some(device) && owner_metadata.inode == Some(inode)
+            });
+            if still_linked {
+                return false;
+            }
+        }
+        self.owners.insert((device, inode), path.to_path_buf());
+        true
+    }
+}
@@ -1,17 +1,30 @@
 use crate::ui::constants::TEXT_PARENT_DIR;
 
 use crate::fs::folder_entry::{FolderEntry, FolderEntryType};
-use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::ffi::{OsStr, OsString};
+use std::time::SystemTime;
 
-use super::SortBy;
+use super::{compare_by_key, sort_entries, SortBy};
 
 #[derive(Debug, Clone)]
 pub struct Folder {
     pub title: String,
     pub cursor_index: usize,
-    pub sorted_by: Option<SortBy>,
-    pub entries: Vec<FolderEntry>,
+    /// Order entries are in, whether it is reversed and whether folders go first, None once
+    /// they change
+    pub sorted_by: Option<(SortBy, bool, bool)>,
+    entries: Vec<FolderEntry>,
     pub has_error: bool,
+    /// Listed from an archive, entries can't be deleted or rescanned
+    pub read_only: bool,
+    /// Names of marked entries, so marks don't depend on entries order
+    pub marked: HashSet<OsString>,
+    /// Modification time folder had when it was read, None when unknown or too recent to tell
+    /// later changes from it
+    pub modified: Option<SystemTime>,
+    /// Sum of entry sizes, kept up to date by entry mutating methods
+    total_size: u64,
 }
 
 impl Folder {
@@ -23,26 +36,186 @@ impl Folder {
             entries: vec![FolderEntry {
                 kind: FolderEntryType::Parent,
                 title: String::from(TEXT_PARENT_DIR),
+                name: OsString::from(TEXT_PARENT_DIR),
                 size: None,
                 is_loaded: true,
+                device: None,
+                allocated: None,
+                modified: None,
+                owner: None,
+                mode: None,
             }],
             has_error: false,
+            read_only: false,
+            marked: HashSet::new(),
+            modified: None,
+            total_size: 0,
         }
     }
 
+    /// Sums entry sizes from scratch, prefer [`Folder::total_size`] outside of checks
     pub fn get_size(&self) -> u64 {
         self.entries
             .iter()
             .fold(0, |acc, entry| acc + entry.size.unwrap_or(0))
     }
 
+    /// Cached sum of entry sizes
+    pub fn total_size(&self) -> u64 {
+        self.total_size
+    }
+
+    /// Entries in their current order, parent entry first. Changed only through methods of
+    /// folder, so the cached sum keeps up with their sizes.
+    pub fn entries(&self) -> &[FolderEntry] {
+        &self.entries
+    }
+
+    pub fn push_entry(&mut self, entry: FolderEntry) {
+        self.total_size += entry.size.unwrap_or(0);
+        self.entries.push(entry);
+    }
+
+    pub fn set_entry_size(&mut self, index: usize, size: Option<u64>) {
+        self.update_entry(index, |entry| entry.size = size);
+    }
+
+    /// Changes entry at index, cached sum follows its size
+    pub fn update_entry(&mut self, index: usize, update: impl FnOnce(&mut FolderEntry)) {
+        let entry = &mut self.entries[index];
+        let previous = entry.size;
+        update(entry);
+        let size = entry.size;
+        self.replace_size(previous, size);
+    }
+
+    /// Swaps size of an entry in the cached sum, every entry size is part of it
+    fn replace_size(&mut self, previous: Option<u64>, size: Option<u64>) {
+        let remaining = self.total_size.checked_sub(previous.unwrap_or(0));
+        debug_assert!(remaining.is_some(), "entry sizes drifted from cached sum");
+        self.total_size = remaining.unwrap_or(0) + size.unwrap_or(0);
+    }
+
     pub fn get_selected_entry_size(&self) -> u64 {
         self.get_selected_entry().size.unwrap_or(0)
     }
 
     pub fn remove_selected(&mut self) {
-        self.entries.remove(self.cursor_index);
-        self.cursor_index = self.cursor_index.min(self.entries.len() - 1);
+        self.remove_entry(self.cursor_index);
+    }
+
+    /// Removes entry keeping cursor on the same entry when possible
+    pub fn remove_entry(&mut self, index: usize) {
+        let entry = self.entries.remove(index);
+        self.replace_size(entry.size, None);
+        self.marked.remove(&entry.name);
+        if index < self.cursor_index {
+            self.cursor_index -= 1;
+        }
+        self.cursor_index = self.cursor_index.min(self.entries.len().saturating_sub(1));
+    }
+
+    pub fn is_marked(&self, name: &OsStr) -> bool {
+        self.marked.contains(name)
+    }
+
+    /// Total size of marked entries, ones still loading count as empty
+    pub fn marked_size(&self) -> u64 {
+        self.entries
+            .iter()
+            .filter(|entry| self.is_marked(&entry.name))
+            .filter_map(|entry| entry.size)
+            .sum()
+    }
+
+    pub fn toggle_mark_selected(&mut self) {
+        let entry = self.get_selected_entry();
+        if entry.kind == FolderEntryType::Parent {
+            return;
+        }
+        let name = entry.name.clone();
+        if !self.marked.remove(&name) {
+            self.marked.insert(name);
+        }
+    }
+
+    /// Returns marked names, or selected entry name if nothing is marked
+    pub fn get_names_to_delete(&self) -> Vec<OsString> {
+        if !self.marked.is_empty() {
+            let mut names: Vec<OsString> = self.marked.iter().cloned().collect();
+            names.sort();
+            return names;
+        }
+        let entry = self.get_selected_entry();
+        match entry.kind {
+            FolderEntryType::Parent => vec![],
+            _ => vec![entry.name.clone()],
+        }
+    }
+
+    /// Keeps cursor and marks of entries that are still present in refreshed folder
+    pub fn retain_state_from(&mut self, previous: &Folder) {
+        self.marked = previous
+            .marked
+            .iter()
+            .filter(|name| self.entries.iter().any(|entry| &entry.name == *name))
+            .cloned()
+            .collect();
+        if let Some(selected) = previous.entries.get(previous.cursor_index) {
+            self.select_name(&selected.name);
+        }
+    }
+
+    /// Moves cursor to entry with provided name on disk, if there is one
+    pub fn select_name(&mut self, name: &OsStr) {
+        if let Some(index) = self.entries.iter().position(|entry| entry.name == name) {
+            self.cursor_index = index;
+        }
+    }
+
+    /// Sorts by provided sizes of entries, biggest first unless reversed, missing ones count as
+    /// empty
+    pub fn sort_by_sizes(&mut self, sizes: &HashMap<OsString, u64>, reversed: bool) {
+        let size = |entry: &FolderEntry| sizes.get(&entry.name).cloned().unwrap_or(0);
+        self.sort_keeping_cursor(|entries| {
+            entries.sort_by(|a, b| {
+                let ordering = match reversed {
+                    true => size(a).cmp(&size(b)),
+                    false => size(b).cmp(&size(a)),
+                };
+                (a.kind != FolderEntryType::Parent)
+                    .cmp(&(b.kind != FolderEntryType::Parent))
+                    .then(ordering)
+                    .then_with(|| a.title.cmp(&b.title))
+            })
+        });
+    }
+
+    /// Sorts by provided numbers of entries inside folders, files hold none and folders missing
+    /// from `counts` are not scanned yet
+    pub fn sort_by_counts(
+        &mut self,
+        counts: &HashMap<OsString, usize>,
+        reversed: bool,
+        folders_first: bool,
+    ) {
+        let count = |entry: &FolderEntry| match entry.kind {
+            FolderEntryType::File => Some(0),
+            _ => counts.get(&entry.name).cloned(),
+        };
+        self.sort_keeping_cursor(|entries| {
+            entries
+                .sort_by(|a, b| compare_by_key(a, b, count(a), count(b), !reversed, folders_first))
+        });
+    }
+
+    /// Reorders entries so that cursor stays on the same entry
+    fn sort_keeping_cursor(&mut self, sort: impl FnOnce(&mut [FolderEntry])) {
+        let selected = self.entries.get(self.cursor_index).map(|e| e.name.clone());
+        sort(&mut self.entries);
+        if let Some(name) = selected {
+            self.select_name(&name);
+        }
     }
 
     pub fn get_selected_entry(&self) -> &FolderEntry {
@@ -60,6 +233,30 @@ impl Folder {
             .collect()
     }
 
+    /// Size of files directly in this folder, without subfolders
+    pub fn own_size(&self) -> u64 {
+        self.entries
+            .iter()
+            .filter(|entry| entry.kind == FolderEntryType::File)
+            .filter_map(|entry| entry.size)
+            .sum()
+    }
+
+    /// Index of the biggest entry, equally sized ones are told apart by title
+    pub fn get_largest_entry_index(&self) -> Option<usize> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.kind != FolderEntryType::Parent)
+            .filter_map(|(index, entry)| entry.size.map(|size| (index, size)))
+            .min_by(|(a, a_size), (b, b_size)| {
+                b_size
+                    .cmp(a_size)
+                    .then_with(|| self.entries[*a].title.cmp(&self.entries[*b].title))
+            })
+            .map(|(index, _)| index)
+    }
+
     pub fn get_max_entry_size(&self) -> u64 {
         let mut max_entry_size = 0;
 
@@ -74,34 +271,10 @@ impl Folder {
         max_entry_size
     }
 
-    pub fn sort_by_title(&mut self) {
-        self.entries.sort();
-    }
-
-    pub fn sort_by_size(&mut self) {
-        self.entries.sort_by(|a, b| {
-            if a.kind == FolderEntryType::Parent || b.kind == FolderEntryType::Parent {
-                // If either entry is a Parent, it should come before
-                if a.kind == FolderEntryType::Parent && b.kind != FolderEntryType::Parent {
-                    Ordering::Less
-                } else if a.kind != FolderEntryType::Parent && b.kind == FolderEntryType::Parent {
-                    Ordering::Greater
-                } else {
-                    Ordering::Equal
-                }
-            } else if let (Some(size_a), Some(size_b)) = (a.size, b.size) {
-                // Sort by size in descending order
-                size_b.cmp(&size_a)
-            } else if a.size.is_some() {
-                // Entries with size come before those without
-                Ordering::Greater
-            } else if b.size.is_some() {
-                // Entries without size come after those with
-                Ordering::Less
-            } else {
-                // If both entries have no size, maintain their order
-                Ordering::Equal
-            }
-        });
+    /// Sorts entries, folders go before files when sorted by name even without `folders_first`
+    pub fn sort_by(&mut self, sort_by: SortBy, reversed: bool, folders_first: bool) {
+        let descending = sort_by.descends_by_default() != reversed;
+        let dirs_first = sort_by == SortBy::Title || folders_first;
+        self.sort_keeping_cursor(|entries| sort_entries(entries, sort_by, descending, dirs_first));
     }
 }
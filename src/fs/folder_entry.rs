@@ -1,4 +1,7 @@
+use crate::fs::{compare_entries, SortBy};
 use std::cmp::Ordering;
+use std::ffi::OsString;
+use std::time::SystemTime;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FolderEntryType {
@@ -29,21 +32,28 @@ impl PartialOrd for FolderEntryType {
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct FolderEntry {
+    /// Name for display, invalid UTF-8 is replaced
     pub title: String,
+    /// Name on disk, used for every path built from the entry
+    pub name: OsString,
     pub size: Option<u64>,
     pub kind: FolderEntryType,
     pub is_loaded: bool,
+    /// Id of the filesystem holding this entry, where platform provides it
+    pub device: Option<u64>,
+    /// Bytes actually taken on disk by a file, where platform provides it
+    pub allocated: Option<u64>,
+    /// Last modification time of a file, where platform provides it
+    pub modified: Option<SystemTime>,
+    /// Id of the user owning this entry, where platform provides it
+    pub owner: Option<u32>,
+    /// Permission bits of this entry, where platform provides them
+    pub mode: Option<u32>,
 }
 
 impl Ord for FolderEntry {
     fn cmp(&self, other: &Self) -> Ordering {
-        let kind_ordering = self.kind.cmp(&other.kind);
-
-        if kind_ordering != Ordering::Equal {
-            return kind_ordering;
-        }
-
-        self.title.cmp(&other.title)
+        compare_entries(self, other, SortBy::Title, false, true)
     }
 }
 
@@ -53,18 +63,17 @@ impl PartialOrd for FolderEntry {
     }
 }
 
+/// Files smaller than this are not reported as sparse, block rounding dominates them
+const SPARSE_MIN_SIZE: u64 = 1024 * 1024;
+
 impl FolderEntry {
-    pub fn sort_by_size(entries: &mut [FolderEntry]) {
-        entries.sort_by(|a, b| {
-            if let (Some(size_a), Some(size_b)) = (a.size, b.size) {
-                size_a.cmp(&size_b)
-            } else if a.size.is_some() {
-                Ordering::Less
-            } else if b.size.is_some() {
-                Ordering::Greater
-            } else {
-                Ordering::Equal
+    /// Whether apparent size of a file exceeds its allocated size more than `ratio` times
+    pub fn is_sparse(&self, ratio: f64) -> bool {
+        match (&self.kind, self.size, self.allocated) {
+            (FolderEntryType::File, Some(size), Some(allocated)) => {
+                size >= SPARSE_MIN_SIZE && size as f64 > allocated as f64 * ratio
             }
-        });
+            _ => false,
+        }
     }
 }
@@ -1,69 +1,543 @@
-use crate::config::UIConfig;
+use crate::config::{PathStyle, UIConfig};
+use crate::filter::glob_match;
 use crate::ui::constants::TEXT_UNKNOWN;
-use std::fs::{read_dir, remove_dir_all, remove_file};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::fs::{read_dir, remove_dir, remove_dir_all, remove_file};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
 use trash;
 
+#[cfg(feature = "archives")]
+mod archive;
+mod deep;
+mod disk;
+pub mod export;
+mod filesystem;
 mod folder;
 mod folder_entry;
+mod names;
+mod owner;
+mod preview;
+mod recent;
+mod scan_error;
+mod session_trash;
+mod snapshot;
+mod sort;
+mod stale;
 mod store;
+mod verify;
+#[cfg(feature = "archives")]
+pub use archive::{archive_to_folders, is_archive};
+pub use deep::{deep_folders, DeepFolder};
+pub use disk::{
+    allocated_size, device_id, disk_space, filesystems_usage, freed_by_filesystem, inode_id,
+    link_count, DiskSpace, FilesystemUsage,
+};
+pub use filesystem::{
+    EntryKind, EntryMetadata, FileSystem, HardLinks, MemoryFileSystem, RealFileSystem,
+};
 pub use folder::Folder;
 pub use folder_entry::{FolderEntry, FolderEntryType};
+pub use names::{duplicate_names, NameGroup, NamedFile};
+pub use owner::{format_mode, owner_id, permission_mode, user_id, user_name};
+pub use preview::{content_kind, hexdump, image_info, ContentKind, ImageInfo, Preview};
+pub use recent::{recent_files, RecentFile};
+pub use scan_error::ScanError;
+pub use session_trash::{purge_trashed, restore_trashed, DeletionStep, TrashedEntry};
+pub use snapshot::{snapshot_path, SizeChange, Snapshot};
+pub use sort::{compare_by_key, compare_entries, compare_names, sort_entries};
+pub use stale::{disk_change, root_available, root_device, ChangeCheck, DiskChange, RootCheck};
 pub use store::{DSHashmap, DataStore, DataStoreKey, DataStoreType};
+pub use verify::{scanned_subtree, verify_tree_with, Discrepancy, Mismatch, Verification};
 
-#[derive(Debug, Clone, PartialEq)]
+/// Age a folder modification time needs before incremental rescans trust it
+const SETTLED_MTIME_AGE: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum SortBy {
+    #[default]
     Title,
     Size,
+    /// Last modification time of files
+    Modified,
+    /// Number of entries directly inside folders
+    Count,
+}
+
+impl SortBy {
+    /// Names go A to Z, everything else biggest or newest first
+    pub fn descends_by_default(&self) -> bool {
+        *self != SortBy::Title
+    }
+}
+
+/// Path as written by reports and exports, paths outside of root stay absolute
+pub fn format_path(path: &Path, root: &Path, style: PathStyle) -> String {
+    match (style, path.strip_prefix(root)) {
+        (PathStyle::Relative, Ok(relative)) if relative.as_os_str().is_empty() => ".".to_string(),
+        (PathStyle::Relative, Ok(relative)) => relative.to_string_lossy().to_string(),
+        _ => path.to_string_lossy().to_string(),
+    }
+}
+
+/// Path a scan is started at
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScanRoot {
+    Folder(PathBuf),
+    /// Shown alone instead of being scanned
+    File(PathBuf),
+}
+
+/// Makes root path absolute like [`normalize_path`] and tells files apart from folders
+pub fn resolve_root(path: &Path) -> ScanRoot {
+    let path = normalize_path(path);
+    match path.is_file() {
+        true => ScanRoot::File(path),
+        false => ScanRoot::Folder(path),
+    }
+}
+
+/// Returns absolute path for provided one, relative paths are resolved against current dir
+pub fn normalize_path(path: impl AsRef<Path>) -> PathBuf {
+    let path_buf = path.as_ref().to_path_buf();
+    if path_buf.is_absolute() {
+        path_buf
+    } else {
+        let current_dir = env::current_dir().unwrap();
+        current_dir.join(&path_buf)
+    }
+}
+
+/// Visits every stored folder below provided root, parents before children
+pub fn for_each_folder<S: DataStore<DataStoreKey>>(
+    store: &S,
+    root: &Path,
+    mut visit: impl FnMut(&Path, &Folder),
+) {
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(path) = stack.pop() {
+        let Some(folder) = store.get_folder(&path) else {
+            continue;
+        };
+        visit(&path, folder);
+        for entry in folder.entries() {
+            if entry.kind == FolderEntryType::Folder {
+                stack.push(path.join(&entry.name));
+            }
+        }
+    }
+}
+
+/// Lists scanned folders below root that hold no files anywhere in their subtree.
+///
+/// Only the topmost of nested empty folders are listed, deleting them takes the rest along.
+/// Folders not scanned yet, unreadable or read-only are never taken for empty.
+pub fn empty_folders<S: DataStore<DataStoreKey>>(store: &S, root: &Path) -> Vec<PathBuf> {
+    let mut found = vec![];
+    if let Some(folder) = store.get_folder(&root.to_path_buf()) {
+        for entry in folder.entries() {
+            if entry.kind == FolderEntryType::Folder {
+                let path = root.join(&entry.name);
+                if collect_empty_folders(store, &path, &mut found) {
+                    found.push(path);
+                }
+            }
+        }
+    }
+    found.sort();
+    found
+}
+
+/// Returns whether folder is empty, otherwise adds its topmost empty subfolders to found
+fn collect_empty_folders<S: DataStore<DataStoreKey>>(
+    store: &S,
+    path: &Path,
+    found: &mut Vec<PathBuf>,
+) -> bool {
+    let Some(folder) = store.get_folder(&path.to_path_buf()) else {
+        return false;
+    };
+    let mut is_empty = !folder.has_error && !folder.read_only;
+    let mut empty_subfolders = vec![];
+    for entry in folder.entries() {
+        match entry.kind {
+            FolderEntryType::Parent => {}
+            FolderEntryType::File => is_empty = false,
+            FolderEntryType::Folder => {
+                let subfolder_path = path.join(&entry.name);
+                if collect_empty_folders(store, &subfolder_path, found) {
+                    empty_subfolders.push(subfolder_path);
+                } else {
+                    is_empty = false;
+                }
+            }
+        }
+    }
+    if !is_empty {
+        found.extend(empty_subfolders);
+    }
+    is_empty
+}
+
+/// Returns copy of folder whose subfolder entries show own size of those subfolders
+pub fn with_own_sizes<S: DataStore<DataStoreKey>>(
+    store: &S,
+    path: &Path,
+    folder: &Folder,
+) -> Folder {
+    let mut folder = folder.clone();
+    for index in 0..folder.entries().len() {
+        if folder.entries()[index].kind == FolderEntryType::Folder {
+            let subfolder_path = path.join(&folder.entries()[index].name);
+            let own_size = store.get_folder(&subfolder_path).map(|f| f.own_size());
+            folder.set_entry_size(index, own_size);
+        }
+    }
+    folder
+}
+
+/// Returns copy of folder whose entries show provided sizes by name, missing ones are shown empty
+pub fn with_sizes(folder: &Folder, sizes: &HashMap<OsString, u64>) -> Folder {
+    let mut folder = folder.clone();
+    for index in 0..folder.entries().len() {
+        if folder.entries()[index].kind != FolderEntryType::Parent {
+            let size = sizes.get(&folder.entries()[index].name).cloned();
+            folder.set_entry_size(index, Some(size.unwrap_or(0)));
+        }
+    }
+    folder
 }
+
+/// Bytes owned by provided user in each entry of folder, keyed by name.
+///
+/// Only entries owned by the user, or holding something they own, are listed.
+pub fn owned_sizes<S: DataStore<DataStoreKey>>(
+    store: &S,
+    path: &Path,
+    folder: &Folder,
+    uid: u32,
+) -> HashMap<OsString, u64> {
+    let mut sizes = HashMap::new();
+    for entry in folder.entries() {
+        let owned = entry.owner == Some(uid);
+        match entry.kind {
+            FolderEntryType::Parent => {}
+            FolderEntryType::File => {
+                if owned {
+                    sizes.insert(entry.name.clone(), entry.size.unwrap_or(0));
+                }
+            }
+            FolderEntryType::Folder => {
+                let mut found = owned;
+                let mut size = 0;
+                for_each_folder(store, &path.join(&entry.name), |_, subfolder| {
+                    for sub_entry in subfolder.entries() {
+                        if sub_entry.kind != FolderEntryType::Parent && sub_entry.owner == Some(uid)
+                        {
+                            found = true;
+                            if sub_entry.kind == FolderEntryType::File {
+                                size += sub_entry.size.unwrap_or(0);
+                            }
+                        }
+                    }
+                });
+                if found {
+                    sizes.insert(entry.name.clone(), size);
+                }
+            }
+        }
+    }
+    sizes
+}
+
+/// Limits on what a scan records
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScanOptions {
+    /// Files smaller than that are not recorded
+    pub min_file_size: Option<u64>,
+    /// Files larger than that are not recorded
+    pub max_file_size: Option<u64>,
+    /// Only files with one of these extensions are recorded, lowercase and without the dot.
+    /// Every file is when empty.
+    pub extensions: Vec<String>,
+    /// Entries with names matching one of these glob patterns are not recorded, folders among
+    /// them are not descended into
+    pub exclude: Vec<String>,
+    /// Patterns of `--exclude-from` file, kept apart from `exclude` so the file can be read again
+    pub exclude_from_file: Vec<String>,
+}
+
+impl ScanOptions {
+    pub fn includes_file(&self, name: &OsStr, size: u64) -> bool {
+        self.min_file_size.is_none_or(|min| size >= min)
+            && self.max_file_size.is_none_or(|max| size <= max)
+            && self.has_allowed_extension(name)
+    }
+
+    /// Whether entry name matches an `--exclude` pattern or one from the exclude file
+    pub fn excludes(&self, name: &OsStr) -> bool {
+        let name = name.to_string_lossy();
+        self.exclude
+            .iter()
+            .chain(&self.exclude_from_file)
+            .any(|pattern| glob_match(pattern, &name))
+    }
+
+    fn has_allowed_extension(&self, name: &OsStr) -> bool {
+        if self.extensions.is_empty() {
+            return true;
+        }
+        Path::new(name)
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase())
+            .is_some_and(|extension| self.extensions.contains(&extension))
+    }
+}
+
 /// Returns new unsorted folder
-pub fn path_to_folder(path: PathBuf) -> Folder {
+pub fn path_to_folder(path: PathBuf, options: &ScanOptions) -> Folder {
+    scan_folder(path, options).0
+}
+
+/// Reads folder from disk, together with every path in it that could not be read
+pub fn scan_folder(path: PathBuf, options: &ScanOptions) -> (Folder, Vec<ScanError>) {
+    scan_folder_with(&RealFileSystem, path, options, &mut HardLinks::default())
+}
+
+/// Reads folder from provided filesystem, hard links already counted elsewhere take no space.
+///
+/// Symbolic links to folders are descended into, unless they point at the folder being read
+/// or one of its parents, those are listed as files so the scan can't loop.
+pub fn scan_folder_with<F: FileSystem>(
+    fs: &F,
+    path: PathBuf,
+    options: &ScanOptions,
+    hard_links: &mut HardLinks,
+) -> (Folder, Vec<ScanError>) {
+    scan_folder_streaming(fs, path, options, hard_links, Duration::MAX, |_| {})
+}
+
+/// Reads folder like [`scan_folder_with`], handing entries read so far to `on_partial` every
+/// `partial_interval`, so huge folders can be shown before they are fully read
+pub fn scan_folder_streaming<F: FileSystem>(
+    fs: &F,
+    path: PathBuf,
+    options: &ScanOptions,
+    hard_links: &mut HardLinks,
+    partial_interval: Duration,
+    mut on_partial: impl FnMut(&Folder),
+) -> (Folder, Vec<ScanError>) {
     let folder_name = path
         .file_name()
-        .and_then(|name| name.to_str())
-        .unwrap_or(TEXT_UNKNOWN);
-    let mut folder = Folder::new(folder_name.to_string());
-
-    match read_dir(path.clone()) {
-        Ok(path) => {
-            for entry in path.into_iter().flatten() {
-                let file_name = entry.file_name();
-                if let Some(file_name) = file_name.to_str() {
-                    let mut folder_entry = FolderEntry {
-                        kind: FolderEntryType::File,
-                        title: file_name.to_owned(),
-                        size: None,
-                        is_loaded: true,
-                    };
-                    if entry.path().is_dir() {
-                        folder_entry.kind = FolderEntryType::Folder;
-                    } else {
-                        match entry.metadata() {
-                            Ok(metadata) => {
-                                folder_entry.size = Some(metadata.len());
-                            }
-                            Err(_) => {
-                                folder.has_error = true;
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or(TEXT_UNKNOWN.to_string());
+    let mut folder = Folder::new(folder_name);
+    let mut errors = vec![];
+    let mut last_partial = Instant::now();
+    // Read before entries, so changes made while they are read show up as a newer time
+    folder.modified = fs
+        .metadata(&path)
+        .ok()
+        .and_then(|metadata| metadata.modified)
+        .filter(|modified| is_settled(*modified));
+
+    match fs.read_dir(&path) {
+        Ok(names) => {
+            for name in names {
+                let file_name = match name {
+                    Ok(name) => name,
+                    Err(err) => {
+                        folder.has_error = true;
+                        errors.push(ScanError::new(path.clone(), &err));
+                        continue;
+                    }
+                };
+                if options.excludes(&file_name) {
+                    continue;
+                }
+                let entry_path = path.join(&file_name);
+                let mut folder_entry = FolderEntry {
+                    kind: FolderEntryType::File,
+                    title: file_name.to_string_lossy().to_string(),
+                    name: file_name,
+                    size: None,
+                    is_loaded: true,
+                    device: None,
+                    allocated: None,
+                    modified: None,
+                    owner: None,
+                    mode: None,
+                };
+                let metadata = fs.symlink_metadata(&entry_path);
+                let is_dir = match &metadata {
+                    Ok(metadata) if metadata.kind == EntryKind::Symlink => {
+                        links_to_folder_outside(fs, &path, &entry_path)
+                    }
+                    Ok(metadata) => metadata.kind == EntryKind::Folder,
+                    Err(_) => fs
+                        .metadata(&entry_path)
+                        .is_ok_and(|metadata| metadata.kind == EntryKind::Folder),
+                };
+                if is_dir {
+                    folder_entry.kind = FolderEntryType::Folder;
+                }
+                match metadata {
+                    Ok(metadata) => {
+                        folder_entry.device = metadata.device;
+                        folder_entry.owner = metadata.owner;
+                        folder_entry.mode = metadata.mode;
+                        if !is_dir {
+                            // Folders are still descended into, only files are left out
+                            if !options.includes_file(&folder_entry.name, metadata.len) {
+                                continue;
                             }
+                            let counted = hard_links.claim(fs, &entry_path, &metadata);
+                            folder_entry.size = Some(if counted { metadata.len } else { 0 });
+                            folder_entry.allocated =
+                                metadata
+                                    .allocated
+                                    .map(|size| if counted { size } else { 0 });
+                            folder_entry.modified = metadata.modified;
+                        }
+                    }
+                    Err(err) => {
+                        if !is_dir {
+                            folder.has_error = true;
                         }
+                        errors.push(ScanError::new(entry_path, &err));
                     }
-                    folder.entries.push(folder_entry);
+                }
+                folder.push_entry(folder_entry);
+                if last_partial.elapsed() >= partial_interval {
+                    on_partial(&folder);
+                    last_partial = Instant::now();
                 }
             }
         }
-        Err(_) => {
+        Err(err) => {
             folder.has_error = true;
+            errors.push(ScanError::new(path, &err));
         }
     }
 
-    folder
+    (folder, errors)
+}
+
+/// Whether modification time is old enough that a later change can't leave it as it is, coarse
+/// timestamps of some filesystems don't move for changes within the same second
+fn is_settled(modified: SystemTime) -> bool {
+    SystemTime::now()
+        .duration_since(modified)
+        .is_ok_and(|age| age >= SETTLED_MTIME_AGE)
+}
+
+/// Whether symbolic link points at a folder the walk didn't go through on its way to the one
+/// holding it. Folders above the path scanned and above where it resolves to are both checked,
+/// so links pointing at each other are followed once at most.
+fn links_to_folder_outside<F: FileSystem>(fs: &F, folder: &Path, link: &Path) -> bool {
+    let target = match fs.metadata(link) {
+        Ok(metadata) if metadata.kind == EntryKind::Folder => metadata,
+        _ => return false,
+    };
+    let (Ok(target_path), Ok(canonical)) = (fs.canonicalize(link), fs.canonicalize(folder)) else {
+        return false;
+    };
+    if canonical.starts_with(&target_path) {
+        return false;
+    }
+    !folder
+        .ancestors()
+        .any(|ancestor| is_same_folder(fs, ancestor, &target, &target_path))
+}
+
+/// Whether folder at path is target, by device and inode when filesystem provides them
+fn is_same_folder<F: FileSystem>(
+    fs: &F,
+    path: &Path,
+    target: &EntryMetadata,
+    target_path: &Path,
+) -> bool {
+    let Ok(metadata) = fs.metadata(path) else {
+        return false;
+    };
+    match (
+        metadata.device.zip(metadata.inode),
+        target.device.zip(target.inode),
+    ) {
+        (Some(identity), Some(target)) => identity == target,
+        _ => fs
+            .canonicalize(path)
+            .is_ok_and(|canonical| canonical == target_path),
+    }
+}
+
+/// Returns synthetic folder listing provided paths as entries, and paths that could not be read
+pub fn paths_to_folder(title: &str, paths: &[PathBuf]) -> (Folder, Vec<ScanError>) {
+    let mut folder = Folder::new(title.to_string());
+    let mut errors = vec![];
+
+    for path in paths {
+        let entry_title = path.to_string_lossy().to_string();
+        if folder
+            .entries()
+            .iter()
+            .any(|entry| entry.name == path.as_os_str())
+        {
+            continue;
+        }
+        match path.metadata() {
+            Ok(metadata) => {
+                let folder_entry = if metadata.is_dir() {
+                    FolderEntry {
+                        kind: FolderEntryType::Folder,
+                        title: entry_title,
+                        name: path.clone().into_os_string(),
+                        size: None,
+                        is_loaded: false,
+                        device: device_id(&metadata),
+                        allocated: None,
+                        modified: None,
+                        owner: owner_id(&metadata),
+                        mode: permission_mode(&metadata),
+                    }
+                } else {
+                    FolderEntry {
+                        kind: FolderEntryType::File,
+                        title: entry_title,
+                        name: path.clone().into_os_string(),
+                        size: Some(metadata.len()),
+                        is_loaded: true,
+                        device: device_id(&metadata),
+                        allocated: allocated_size(&metadata),
+                        modified: metadata.modified().ok(),
+                        owner: owner_id(&metadata),
+                        mode: permission_mode(&metadata),
+                    }
+                };
+                folder.push_entry(folder_entry);
+            }
+            Err(err) => {
+                folder.has_error = true;
+                errors.push(ScanError::new(path.clone(), &err));
+            }
+        }
+    }
+
+    (folder, errors)
+}
+
+/// Checks whether folder holds a git repository or worktree, so deleting it may lose work
+pub fn is_git_repository(path: &Path) -> bool {
+    path.join(".git").exists()
 }
 
 pub fn delete_folder(path: &PathBuf, config: &UIConfig) -> std::io::Result<()> {
     if config.move_to_trash {
         match trash::delete(path) {
             Ok(_) => Ok(()),
-            Err(err) => Err(std::io::Error::new(std::io::ErrorKind::Other, err)),
+            Err(err) => Err(std::io::Error::other(err)),
         }
     } else {
         remove_dir_all(path)?;
@@ -71,11 +545,44 @@ pub fn delete_folder(path: &PathBuf, config: &UIConfig) -> std::io::Result<()> {
     }
 }
 
+/// Deletes folder holding nothing but empty folders, as read from disk now. Files the scan left
+/// out, e.g. excluded ones, keep their folder. Without trash folders are removed bottom-up, so
+/// one filled in the meantime stays too.
+pub fn delete_empty_folder(path: &PathBuf, config: &UIConfig) -> std::io::Result<()> {
+    if !holds_only_folders(path)? {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::DirectoryNotEmpty,
+            "holds files the scan didn't list",
+        ));
+    }
+    if config.move_to_trash {
+        return delete_folder(path, config);
+    }
+    remove_empty_folder(path)
+}
+
+fn holds_only_folders(path: &Path) -> std::io::Result<bool> {
+    for entry in read_dir(path)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() || !holds_only_folders(&entry.path())? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+fn remove_empty_folder(path: &Path) -> std::io::Result<()> {
+    for entry in read_dir(path)? {
+        remove_empty_folder(&entry?.path())?;
+    }
+    remove_dir(path)
+}
+
 pub fn delete_file(path: &PathBuf, config: &UIConfig) -> std::io::Result<()> {
     if config.move_to_trash {
         match trash::delete(path) {
             Ok(_) => Ok(()),
-            Err(err) => Err(std::io::Error::new(std::io::ErrorKind::Other, err)),
+            Err(err) => Err(std::io::Error::other(err)),
         }
     } else {
         remove_file(path)?;
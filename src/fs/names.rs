@@ -0,0 +1,71 @@
+use crate::fs::{for_each_folder, DataStore, DataStoreKey, FolderEntryType};
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// Scanned file sharing its name with files in other folders
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedFile {
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+/// Files with the same name in different folders
+#[derive(Debug, Clone, PartialEq)]
+pub struct NameGroup {
+    pub name: String,
+    /// Occurrences ordered by path
+    pub files: Vec<NamedFile>,
+}
+
+impl NameGroup {
+    pub fn total_size(&self) -> u64 {
+        self.files.iter().map(|file| file.size).sum()
+    }
+}
+
+/// Groups scanned files below provided root by name, keeping names found more than once.
+///
+/// Only names are compared, contents are never read. Most repeated names come first.
+pub fn duplicate_names<S: DataStore<DataStoreKey>>(store: &S, root: &Path) -> Vec<NameGroup> {
+    let mut by_name: HashMap<OsString, Vec<NamedFile>> = HashMap::new();
+
+    for_each_folder(store, root, |path, folder| {
+        // Archive contents can't be opened or deleted one by one
+        if folder.read_only {
+            return;
+        }
+        for entry in folder.entries() {
+            if entry.kind != FolderEntryType::File {
+                continue;
+            }
+            by_name
+                .entry(entry.name.clone())
+                .or_default()
+                .push(NamedFile {
+                    path: path.join(&entry.name),
+                    size: entry.size.unwrap_or(0),
+                });
+        }
+    });
+
+    let mut groups: Vec<NameGroup> = by_name
+        .into_iter()
+        .filter(|(_, files)| files.len() > 1)
+        .map(|(name, mut files)| {
+            files.sort_by(|a, b| a.path.cmp(&b.path));
+            NameGroup {
+                name: name.to_string_lossy().to_string(),
+                files,
+            }
+        })
+        .collect();
+    groups.sort_by(|a, b| {
+        b.files
+            .len()
+            .cmp(&a.files.len())
+            .then(b.total_size().cmp(&a.total_size()))
+            .then(a.name.cmp(&b.name))
+    });
+    groups
+}
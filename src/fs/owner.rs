@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use std::fs::Metadata;
+use std::sync::{Mutex, OnceLock};
+
+/// Returns id of the user owning entry with provided metadata
+#[cfg(unix)]
+pub fn owner_id(metadata: &Metadata) -> Option<u32> {
+    use std::os::unix::fs::MetadataExt;
+
+    Some(metadata.uid())
+}
+
+/// Returns id of the user owning entry with provided metadata
+#[cfg(not(unix))]
+pub fn owner_id(_metadata: &Metadata) -> Option<u32> {
+    None
+}
+
+/// Returns permission bits of entry with provided metadata
+#[cfg(unix)]
+pub fn permission_mode(metadata: &Metadata) -> Option<u32> {
+    use std::os::unix::fs::MetadataExt;
+
+    Some(metadata.mode() & 0o777)
+}
+
+/// Returns permission bits of entry with provided metadata
+#[cfg(not(unix))]
+pub fn permission_mode(_metadata: &Metadata) -> Option<u32> {
+    None
+}
+
+/// Formats permission bits like `rwxr-xr-x`
+pub fn format_mode(mode: u32) -> String {
+    let symbols = ['r', 'w', 'x'];
+    (0..9)
+        .map(|bit| match mode & (0o400 >> bit) {
+            0 => '-',
+            _ => symbols[bit % 3],
+        })
+        .collect()
+}
+
+/// Returns name of the user with provided id, or the id itself when it has no name.
+///
+/// Lookups are cached, rows are rendered on every frame.
+pub fn user_name(uid: u32) -> String {
+    static NAMES: OnceLock<Mutex<HashMap<u32, String>>> = OnceLock::new();
+    let mut names = NAMES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    names
+        .entry(uid)
+        .or_insert_with(|| lookup_user_name(uid).unwrap_or_else(|| uid.to_string()))
+        .clone()
+}
+
+/// Returns id of the user with provided name, plain numbers are taken as ids
+pub fn user_id(name: &str) -> Option<u32> {
+    name.parse().ok().or_else(|| lookup_user_id(name))
+}
+
+#[cfg(unix)]
+fn lookup_user_name(uid: u32) -> Option<String> {
+    use std::ffi::CStr;
+
+    let mut buf: Vec<libc::c_char> = vec![0; 1024];
+    let mut passwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut result: *mut libc::passwd = std::ptr::null_mut();
+    loop {
+        let code =
+            unsafe { libc::getpwuid_r(uid, &mut passwd, buf.as_mut_ptr(), buf.len(), &mut result) };
+        // Buffer too small for this entry
+        if code == libc::ERANGE && buf.len() < 1 << 20 {
+            buf.resize(buf.len() * 2, 0);
+            continue;
+        }
+        if code != 0 || result.is_null() {
+            return None;
+        }
+        let name = unsafe { CStr::from_ptr(passwd.pw_name) };
+        return Some(name.to_string_lossy().to_string());
+    }
+}
+
+#[cfg(not(unix))]
+fn lookup_user_name(_uid: u32) -> Option<String> {
+    None
+}
+
+#[cfg(unix)]
+fn lookup_user_id(name: &str) -> Option<u32> {
+    use std::ffi::CString;
+
+    let c_name = CString::new(name).ok()?;
+    let mut buf: Vec<libc::c_char> = vec![0; 1024];
+    let mut passwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut result: *mut libc::passwd = std::ptr::null_mut();
+    loop {
+        let code = unsafe {
+            libc::getpwnam_r(
+                c_name.as_ptr(),
+                &mut passwd,
+                buf.as_mut_ptr(),
+                buf.len(),
+                &mut result,
+            )
+        };
+        // Buffer too small for this entry
+        if code == libc::ERANGE && buf.len() < 1 << 20 {
+            buf.resize(buf.len() * 2, 0);
+            continue;
+        }
+        if code != 0 || result.is_null() {
+            return None;
+        }
+        return Some(passwd.pw_uid);
+    }
+}
+
+#[cfg(not(unix))]
+fn lookup_user_id(_name: &str) -> Option<u32> {
+    None
+}
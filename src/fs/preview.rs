@@ -0,0 +1,165 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Share of control characters above which a chunk is not treated as text
+const CONTROL_RATIO: f64 = 0.1;
+
+/// What the preview pane shows for selected entry
+#[derive(Debug, Clone, PartialEq)]
+pub enum Preview {
+    /// Start of a file, `len` is the size of the whole file
+    File {
+        chunk: Vec<u8>,
+        len: u64,
+        kind: ContentKind,
+    },
+    /// Folder with its entries as scanned so far, None when not scanned yet
+    Folder {
+        children: Option<usize>,
+        size: Option<u64>,
+    },
+    /// File could not be read
+    Error(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ContentKind {
+    Text,
+    Binary,
+    Image(ImageInfo),
+}
+
+/// Format and dimensions read from image headers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageInfo {
+    pub format: &'static str,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Preview {
+    /// Reads at most `limit` bytes from the start of file
+    pub fn read_file(path: &Path, limit: u64) -> Preview {
+        match read_chunk(path, limit) {
+            Ok((chunk, len)) => {
+                let kind = content_kind(&chunk, (chunk.len() as u64) < len);
+                Preview::File { chunk, len, kind }
+            }
+            Err(err) => Preview::Error(err.to_string()),
+        }
+    }
+
+    /// Whether only the start of the file was read
+    pub fn is_truncated(&self) -> bool {
+        matches!(self, Preview::File { chunk, len, .. } if (chunk.len() as u64) < *len)
+    }
+}
+
+/// Start of file and size of the whole file, the rest is never read. Only regular files are
+/// opened, opening a FIFO or a device can block.
+fn read_chunk(path: &Path, limit: u64) -> io::Result<(Vec<u8>, u64)> {
+    if !path.metadata()?.is_file() {
+        return Err(io::Error::other("not a regular file"));
+    }
+    let file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let mut chunk = Vec::with_capacity(limit.min(len) as usize);
+    file.take(limit).read_to_end(&mut chunk)?;
+    Ok((chunk, len))
+}
+
+/// Guesses what chunk holds, a character cut at the end of a truncated chunk is still text
+pub fn content_kind(chunk: &[u8], truncated: bool) -> ContentKind {
+    if let Some(image) = image_info(chunk) {
+        return ContentKind::Image(image);
+    }
+    let text = match std::str::from_utf8(chunk) {
+        Ok(text) => text,
+        Err(err) if truncated && err.error_len().is_none() => {
+            // Valid up to the cut, checked just above
+            std::str::from_utf8(&chunk[..err.valid_up_to()]).unwrap_or_default()
+        }
+        Err(_) => return ContentKind::Binary,
+    };
+    let controls = text
+        .chars()
+        .filter(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t' | '\x0c'))
+        .count();
+    match text.contains('\0') || controls as f64 > text.len() as f64 * CONTROL_RATIO {
+        true => ContentKind::Binary,
+        false => ContentKind::Text,
+    }
+}
+
+/// Dimensions of PNG, GIF, BMP and JPEG images, None for anything else
+pub fn image_info(chunk: &[u8]) -> Option<ImageInfo> {
+    let be32 = |at: usize| Some(u32::from_be_bytes(chunk.get(at..at + 4)?.try_into().ok()?));
+    let be16 = |at: usize| Some(u16::from_be_bytes(chunk.get(at..at + 2)?.try_into().ok()?) as u32);
+    let le16 = |at: usize| Some(u16::from_le_bytes(chunk.get(at..at + 2)?.try_into().ok()?) as u32);
+    let le32 = |at: usize| Some(i32::from_le_bytes(chunk.get(at..at + 4)?.try_into().ok()?));
+
+    let (format, width, height) = if chunk.starts_with(b"\x89PNG\r\n\x1a\n") {
+        ("PNG", be32(16)?, be32(20)?)
+    } else if chunk.starts_with(b"GIF87a") || chunk.starts_with(b"GIF89a") {
+        ("GIF", le16(6)?, le16(8)?)
+    } else if chunk.starts_with(b"BM") && chunk.len() >= 26 {
+        // Negative height means rows are stored top down
+        ("BMP", le32(18)?.unsigned_abs(), le32(22)?.unsigned_abs())
+    } else if chunk.starts_with(&[0xff, 0xd8]) {
+        let (width, height) = jpeg_size(chunk, be16)?;
+        ("JPEG", width, height)
+    } else {
+        return None;
+    };
+    Some(ImageInfo {
+        format,
+        width,
+        height,
+    })
+}
+
+/// Walks JPEG segments up to the first start of frame, which holds the size
+fn jpeg_size(chunk: &[u8], be16: impl Fn(usize) -> Option<u32>) -> Option<(u32, u32)> {
+    let mut at = 2;
+    loop {
+        if *chunk.get(at)? != 0xff {
+            return None;
+        }
+        let marker = *chunk.get(at + 1)?;
+        match marker {
+            // Padding before a marker
+            0xff => at += 1,
+            // Frame headers, except DHT, JPG and DAC which share the range
+            0xc0..=0xcf if !matches!(marker, 0xc4 | 0xc8 | 0xcc) => {
+                return Some((be16(at + 7)?, be16(at + 5)?));
+            }
+            _ => at += 2 + be16(at + 2)? as usize,
+        }
+    }
+}
+
+/// Lines of offset, hex bytes and printable characters, `per_line` bytes each
+pub fn hexdump(bytes: &[u8], per_line: usize) -> Vec<String> {
+    let per_line = per_line.max(1);
+    bytes
+        .chunks(per_line)
+        .enumerate()
+        .map(|(index, line)| {
+            let hex: Vec<String> = line.iter().map(|byte| format!("{byte:02x}")).collect();
+            let ascii: String = line
+                .iter()
+                .map(|byte| match byte.is_ascii_graphic() || *byte == b' ' {
+                    true => *byte as char,
+                    false => '.',
+                })
+                .collect();
+            format!(
+                "{:08x}  {:<width$}  |{ascii}|",
+                index * per_line,
+                hex.join(" "),
+                width = per_line * 3 - 1
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,34 @@
+use crate::fs::{for_each_folder, DataStore, DataStoreKey, FolderEntryType};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Scanned file with known modification time
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecentFile {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: SystemTime,
+}
+
+/// Lists scanned files below provided root, most recently modified first
+pub fn recent_files<S: DataStore<DataStoreKey>>(store: &S, root: &Path) -> Vec<RecentFile> {
+    let mut files = vec![];
+
+    for_each_folder(store, root, |path, folder| {
+        for entry in folder.entries() {
+            if entry.kind != FolderEntryType::File {
+                continue;
+            }
+            if let Some(modified) = entry.modified {
+                files.push(RecentFile {
+                    path: path.join(&entry.name),
+                    size: entry.size.unwrap_or(0),
+                    modified,
+                });
+            }
+        }
+    });
+
+    files.sort_by(|a, b| b.modified.cmp(&a.modified).then(a.path.cmp(&b.path)));
+    files
+}
@@ -0,0 +1,22 @@
+use std::io;
+use std::path::PathBuf;
+
+/// Path that could not be read while scanning
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanError {
+    pub path: PathBuf,
+    pub kind: io::ErrorKind,
+}
+
+impl ScanError {
+    pub fn new(path: PathBuf, err: &io::Error) -> Self {
+        ScanError {
+            path,
+            kind: err.kind(),
+        }
+    }
+
+    pub fn is_permission_denied(&self) -> bool {
+        self.kind == io::ErrorKind::PermissionDenied
+    }
+}
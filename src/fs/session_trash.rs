@@ -0,0 +1,161 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Most seconds between trashing an entry and the deletion time the trash records for it
+const TRASHED_AT_WINDOW: i64 = 2;
+
+/// Entry moved to trash during this session, still recoverable from the trash until purged
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrashedEntry {
+    pub path: PathBuf,
+    pub size: u64,
+    /// Seconds since epoch, tells it apart from earlier trashed entries with the same path
+    pub trashed_at: i64,
+}
+
+impl TrashedEntry {
+    pub fn new(path: PathBuf, size: u64) -> Self {
+        let trashed_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since| since.as_secs() as i64)
+            .unwrap_or(0);
+        TrashedEntry {
+            path,
+            size,
+            trashed_at,
+        }
+    }
+
+    /// Index of trash item that is this entry, among items by original path and deletion time.
+    ///
+    /// It is the one at its path deleted closest to when it was trashed, within a couple of
+    /// seconds, so the same path trashed again from elsewhere is never taken for it.
+    pub fn find_in<'a>(&self, items: impl Iterator<Item = (&'a Path, i64)>) -> Option<usize> {
+        items
+            .enumerate()
+            .filter(|(_, (path, _))| *path == self.path)
+            .map(|(index, (_, time_deleted))| (index, (time_deleted - self.trashed_at).abs()))
+            .filter(|(_, distance)| *distance <= TRASHED_AT_WINDOW)
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(index, _)| index)
+    }
+}
+
+/// Entries removed by a single confirmed deletion, undone and redone as a whole
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeletionStep {
+    pub entries: Vec<TrashedEntry>,
+    /// Entries were deleted without the trash, so the step can't be undone
+    pub permanent: bool,
+}
+
+/// Trash item that is provided entry, removed from items so no other entry takes it
+#[cfg(any(
+    windows,
+    all(
+        unix,
+        not(target_os = "macos"),
+        not(target_os = "ios"),
+        not(target_os = "android")
+    )
+))]
+fn take_trashed_entry(
+    items: &mut Vec<trash::TrashItem>,
+    entry: &TrashedEntry,
+) -> Option<trash::TrashItem> {
+    let paths: Vec<PathBuf> = items.iter().map(|item| item.original_path()).collect();
+    let index = entry.find_in(
+        paths
+            .iter()
+            .zip(items.iter())
+            .map(|(path, item)| (path.as_path(), item.time_deleted)),
+    )?;
+    Some(items.swap_remove(index))
+}
+
+/// Permanently removes provided entries from the trash, returns how many were found there.
+///
+/// Entries restored or emptied from the trash in the meantime are skipped.
+#[cfg(any(
+    windows,
+    all(
+        unix,
+        not(target_os = "macos"),
+        not(target_os = "ios"),
+        not(target_os = "android")
+    )
+))]
+pub fn purge_trashed(entries: &[TrashedEntry]) -> io::Result<usize> {
+    let mut items = trash::os_limited::list().map_err(io::Error::other)?;
+    let to_purge: Vec<trash::TrashItem> = entries
+        .iter()
+        .filter_map(|entry| take_trashed_entry(&mut items, entry))
+        .collect();
+    let found = to_purge.len();
+    trash::os_limited::purge_all(to_purge).map_err(io::Error::other)?;
+    Ok(found)
+}
+
+/// Puts provided entries back where they were deleted from.
+///
+/// Nothing is restored when one of them is no longer in the trash.
+#[cfg(any(
+    windows,
+    all(
+        unix,
+        not(target_os = "macos"),
+        not(target_os = "ios"),
+        not(target_os = "android")
+    )
+))]
+pub fn restore_trashed(entries: &[TrashedEntry]) -> io::Result<()> {
+    let mut items = trash::os_limited::list().map_err(io::Error::other)?;
+    let mut to_restore = vec![];
+    for entry in entries {
+        let Some(item) = take_trashed_entry(&mut items, entry) else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} is no longer in trash", entry.path.to_string_lossy()),
+            ));
+        };
+        to_restore.push(item);
+    }
+    trash::os_limited::restore_all(to_restore).map_err(|err| match err {
+        trash::Error::RestoreCollision { path, .. } => io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", path.to_string_lossy()),
+        ),
+        err => io::Error::other(err),
+    })
+}
+
+#[cfg(not(any(
+    windows,
+    all(
+        unix,
+        not(target_os = "macos"),
+        not(target_os = "ios"),
+        not(target_os = "android")
+    )
+)))]
+pub fn purge_trashed(_entries: &[TrashedEntry]) -> io::Result<usize> {
+    Err(io::Error::other(
+        "Emptying trash is not supported on this platform",
+    ))
+}
+
+#[cfg(not(any(
+    windows,
+    all(
+        unix,
+        not(target_os = "macos"),
+        not(target_os = "ios"),
+        not(target_os = "android")
+    )
+)))]
+pub fn restore_trashed(_entries: &[TrashedEntry]) -> io::Result<()> {
+    Err(io::Error::other(
+        "Restoring from trash is not supported on this platform",
+    ))
+}
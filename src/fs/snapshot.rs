@@ -0,0 +1,144 @@
+use crate::fs::{for_each_folder, DataStore, DataStoreKey, FolderEntryType};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{create_dir_all, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Sizes of every scanned entry below a root, kept between runs to compare scans
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub root: PathBuf,
+    /// Entry paths relative to root with their sizes
+    pub sizes: BTreeMap<String, u64>,
+    /// Sizes of folders at each cached scan, oldest first, as seconds since epoch and size.
+    /// Root itself is kept under an empty path.
+    #[serde(default)]
+    pub history: BTreeMap<String, Vec<(u64, u64)>>,
+}
+
+/// Entry whose size differs between two snapshots
+#[derive(Debug, Clone, PartialEq)]
+pub struct SizeChange {
+    pub path: String,
+    /// None when entry was added since previous snapshot
+    pub before: Option<u64>,
+    /// None when entry was removed since previous snapshot
+    pub after: Option<u64>,
+}
+
+impl SizeChange {
+    pub fn delta(&self) -> i128 {
+        self.after.unwrap_or(0) as i128 - self.before.unwrap_or(0) as i128
+    }
+}
+
+impl Snapshot {
+    pub fn build<S: DataStore<DataStoreKey>>(store: &S, root: &Path) -> Snapshot {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+        let mut sizes = BTreeMap::new();
+        let mut history = BTreeMap::new();
+        if let Some(folder) = store.get_folder(&root.to_path_buf()) {
+            history.insert(String::new(), vec![(now, folder.total_size())]);
+        }
+        for_each_folder(store, root, |path, folder| {
+            for entry in folder.entries() {
+                if entry.kind == FolderEntryType::Parent {
+                    continue;
+                }
+                if let Some(size) = entry.size {
+                    let path = path.join(&entry.name);
+                    let relative = path.strip_prefix(root).unwrap_or(&path);
+                    let relative = relative.to_string_lossy().to_string();
+                    if entry.kind == FolderEntryType::Folder {
+                        history.insert(relative.clone(), vec![(now, size)]);
+                    }
+                    sizes.insert(relative, size);
+                }
+            }
+        });
+        Snapshot {
+            root: root.to_path_buf(),
+            sizes,
+            history,
+        }
+    }
+
+    /// Puts sizes folders had in previous scans before current ones, keeping the last `depth`.
+    /// Folders gone since are dropped.
+    pub fn keep_history(&mut self, previous: &Snapshot, depth: usize) {
+        for (path, points) in self.history.iter_mut() {
+            if let Some(earlier) = previous.history.get(path) {
+                points.splice(0..0, earlier.iter().cloned());
+            }
+            let extra = points.len().saturating_sub(depth.max(1));
+            points.drain(..extra);
+        }
+    }
+
+    pub fn load(path: &Path) -> io::Result<Snapshot> {
+        let reader = BufReader::new(File::open(path)?);
+        serde_json::from_reader(reader).map_err(io::Error::other)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(writer, self).map_err(io::Error::other)
+    }
+
+    /// Entries added, removed or resized since previous snapshot, biggest change first
+    pub fn changes_since(&self, previous: &Snapshot) -> Vec<SizeChange> {
+        let mut changes: Vec<SizeChange> = self
+            .sizes
+            .iter()
+            .filter(|(path, size)| previous.sizes.get(*path) != Some(size))
+            .map(|(path, size)| SizeChange {
+                path: path.clone(),
+                before: previous.sizes.get(path).cloned(),
+                after: Some(*size),
+            })
+            .chain(
+                previous
+                    .sizes
+                    .iter()
+                    .filter(|(path, _)| !self.sizes.contains_key(*path))
+                    .map(|(path, size)| SizeChange {
+                        path: path.clone(),
+                        before: Some(*size),
+                        after: None,
+                    }),
+            )
+            .collect();
+        changes.sort_by(|a, b| {
+            b.delta()
+                .abs()
+                .cmp(&a.delta().abs())
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        changes
+    }
+}
+
+/// Where snapshot of provided root is kept, in the user cache folder
+pub fn snapshot_path(root: &Path) -> Option<PathBuf> {
+    let cache_dir = match env::var_os("XDG_CACHE_HOME").filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".cache"),
+    };
+    let file_name = format!("{:016x}.json", fnv1a(root.as_os_str().as_encoded_bytes()));
+    Some(cache_dir.join("wiper").join("snapshots").join(file_name))
+}
+
+/// Hash that stays the same across builds, unlike the standard one
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
@@ -0,0 +1,118 @@
+use crate::fs::{FolderEntry, FolderEntryType, SortBy};
+use std::cmp::Ordering;
+
+/// Sorts entries in place, parent entry always stays first.
+///
+/// Names compare case-insensitively with digit runs read as numbers, so `file2` comes before
+/// `file10`. Entries without a known size or modification time come before the rest in either
+/// direction.
+/// Ties fall back to name, then to exact title, so the result never depends on input order.
+pub fn sort_entries(
+    entries: &mut [FolderEntry],
+    sort_by: SortBy,
+    descending: bool,
+    dirs_first: bool,
+) {
+    entries.sort_by(|a, b| compare_entries(a, b, sort_by, descending, dirs_first));
+}
+
+/// Ordering used by [`sort_entries`]. Counts are not kept on entries, so [`SortBy::Count`]
+/// only groups them here, [`compare_by_key`] takes counts from elsewhere.
+pub fn compare_entries(
+    a: &FolderEntry,
+    b: &FolderEntry,
+    sort_by: SortBy,
+    descending: bool,
+    dirs_first: bool,
+) -> Ordering {
+    match sort_by {
+        SortBy::Title => {
+            let ordering = compare_names(&a.title, &b.title);
+            group_order(a, b, dirs_first).then(match descending {
+                true => ordering.reverse(),
+                false => ordering,
+            })
+        }
+        SortBy::Size => compare_by_key(a, b, a.size, b.size, descending, dirs_first),
+        SortBy::Modified => compare_by_key(a, b, a.modified, b.modified, descending, dirs_first),
+        SortBy::Count => compare_by_key::<u64>(a, b, None, None, descending, dirs_first),
+    }
+}
+
+/// Orders entries by provided keys, entries without a key come before the rest in either
+/// direction. Ties fall back to name, like [`compare_entries`].
+pub fn compare_by_key<T: Ord>(
+    a: &FolderEntry,
+    b: &FolderEntry,
+    key_a: Option<T>,
+    key_b: Option<T>,
+    descending: bool,
+    dirs_first: bool,
+) -> Ordering {
+    let primary = match (key_a, key_b) {
+        (Some(key_a), Some(key_b)) if descending => key_b.cmp(&key_a),
+        (Some(key_a), Some(key_b)) => key_a.cmp(&key_b),
+        (Some(_), None) => Ordering::Greater,
+        (None, Some(_)) => Ordering::Less,
+        (None, None) => Ordering::Equal,
+    };
+
+    group_order(a, b, dirs_first)
+        .then(primary)
+        .then_with(|| compare_names(&a.title, &b.title))
+}
+
+/// Parent first, then folders before files when asked for
+fn group_order(a: &FolderEntry, b: &FolderEntry, dirs_first: bool) -> Ordering {
+    let is_parent = |entry: &FolderEntry| entry.kind == FolderEntryType::Parent;
+    let is_file = |entry: &FolderEntry| entry.kind == FolderEntryType::File;
+    is_parent(b)
+        .cmp(&is_parent(a))
+        .then_with(|| match dirs_first {
+            true => is_file(a).cmp(&is_file(b)),
+            false => Ordering::Equal,
+        })
+}
+
+/// Case-insensitive natural ordering of names, exact comparison breaks ties
+pub fn compare_names(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+
+    loop {
+        match (a_chars.peek(), b_chars.peek()) {
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a_char), Some(b_char)) if a_char.is_ascii_digit() && b_char.is_ascii_digit() => {
+                let a_number = take_digits(&mut a_chars);
+                let b_number = take_digits(&mut b_chars);
+                // Longer run without leading zeros is the bigger number
+                let ordering = a_number
+                    .len()
+                    .cmp(&b_number.len())
+                    .then_with(|| a_number.cmp(&b_number));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(a_char), Some(b_char)) => {
+                let ordering = a_char.to_lowercase().cmp(b_char.to_lowercase());
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                a_chars.next();
+                b_chars.next();
+            }
+        }
+    }
+}
+
+/// Consumes a run of digits, returns it without leading zeros
+fn take_digits(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(c) = chars.next_if(char::is_ascii_digit) {
+        digits.push(c);
+    }
+    digits.trim_start_matches('0').to_string()
+}
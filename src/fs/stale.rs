@@ -0,0 +1,154 @@
+use crate::fs::{
+    device_id, link_count, scan_folder, Folder, FolderEntry, FolderEntryType, ScanOptions,
+};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+/// How entry on disk differs from what the scan recorded
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiskChange {
+    Missing,
+    /// File size or modification time, or direct contents of a folder changed
+    Modified,
+}
+
+/// Deletion targets being compared with disk on their own thread, so listing large folders
+/// doesn't hold up the prompt
+#[derive(Debug)]
+pub struct ChangeCheck {
+    receiver: Receiver<Vec<(String, DiskChange)>>,
+}
+
+impl ChangeCheck {
+    /// Compares each target, its path, entry and stored listing, with disk in background
+    pub fn start(
+        targets: Vec<(PathBuf, FolderEntry, Option<Folder>)>,
+        options: &ScanOptions,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let options = options.clone();
+        thread::spawn(move || {
+            let changes = targets
+                .iter()
+                .filter_map(|(path, entry, scanned)| {
+                    disk_change(path, entry, scanned.as_ref(), &options)
+                        .map(|change| (entry.title.clone(), change))
+                })
+                .collect();
+            // Nobody waits for the result once the deletion was answered
+            let _ = sender.send(changes);
+        });
+        ChangeCheck { receiver }
+    }
+
+    /// Titles of targets that changed, in the order they were provided, once all were compared
+    pub fn try_finish(&self) -> Option<Vec<(String, DiskChange)>> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+/// Compares entry at path with its scanned state, `scanned` is the stored listing of a folder.
+///
+/// Folders are checked one level deep, so changes further down are not noticed. Sizes of files
+/// with several hard links are not compared, the scan may have counted them at another path.
+pub fn disk_change(
+    path: &Path,
+    entry: &FolderEntry,
+    scanned: Option<&Folder>,
+    options: &ScanOptions,
+) -> Option<DiskChange> {
+    let Ok(metadata) = path.symlink_metadata() else {
+        return Some(DiskChange::Missing);
+    };
+    let changed = match entry.kind {
+        FolderEntryType::Parent => false,
+        FolderEntryType::File => {
+            (link_count(&metadata) <= 1 && entry.size.is_some_and(|size| size != metadata.len()))
+                || entry
+                    .modified
+                    .is_some_and(|modified| metadata.modified().ok() != Some(modified))
+        }
+        FolderEntryType::Folder => {
+            // Folder not scanned yet has nothing to compare with
+            let scanned = scanned?;
+            let (current, _) = scan_folder(path.to_path_buf(), options);
+            listing(path, &current) != listing(path, scanned)
+        }
+    };
+    changed.then_some(DiskChange::Modified)
+}
+
+/// Checks of scan roots running on their own thread, so a hung mount doesn't freeze the UI
+#[derive(Debug)]
+pub struct RootCheck {
+    receiver: Receiver<bool>,
+}
+
+impl RootCheck {
+    /// Checks in background whether any of the roots, with devices they were scanned on, is there
+    pub fn start(roots: Vec<(PathBuf, Option<u64>)>) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let available = roots
+                .iter()
+                .any(|(path, device)| root_available(path, *device));
+            // Nobody waits for the result once the root was replaced
+            let _ = sender.send(available);
+        });
+        RootCheck { receiver }
+    }
+
+    /// Whether any root is available, once checked
+    pub fn try_finish(&self) -> Option<bool> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+/// Whether scan root can still be read, a folder whose filesystem went away fails to list.
+///
+/// A folder on another device than the one it was scanned on is an empty mount point left
+/// behind by an unmounted drive, so it is not available either.
+pub fn root_available(path: &Path, device: Option<u64>) -> bool {
+    match path.metadata() {
+        Ok(metadata) if metadata.is_dir() => {
+            let moved = device
+                .zip(device_id(&metadata))
+                .is_some_and(|(scanned, current)| scanned != current);
+            !moved && std::fs::read_dir(path).is_ok()
+        }
+        Ok(_) => true,
+        Err(_) => false,
+    }
+}
+
+/// Device holding path, recorded when it is scanned so an unmounted drive can be told apart
+/// from the folder it was mounted on
+pub fn root_device(path: &Path) -> Option<u64> {
+    path.metadata()
+        .ok()
+        .and_then(|metadata| device_id(&metadata))
+}
+
+/// Names of folder entries at path with sizes of files, sorted so listings can be compared
+fn listing<'a>(path: &Path, folder: &'a Folder) -> Vec<(&'a std::ffi::OsStr, Option<u64>)> {
+    let mut listing: Vec<_> = folder
+        .entries()
+        .iter()
+        .filter(|entry| entry.kind != FolderEntryType::Parent)
+        .map(|entry| {
+            let size = match entry.kind {
+                FolderEntryType::File if !is_hard_linked(&path.join(&entry.name)) => entry.size,
+                _ => None,
+            };
+            (entry.name.as_os_str(), size)
+        })
+        .collect();
+    listing.sort();
+    listing
+}
+
+fn is_hard_linked(path: &Path) -> bool {
+    path.symlink_metadata()
+        .is_ok_and(|metadata| link_count(&metadata) > 1)
+}
@@ -1,5 +1,6 @@
-use crate::fs::{path_to_folder, DataStore, Folder, FolderEntryType, SortBy};
+use crate::fs::{path_to_folder, DataStore, Folder, FolderEntryType, ScanOptions, SortBy};
 use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
 use std::path::PathBuf;
 
 use super::DataStoreKey;
@@ -11,6 +12,10 @@ pub struct DSHashmap {
     pub current_path: PathBuf,
     /// Map for all file paths
     pub store: FileTreeMap,
+    /// Paths listed under the virtual root, if there is one
+    pub roots: Vec<PathBuf>,
+    /// Limits applied to folders read by the store
+    pub scan_options: ScanOptions,
 }
 
 impl DataStore<DataStoreKey> for DSHashmap {
@@ -18,6 +23,8 @@ impl DataStore<DataStoreKey> for DSHashmap {
         DSHashmap {
             current_path: PathBuf::from("."),
             store: HashMap::new(),
+            roots: vec![],
+            scan_options: ScanOptions::default(),
         }
     }
 
@@ -45,6 +52,10 @@ impl DataStore<DataStoreKey> for DSHashmap {
         self.store.insert(path.clone(), folder);
     }
 
+    fn get_folder(&self, path: &PathBuf) -> Option<&Folder> {
+        self.store.get(path)
+    }
+
     fn get_folder_mut(&mut self, path: &PathBuf) -> Option<&mut Folder> {
         self.store.get_mut(path)
     }
@@ -53,28 +64,32 @@ impl DataStore<DataStoreKey> for DSHashmap {
         self.set_folder(&self.current_path.clone(), folder);
     }
 
-    // TODO: refactor
-    fn sort_current_folder(&mut self, sort_by: SortBy) {
+    fn sort_current_folder(&mut self, sort_by: SortBy, reversed: bool, folders_first: bool) {
+        let order = Some((sort_by, reversed, folders_first));
+        if self
+            .get_current_folder()
+            .is_none_or(|folder| folder.sorted_by == order)
+        {
+            return;
+        }
+        let counts = match sort_by {
+            SortBy::Count => self.entry_counts(&self.current_path),
+            _ => HashMap::new(),
+        };
         if let Some(folder) = self.get_current_folder_mut() {
-            match &folder.sorted_by {
-                None => match sort_by {
-                    SortBy::Title => folder.sort_by_title(),
-                    SortBy::Size => folder.sort_by_size(),
-                },
-                Some(folder_sort_by) => {
-                    if folder_sort_by.clone() != sort_by {
-                        match sort_by {
-                            SortBy::Title => folder.sort_by_title(),
-                            SortBy::Size => folder.sort_by_size(),
-                        };
-                    };
-                }
+            match sort_by {
+                SortBy::Count => folder.sort_by_counts(&counts, reversed, folders_first),
+                _ => folder.sort_by(sort_by, reversed, folders_first),
             }
-            folder.sorted_by = Some(sort_by);
+            folder.sorted_by = order;
         }
     }
 
     fn move_to_parent(&mut self) -> Vec<PathBuf> {
+        if self.roots.contains(&self.current_path) {
+            self.current_path = PathBuf::new();
+            return vec![];
+        }
         if let Some(parent) = &self.current_path.parent() {
             let parent_buf = parent.to_path_buf();
             self.current_path = parent_buf.clone();
@@ -86,16 +101,16 @@ impl DataStore<DataStoreKey> for DSHashmap {
     }
 
     // TODO: Returns string that should be processed
-    fn move_to_child(&mut self, title: &str) -> PathBuf {
+    fn move_to_child(&mut self, name: &OsStr) -> PathBuf {
         let mut new_path = PathBuf::from(&self.current_path);
-        new_path.push(title);
+        new_path.push(name);
         self.current_path = new_path.clone();
 
         new_path
     }
 
     fn get_entry_size(&mut self, path: &PathBuf) -> Option<u64> {
-        self.store.get(path).map(|entry| entry.get_size())
+        self.store.get(path).map(|entry| entry.total_size())
     }
 
     fn remove_path(&mut self, path: &PathBuf) {
@@ -110,37 +125,33 @@ impl DataStore<DataStoreKey> for DSHashmap {
         let mut to_process_subfolders: Vec<PathBuf> = vec![];
 
         if !self.has_path(path_buf) {
-            let mut folder = path_to_folder(path_buf.clone());
-            for child_entry in folder.entries.iter_mut() {
-                if child_entry.kind == FolderEntryType::Folder {
-                    let mut subfolder_path = path_buf.clone();
-                    subfolder_path.push(&child_entry.title);
-                    child_entry.size = self.get_entry_size(&subfolder_path);
+            let mut folder = path_to_folder(path_buf.clone(), &self.scan_options);
+            for index in 0..folder.entries().len() {
+                if folder.entries()[index].kind == FolderEntryType::Folder {
+                    let subfolder_path = path_buf.join(&folder.entries()[index].name);
+                    folder.set_entry_size(index, self.get_entry_size(&subfolder_path));
                     folder.sorted_by = None;
 
                     to_process_subfolders.push(subfolder_path);
                 }
             }
-            self.set_folder(path_buf, folder.clone());
 
-            let mut t = folder.clone();
+            let mut size = folder.total_size();
             let mut p = path_buf.clone();
+            self.set_folder(path_buf, folder);
 
-            while let Some(parent_buf) = p.parent() {
+            while let Some((parent_buf, name)) = self.get_parent(&p) {
                 if parent_buf == p {
                     break;
                 }
-                if let Some(parent_folder) = self.get_folder_mut(&PathBuf::from(parent_buf)) {
-                    for entry in parent_folder.entries.iter_mut() {
-                        if entry.title == t.title {
-                            entry.size = Some(t.get_size());
-                            parent_folder.sorted_by = None;
-
-                            break;
-                        }
+                if let Some(parent_folder) = self.get_folder_mut(&parent_buf) {
+                    if let Some(index) = parent_folder.entries().iter().position(|e| e.name == name)
+                    {
+                        parent_folder.set_entry_size(index, Some(size));
+                        parent_folder.sorted_by = None;
                     }
-                    t = parent_folder.clone();
-                    p = parent_buf.to_path_buf();
+                    size = parent_folder.total_size();
+                    p = parent_buf;
                 } else {
                     break;
                 }
@@ -151,4 +162,46 @@ impl DataStore<DataStoreKey> for DSHashmap {
             to_process_subfolders
         }
     }
+
+    fn set_virtual_root(&mut self, folder: Folder, roots: Vec<PathBuf>) {
+        self.current_path = PathBuf::new();
+        self.set_folder(&PathBuf::new(), folder);
+        self.roots = roots;
+    }
+
+    fn get_parent(&self, path: &PathBuf) -> Option<(PathBuf, OsString)> {
+        if self.roots.contains(path) {
+            return Some((PathBuf::new(), path.clone().into_os_string()));
+        }
+        let parent = path.parent()?;
+        let name = path.file_name()?.to_os_string();
+        Some((parent.to_path_buf(), name))
+    }
+
+    fn set_scan_options(&mut self, options: ScanOptions) {
+        self.scan_options = options;
+    }
+}
+
+impl DSHashmap {
+    /// Number of entries in each scanned folder of folder at provided path, by name
+    fn entry_counts(&self, path: &PathBuf) -> HashMap<OsString, usize> {
+        let Some(folder) = self.store.get(path) else {
+            return HashMap::new();
+        };
+        folder
+            .entries()
+            .iter()
+            .filter(|entry| entry.kind == FolderEntryType::Folder)
+            .filter_map(|entry| {
+                let child = self.store.get(&path.join(&entry.name))?;
+                let count = child
+                    .entries()
+                    .iter()
+                    .filter(|entry| entry.kind != FolderEntryType::Parent)
+                    .count();
+                Some((entry.name.clone(), count))
+            })
+            .collect()
+    }
 }
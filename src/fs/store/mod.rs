@@ -1,7 +1,8 @@
 mod ds_hashmap;
 pub use ds_hashmap::DSHashmap;
 
-use crate::fs::{Folder, SortBy};
+use crate::fs::{Folder, ScanOptions, SortBy};
+use std::ffi::{OsStr, OsString};
 use std::path::PathBuf;
 
 pub trait DataStore<T> {
@@ -22,6 +23,9 @@ pub trait DataStore<T> {
     /// Get optional current active mutable Folder
     fn get_current_folder_mut(&mut self) -> Option<&mut Folder>;
 
+    /// Get optional Folder for provided path
+    fn get_folder(&self, path: &T) -> Option<&Folder>;
+
     /// Get optional mutable Folder for provided path
     fn get_folder_mut(&mut self, path: &T) -> Option<&mut Folder>;
 
@@ -31,14 +35,14 @@ pub trait DataStore<T> {
     /// Update current active folder
     fn set_current_folder(&mut self, folder: Folder);
 
-    /// Sort current active folder by provided order
-    fn sort_current_folder(&mut self, sort_by: SortBy);
+    /// Sort current active folder by provided order, reversed and with folders first when asked for
+    fn sort_current_folder(&mut self, sort_by: SortBy, reversed: bool, folders_first: bool);
 
     /// Update current active path to its parent
     fn move_to_parent(&mut self) -> Vec<T>;
 
     /// Update current active path to child folder by provided title
-    fn move_to_child(&mut self, title: &str) -> T;
+    fn move_to_child(&mut self, name: &OsStr) -> T;
 
     /// Remove provided path record from store
     fn remove_path(&mut self, path: &T);
@@ -51,6 +55,15 @@ pub trait DataStore<T> {
 
     /// Processes received folder, stores, returns array of paths to process later
     fn process_path(&mut self, path: &T) -> Vec<T>;
+
+    /// Store synthetic folder listing provided roots and make it current
+    fn set_virtual_root(&mut self, folder: Folder, roots: Vec<T>);
+
+    /// Get parent path and the title provided path has in it
+    fn get_parent(&self, path: &T) -> Option<(T, OsString)>;
+
+    /// Limits applied to folders the store reads itself
+    fn set_scan_options(&mut self, options: ScanOptions);
 }
 
 pub type DataStoreKey = PathBuf;
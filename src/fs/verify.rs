@@ -0,0 +1,223 @@
+use crate::fs::{
+    for_each_folder, scan_folder_with, DataStore, DataStoreKey, FileSystem, Folder,
+    FolderEntryType, HardLinks, RealFileSystem, ScanOptions,
+};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// How scanned tree differs from disk at a path
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Discrepancy {
+    /// Entry on disk that the scan doesn't list
+    Unlisted,
+    /// Entry the scan lists that is gone from disk
+    Missing,
+    /// Size kept in memory differs from size on disk
+    Size { recorded: u64, disk: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mismatch {
+    pub path: PathBuf,
+    pub discrepancy: Discrepancy,
+}
+
+/// Verification of a scanned subtree running on its own thread, against a copy of it
+#[derive(Debug)]
+pub struct Verification {
+    /// Folder being verified
+    pub path: PathBuf,
+    /// Scanned folders in the subtree
+    pub total: usize,
+    /// Folders read from disk so far
+    pub read: Arc<Mutex<usize>>,
+    cancelled: Arc<Mutex<bool>>,
+    receiver: Receiver<Vec<Mismatch>>,
+}
+
+impl Verification {
+    /// Copies scanned folders at and below root, then reads them from disk again in background
+    pub fn start<S: DataStore<DataStoreKey>>(
+        store: &S,
+        root: &Path,
+        options: &ScanOptions,
+    ) -> Verification {
+        let scanned = scanned_subtree(store, root);
+        let total = scanned.len();
+        let read = Arc::new(Mutex::new(0));
+        let cancelled = Arc::new(Mutex::new(false));
+        let (sender, receiver) = mpsc::channel();
+
+        let worker_read = Arc::clone(&read);
+        let worker_cancelled = Arc::clone(&cancelled);
+        let root_path = root.to_path_buf();
+        let options = options.clone();
+        thread::spawn(move || {
+            let mismatches =
+                verify_tree_with(&scanned, &RealFileSystem, &root_path, &options, || {
+                    *worker_read.lock().unwrap() += 1;
+                    !*worker_cancelled.lock().unwrap()
+                });
+            // Nobody waits for the result once cancelled
+            let _ = sender.send(mismatches);
+        });
+
+        Verification {
+            path: root.to_path_buf(),
+            total,
+            read,
+            cancelled,
+            receiver,
+        }
+    }
+
+    /// Differences found, once every folder was read
+    pub fn try_finish(&self) -> Option<Vec<Mismatch>> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Stops reading folders, the thread ends after the one it is reading
+    pub fn cancel(&self) {
+        *self.cancelled.lock().unwrap() = true;
+    }
+}
+
+/// Scanned folders at and below root by their path, archive contents are left out
+pub fn scanned_subtree<S: DataStore<DataStoreKey>>(
+    store: &S,
+    root: &Path,
+) -> HashMap<PathBuf, Folder> {
+    let mut scanned = HashMap::new();
+    for_each_folder(store, root, |path, folder| {
+        if !folder.read_only {
+            scanned.insert(path.to_path_buf(), folder.clone());
+        }
+    });
+    scanned
+}
+
+/// Reads folder at root and every scanned folder below it from provided filesystem again,
+/// listing where scanned ones differ. `on_folder` is called before each folder is read, reading
+/// stops once it returns false.
+///
+/// Folders not scanned yet are not read, sizes of folders above them are not compared. Files
+/// with several hard links may have been counted at another path, their sizes are taken as
+/// scanned.
+pub fn verify_tree_with<F: FileSystem>(
+    scanned: &HashMap<PathBuf, Folder>,
+    fs: &F,
+    root: &Path,
+    options: &ScanOptions,
+    mut on_folder: impl FnMut() -> bool,
+) -> Vec<Mismatch> {
+    let mut mismatches = vec![];
+    verify_folder(
+        scanned,
+        fs,
+        root,
+        options,
+        &mut HardLinks::default(),
+        &mut on_folder,
+        &mut mismatches,
+    );
+    mismatches
+}
+
+/// Compares folder and what is below it with disk, returns its size on disk when every folder
+/// below it is scanned
+fn verify_folder<F: FileSystem>(
+    scanned: &HashMap<PathBuf, Folder>,
+    fs: &F,
+    path: &Path,
+    options: &ScanOptions,
+    hard_links: &mut HardLinks,
+    on_folder: &mut dyn FnMut() -> bool,
+    mismatches: &mut Vec<Mismatch>,
+) -> Option<u64> {
+    let recorded = scanned.get(path)?;
+    if !on_folder() {
+        return None;
+    }
+    let path = path.to_path_buf();
+    let (disk, _) = scan_folder_with(fs, path.clone(), options, hard_links);
+    let found = mismatches.len();
+    let mut size = Some(0);
+
+    for entry in disk.entries() {
+        if entry.kind == FolderEntryType::Parent {
+            continue;
+        }
+        let entry_path = path.join(&entry.name);
+        let Some(listed) = recorded
+            .entries()
+            .iter()
+            .find(|listed| listed.kind != FolderEntryType::Parent && listed.name == entry.name)
+        else {
+            mismatches.push(Mismatch {
+                path: entry_path,
+                discrepancy: Discrepancy::Unlisted,
+            });
+            continue;
+        };
+        let entry_size = match entry.kind {
+            FolderEntryType::Folder => verify_folder(
+                scanned,
+                fs,
+                &entry_path,
+                options,
+                hard_links,
+                on_folder,
+                mismatches,
+            ),
+            _ if is_hard_linked(fs, &entry_path) => listed.size,
+            _ => entry.size,
+        };
+        if entry.kind == FolderEntryType::File {
+            if let (Some(recorded), Some(disk)) = (listed.size, entry_size) {
+                if recorded != disk {
+                    mismatches.push(Mismatch {
+                        path: entry_path,
+                        discrepancy: Discrepancy::Size { recorded, disk },
+                    });
+                }
+            }
+        }
+        size = size
+            .zip(entry_size)
+            .map(|(size, entry_size)| size + entry_size);
+    }
+
+    for listed in recorded.entries() {
+        if listed.kind != FolderEntryType::Parent
+            && !disk.entries().iter().any(|entry| entry.name == listed.name)
+        {
+            mismatches.push(Mismatch {
+                path: path.join(&listed.name),
+                discrepancy: Discrepancy::Missing,
+            });
+        }
+    }
+
+    // Size drifting while nothing below differs comes from sizes kept in memory
+    if mismatches.len() == found {
+        if let Some(disk) = size {
+            let recorded = recorded.total_size();
+            if recorded != disk {
+                mismatches.push(Mismatch {
+                    path,
+                    discrepancy: Discrepancy::Size { recorded, disk },
+                });
+            }
+        }
+    }
+    size
+}
+
+/// Whether file has several hard links, so which of its paths the scan counted it at can't be told
+pub(crate) fn is_hard_linked<F: FileSystem>(fs: &F, path: &Path) -> bool {
+    fs.symlink_metadata(path)
+        .is_ok_and(|metadata| metadata.links > 1)
+}
@@ -22,6 +22,21 @@ pub mod task_manager;
 /// Debug logger
 pub mod logger;
 
+/// Entry name matching
+pub mod filter;
+
+/// Non-interactive reports
+pub mod report;
+
+/// Scan finish notifications
+pub mod notify;
+
+/// Secondary views over scanned data
+pub mod views;
+
+/// Start path prompt
+pub mod prompt;
+
 /// FPS counter
 pub mod fps_counter;
 
@@ -1,15 +1,36 @@
 use std::collections::VecDeque;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-#[derive(Debug)]
+/// Messages kept, older ones are dropped
+const MAX_MESSAGES: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MessageLevel {
     Info,
     Error,
 }
 
+/// Logged message, repeats of it are counted instead of listed
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    /// When it was last logged, milliseconds since the epoch
+    pub timestamp: u128,
+    pub level: MessageLevel,
+    /// Latest message logged under the key
+    pub message: String,
+    /// Messages with the same level and key are the same entry
+    pub key: String,
+    pub count: usize,
+}
+
 #[derive(Debug)]
 pub struct Logger {
-    pub messages: VecDeque<(u128, MessageLevel, String)>,
+    pub messages: VecDeque<LogEntry>,
+    /// Errors are kept in view at least that long, however many messages follow them
+    pub hold: Duration,
+    /// Latest error is pinned above other messages until acknowledged
+    pub pin_errors: bool,
+    pub pinned: Option<LogEntry>,
 }
 
 impl Default for Logger {
@@ -22,18 +43,110 @@ impl Logger {
     fn new() -> Self {
         Logger {
             messages: VecDeque::new(),
+            hold: Duration::ZERO,
+            pin_errors: false,
+            pinned: None,
         }
     }
 
+    /// Logs message, repeating a kept one only bumps its count
     pub fn log(&mut self, message: String, level: MessageLevel) {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards")
-            .as_millis();
+        let key = message.clone();
+        self.log_keyed(message, key, level);
+    }
 
-        if self.messages.len() >= 10 {
-            self.messages.pop_back();
+    /// Logs message that collapses with others of the same level and key, e.g. errors of
+    /// one kind met on different paths
+    pub fn log_keyed(&mut self, message: String, key: String, level: MessageLevel) {
+        let timestamp = now_ms();
+
+        let existing = self
+            .messages
+            .iter()
+            .position(|entry| entry.level == level && entry.key == key);
+        let count = match existing.and_then(|index| self.messages.remove(index)) {
+            Some(entry) => entry.count + 1,
+            None => 1,
+        };
+        if self.messages.len() >= MAX_MESSAGES {
+            // Oldest message not held, or the oldest one when all of them are
+            let dropped = self
+                .messages
+                .iter()
+                .rposition(|entry| !self.is_held(entry, timestamp))
+                .unwrap_or(self.messages.len() - 1);
+            self.messages.remove(dropped);
+        }
+        let entry = LogEntry {
+            timestamp,
+            level,
+            message,
+            key,
+            count,
+        };
+        if self.pin_errors && level == MessageLevel::Error {
+            self.pinned = Some(entry.clone());
         }
-        self.messages.push_front((timestamp, level, message));
+        self.messages.push_front(entry);
     }
+
+    /// Whether entry is an error logged less than `hold` before `now`
+    fn is_held(&self, entry: &LogEntry, now: u128) -> bool {
+        entry.level == MessageLevel::Error
+            && now.saturating_sub(entry.timestamp) < self.hold.as_millis()
+    }
+
+    /// Messages fitting in `rows`, newest first. Held errors take rows before newer messages,
+    /// so a burst of them can't push an error out of view as soon as it shows up.
+    pub fn visible(&self, rows: usize) -> Vec<&LogEntry> {
+        let now = now_ms();
+        let mut held = self
+            .messages
+            .iter()
+            .filter(|entry| self.is_held(entry, now))
+            .count()
+            .min(rows);
+        let mut others = rows - held;
+        self.messages
+            .iter()
+            .filter(|entry| {
+                let left = match self.is_held(entry, now) {
+                    true => &mut held,
+                    false => &mut others,
+                };
+                let shown = *left > 0;
+                *left = left.saturating_sub(1);
+                shown
+            })
+            .collect()
+    }
+
+    /// Unpins latest error, returns whether one was pinned
+    pub fn acknowledge(&mut self) -> bool {
+        self.pinned.take().is_some()
+    }
+}
+
+/// Milliseconds since the epoch, what log entries are stamped with
+pub fn now_ms() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_millis()
+}
+
+/// Bytes of memory this process holds resident, shown in the debug panel
+#[cfg(target_os = "linux")]
+pub fn resident_memory() -> Option<u64> {
+    // Second field is the resident set, in pages
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    (page_size > 0).then(|| pages * page_size as u64)
+}
+
+/// Bytes of memory this process holds resident, shown in the debug panel
+#[cfg(not(target_os = "linux"))]
+pub fn resident_memory() -> Option<u64> {
+    None
 }
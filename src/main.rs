@@ -1,40 +1,340 @@
+use crossterm::event::{KeyCode, KeyModifiers};
 use ratatui::backend::CrosstermBackend;
-use ratatui::Terminal;
+use ratatui::{Terminal, TerminalOptions, Viewport};
 use std::env;
-use std::io;
+use std::fs::File;
+use std::io::{self, BufWriter, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use std::process;
+use std::time::Duration;
 use wiper::app::{App, AppResult};
 use wiper::config::InitConfig;
-use wiper::config::EVENT_INTERVAL;
-use wiper::events::{handle_key_events, Event, EventHandler};
-use wiper::fs::DataStoreType;
+use wiper::config::{BENCH_RUNS, BROAD_ENTRIES, EVENT_INTERVAL};
+use wiper::events::{handle_key_events, handle_mouse_events, Event, EventHandler};
+use wiper::filter::{MatchMode, NameFilter};
+use wiper::fs::{export, normalize_path, resolve_root, DataStore, DataStoreType, ScanRoot};
+use wiper::logger::MessageLevel;
+use wiper::prompt::{
+    default_broad_paths, history_path, is_broad_root, load_history, save_history, PathPrompt,
+};
+use wiper::report::{BatchDeletion, HeaviestPath, Metrics, Outcome, ScanBench, Summary};
 use wiper::tui::Tui;
 
+/// Exit codes, so scripts can tell partial failures apart without parsing output:
+///
+/// - 0: everything was scanned, every requested deletion succeeded
+/// - 1: invalid arguments, or a requested file could not be written
+/// - 2 ([`wiper::report::EXIT_READ_ERRORS`]): some paths could not be read, results are partial
+/// - 3 ([`wiper::report::EXIT_DELETION_FAILED`]): a requested deletion failed, batch or interactive,
+///   reported even when read errors occurred too
+/// - 4 ([`wiper::report::EXIT_DELETION_REFUSED`]): batch deletion left out files matching
+///   `--danger`, reported over read errors but not over failed deletions
+const EXIT_USAGE: i32 = 1;
+
 fn main() -> AppResult<()> {
-    let config = InitConfig::build(env::args()).unwrap_or_else(|err| {
+    let mut config = InitConfig::load(env::args()).unwrap_or_else(|err| {
         eprintln!("Problem parsing arguments: {err}");
-        process::exit(1);
+        process::exit(EXIT_USAGE);
     });
 
+    if let Some(path) = &config.bench_scan {
+        let runs = config.bench_runs.unwrap_or(BENCH_RUNS);
+        let bench = ScanBench::run(&config, path, runs);
+        bench.write(&mut io::stdout(), config.json)?;
+        exit_with(Outcome {
+            read_errors: bench.read_errors,
+            ..Outcome::default()
+        });
+    }
+
+    let stdin = io::stdin();
+    let stdin_is_terminal = stdin.is_terminal();
+    if config.read_stdin || (config.file_path.is_none() && !stdin.is_terminal()) {
+        config.root_paths.extend(
+            stdin
+                .lines()
+                .map_while(Result::ok)
+                .filter(|line| !line.trim().is_empty()),
+        );
+    }
+
+    let summary = config.summary;
+    let heaviest_path = config.heaviest_path;
+    let json = config.json;
+    let title_progress = config.title_progress;
+    let batch_deletion = config.is_batch_deletion();
+    let batch_filter = NameFilter {
+        query: config.delete_matching.clone().unwrap_or("*".to_string()),
+        case: config.filter_case,
+        mode: MatchMode::Glob,
+    };
+    let older_than = config.older_than;
+    let yes = config.yes;
+    let metrics_file = config.metrics_file.clone();
+    let output_format = config.output_format;
+    let output = config.output.clone().unwrap_or("-".to_string());
+    let export_visible = config.export_visible;
+    let path_style = config.path_style;
+    let inline = config.inline;
+    let interactive = !(summary
+        || batch_deletion
+        || heaviest_path
+        || metrics_file.is_some()
+        || output_format.is_some());
+    if interactive && !config.no_history {
+        config.history_file = history_path();
+    }
+
+    // Scanning current folder by accident can be expensive, e.g. in home folder
+    let mut tui = None;
+    if interactive
+        && config.file_path.is_none()
+        && config.root_paths.is_empty()
+        && stdin_is_terminal
+    {
+        let mut prompt_tui = new_tui(inline)?;
+        prompt_tui.init()?;
+        match prompt_for_path(&mut prompt_tui, config.history_file.as_deref())? {
+            Some(path) => config.file_path = Some(path),
+            None => {
+                prompt_tui.exit()?;
+                return Ok(());
+            }
+        }
+        tui = Some(prompt_tui);
+    }
+
+    // Asked once, anything smaller is scanned right away
+    if interactive && stdin_is_terminal && !config.no_broad_check {
+        if let Some(root) = broad_root(&config) {
+            let mut prompt_tui = match tui.take() {
+                Some(tui) => tui,
+                None => {
+                    let mut tui = new_tui(inline)?;
+                    tui.init()?;
+                    tui
+                }
+            };
+            if !confirm_broad_root(&mut prompt_tui, &root)? {
+                prompt_tui.exit()?;
+                return Ok(());
+            }
+            tui = Some(prompt_tui);
+        }
+    }
+
     let mut app: App<DataStoreType> = App::new(config);
     app.init();
 
-    let backend = CrosstermBackend::new(io::stderr());
-    let terminal = Terminal::new(backend)?;
-    let events = EventHandler::new(EVENT_INTERVAL);
-    let mut tui = Tui::new(terminal, events);
-    tui.init()?;
+    if !interactive {
+        app.wait_for_tasks();
+    }
+    if let Some(path) = &metrics_file {
+        let root = app.store.get_current_path().clone();
+        let duration = Duration::from_millis(app.task_manager.time_taken().unwrap_or(0) as u64);
+        let metrics = Metrics::build(&app.store, &root, duration, app.scan_errors.len());
+        if let Err(err) = metrics.write_to_file(Path::new(path)) {
+            eprintln!("Could not write metrics to {path}: {err}");
+            process::exit(EXIT_USAGE);
+        }
+    }
+    if let Some(format) = output_format {
+        let root = app.store.get_current_path().clone();
+        app.update_filters();
+        let visible = export_visible.then_some(&app.ui_config);
+        let result = match output.as_str() {
+            "-" => export::export(
+                format,
+                &app.store,
+                &root,
+                path_style,
+                visible,
+                &mut io::stdout().lock(),
+            ),
+            path => File::create(path).and_then(|file| {
+                let mut writer = BufWriter::new(file);
+                export::export(format, &app.store, &root, path_style, visible, &mut writer)
+                    .and_then(|_| writer.flush())
+            }),
+        };
+        if let Err(err) = result {
+            eprintln!("Could not write export to {output}: {err}");
+            process::exit(EXIT_USAGE);
+        }
+    }
+    // Only files were asked for
+    if !(interactive || summary || batch_deletion || heaviest_path) {
+        exit_with(app.outcome());
+    }
+    if summary {
+        let root = app.store.get_current_path().clone();
+        Summary::build(&app.store, &root, path_style).write(&mut io::stdout(), json)?;
+        exit_with(app.outcome());
+    }
+    if batch_deletion {
+        let root = app.store.get_current_path().clone();
+        let mut batch = BatchDeletion::build(
+            &app.store,
+            &root,
+            &batch_filter,
+            older_than,
+            &app.ui_config.danger_patterns,
+            path_style,
+        );
+        if yes {
+            batch.delete(&app.ui_config);
+        }
+        batch.write(&mut io::stdout(), json)?;
+        exit_with(Outcome {
+            deletion_failed: batch.has_errors(),
+            deletion_refused: !batch.dry_run && batch.has_refused(),
+            ..app.outcome()
+        });
+    }
+    if heaviest_path {
+        let root = app.store.get_current_path().clone();
+        HeaviestPath::build(&app.store, &root, path_style).write(&mut io::stdout(), json)?;
+        exit_with(app.outcome());
+    }
+
+    let mut tui = match tui {
+        Some(tui) => tui,
+        None => {
+            let mut tui = new_tui(inline)?;
+            tui.init()?;
+            tui
+        }
+    };
 
+    // Idle app is drawn once more to show it is asleep, then left alone until a key arrives
+    let mut drawn_idle = false;
     while app.running {
-        tui.draw(&mut app)?;
+        if title_progress {
+            tui.set_title(app.window_title())?;
+        }
+        if !(app.idle && drawn_idle) {
+            tui.draw(&mut app)?;
+        }
+        drawn_idle = app.idle;
         match tui.events.next()? {
             Event::Tick => app.tick(),
             Event::Key(key_event) => handle_key_events(key_event, &mut app)?,
-            Event::Mouse(_) => {}
-            Event::Resize(_, _) => {}
+            Event::Mouse(mouse_event) => handle_mouse_events(mouse_event, &mut app)?,
+            Event::Resize(_, _) => {
+                tui.resize()?;
+                // Asleep app is drawn once more at its new size
+                drawn_idle = false;
+            }
+        }
+        if let Some(launch) = app.launch.take() {
+            if let Err(err) = tui.launch(&launch) {
+                app.logger.log(
+                    format!("Could not launch {launch:?}: {err}"),
+                    MessageLevel::Error,
+                );
+            }
         }
     }
 
     tui.exit()?;
-    Ok(())
+    exit_with(app.outcome());
+}
+
+/// Ends the process with exit code of provided outcome
+fn exit_with(outcome: Outcome) -> ! {
+    let _ = io::stdout().flush();
+    process::exit(outcome.exit_code())
+}
+
+/// Interface taking over the alternate screen, or drawn on the main screen when inline
+fn new_tui(inline: bool) -> AppResult<Tui<CrosstermBackend<io::Stderr>, DataStoreType>> {
+    let backend = CrosstermBackend::new(io::stderr());
+    let terminal = match inline {
+        // Whole height, earlier output scrolls up into scrollback
+        true => Terminal::with_options(
+            backend,
+            TerminalOptions {
+                viewport: Viewport::Inline(crossterm::terminal::size()?.1),
+            },
+        )?,
+        false => Terminal::new(backend)?,
+    };
+    let events = EventHandler::new(EVENT_INTERVAL);
+    Ok(Tui::new(terminal, events, inline))
+}
+
+/// First root about to be scanned that is configured as broad or looks huge
+fn broad_root(config: &InitConfig) -> Option<PathBuf> {
+    let broad_paths = match config.broad_paths.is_empty() {
+        true => default_broad_paths(),
+        false => config.broad_paths.iter().map(normalize_path).collect(),
+    };
+    let max_entries = config.broad_entries.unwrap_or(BROAD_ENTRIES);
+    let roots = match &config.file_path {
+        Some(path) => vec![path.clone()],
+        None if config.root_paths.is_empty() => vec![".".to_string()],
+        None => config.root_paths.clone(),
+    };
+    roots
+        .iter()
+        .find_map(|path| match resolve_root(Path::new(path)) {
+            ScanRoot::Folder(root) if is_broad_root(&root, &broad_paths, max_entries) => Some(root),
+            _ => None,
+        })
+}
+
+/// Whether user wants broad root scanned, only `y` confirms
+fn confirm_broad_root(
+    tui: &mut Tui<CrosstermBackend<io::Stderr>, DataStoreType>,
+    root: &Path,
+) -> AppResult<bool> {
+    loop {
+        tui.draw_broad_prompt(root)?;
+        if let Event::Key(key_event) = tui.events.next()? {
+            return Ok(matches!(key_event.code, KeyCode::Char('y' | 'Y')));
+        }
+    }
+}
+
+/// Asks which folder to scan, None when user quits instead
+fn prompt_for_path(
+    tui: &mut Tui<CrosstermBackend<io::Stderr>, DataStoreType>,
+    history_file: Option<&Path>,
+) -> AppResult<Option<String>> {
+    let mut prompt = PathPrompt::new();
+    if let Some(history_file) = history_file {
+        prompt = PathPrompt::with_history(
+            Path::new(prompt.input.as_str()),
+            &load_history(history_file),
+        );
+    }
+    loop {
+        tui.draw_prompt(&prompt)?;
+        let Event::Key(key_event) = tui.events.next()? else {
+            continue;
+        };
+        match key_event.code {
+            KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => return Ok(None),
+            KeyCode::Esc => return Ok(None),
+            KeyCode::Enter => {
+                let history_len = prompt.history.len();
+                if let Some(path) = prompt.submit() {
+                    return Ok(Some(path.to_string_lossy().to_string()));
+                }
+                // Recent root that no longer exists was dropped
+                if let (Some(history_file), true) =
+                    (history_file, prompt.history.len() < history_len)
+                {
+                    let roots: Vec<PathBuf> = prompt.history.iter().map(PathBuf::from).collect();
+                    let _ = save_history(history_file, &roots);
+                }
+            }
+            KeyCode::Up => prompt.on_history_up(),
+            KeyCode::Down => prompt.on_history_down(),
+            KeyCode::Tab => prompt.on_complete(),
+            KeyCode::Backspace => prompt.on_backspace(),
+            KeyCode::Char(c) => prompt.on_input(c),
+            _ => {}
+        }
+    }
 }
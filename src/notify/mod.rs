@@ -0,0 +1,49 @@
+use crate::report::Summary;
+use crate::ui::format_file_size;
+use std::io::{self, Write};
+
+/// Rings terminal bell
+pub fn ring_bell() {
+    let mut stderr = io::stderr();
+    let _ = stderr.write_all(b"\x07");
+    let _ = stderr.flush();
+}
+
+/// Shows desktop notification
+#[cfg(feature = "notifications")]
+pub fn send_notification(summary: &str, body: &str) -> Result<(), String> {
+    notify_rust::Notification::new()
+        .summary(summary)
+        .body(body)
+        .show()
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+/// Shows desktop notification
+#[cfg(not(feature = "notifications"))]
+pub fn send_notification(_summary: &str, _body: &str) -> Result<(), String> {
+    Err("built without notifications feature".to_string())
+}
+
+/// Describes finished scan, e.g. "scanned /data — 1.20 TB in 134,000 files"
+pub fn scan_finished_message(summary: &Summary) -> String {
+    format!(
+        "scanned {} — {} in {} files",
+        summary.path,
+        format_file_size(summary.total_size),
+        group_digits(summary.files)
+    )
+}
+
+fn group_digits(value: u64) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::new();
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
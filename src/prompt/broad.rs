@@ -0,0 +1,27 @@
+use std::env;
+use std::fs::read_dir;
+use std::path::{Path, PathBuf};
+
+/// Roots asked about when none are configured, filesystem root and home folder
+pub fn default_broad_paths() -> Vec<PathBuf> {
+    let mut paths = vec![PathBuf::from("/")];
+    paths.extend(
+        env::var_os("HOME")
+            .filter(|home| !home.is_empty())
+            .map(PathBuf::from),
+    );
+    paths
+}
+
+/// Whether scanning root may take very long, so it is confirmed first: root is one of
+/// `broad_paths`, or a look at its direct entries finds more than `max_entries` of them
+pub fn is_broad_root(root: &Path, broad_paths: &[PathBuf], max_entries: usize) -> bool {
+    let canonical = |path: &Path| path.canonicalize().unwrap_or(path.to_path_buf());
+    let root = canonical(root);
+    if broad_paths.iter().any(|path| canonical(path) == root) {
+        return true;
+    }
+    // Stops counting past the limit, listing a huge folder is slow too
+    read_dir(&root)
+        .is_ok_and(|entries| entries.take(max_entries.saturating_add(1)).count() > max_entries)
+}
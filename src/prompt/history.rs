@@ -0,0 +1,31 @@
+use std::env;
+use std::fs::{create_dir_all, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+/// Where recently scanned roots are kept, `$XDG_STATE_HOME/wiper/recent_roots.json`
+pub fn history_path() -> Option<PathBuf> {
+    let state_dir = match env::var_os("XDG_STATE_HOME").filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?)
+            .join(".local")
+            .join("state"),
+    };
+    Some(state_dir.join("wiper").join("recent_roots.json"))
+}
+
+/// Recently scanned roots, most recent first, empty when nothing was kept yet
+pub fn load_history(path: &Path) -> Vec<PathBuf> {
+    File::open(path)
+        .ok()
+        .and_then(|file| serde_json::from_reader::<_, Vec<PathBuf>>(BufReader::new(file)).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_history(path: &Path, roots: &[PathBuf]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent)?;
+    }
+    let writer = BufWriter::new(File::create(path)?);
+    serde_json::to_writer(writer, roots).map_err(io::Error::other)
+}
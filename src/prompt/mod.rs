@@ -0,0 +1,174 @@
+use std::env;
+use std::fs::read_dir;
+use std::path::{Path, PathBuf, MAIN_SEPARATOR};
+
+mod broad;
+mod history;
+pub use broad::{default_broad_paths, is_broad_root};
+pub use history::{history_path, load_history, save_history};
+
+/// Asks which folder to scan when wiper is started without a path
+#[derive(Debug, Default)]
+pub struct PathPrompt {
+    pub input: String,
+    /// Folder names offered by the last ambiguous completion
+    pub completions: Vec<String>,
+    /// Why the last submitted path can't be scanned
+    pub error: Option<String>,
+    /// Paths scanned before, most recent first
+    pub history: Vec<String>,
+    /// Position in history shown in input, None while typing
+    pub history_index: Option<usize>,
+}
+
+impl PathPrompt {
+    /// Starts with current folder filled in, so Enter alone scans it
+    pub fn new() -> Self {
+        let input = env::current_dir()
+            .map(|dir| with_separator(dir.to_string_lossy().to_string()))
+            .unwrap_or_default();
+        PathPrompt {
+            input,
+            ..Default::default()
+        }
+    }
+
+    /// Starts with provided path filled in, Up and Down go through previously scanned ones
+    pub fn with_history(path: &Path, history: &[PathBuf]) -> Self {
+        PathPrompt {
+            input: with_separator(path.to_string_lossy().to_string()),
+            history: history
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Fills in next older path from history
+    pub fn on_history_up(&mut self) {
+        let index = self.history_index.map_or(0, |index| index + 1);
+        if let Some(path) = self.history.get(index) {
+            self.input = path.clone();
+            self.history_index = Some(index);
+            self.completions.clear();
+            self.error = None;
+        }
+    }
+
+    /// Fills in next newer path from history
+    pub fn on_history_down(&mut self) {
+        if let Some(index) = self.history_index.and_then(|index| index.checked_sub(1)) {
+            self.input = self.history[index].clone();
+            self.history_index = Some(index);
+            self.completions.clear();
+            self.error = None;
+        }
+    }
+
+    pub fn on_input(&mut self, c: char) {
+        self.input.push(c);
+        self.history_index = None;
+        self.completions.clear();
+        self.error = None;
+    }
+
+    pub fn on_backspace(&mut self) {
+        self.input.pop();
+        self.history_index = None;
+        self.completions.clear();
+        self.error = None;
+    }
+
+    /// Completes last path component to folder names, lists them when several fit
+    pub fn on_complete(&mut self) {
+        let (parent, prefix) = match self.input.rfind(MAIN_SEPARATOR) {
+            Some(index) => self.input.split_at(index + 1),
+            None => ("", self.input.as_str()),
+        };
+        let search_dir = match parent {
+            "" => PathBuf::from("."),
+            _ => expand_home(parent),
+        };
+        let mut names: Vec<String> = read_dir(search_dir)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .filter(|entry| entry.path().is_dir())
+                    .map(|entry| entry.file_name().to_string_lossy().to_string())
+                    .filter(|name| name.starts_with(prefix))
+                    // Hidden folders only when asked for
+                    .filter(|name| prefix.starts_with('.') || !name.starts_with('.'))
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.sort();
+
+        let common = common_prefix(&names);
+        let parent = parent.to_string();
+        match names.len() {
+            0 => self.completions.clear(),
+            1 => {
+                self.input = with_separator(format!("{}{}", parent, names[0]));
+                self.completions.clear();
+            }
+            _ => {
+                self.input = format!("{}{}", parent, common);
+                self.completions = names;
+            }
+        }
+        self.error = None;
+    }
+
+    /// Folder to scan, None with error set when input is not a folder.
+    ///
+    /// Recent path picked from history that no longer exists is dropped from it.
+    pub fn submit(&mut self) -> Option<PathBuf> {
+        let path = expand_home(self.input.trim());
+        if path.is_dir() || path.is_file() {
+            return Some(path);
+        }
+        if let Some(index) = self.history_index.take() {
+            self.history.remove(index);
+            self.error = Some(format!("No longer exists: {}", self.input.trim()));
+            return None;
+        }
+        self.error = Some(format!("No such folder or file: {}", self.input.trim()));
+        None
+    }
+}
+
+fn with_separator(mut path: String) -> String {
+    if !path.ends_with(MAIN_SEPARATOR) {
+        path.push(MAIN_SEPARATOR);
+    }
+    path
+}
+
+/// Replaces leading `~` with home folder
+fn expand_home(path: &str) -> PathBuf {
+    let home = env::var_os("HOME").map(PathBuf::from);
+    match (path.strip_prefix('~'), home) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with(MAIN_SEPARATOR) => {
+            home.join(rest.trim_start_matches(MAIN_SEPARATOR))
+        }
+        _ => Path::new(path).to_path_buf(),
+    }
+}
+
+fn common_prefix(names: &[String]) -> String {
+    let Some(first) = names.first() else {
+        return String::new();
+    };
+    let mut len = first.len();
+    for name in &names[1..] {
+        len = first
+            .char_indices()
+            .zip(name.chars())
+            .take_while(|((_, a), b)| a == b)
+            .last()
+            .map_or(0, |((index, c), _)| index + c.len_utf8())
+            .min(len);
+    }
+    first[..len].to_string()
+}
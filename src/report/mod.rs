@@ -0,0 +1,687 @@
+use crate::app::{App, AppResult};
+use crate::config::{InitConfig, PathStyle, SizeUnit, UIConfig};
+use crate::filter::{danger_pattern, NameFilter};
+use crate::fs::{
+    compare_names, delete_file, disk_space, for_each_folder, format_path, DataStore, DataStoreKey,
+    DataStoreType, Folder, FolderEntryType,
+};
+use crate::ui::constants::TEXT_UNKNOWN;
+use crate::ui::{format_file_size, format_size};
+use serde::Serialize;
+use std::fs::{rename, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Some paths could not be read, results are partial
+pub const EXIT_READ_ERRORS: i32 = 2;
+/// A requested deletion failed, takes precedence over read errors
+pub const EXIT_DELETION_FAILED: i32 = 3;
+/// Batch deletion left out files matching a danger pattern, failures take precedence
+pub const EXIT_DELETION_REFUSED: i32 = 4;
+
+/// What went wrong during a run, turned into the process exit code
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Outcome {
+    pub read_errors: bool,
+    pub deletion_failed: bool,
+    pub deletion_refused: bool,
+}
+
+impl Outcome {
+    /// 0 when the scan and every requested deletion succeeded
+    pub fn exit_code(&self) -> i32 {
+        if self.deletion_failed {
+            EXIT_DELETION_FAILED
+        } else if self.deletion_refused {
+            EXIT_DELETION_REFUSED
+        } else if self.read_errors {
+            EXIT_READ_ERRORS
+        } else {
+            0
+        }
+    }
+}
+
+/// Totals of a finished scan, printed by `--summary`
+#[derive(Debug, Serialize)]
+pub struct Summary {
+    pub path: String,
+    pub total_size: u64,
+    pub files: u64,
+    pub directories: u64,
+    pub largest_file: Option<SummaryEntry>,
+    pub free_space: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SummaryEntry {
+    pub path: String,
+    pub size: u64,
+}
+
+impl Summary {
+    /// Walks every stored folder below provided root
+    pub fn build<S: DataStore<DataStoreKey>>(
+        store: &S,
+        root: &PathBuf,
+        paths: PathStyle,
+    ) -> Summary {
+        let mut summary = Summary {
+            path: format_path(root, root, paths),
+            total_size: store
+                .get_folder(root)
+                .map_or(0, |folder| folder.total_size()),
+            files: 0,
+            directories: 0,
+            largest_file: None,
+            free_space: disk_space(root).map(|space| space.free),
+        };
+
+        for_each_folder(store, root, |path, folder| {
+            for entry in folder.entries() {
+                match entry.kind {
+                    FolderEntryType::Parent => {}
+                    FolderEntryType::Folder => summary.directories += 1,
+                    FolderEntryType::File => {
+                        summary.files += 1;
+                        let size = entry.size.unwrap_or(0);
+                        let entry_path = format_path(&path.join(&entry.name), root, paths);
+                        let is_larger = match &summary.largest_file {
+                            None => true,
+                            Some(largest) => {
+                                size > largest.size
+                                    || (size == largest.size && entry_path < largest.path)
+                            }
+                        };
+                        if is_larger {
+                            summary.largest_file = Some(SummaryEntry {
+                                path: entry_path,
+                                size,
+                            });
+                        }
+                    }
+                }
+            }
+        });
+
+        summary
+    }
+
+    /// Writes summary as `key: value` lines, or as a single JSON object
+    pub fn write(&self, writer: &mut impl Write, json: bool) -> AppResult<()> {
+        if json {
+            serde_json::to_writer(&mut *writer, self)?;
+            writeln!(writer)?;
+            return Ok(());
+        }
+
+        writeln!(writer, "path: {}", self.path)?;
+        writeln!(writer, "total_size: {}", with_human_size(self.total_size))?;
+        writeln!(writer, "files: {}", self.files)?;
+        writeln!(writer, "directories: {}", self.directories)?;
+        match &self.largest_file {
+            Some(entry) => writeln!(
+                writer,
+                "largest_file: {} {}",
+                with_human_size(entry.size),
+                entry.path
+            )?,
+            None => writeln!(writer, "largest_file: {}", TEXT_UNKNOWN)?,
+        }
+        match self.free_space {
+            Some(free_space) => writeln!(writer, "free_space: {}", with_human_size(free_space))?,
+            None => writeln!(writer, "free_space: {}", TEXT_UNKNOWN)?,
+        }
+        Ok(())
+    }
+}
+
+/// Scan totals in Prometheus text format, written by `--metrics-file`.
+///
+/// Metric names and labels are relied on by dashboards, don't rename them:
+/// - `wiper_scan_size_bytes{root}` - total size of the scan root
+/// - `wiper_scan_files{root}` - files found
+/// - `wiper_scan_directories{root}` - directories found
+/// - `wiper_scan_duration_seconds{root}` - time scan took
+/// - `wiper_scan_errors{root}` - paths that could not be read
+/// - `wiper_directory_size_bytes{root, directory}` - size of every directory directly in root
+#[derive(Debug)]
+pub struct Metrics {
+    pub summary: Summary,
+    pub duration: Duration,
+    pub errors: usize,
+    /// Directories directly in root with their sizes, by name
+    pub directories: Vec<(String, u64)>,
+}
+
+impl Metrics {
+    pub fn build<S: DataStore<DataStoreKey>>(
+        store: &S,
+        root: &PathBuf,
+        duration: Duration,
+        errors: usize,
+    ) -> Metrics {
+        let mut directories: Vec<(String, u64)> = store
+            .get_folder(root)
+            .map(|folder| {
+                folder
+                    .entries()
+                    .iter()
+                    .filter(|entry| entry.kind == FolderEntryType::Folder)
+                    .map(|entry| (entry.title.clone(), entry.size.unwrap_or(0)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        directories.sort();
+
+        Metrics {
+            summary: Summary::build(store, root, PathStyle::Absolute),
+            duration,
+            errors,
+            directories,
+        }
+    }
+
+    pub fn write(&self, writer: &mut impl Write) -> AppResult<()> {
+        let root = format!("root=\"{}\"", escape_label(&self.summary.path));
+        let totals = [
+            (
+                "wiper_scan_size_bytes",
+                "Total size of the scan root in bytes.",
+                self.summary.total_size.to_string(),
+            ),
+            (
+                "wiper_scan_files",
+                "Number of files found by the scan.",
+                self.summary.files.to_string(),
+            ),
+            (
+                "wiper_scan_directories",
+                "Number of directories found by the scan.",
+                self.summary.directories.to_string(),
+            ),
+            (
+                "wiper_scan_duration_seconds",
+                "Time the scan took in seconds.",
+                self.duration.as_secs_f64().to_string(),
+            ),
+            (
+                "wiper_scan_errors",
+                "Number of paths the scan could not read.",
+                self.errors.to_string(),
+            ),
+        ];
+        for (name, help, value) in totals {
+            writeln!(writer, "# HELP {} {}", name, help)?;
+            writeln!(writer, "# TYPE {} gauge", name)?;
+            writeln!(writer, "{}{{{}}} {}", name, root, value)?;
+        }
+
+        let name = "wiper_directory_size_bytes";
+        writeln!(
+            writer,
+            "# HELP {} Size of a directory directly in the scan root in bytes.",
+            name
+        )?;
+        writeln!(writer, "# TYPE {} gauge", name)?;
+        for (directory, size) in &self.directories {
+            writeln!(
+                writer,
+                "{}{{{},directory=\"{}\"}} {}",
+                name,
+                root,
+                escape_label(directory),
+                size
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Writes next to provided path first and renames, so collectors never read a partial file
+    pub fn write_to_file(&self, path: &Path) -> AppResult<()> {
+        let mut tmp_name = path.as_os_str().to_os_string();
+        tmp_name.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_name);
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        self.write(&mut writer)?;
+        writer.flush()?;
+        drop(writer);
+        rename(&tmp_path, path)?;
+        Ok(())
+    }
+}
+
+/// Escapes label value as Prometheus text format requires
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Chain of the biggest entries from root down to a file, printed by `--heaviest-path`
+#[derive(Debug, Serialize)]
+pub struct HeaviestPath {
+    pub levels: Vec<SummaryEntry>,
+}
+
+impl HeaviestPath {
+    pub fn build<S: DataStore<DataStoreKey>>(
+        store: &S,
+        root: &PathBuf,
+        paths: PathStyle,
+    ) -> HeaviestPath {
+        let mut levels = vec![SummaryEntry {
+            path: format_path(root, root, paths),
+            size: store
+                .get_folder(root)
+                .map_or(0, |folder| folder.total_size()),
+        }];
+
+        let mut path = root.clone();
+        while let Some(folder) = store.get_folder(&path) {
+            let Some(index) = folder.get_largest_entry_index() else {
+                break;
+            };
+            let entry = &folder.entries()[index];
+            path = path.join(&entry.name);
+            levels.push(SummaryEntry {
+                path: format_path(&path, root, paths),
+                size: entry.size.unwrap_or(0),
+            });
+            if entry.kind != FolderEntryType::Folder {
+                break;
+            }
+        }
+
+        HeaviestPath { levels }
+    }
+
+    /// Writes one indented line per level, or levels as a JSON array
+    pub fn write(&self, writer: &mut impl Write, json: bool) -> AppResult<()> {
+        if json {
+            serde_json::to_writer(&mut *writer, &self.levels)?;
+            writeln!(writer)?;
+            return Ok(());
+        }
+
+        for (depth, level) in self.levels.iter().enumerate() {
+            let title = match depth {
+                0 => level.path.clone(),
+                _ => PathBuf::from(&level.path)
+                    .file_name()
+                    .map_or(level.path.clone(), |name| {
+                        name.to_string_lossy().to_string()
+                    }),
+            };
+            writeln!(
+                writer,
+                "{}{} {}",
+                "  ".repeat(depth),
+                title,
+                with_human_size(level.size)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn with_human_size(size: u64) -> String {
+    format!("{} ({})", size, format_file_size(size))
+}
+
+/// Timings of repeated scans of the same path, printed by `--bench-scan`.
+///
+/// Every run scans from scratch through the same worker and store the UI uses, with scan options
+/// of provided config. Later runs are usually faster, the first one fills the OS caches.
+#[derive(Debug)]
+pub struct ScanBench {
+    pub path: String,
+    /// Duration of every run, in the order they ran
+    pub runs: Vec<Duration>,
+    pub files: u64,
+    pub directories: u64,
+    pub size: u64,
+    /// Some paths could not be read in the last run
+    pub read_errors: bool,
+}
+
+impl ScanBench {
+    pub fn run(config: &InitConfig, path: &str, runs: usize) -> ScanBench {
+        let mut bench = ScanBench {
+            path: path.to_string(),
+            runs: vec![],
+            files: 0,
+            directories: 0,
+            size: 0,
+            read_errors: false,
+        };
+        for _ in 0..runs {
+            let config = InitConfig {
+                file_path: Some(path.to_string()),
+                root_paths: vec![],
+                bench_scan: None,
+                ..config.clone()
+            };
+            let started = Instant::now();
+            let mut app: App<DataStoreType> = App::new(config);
+            app.init();
+            // Polls more often than `wait_for_tasks`, so small trees are not timed by its sleep
+            while !app.task_manager.is_done() {
+                app.tick();
+                thread::sleep(Duration::from_millis(1));
+            }
+            app.tick();
+            bench.runs.push(started.elapsed());
+
+            let root = app.store.get_current_path().clone();
+            let summary = Summary::build(&app.store, &root, PathStyle::default());
+            bench.path = summary.path;
+            bench.files = summary.files;
+            bench.directories = summary.directories;
+            bench.size = summary.total_size;
+            bench.read_errors = app.outcome().read_errors;
+        }
+        bench
+    }
+
+    /// Shortest, median and longest run
+    pub fn stats(&self) -> (Duration, Duration, Duration) {
+        let mut runs = self.runs.clone();
+        runs.sort();
+        match (runs.first(), runs.last()) {
+            (Some(min), Some(max)) => (*min, runs[runs.len() / 2], *max),
+            _ => Default::default(),
+        }
+    }
+
+    /// Files and MiB scanned per second in the median run
+    pub fn throughput(&self) -> (f64, f64) {
+        let secs = self.stats().1.as_secs_f64();
+        if secs == 0.0 {
+            return (0.0, 0.0);
+        }
+        (
+            self.files as f64 / secs,
+            self.size as f64 / (1 << 20) as f64 / secs,
+        )
+    }
+
+    /// Writes timings as `key: value` lines, or as a single JSON object
+    pub fn write(&self, writer: &mut impl Write, json: bool) -> AppResult<()> {
+        let (min, median, max) = self.stats();
+        let (files_per_sec, mib_per_sec) = self.throughput();
+        if json {
+            let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+            let bench = serde_json::json!({
+                "path": self.path,
+                "runs": self.runs.len(),
+                "files": self.files,
+                "directories": self.directories,
+                "size": self.size,
+                "min_ms": ms(min),
+                "median_ms": ms(median),
+                "max_ms": ms(max),
+                "files_per_sec": files_per_sec,
+                "mib_per_sec": mib_per_sec,
+            });
+            serde_json::to_writer(&mut *writer, &bench)?;
+            writeln!(writer)?;
+            return Ok(());
+        }
+
+        writeln!(writer, "path: {}", self.path)?;
+        writeln!(writer, "runs: {}", self.runs.len())?;
+        writeln!(writer, "files: {}", self.files)?;
+        writeln!(writer, "directories: {}", self.directories)?;
+        writeln!(writer, "size: {}", with_human_size(self.size))?;
+        writeln!(writer, "min: {:.1?}", min)?;
+        writeln!(writer, "median: {:.1?}", median)?;
+        writeln!(writer, "max: {:.1?}", max)?;
+        writeln!(writer, "files/s: {:.0}", files_per_sec)?;
+        writeln!(writer, "MiB/s: {:.1}", mib_per_sec)?;
+        Ok(())
+    }
+}
+
+/// Marked entries of a folder with their sizes, copied to share findings
+#[derive(Debug, PartialEq)]
+pub struct SelectionReport {
+    pub path: PathBuf,
+    /// Names with a trailing `/` for folders, biggest first. None sizes are still loading.
+    pub entries: Vec<(String, Option<u64>)>,
+}
+
+impl SelectionReport {
+    /// Marked entries, or the selected one when nothing is marked
+    pub fn build(folder: &Folder, path: &Path) -> SelectionReport {
+        let mut entries: Vec<(String, Option<u64>)> = folder
+            .entries()
+            .iter()
+            .filter(|entry| entry.kind != FolderEntryType::Parent)
+            .filter(|entry| match folder.marked.is_empty() {
+                true => entry.name == folder.get_selected_entry().name,
+                false => folder.is_marked(&entry.name),
+            })
+            .map(|entry| {
+                let name = match entry.kind {
+                    FolderEntryType::Folder => format!("{}/", entry.title),
+                    _ => entry.title.clone(),
+                };
+                (name, entry.size)
+            })
+            .collect();
+        entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| compare_names(&a.0, &b.0)));
+        SelectionReport {
+            path: path.to_path_buf(),
+            entries,
+        }
+    }
+
+    /// Sum of entries that finished loading
+    pub fn total(&self) -> u64 {
+        self.entries.iter().filter_map(|(_, size)| *size).sum()
+    }
+
+    /// Markdown table padded into aligned columns, so it reads the same pasted as plain text
+    pub fn to_table(&self, unit: SizeUnit) -> String {
+        let format = |size: Option<u64>| match size {
+            Some(size) => format_size(size, unit),
+            None => TEXT_UNKNOWN.to_string(),
+        };
+        let total = format!("Total ({})", self.entries.len());
+        let mut rows: Vec<(String, String)> = self
+            .entries
+            .iter()
+            .map(|(name, size)| (name.replace('|', "\\|"), format(*size)))
+            .collect();
+        rows.push((total, format(Some(self.total()))));
+
+        let name_width = rows
+            .iter()
+            .map(|(name, _)| name.chars().count())
+            .chain(["Name".len()])
+            .max()
+            .unwrap_or_default();
+        let size_width = rows
+            .iter()
+            .map(|(_, size)| size.chars().count())
+            .chain(["Size".len()])
+            .max()
+            .unwrap_or_default();
+
+        let mut lines = vec![
+            self.path.to_string_lossy().to_string(),
+            String::new(),
+            format!("| {:<name_width$} | {:>size_width$} |", "Name", "Size"),
+            format!(
+                "|{}|{}:|",
+                "-".repeat(name_width + 2),
+                "-".repeat(size_width + 1)
+            ),
+        ];
+        lines.extend(
+            rows.iter()
+                .map(|(name, size)| format!("| {name:<name_width$} | {size:>size_width$} |")),
+        );
+        lines.join("\n") + "\n"
+    }
+
+    /// Whole report on a single line, for places that can't hold a table
+    pub fn to_line(&self, unit: SizeUnit) -> String {
+        let entries: Vec<String> = self
+            .entries
+            .iter()
+            .map(|(name, size)| match size {
+                Some(size) => format!("{name} {}", format_size(*size, unit)),
+                None => format!("{name} {TEXT_UNKNOWN}"),
+            })
+            .collect();
+        format!(
+            "{}: {}, total {}",
+            self.path.to_string_lossy(),
+            entries.join(", "),
+            format_size(self.total(), unit)
+        )
+    }
+}
+
+/// Files picked by `--delete-matching` and `--older-than`, deleted only with `--yes`
+#[derive(Debug, Serialize)]
+pub struct BatchDeletion {
+    pub files: Vec<BatchFile>,
+    /// Files were only listed
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchFile {
+    pub path: String,
+    pub size: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Danger pattern the file matches, such files are never deleted in batch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub danger_pattern: Option<String>,
+    /// Original path, display one may have invalid UTF-8 replaced
+    #[serde(skip)]
+    pub path_buf: PathBuf,
+}
+
+impl BatchDeletion {
+    /// Picks scanned files below root whose names match filter and, if provided,
+    /// that were not modified for at least that long. Files matching a danger pattern are
+    /// listed as refused, there is nobody to type their name.
+    pub fn build<S: DataStore<DataStoreKey>>(
+        store: &S,
+        root: &Path,
+        filter: &NameFilter,
+        older_than: Option<Duration>,
+        danger_patterns: &[String],
+        paths: PathStyle,
+    ) -> BatchDeletion {
+        let modified_before = older_than.and_then(|age| SystemTime::now().checked_sub(age));
+        let mut files = vec![];
+
+        for_each_folder(store, root, |path, folder| {
+            // Archive contents can't be deleted one by one
+            if folder.read_only {
+                return;
+            }
+            for entry in folder.entries() {
+                if entry.kind != FolderEntryType::File || !filter.matches(&entry.title) {
+                    continue;
+                }
+                if let Some(before) = modified_before {
+                    // Files of unknown age are kept
+                    if entry.modified.is_none_or(|modified| modified > before) {
+                        continue;
+                    }
+                }
+                let path_buf = path.join(&entry.name);
+                files.push(BatchFile {
+                    path: format_path(&path_buf, root, paths),
+                    size: entry.size.unwrap_or(0),
+                    error: None,
+                    danger_pattern: danger_pattern(&path_buf, danger_patterns).map(String::from),
+                    path_buf,
+                });
+            }
+        });
+        files.sort_by(|a, b| a.path_buf.cmp(&b.path_buf));
+
+        BatchDeletion {
+            files,
+            dry_run: true,
+        }
+    }
+
+    /// Deletes picked files but refused ones, failures are kept next to their files
+    pub fn delete(&mut self, config: &UIConfig) {
+        for file in &mut self.files {
+            if file.danger_pattern.is_some() {
+                continue;
+            }
+            if let Err(err) = delete_file(&file.path_buf, config) {
+                file.error = Some(err.to_string());
+            }
+        }
+        self.dry_run = false;
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.files.iter().any(|file| file.error.is_some())
+    }
+
+    pub fn has_refused(&self) -> bool {
+        self.files.iter().any(|file| file.danger_pattern.is_some())
+    }
+
+    /// Writes one line per file and a total, or everything as a single JSON object
+    pub fn write(&self, writer: &mut impl Write, json: bool) -> AppResult<()> {
+        if json {
+            serde_json::to_writer(&mut *writer, self)?;
+            writeln!(writer)?;
+            return Ok(());
+        }
+
+        for file in &self.files {
+            let action = match (&file.error, &file.danger_pattern, self.dry_run) {
+                (Some(err), _, _) => format!("failed ({})", err),
+                (None, Some(pattern), _) => format!("refused (matches danger pattern {})", pattern),
+                (None, None, true) => "would delete".to_string(),
+                (None, None, false) => "deleted".to_string(),
+            };
+            writeln!(
+                writer,
+                "{} {} {}",
+                action,
+                with_human_size(file.size),
+                file.path
+            )?;
+        }
+        let total = self
+            .files
+            .iter()
+            .filter(|file| file.error.is_none() && file.danger_pattern.is_none())
+            .map(|file| file.size)
+            .sum();
+        let summary = match self.dry_run {
+            true => "would free",
+            false => "freed",
+        };
+        writeln!(
+            writer,
+            "{} files, {} {}",
+            self.files.len(),
+            summary,
+            with_human_size(total)
+        )?;
+        if self.dry_run && !self.files.is_empty() {
+            writeln!(writer, "pass --yes to delete them")?;
+        }
+        Ok(())
+    }
+}
@@ -30,6 +30,14 @@ impl Spinner {
         let left_symbol = self.symbols[left_symbol_index];
         (left_symbol, right_symbol)
     }
+
+    /// Icons that never move, an ellipsis while loading and blanks once done
+    pub fn get_still_icons(&self, is_loaded: bool) -> (char, char) {
+        match is_loaded {
+            true => (' ', ' '),
+            false => ('…', '…'),
+        }
+    }
 }
 
 impl Default for Spinner {
@@ -1,11 +1,18 @@
-use crate::fs::{path_to_folder, DataStore, DataStoreKey, Folder, FolderEntryType};
-use std::collections::VecDeque;
+use crate::fs::{
+    for_each_folder, scan_folder_streaming, DataStore, DataStoreKey, FileSystem, Folder,
+    FolderEntryType, HardLinks, RealFileSystem, ScanError, ScanOptions,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ffi::OsString;
 use std::marker::PhantomData;
 use std::path::{Path, PathBuf};
-use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
+
+mod priority;
+pub use priority::{lower_thread_priority, LOW_PRIORITY_SUPPORTED};
 
 #[derive(Debug)]
 pub struct TaskTimer {
@@ -13,17 +20,64 @@ pub struct TaskTimer {
     pub finish: Option<u128>,
 }
 
+/// Scanned folder with paths in it that could not be read
+pub type ScanResult = (PathBuf, Folder, Vec<ScanError>);
+
+/// Modification time a stored folder was read with and names of its files
+pub type KnownFolder = (SystemTime, Vec<OsString>);
+
+/// How often entries of a folder still being read are handed to the UI
+const PARTIAL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// What the worker sends back while scanning
+#[derive(Debug)]
+pub enum ScanMessage {
+    /// Entries of a folder read so far, subfolders in it are not queued yet
+    Partial(PathBuf, Folder),
+    /// Completely read folder
+    Done(ScanResult),
+    /// Folder queued for an incremental rescan still has the modification time it was read
+    /// with, so it was not read again. Its stored files were stated again, rewriting a file
+    /// doesn't change the time of its folder.
+    Unchanged(PathBuf, Vec<RestatedFile>),
+}
+
+/// Stored file of a folder an incremental rescan didn't read again, as it is on disk now
+#[derive(Debug)]
+pub struct RestatedFile {
+    pub name: OsString,
+    /// None when the file is left out by scan options now
+    pub size: Option<u64>,
+    pub allocated: Option<u64>,
+    pub modified: Option<SystemTime>,
+}
+
 #[derive(Debug)]
 pub struct TaskManager<S: DataStore<DataStoreKey>> {
     /// Stack of file paths to process
     pub path_buf_stack: Arc<Mutex<VecDeque<PathBuf>>>,
     /// Single receiver to accept processed paths
-    pub receiver: Receiver<(PathBuf, Folder)>,
+    pub receiver: Receiver<ScanMessage>,
     /// Sender associated with the single receiver
-    pub sender: Sender<(PathBuf, Folder)>,
+    pub sender: Sender<ScanMessage>,
+    /// Stored folders that hold only part of their entries, until they are read completely
+    pub partial_paths: HashSet<PathBuf>,
     /// Job execution timer
     pub task_timer: TaskTimer,
     pub running_tasks: Arc<Mutex<usize>>,
+    /// Folders scanned since the scan was started
+    pub finished_tasks: usize,
+    /// Folders of those an incremental rescan found unchanged and didn't read again
+    pub unchanged_tasks: usize,
+    /// Modification times queued folders were read with and names of their stored files, a
+    /// folder still having its time is not read again
+    pub known_modified: Arc<Mutex<HashMap<PathBuf, KnownFolder>>>,
+    /// Limits applied by the worker to every scanned folder
+    pub scan_options: Arc<Mutex<ScanOptions>>,
+    /// Whether the worker lowers its I/O and CPU priority before scanning
+    pub low_priority: Arc<Mutex<bool>>,
+    /// Wakes the worker up when a task is added while it waits for one
+    wake: Sender<()>,
     _store: PhantomData<S>,
 }
 
@@ -38,28 +92,70 @@ fn _heavy_computation() {
 impl<S: DataStore<DataStoreKey>> TaskManager<S> {
     fn new() -> Self {
         let (sender, receiver) = mpsc::channel();
+        let (wake, wake_receiver) = mpsc::channel();
         let path_buf_stack = Arc::new(Mutex::new(VecDeque::<PathBuf>::new()));
         let running_tasks = Arc::new(Mutex::new(0));
+        let scan_options = Arc::new(Mutex::new(ScanOptions::default()));
+        let low_priority = Arc::new(Mutex::new(false));
+        let known_modified = Arc::new(Mutex::new(HashMap::new()));
 
         let worker_stack = Arc::clone(&path_buf_stack);
         let worker_sender = sender.clone();
         let running_tasks_clone = Arc::clone(&running_tasks);
+        let worker_options = Arc::clone(&scan_options);
+        let worker_priority = Arc::clone(&low_priority);
+        let worker_known = Arc::clone(&known_modified);
+        let mut lowered = false;
+        // Kept for the whole session, so rescans count hard links at the same paths
+        let mut hard_links = HardLinks::default();
         thread::spawn(move || loop {
             let task = {
                 let mut stack = worker_stack.lock().unwrap();
-                stack.pop_front()
+                let task = stack.pop_front();
+                // Counted before the stack is released, so the queue never looks done in between
+                if task.is_some() {
+                    *running_tasks_clone.lock().unwrap() += 1;
+                }
+                task
             };
 
             if let Some(path_buf) = task {
-                let mut tasks = running_tasks_clone.lock().unwrap();
-                *tasks += 1;
-                drop(tasks);
-
-                let folder = path_to_folder(path_buf.clone());
+                let known = worker_known.lock().unwrap().remove(&path_buf);
+                if let Some((_, files)) = known.filter(|(known, _)| {
+                    RealFileSystem
+                        .metadata(&path_buf)
+                        .is_ok_and(|metadata| metadata.modified == Some(*known))
+                }) {
+                    let options = worker_options.lock().unwrap().clone();
+                    let files =
+                        restat_files(&RealFileSystem, &path_buf, files, &options, &mut hard_links);
+                    let _ = worker_sender.send(ScanMessage::Unchanged(path_buf, files));
+                    continue;
+                }
+                if !lowered && *worker_priority.lock().unwrap() {
+                    // Applies to this thread only, so only scanning yields to other processes
+                    lowered = true;
+                    let _ = lower_thread_priority();
+                }
+                let options = worker_options.lock().unwrap().clone();
+                let (folder, errors) = scan_folder_streaming(
+                    &RealFileSystem,
+                    path_buf.clone(),
+                    &options,
+                    &mut hard_links,
+                    PARTIAL_INTERVAL,
+                    |partial| {
+                        let message = ScanMessage::Partial(path_buf.clone(), partial.clone());
+                        let _ = worker_sender.send(message);
+                    },
+                );
 
-                let _ = worker_sender.send((path_buf, folder));
-            } else {
-                thread::sleep(std::time::Duration::from_millis(100));
+                let _ = worker_sender.send(ScanMessage::Done((path_buf, folder, errors)));
+            } else if let Err(RecvTimeoutError::Disconnected) =
+                wake_receiver.recv_timeout(Duration::from_millis(100))
+            {
+                // Task manager is gone, nothing can queue tasks anymore
+                return;
             }
         });
 
@@ -67,24 +163,80 @@ impl<S: DataStore<DataStoreKey>> TaskManager<S> {
             path_buf_stack,
             receiver,
             sender,
+            partial_paths: HashSet::new(),
             task_timer: TaskTimer {
                 start: None,
                 finish: None,
             },
             running_tasks,
+            finished_tasks: 0,
+            unchanged_tasks: 0,
+            known_modified,
+            scan_options,
+            low_priority,
+            wake,
             _store: PhantomData,
         }
     }
 
+    pub fn set_scan_options(&mut self, options: ScanOptions) {
+        *self.scan_options.lock().unwrap() = options;
+    }
+
+    pub fn set_low_priority(&mut self, low_priority: bool) {
+        *self.low_priority.lock().unwrap() = low_priority;
+    }
+
     pub fn add_task(&mut self, path_buf: &Path) {
         {
             let mut stack = self.path_buf_stack.lock().unwrap();
             stack.push_back(path_buf.to_path_buf());
         } // Lock is released here
+        let _ = self.wake.send(());
 
         self.maybe_start_timer();
     }
 
+    /// Queues stored folder for a rescan that only states its files again when its modification
+    /// time is still the one it was read with. Folders without a known time are always read again.
+    pub fn add_rescan_task(&mut self, path_buf: &Path, folder: &Folder) {
+        if let Some(modified) = folder.modified {
+            let files = folder
+                .entries()
+                .iter()
+                .filter(|entry| entry.kind == FolderEntryType::File)
+                .map(|entry| entry.name.clone())
+                .collect();
+            self.known_modified
+                .lock()
+                .unwrap()
+                .insert(path_buf.to_path_buf(), (modified, files));
+        }
+        self.add_task(path_buf);
+    }
+
+    /// Drops queued folders and waits out the ones being scanned, so nothing reaches the store
+    pub fn discard_tasks(&mut self) {
+        self.path_buf_stack.lock().unwrap().clear();
+        while *self.running_tasks.lock().unwrap() > 0 {
+            match self.receiver.recv() {
+                Ok(ScanMessage::Partial(..)) => continue,
+                Ok(ScanMessage::Done(_) | ScanMessage::Unchanged(..)) => {
+                    *self.running_tasks.lock().unwrap() -= 1
+                }
+                Err(_) => break,
+            }
+        }
+        self.partial_paths.clear();
+        self.known_modified.lock().unwrap().clear();
+        self.task_timer = TaskTimer {
+            start: None,
+            finish: None,
+        };
+        self.finished_tasks = 0;
+        self.unchanged_tasks = 0;
+    }
+
     pub fn is_done(&self) -> bool {
         let stack = self.path_buf_stack.lock().unwrap();
         let running_tasks = self.running_tasks.lock().unwrap();
@@ -100,73 +252,138 @@ impl<S: DataStore<DataStoreKey>> TaskManager<S> {
         }
     }
 
-    pub fn handle_results(&mut self, store: &mut S) {
-        let mut tasks_finished = 0;
-        while let Ok((path_buf, folder)) = self.receiver.try_recv() {
-            tasks_finished += 1;
-            self.process_entry(store, &path_buf, folder);
+    /// Stores scanned folders, returns scanned paths with errors met while reading them
+    pub fn handle_results(&mut self, store: &mut S) -> Vec<(PathBuf, Vec<ScanError>)> {
+        let mut scanned = vec![];
+        let mut unchanged = 0;
+        while let Ok(message) = self.receiver.try_recv() {
+            match message {
+                ScanMessage::Partial(path_buf, folder) => {
+                    self.process_partial(store, &path_buf, folder);
+                }
+                ScanMessage::Done((path_buf, folder, errors)) => {
+                    self.partial_paths.remove(&path_buf);
+                    self.process_entry(store, &path_buf, folder);
+                    scanned.push((path_buf, errors));
+                }
+                // Stored folder and its errors stay as they are, only its files are updated
+                ScanMessage::Unchanged(path_buf, files) => {
+                    self.process_restated(store, &path_buf, files);
+                    unchanged += 1;
+                }
+            }
         }
 
         self.maybe_stop_timer();
 
+        self.finished_tasks += scanned.len() + unchanged;
+        self.unchanged_tasks += unchanged;
         let mut running_tasks = self.running_tasks.lock().unwrap();
-        *running_tasks -= tasks_finished;
+        *running_tasks -= scanned.len() + unchanged;
+        scanned
     }
 
     pub fn process_entry(&mut self, store: &mut S, path_buf: &PathBuf, mut folder: Folder) {
-        for child_entry in folder.entries.iter_mut() {
-            if child_entry.kind == FolderEntryType::Folder {
-                let mut subfolder_path = path_buf.clone();
-                subfolder_path.push(&child_entry.title);
-                child_entry.size = store.get_entry_size(&subfolder_path);
+        for index in 0..folder.entries().len() {
+            if folder.entries()[index].kind == FolderEntryType::Folder {
+                let subfolder_path = path_buf.join(&folder.entries()[index].name);
+                folder.set_entry_size(index, store.get_entry_size(&subfolder_path));
                 folder.sorted_by = None;
 
                 let task_added = self.maybe_add_task(store, &subfolder_path);
                 if task_added {
-                    child_entry.is_loaded = false;
+                    folder.update_entry(index, |entry| entry.is_loaded = false);
                 }
             }
         }
 
-        store.set_folder(path_buf, folder.clone());
+        if let Some(previous) = store.get_folder(path_buf).cloned() {
+            folder.retain_state_from(&previous);
+            remove_vanished_subfolders(store, path_buf, &previous, &folder);
+        }
 
-        let mut folder_traverse = folder.clone();
-        let mut path_traverse = path_buf.clone();
-        let mut is_loaded_traverse = folder.entries.iter().all(|entry| entry.is_loaded);
+        let is_loaded = folder.entries().iter().all(|entry| entry.is_loaded);
+        let size = folder.total_size();
+        store.set_folder(path_buf, folder);
+        propagate_size(store, path_buf, size, is_loaded);
+    }
 
-        while let Some(parent_buf) = path_traverse.parent() {
-            if parent_buf == path_traverse {
-                break;
+    /// Updates files of a stored folder that was not read again
+    pub fn process_restated(
+        &mut self,
+        store: &mut S,
+        path_buf: &PathBuf,
+        files: Vec<RestatedFile>,
+    ) {
+        let Some(folder) = store.get_folder_mut(path_buf) else {
+            return;
+        };
+        let mut changed = false;
+        for file in files {
+            let Some(index) = folder
+                .entries()
+                .iter()
+                .position(|entry| entry.kind == FolderEntryType::File && entry.name == file.name)
+            else {
+                continue;
+            };
+            if file.size.is_none() {
+                folder.remove_entry(index);
+                changed = true;
+                continue;
             }
-            if let Some(parent_folder) = store.get_folder_mut(&PathBuf::from(parent_buf)) {
-                for entry in parent_folder.entries.iter_mut() {
-                    if entry.title == folder_traverse.title {
-                        entry.size = Some(folder_traverse.get_size());
-                        entry.is_loaded = is_loaded_traverse;
-                        parent_folder.sorted_by = None;
-
-                        break;
-                    }
-                }
-                folder_traverse = parent_folder.clone();
-                path_traverse = parent_buf.to_path_buf();
-                is_loaded_traverse = parent_folder.entries.iter().all(|entry| entry.is_loaded);
-            } else {
-                break;
+            if folder.entries()[index].size != file.size {
+                folder.set_entry_size(index, file.size);
+                changed = true;
             }
+            folder.update_entry(index, |entry| {
+                entry.allocated = file.allocated;
+                entry.modified = file.modified;
+            });
+        }
+        if changed {
+            folder.sorted_by = None;
+            let size = folder.total_size();
+            let is_loaded = folder.entries().iter().all(|entry| entry.is_loaded);
+            propagate_size(store, path_buf, size, is_loaded);
         }
     }
+
+    /// Shows entries of a folder still being read, a rescanned folder keeps its complete entries
+    /// until the rescan is over
+    pub fn process_partial(&mut self, store: &mut S, path_buf: &PathBuf, mut folder: Folder) {
+        if store.has_path(path_buf) && !self.partial_paths.contains(path_buf) {
+            return;
+        }
+        for index in 0..folder.entries().len() {
+            if folder.entries()[index].kind == FolderEntryType::Folder {
+                folder.update_entry(index, |entry| entry.is_loaded = false);
+            }
+        }
+        if let Some(previous) = store.get_folder(path_buf) {
+            folder.retain_state_from(previous);
+        }
+        let size = folder.total_size();
+        store.set_folder(path_buf, folder);
+        self.partial_paths.insert(path_buf.clone());
+        propagate_size(store, path_buf, size, false);
+    }
+
     fn maybe_start_timer(&mut self) {
         if let Ok(duration) = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
             if self.task_timer.start.is_none() {
                 // Start is None - record start
                 self.task_timer.start = Some(duration.as_millis());
+                self.finished_tasks = 0;
+                self.unchanged_tasks = 0;
             } else {
                 // Start is not None
                 if self.task_timer.finish.is_some() {
                     // Finish is not None - restart
                     self.task_timer.start = Some(duration.as_millis());
                     self.task_timer.finish = None;
+                    self.finished_tasks = 0;
+                    self.unchanged_tasks = 0;
                 }
             };
         };
@@ -184,6 +401,17 @@ impl<S: DataStore<DataStoreKey>> TaskManager<S> {
         };
     }
 
+    /// Rough percent of scanned folders, None when nothing is being scanned.
+    /// Folders found later grow the total, so it can move backwards.
+    pub fn progress(&self) -> Option<u8> {
+        let queued =
+            self.path_buf_stack.lock().unwrap().len() + *self.running_tasks.lock().unwrap();
+        if queued == 0 {
+            return None;
+        }
+        Some((self.finished_tasks * 100 / (self.finished_tasks + queued)) as u8)
+    }
+
     pub fn time_taken(&self) -> Option<u128> {
         self.task_timer
             .start
@@ -191,6 +419,95 @@ impl<S: DataStore<DataStoreKey>> TaskManager<S> {
     }
 }
 
+/// States files of a folder again the way a scan lists them, files that can't be stated are left
+/// out so they keep their stored state
+fn restat_files<F: FileSystem>(
+    fs: &F,
+    path: &Path,
+    files: Vec<OsString>,
+    options: &ScanOptions,
+    hard_links: &mut HardLinks,
+) -> Vec<RestatedFile> {
+    files
+        .into_iter()
+        .filter_map(|name| {
+            let file_path = path.join(&name);
+            let metadata = fs.symlink_metadata(&file_path).ok()?;
+            if !options.includes_file(&name, metadata.len) {
+                return Some(RestatedFile {
+                    name,
+                    size: None,
+                    allocated: None,
+                    modified: None,
+                });
+            }
+            let counted = hard_links.claim(fs, &file_path, &metadata);
+            Some(RestatedFile {
+                name,
+                size: Some(if counted { metadata.len } else { 0 }),
+                allocated: metadata
+                    .allocated
+                    .map(|size| if counted { size } else { 0 }),
+                modified: metadata.modified,
+            })
+        })
+        .collect()
+}
+
+/// Updates size and loaded state of folder in every parent up to the root
+fn propagate_size<S: DataStore<DataStoreKey>>(
+    store: &mut S,
+    path_buf: &Path,
+    size: u64,
+    is_loaded: bool,
+) {
+    let mut size_traverse = size;
+    let mut path_traverse = path_buf.to_path_buf();
+    let mut is_loaded_traverse = is_loaded;
+    while let Some((parent_buf, name)) = store.get_parent(&path_traverse) {
+        if parent_buf == path_traverse {
+            break;
+        }
+        if let Some(parent_folder) = store.get_folder_mut(&parent_buf) {
+            if let Some(index) = parent_folder.entries().iter().position(|e| e.name == name) {
+                parent_folder.update_entry(index, |entry| {
+                    entry.size = Some(size_traverse);
+                    entry.is_loaded = is_loaded_traverse;
+                });
+                parent_folder.sorted_by = None;
+            }
+            size_traverse = parent_folder.total_size();
+            path_traverse = parent_buf;
+            is_loaded_traverse = parent_folder.entries().iter().all(|entry| entry.is_loaded);
+        } else {
+            break;
+        }
+    }
+}
+
+/// Drops stored subtrees of folders that are gone after a rescan
+fn remove_vanished_subfolders<S: DataStore<DataStoreKey>>(
+    store: &mut S,
+    path_buf: &Path,
+    previous: &Folder,
+    folder: &Folder,
+) {
+    for entry in previous.entries() {
+        if entry.kind != FolderEntryType::Folder
+            || folder.entries().iter().any(|e| e.name == entry.name)
+        {
+            continue;
+        }
+        let mut vanished = vec![];
+        for_each_folder(store, &path_buf.join(&entry.name), |path, _| {
+            vanished.push(path.to_path_buf());
+        });
+        for path in vanished {
+            store.remove_path(&path);
+        }
+    }
+}
+
 impl<S: DataStore<DataStoreKey>> Default for TaskManager<S> {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,35 @@
+use std::io;
+
+/// Whether scan priority can be lowered on this platform
+pub const LOW_PRIORITY_SUPPORTED: bool = cfg!(target_os = "linux");
+
+/// `ioprio_set` targets a single thread or process
+#[cfg(target_os = "linux")]
+const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+/// Disk time is granted only when nobody else asks for it
+#[cfg(target_os = "linux")]
+const IOPRIO_CLASS_IDLE: libc::c_int = 3;
+#[cfg(target_os = "linux")]
+const IOPRIO_CLASS_SHIFT: libc::c_int = 13;
+/// Lowest CPU priority
+#[cfg(target_os = "linux")]
+const LOWEST_NICENESS: libc::c_int = 19;
+
+/// Moves calling thread to idle I/O class and lowest CPU priority, so it yields to other work.
+/// On Linux both calls with id 0 apply to the calling thread only.
+#[cfg(target_os = "linux")]
+pub fn lower_thread_priority() -> io::Result<()> {
+    let priority = IOPRIO_CLASS_IDLE << IOPRIO_CLASS_SHIFT;
+    if unsafe { libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, 0, priority) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    if unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, LOWEST_NICENESS) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn lower_thread_priority() -> io::Result<()> {
+    Err(io::Error::from(io::ErrorKind::Unsupported))
+}
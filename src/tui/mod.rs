@@ -1,13 +1,23 @@
-use crate::app::{App, AppResult};
+use crate::app::{App, AppResult, Launch};
 use crate::events::EventHandler;
 use crate::fs::{DataStore, DataStoreKey};
+use crate::prompt::PathPrompt;
+use crate::ui::constants::TEXT_TITLE;
+use crate::ui::{render_broad_prompt, render_path_prompt};
 use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
-use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen, SetTitle};
 use ratatui::backend::Backend;
 use ratatui::Terminal;
 use std::io;
 use std::marker::PhantomData;
 use std::panic;
+use std::path::Path;
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
 
 /// Representation of a terminal user interface.
 ///
@@ -19,15 +29,21 @@ pub struct Tui<B: Backend, S: DataStore<DataStoreKey>> {
     terminal: Terminal<B>,
     /// Terminal event handler.
     pub events: EventHandler,
+    /// Window title last shown, None when titles are not managed.
+    title: Option<String>,
+    /// Drawn on the main screen instead of the alternate one, last frame stays in scrollback.
+    inline: bool,
     _store: PhantomData<S>,
 }
 
 impl<B: Backend, S: DataStore<DataStoreKey>> Tui<B, S> {
     /// Constructs a new instance of [`Tui`].
-    pub fn new(terminal: Terminal<B>, events: EventHandler) -> Self {
+    pub fn new(terminal: Terminal<B>, events: EventHandler, inline: bool) -> Self {
         Self {
             terminal,
             events,
+            title: None,
+            inline,
             _store: PhantomData,
         }
     }
@@ -36,14 +52,14 @@ impl<B: Backend, S: DataStore<DataStoreKey>> Tui<B, S> {
     ///
     /// It enables the raw mode and sets terminal properties.
     pub fn init(&mut self) -> AppResult<()> {
-        terminal::enable_raw_mode()?;
-        crossterm::execute!(io::stderr(), EnterAlternateScreen, EnableMouseCapture)?;
+        Self::enter(self.inline)?;
 
         // Define a custom panic hook to reset the terminal properties.
         // This way, you won't have your terminal messed up if an unexpected error happens.
         let panic_hook = panic::take_hook();
+        let inline = self.inline;
         panic::set_hook(Box::new(move |panic| {
-            Self::reset().expect("failed to reset the terminal");
+            Self::reset(inline).expect("failed to reset the terminal");
             panic_hook(panic);
         }));
 
@@ -62,13 +78,95 @@ impl<B: Backend, S: DataStore<DataStoreKey>> Tui<B, S> {
         Ok(())
     }
 
+    /// Drops what was drawn before a resize, next [`Tui::draw`] repaints every cell.
+    pub fn resize(&mut self) -> AppResult<()> {
+        self.terminal.autoresize()?;
+        self.terminal.clear()?;
+        Ok(())
+    }
+
+    /// Draws start prompt asking which folder to scan.
+    pub fn draw_prompt(&mut self, prompt: &PathPrompt) -> AppResult<()> {
+        self.terminal.draw(|frame| {
+            render_path_prompt(frame.size(), frame.buffer_mut(), prompt, TEXT_TITLE)
+        })?;
+        Ok(())
+    }
+
+    /// Draws question whether to scan a broad root.
+    pub fn draw_broad_prompt(&mut self, root: &Path) -> AppResult<()> {
+        self.terminal.draw(|frame| {
+            render_broad_prompt(frame.size(), frame.buffer_mut(), root, TEXT_TITLE)
+        })?;
+        Ok(())
+    }
+
+    /// Sets terminal window title, saving the original one on first call.
+    ///
+    /// Title is only written when it changes.
+    pub fn set_title(&mut self, title: String) -> AppResult<()> {
+        if self.title.as_ref() == Some(&title) {
+            return Ok(());
+        }
+        if self.title.is_none() {
+            // Push current title on terminal's title stack
+            eprint!("\x1b[22;0t");
+        }
+        crossterm::execute!(io::stderr(), SetTitle(&title))?;
+        self.title = Some(title);
+        Ok(())
+    }
+
+    /// Does work the app asked the terminal for.
+    ///
+    /// Commands get the terminal to themselves, the interface is restored once they exit.
+    pub fn launch(&mut self, launch: &Launch) -> AppResult<()> {
+        match launch {
+            Launch::Command(command) => {
+                let Some((program, args)) = command.split_first() else {
+                    return Ok(());
+                };
+                self.events.pause();
+                // Lets a read already in progress finish before the program takes input
+                thread::sleep(Duration::from_millis(20));
+                Self::reset(self.inline)?;
+                self.terminal.show_cursor()?;
+                let status = Command::new(program).args(args).status();
+
+                Self::enter(self.inline)?;
+                self.terminal.hide_cursor()?;
+                self.terminal.clear()?;
+                self.events.resume();
+                status?;
+            }
+            Launch::Clipboard(text) => {
+                // OSC 52, terminals that support it set their clipboard
+                eprint!("\x1b]52;c;{}\x07", encode_base64(text.as_bytes()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Enables raw mode and mouse capture, and the alternate screen unless drawing inline.
+    fn enter(inline: bool) -> AppResult<()> {
+        terminal::enable_raw_mode()?;
+        if !inline {
+            crossterm::execute!(io::stderr(), EnterAlternateScreen)?;
+        }
+        crossterm::execute!(io::stderr(), EnableMouseCapture)?;
+        Ok(())
+    }
+
     /// Resets the terminal interface.
     ///
     /// This function is also used for the panic hook to revert
     /// the terminal properties if unexpected errors occur.
-    fn reset() -> AppResult<()> {
+    fn reset(inline: bool) -> AppResult<()> {
         terminal::disable_raw_mode()?;
-        crossterm::execute!(io::stderr(), LeaveAlternateScreen, DisableMouseCapture)?;
+        if !inline {
+            crossterm::execute!(io::stderr(), LeaveAlternateScreen)?;
+        }
+        crossterm::execute!(io::stderr(), DisableMouseCapture)?;
         Ok(())
     }
 
@@ -76,8 +174,38 @@ impl<B: Backend, S: DataStore<DataStoreKey>> Tui<B, S> {
     ///
     /// It disables the raw mode and reverts back the terminal properties.
     pub fn exit(&mut self) -> AppResult<()> {
-        Self::reset()?;
+        Self::reset(self.inline)?;
+        if self.title.take().is_some() {
+            // Pop title saved by `set_title`
+            eprint!("\x1b[23;0t");
+        }
+        if self.inline {
+            // Shell prompt goes below the last frame
+            let area = self.terminal.get_frame().size();
+            self.terminal
+                .set_cursor(0, area.bottom().saturating_sub(1))?;
+            eprintln!();
+        }
         self.terminal.show_cursor()?;
         Ok(())
     }
 }
+
+/// Standard base64 with padding
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, byte)| {
+            group | (*byte as u32) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => {
+                    encoded.push(BASE64_ALPHABET[(group >> (18 - 6 * i) & 63) as usize] as char)
+                }
+                false => encoded.push('='),
+            }
+        }
+    }
+    encoded
+}
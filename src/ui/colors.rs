@@ -0,0 +1,103 @@
+use crate::config::ColorDepth;
+use crate::ui::constants::{
+    NORMAL_ROW_COLOR, TABLE_HEADER_BG, TEXT_COLOR, TEXT_DELETED_FG, TEXT_FLASH_BG,
+    TEXT_HIGHLIGHTED, TEXT_PRE_DELETED_BG, TEXT_SELECTED_BG,
+};
+use ratatui::prelude::*;
+
+/// Theme colors with a fixed counterpart among 16 colors, the nearest one would lose contrast
+/// between rows, header and selection which are all shades of slate
+const ANSI16_SCHEME: [(Color, Color); 8] = [
+    (NORMAL_ROW_COLOR, Color::Black),
+    (TEXT_COLOR, Color::Gray),
+    (TABLE_HEADER_BG, Color::Blue),
+    (TEXT_SELECTED_BG, Color::DarkGray),
+    (TEXT_PRE_DELETED_BG, Color::Red),
+    (TEXT_FLASH_BG, Color::LightRed),
+    (TEXT_HIGHLIGHTED, Color::Yellow),
+    (TEXT_DELETED_FG, Color::DarkGray),
+];
+
+/// 16 colors as xterm draws them by default
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Channel values of the 6×6×6 color cube of 256 color terminals
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Replaces colors of every cell of rendered frame with ones terminal can show
+pub fn reduce_colors(buf: &mut Buffer, depth: ColorDepth) {
+    if depth == ColorDepth::TrueColor {
+        return;
+    }
+    for cell in buf.content.iter_mut() {
+        cell.fg = reduce_color(cell.fg, depth);
+        cell.bg = reduce_color(cell.bg, depth);
+    }
+}
+
+/// Nearest color of provided depth, colors that aren't RGB are left as they are
+pub fn reduce_color(color: Color, depth: ColorDepth) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+    match depth {
+        ColorDepth::TrueColor => color,
+        ColorDepth::Ansi256 => Color::Indexed(nearest_256((r, g, b))),
+        ColorDepth::Ansi16 => ANSI16_SCHEME
+            .iter()
+            .find(|(theme, _)| *theme == color)
+            .map(|(_, reduced)| *reduced)
+            .unwrap_or_else(|| nearest_16((r, g, b))),
+    }
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let channel = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    channel(a.0, b.0) + channel(a.1, b.1) + channel(a.2, b.2)
+}
+
+fn nearest_16(rgb: (u8, u8, u8)) -> Color {
+    ANSI16
+        .iter()
+        .min_by_key(|(_, ansi)| distance(rgb, *ansi))
+        .map(|(color, _)| *color)
+        .unwrap_or(Color::Reset)
+}
+
+/// Index of nearest color in the cube or on the gray ramp after it
+fn nearest_256(rgb: (u8, u8, u8)) -> u8 {
+    let level = |value: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|index| (CUBE_LEVELS[*index] as i32 - value as i32).abs())
+            .unwrap_or(0)
+    };
+    let (r, g, b) = (level(rgb.0), level(rgb.1), level(rgb.2));
+    let cube = (CUBE_LEVELS[r], CUBE_LEVELS[g], CUBE_LEVELS[b]);
+
+    // Gray ramp runs from 8 to 238 in steps of 10
+    let average = (rgb.0 as u32 + rgb.1 as u32 + rgb.2 as u32) / 3;
+    let step = (average.saturating_sub(3) / 10).min(23) as u8;
+    let gray = 8 + step * 10;
+
+    match distance(rgb, (gray, gray, gray)) < distance(rgb, cube) {
+        true => 232 + step,
+        false => (16 + 36 * r + 6 * g + b) as u8,
+    }
+}
@@ -6,11 +6,37 @@ pub const TABLE_HEADER_FG: Color = tailwind::SLATE.c200;
 pub const TABLE_HEADER_BG: Color = tailwind::SLATE.c900;
 pub const TEXT_SELECTED_BG: Color = tailwind::SLATE.c700;
 pub const TEXT_PRE_DELETED_BG: Color = tailwind::RED.c600;
+pub const TEXT_FLASH_BG: Color = tailwind::RED.c200;
 pub const TEXT_HIGHLIGHTED: Color = tailwind::YELLOW.c400;
+pub const TEXT_DELETED_FG: Color = tailwind::SLATE.c500;
 pub const TABLE_SPACE_WIDTH: usize = 40;
+pub const OVERVIEW_COLORS: [Color; 8] = [
+    tailwind::BLUE.c500,
+    tailwind::EMERALD.c500,
+    tailwind::AMBER.c500,
+    tailwind::ROSE.c500,
+    tailwind::VIOLET.c500,
+    tailwind::CYAN.c500,
+    tailwind::LIME.c500,
+    tailwind::SLATE.c500,
+];
 
 // Texts
 pub const TEXT_UNKNOWN: &str = "N/A";
+pub const TEXT_SCANNING: &str = "scanning…";
+pub const TEXT_ELLIPSIS: &str = "…";
 pub const TEXT_PARENT_DIR: &str = "..";
+pub const TEXT_MARKED: &str = "*";
+pub const TEXT_HIGHLIGHT_SYMBOL: &str = "> ";
+pub const TEXT_SPARSE: &str = "≈";
+pub const TEXT_NONE: &str = "—";
+pub const TEXT_PINNED: &str = "!";
+pub const TEXT_VIRTUAL_ROOT: &str = "Selected paths";
+pub const TEXT_READ_ONLY: &str = "read-only";
+pub const TEXT_READ_ONLY_BADGE: &str = " READ ONLY ";
+pub const TEXT_OVERVIEW_OTHER: &str = "other";
 pub const TEXT_TITLE: &str = "Wiper";
 pub const TEXT_HINT_NAVIGATE: &str = "←↓↑→/Enter/Backspace - navigate";
+pub const TEXT_HINT_NAVIGATE_FILE: &str = "←↓↑→/Backspace - navigate";
+pub const TEXT_HINT_PERMISSION_DENIED: &str =
+    "Permission denied - re-run with elevated privileges (e.g. sudo) to scan it";
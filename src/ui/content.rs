@@ -1,58 +1,212 @@
-use crate::config::UIConfig;
-use crate::fs::Folder;
+use crate::config::{Column, UIConfig};
 use crate::fs::SortBy;
-use crate::logger::Logger;
+use crate::fs::{Folder, FolderEntryType};
 use crate::logger::MessageLevel;
+use crate::logger::{now_ms, LogEntry, Logger};
 use ratatui::{prelude::*, widgets::*};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use unicode_width::UnicodeWidthStr;
 
 use crate::ui::constants::{
-    NORMAL_ROW_COLOR, TABLE_HEADER_BG, TABLE_HEADER_FG, TABLE_SPACE_WIDTH, TEXT_COLOR,
-    TEXT_PRE_DELETED_BG, TEXT_SELECTED_BG,
+    NORMAL_ROW_COLOR, TABLE_HEADER_BG, TABLE_HEADER_FG, TEXT_COLOR, TEXT_DELETED_FG, TEXT_FLASH_BG,
+    TEXT_NONE, TEXT_PINNED, TEXT_PRE_DELETED_BG, TEXT_SELECTED_BG, TEXT_UNKNOWN,
 };
-use crate::ui::utils::folder_to_rows;
+use crate::ui::treemap::render_treemap;
+use crate::ui::utils::{
+    folder_to_rows, format_count, format_file_size, highlight_spacing, truncate_middle,
+    visible_cursor_index,
+};
+
+/// Columns taken by the second size column, sizes are at most 10 characters wide
+const OTHER_SIZE_WIDTH: u16 = 10;
+/// Columns taken by the Size column
+const SIZE_WIDTH: u16 = 20;
+/// Fewest columns left to the Name column, so names are still told apart
+const MIN_NAME_WIDTH: u16 = 10;
+/// Table border and header with a single row below them
+const MIN_TABLE_HEIGHT: u16 = 4;
 
-const MAX_LOG_LEN: usize = 40;
 #[derive(Debug)]
 pub struct DebugData {
     pub time_taken: Option<u128>,
     pub fps: String,
     pub skipped_frames: String,
     pub folders: usize,
+    /// Resident memory of the process, None where platform doesn't report it
+    pub memory: Option<u64>,
     pub spin_symbol: (char, char),
+    /// What is scanned and how, one parameter per line
+    pub scan: Vec<String>,
+}
+
+/// Where folder view was drawn, for telling what a click lands on
+#[derive(Debug, Default)]
+pub struct DrawnContent {
+    /// Area of folder table, None when it did not fit or treemap is shown
+    pub table: Option<Rect>,
+    /// Treemap cells with index of the entry each one shows
+    pub treemap: Vec<(usize, Rect)>,
 }
 
 pub fn render_content(
     area: Rect,
     buf: &mut Buffer,
     maybe_folder: Option<&Folder>,
+    snapshot: Option<&Folder>,
     config: &UIConfig,
     logger: &Logger,
     debug_data: &DebugData,
-) {
+) -> DrawnContent {
     let horizontal_layout = Layout::horizontal(match config.debug_enabled {
         true => [Constraint::Min(1), Constraint::Min(1)],
         false => [Constraint::Min(1), Constraint::Max(0)],
     });
 
-    let [content_col, debug_col] = horizontal_layout.areas(area);
+    let [mut content_col, debug_col] = horizontal_layout.areas(area);
 
+    if let (Some(folder), Some(snapshot), true) = (maybe_folder, snapshot, config.split_view) {
+        let [before_col, now_col] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(content_col);
+        render_snapshot_table(before_col, buf, snapshot, folder, config);
+        content_col = now_col;
+    }
+    let mut drawn = DrawnContent::default();
     if let Some(folder) = maybe_folder {
-        render_table(content_col, buf, folder, config, debug_data.spin_symbol.0);
+        match config.treemap {
+            true => drawn.treemap = render_treemap(content_col, buf, folder, config),
+            false => {
+                if render_table(content_col, buf, folder, config, debug_data.spin_symbol.0) {
+                    drawn.table = Some(content_col);
+                }
+            }
+        }
     }
 
     if config.debug_enabled {
-        render_debug_panel(debug_col, buf, logger, debug_data);
+        render_debug_panel(debug_col, buf, logger, debug_data, config.log_width);
     }
+    drawn
+}
+
+/// Header titles and widths of table columns, after the marker column which has no column.
+/// The sorted column has an arrow.
+fn table_columns(config: &UIConfig) -> Vec<(Option<Column>, String, Constraint)> {
+    let arrow = match config.sort_reversed {
+        true => "↑",
+        false => "↓",
+    };
+    let visible = config.visible_columns();
+    let has_space = visible.contains(&Column::Space);
+    let mut columns = vec![(None, String::new(), Constraint::Length(1))];
+    for column in visible {
+        let (title, width) = match column {
+            Column::Name => {
+                let title = match config.sort_by {
+                    SortBy::Title => format!("Name {arrow}"),
+                    SortBy::Size => "Name".to_string(),
+                    SortBy::Modified => format!("Name (modified {arrow})"),
+                    SortBy::Count => format!("Name (entries {arrow})"),
+                };
+                // Names take width of hidden Space column
+                let width = match has_space {
+                    true => Constraint::Length(40),
+                    false => Constraint::Fill(1),
+                };
+                (title, width)
+            }
+            Column::Size => {
+                let title = match (config.sort_by, config.own_size) {
+                    (SortBy::Size, false) => format!("Size {arrow}"),
+                    (SortBy::Size, true) => format!("Own size (total {arrow})"),
+                    (_, false) => "Size".to_string(),
+                    (_, true) => "Own size".to_string(),
+                };
+                (title, Constraint::Length(SIZE_WIDTH))
+            }
+            Column::OtherSize => {
+                let title = match config.own_size {
+                    true => "Total",
+                    false => "Own",
+                };
+                (title.to_string(), Constraint::Length(OTHER_SIZE_WIDTH))
+            }
+            Column::Owner => ("Owner".to_string(), Constraint::Length(12)),
+            Column::Mode => ("Mode".to_string(), Constraint::Length(10)),
+            Column::Age => ("Age".to_string(), Constraint::Length(5)),
+            Column::DiskShare => ("% of disk".to_string(), Constraint::Length(9)),
+            Column::Space => (
+                format!("Space (of {})", config.space_scale.title()),
+                Constraint::Length(config.space_width as u16),
+            ),
+        };
+        columns.push((Some(column), title, width));
+    }
+    columns
+}
+
+/// Column sorted by clicking header of table drawn in `area` at provided screen column.
+///
+/// Lays columns out the way the table does, after borders, padding and highlight symbol.
+pub fn sort_column_at(area: Rect, config: &UIConfig, x: u16) -> Option<SortBy> {
+    let inner_area = Rect::new(area.x + 2, area.y + 1, area.width.saturating_sub(4), 1);
+    let selection_width = match highlight_spacing(config) {
+        HighlightSpacing::Never => 0,
+        _ => config.highlight_symbol.width() as u16,
+    };
+    let [_, columns_area] =
+        Layout::horizontal([Constraint::Length(selection_width), Constraint::Fill(0)])
+            .areas(inner_area);
+    let columns = table_columns(config);
+    let widths: Vec<Constraint> = columns.iter().map(|(_, _, width)| *width).collect();
+    let areas = Layout::horizontal(widths).spacing(1).split(columns_area);
+    let index = areas
+        .iter()
+        .position(|column| x >= column.x && x < column.right())?;
+    match columns[index].0 {
+        Some(Column::Name) => Some(SortBy::Title),
+        Some(Column::Size) => Some(SortBy::Size),
+        _ => None,
+    }
+}
+
+/// Smallest area table is drawn in, enough for borders and the Name and Size columns of one row
+pub fn min_table_size(config: &UIConfig) -> (u16, u16) {
+    let selection_width = match highlight_spacing(config) {
+        HighlightSpacing::Never => 0,
+        _ => config.highlight_symbol.width() as u16,
+    };
+    // Borders and padding, marker column and the spaces after it and after names
+    let width = 4 + selection_width + 1 + 1 + MIN_NAME_WIDTH + 1 + SIZE_WIDTH;
+    (width, MIN_TABLE_HEIGHT)
+}
+
+/// Message drawn instead of a view that doesn't fit, it wraps when even the message doesn't
+pub fn render_too_small(area: Rect, buf: &mut Buffer, text: &str) {
+    Paragraph::new(text)
+        .fg(TEXT_COLOR)
+        .bg(NORMAL_ROW_COLOR)
+        .centered()
+        .wrap(Wrap { trim: true })
+        .render(area, buf);
 }
 
+/// Draws folder table, or a message when area is too small for it. Returns whether the table
+/// was drawn.
 pub fn render_table(
     area: Rect,
     buf: &mut Buffer,
     folder: &Folder,
     config: &UIConfig,
     loading_indicator: char,
-) {
+) -> bool {
+    let (min_width, min_height) = min_table_size(config);
+    if area.width < min_width || area.height < min_height {
+        let text = format!("Too small for the table, need at least {min_width}×{min_height}");
+        render_too_small(area, buf, &text);
+        return false;
+    }
+
     let block = Block::default()
         .borders(Borders::ALL)
         .padding(Padding::horizontal(1))
@@ -61,51 +215,142 @@ pub fn render_table(
         .bg(NORMAL_ROW_COLOR);
 
     let header_style = Style::default().fg(TABLE_HEADER_FG).bg(TABLE_HEADER_BG);
-    let selected_style = if config.confirming_deletion {
+    let selected_style = if config.flashing {
+        Style::default().bg(TEXT_FLASH_BG).fg(NORMAL_ROW_COLOR)
+    } else if config.confirming_deletion {
         Style::default().bg(TEXT_PRE_DELETED_BG)
     } else {
         Style::default().bg(TEXT_SELECTED_BG)
     };
 
-    let header_titles = match config.sort_by {
-        SortBy::Title => ["", "Name ↓", "Size", "Space"],
-        SortBy::Size => ["", "Name", "Size ↓", "Space"],
-    };
+    let columns = table_columns(config);
+    let widths: Vec<Constraint> = columns.iter().map(|(_, _, width)| *width).collect();
 
-    let header = header_titles
+    let header = columns
         .into_iter()
-        .map(Cell::from)
+        .map(|(column, title, _)| match (column, config.align_sizes) {
+            (Some(Column::Size | Column::OtherSize), true) => {
+                Cell::from(Text::from(title).alignment(Alignment::Right))
+            }
+            _ => Cell::from(title),
+        })
         .collect::<Row>()
         .style(header_style)
         .height(1);
 
     let rows = folder_to_rows(folder, config, loading_indicator);
+    let rows_count = rows.len();
 
-    let table = Table::new(
-        rows,
-        [
-            Constraint::Length(1),
-            Constraint::Length(40),
-            Constraint::Length(20),
-            Constraint::Length(TABLE_SPACE_WIDTH as u16),
-        ],
-    )
-    .block(block)
-    .header(header)
-    .highlight_symbol("> ")
-    .highlight_style(selected_style)
-    .highlight_spacing(HighlightSpacing::Always);
+    let table = Table::new(rows, widths)
+        .block(block)
+        .header(header)
+        .highlight_symbol(config.highlight_symbol.as_str())
+        .highlight_style(selected_style)
+        .highlight_spacing(highlight_spacing(config));
 
+    let cursor_index = visible_cursor_index(folder, config);
     StatefulWidget::render(
         table,
         area,
         buf,
-        &mut TableState::default().with_selected(Some(folder.cursor_index)),
+        &mut TableState::default().with_selected(Some(cursor_index)),
+    );
+
+    // Rows fitting between borders below the header
+    let visible_rows = area.height.saturating_sub(3);
+    if rows_count > visible_rows as usize {
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(None)
+            .end_symbol(None);
+        let mut state = ScrollbarState::new(rows_count)
+            .viewport_content_length(visible_rows as usize)
+            .position(cursor_index);
+        // Drawn in right padding column, next to rows
+        let scrollbar_area = Rect::new(area.right().saturating_sub(2), area.y + 2, 1, visible_rows);
+        StatefulWidget::render(scrollbar, scrollbar_area, buf, &mut state);
+    }
+    true
+}
+
+/// Folder as it was when entered, entries deleted since are grayed out and struck through
+pub fn render_snapshot_table(
+    area: Rect,
+    buf: &mut Buffer,
+    snapshot: &Folder,
+    folder: &Folder,
+    config: &UIConfig,
+) {
+    let freed = snapshot.total_size().saturating_sub(folder.total_size());
+    let block = Block::default()
+        .title(format!(" Before | freed {} ", format_file_size(freed)))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .padding(Padding::horizontal(1))
+        .border_set(symbols::border::PROPORTIONAL_TALL)
+        .fg(TEXT_COLOR)
+        .bg(NORMAL_ROW_COLOR);
+
+    let header = ["", "Name", "Size"]
+        .into_iter()
+        .map(Cell::from)
+        .collect::<Row>()
+        .style(Style::default().fg(TABLE_HEADER_FG).bg(TABLE_HEADER_BG))
+        .height(1);
+
+    let present: HashSet<&OsStr> = folder
+        .entries()
+        .iter()
+        .map(|e| e.name.as_os_str())
+        .collect();
+    let rows: Vec<Row> = config
+        .visible_entries(snapshot)
+        .filter(|(_, entry)| entry.kind != FolderEntryType::Parent)
+        .map(|(_, entry)| {
+            let size = entry
+                .size
+                .map(format_file_size)
+                .unwrap_or(TEXT_UNKNOWN.to_string());
+            let row = Row::new(vec![
+                Text::from(""),
+                Text::from(entry.title.clone()),
+                Text::from(size),
+            ]);
+            match present.contains(entry.name.as_os_str()) {
+                true => row,
+                false => row.style(
+                    Style::default()
+                        .fg(TEXT_DELETED_FG)
+                        .add_modifier(Modifier::CROSSED_OUT),
+                ),
+            }
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(1),
+        Constraint::Fill(1),
+        Constraint::Length(12),
+    ];
+    Widget::render(
+        Table::new(rows, widths).block(block).header(header),
+        area,
+        buf,
     );
 }
 
-pub fn render_debug_panel(area: Rect, buf: &mut Buffer, logger: &Logger, debug_data: &DebugData) {
-    let [top, bottom] = Layout::vertical([Constraint::Max(5), Constraint::Fill(1)]).areas(area);
+pub fn render_debug_panel(
+    area: Rect,
+    buf: &mut Buffer,
+    logger: &Logger,
+    debug_data: &DebugData,
+    log_width: usize,
+) {
+    let [top, middle, bottom] = Layout::vertical([
+        Constraint::Max(5),
+        Constraint::Max(debug_data.scan.len() as u16 + 2),
+        Constraint::Fill(1),
+    ])
+    .areas(area);
 
     // Stats
     let time_taken = debug_data
@@ -113,9 +358,14 @@ pub fn render_debug_panel(area: Rect, buf: &mut Buffer, logger: &Logger, debug_d
         .map(|t| t.to_string())
         .unwrap_or_else(|| "...".to_string());
 
+    let memory = debug_data
+        .memory
+        .map(format_file_size)
+        .unwrap_or(TEXT_NONE.to_string());
+
     let stats_text = Text::from(format!(
-        "Folders: {}\nDone in: {}\nFPS: {} | Skipped: {}",
-        debug_data.folders, time_taken, debug_data.fps, debug_data.skipped_frames
+        "Folders: {} | Memory: {}\nDone in: {}\nFPS: {} | Skipped: {}",
+        debug_data.folders, memory, time_taken, debug_data.fps, debug_data.skipped_frames
     ));
 
     let stats_block = Block::default()
@@ -129,6 +379,23 @@ pub fn render_debug_panel(area: Rect, buf: &mut Buffer, logger: &Logger, debug_d
 
     Widget::render(stats, top, buf);
 
+    // Scan parameters
+    let scan_block = Block::default()
+        .padding(Padding::horizontal(1))
+        .borders(Borders::ALL)
+        .border_set(symbols::border::PROPORTIONAL_TALL)
+        .title(" Scan ")
+        .title_alignment(Alignment::Center);
+
+    let scan_lines: Vec<Line> = debug_data
+        .scan
+        .iter()
+        .map(|line| Line::from(truncate_middle(line, log_width)))
+        .collect();
+    let scan = Paragraph::new(scan_lines).left_aligned().block(scan_block);
+
+    Widget::render(scan, middle, buf);
+
     // Logs
     let logs_block = Block::default()
         .padding(Padding::horizontal(1))
@@ -137,33 +404,46 @@ pub fn render_debug_panel(area: Rect, buf: &mut Buffer, logger: &Logger, debug_d
         .title(" Logs ")
         .title_alignment(Alignment::Center);
 
-    let logs: Vec<ListItem> = logger
-        .messages
-        .iter()
-        .enumerate()
-        .map(|(_i, (timestamp, level, message))| {
-            let mut message = message.clone();
-            let current_timestamp_ms = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .expect("Time went backwards")
-                .as_millis();
-            let elapsed_ms = current_timestamp_ms - timestamp;
-            if message.len() > MAX_LOG_LEN {
-                message = format!(
-                    "{}..{}",
-                    &message[..MAX_LOG_LEN / 4],
-                    &message[message.len() - MAX_LOG_LEN / 4 * 3..]
-                );
-            }
-            message = format!("[{:.1}] - {}", elapsed_ms as f64 / 1000.0, message);
-
-            let style = Style::default();
-            let style = match level {
-                MessageLevel::Info => style.fg(TEXT_COLOR),
-                MessageLevel::Error => style.fg(TEXT_PRE_DELETED_BG),
-            };
-            ListItem::from(message).style(style)
-        })
+    let current_timestamp_ms = now_ms();
+    let log_item = |entry: &LogEntry, separator: &str| {
+        let elapsed_ms = current_timestamp_ms.saturating_sub(entry.timestamp);
+        // Repeats are summed up by what they share
+        let message = match entry.count {
+            1 => entry.message.clone(),
+            count => format!("{} ×{}", entry.key, format_count(count)),
+        };
+        let message = format!(
+            "[{:.1}] {separator} {}",
+            elapsed_ms as f64 / 1000.0,
+            truncate_middle(&message, log_width)
+        );
+
+        let style = Style::default();
+        let style = match entry.level {
+            MessageLevel::Info => style.fg(TEXT_COLOR),
+            MessageLevel::Error => style.fg(TEXT_PRE_DELETED_BG),
+        };
+        ListItem::from(message).style(style)
+    };
+
+    // Pinned error takes the first row, and is not listed again below it
+    let rows = logs_block.inner(bottom).height as usize;
+    let pinned = logger.pinned.as_ref().filter(|_| rows > 0);
+    let room = rows - pinned.is_some() as usize;
+    let logs: Vec<ListItem> = pinned
+        .map(|entry| log_item(entry, TEXT_PINNED).bold())
+        .into_iter()
+        .chain(
+            logger
+                .visible(rows)
+                .into_iter()
+                .filter(|entry| {
+                    pinned
+                        .is_none_or(|pinned| pinned.level != entry.level || pinned.key != entry.key)
+                })
+                .take(room)
+                .map(|entry| log_item(entry, "-")),
+        )
         .collect();
 
     let items = List::new(logs).block(logs_block);
@@ -1,9 +1,22 @@
-use crate::ui::constants::TEXT_HINT_NAVIGATE;
+use crate::config::{PromptKey, UIConfig};
+use crate::fs::FilesystemUsage;
+use crate::ui::constants::{
+    TEXT_HIGHLIGHTED, TEXT_HINT_NAVIGATE, TEXT_HINT_NAVIGATE_FILE, TEXT_PRE_DELETED_BG,
+};
 use ratatui::{prelude::*, widgets::*};
+use std::path::PathBuf;
+use std::time::Duration;
 
-use super::utils::color_capital_letter;
+use super::utils::{color_capital_letter, format_file_size, join_paths};
 
-pub fn render_footer(area: Rect, buf: &mut Buffer) {
+/// Key hints, Enter is described separately when it does something else than navigate
+pub fn render_footer(
+    area: Rect,
+    buf: &mut Buffer,
+    pending_count: Option<usize>,
+    enter_hint: Option<&str>,
+    read_only: bool,
+) {
     let block = Block::default().padding(Padding::top(1));
     let inner_area = block.inner(area);
     Widget::render(block, area, buf);
@@ -13,14 +26,22 @@ pub fn render_footer(area: Rect, buf: &mut Buffer) {
         Constraint::Max(13),
         Constraint::Max(6),
         Constraint::Max(4),
+        Constraint::Max(5),
     ]);
-    let [col_navigate, col_delete, col_sort, col_quit] = layout.areas(inner_area);
+    let [col_navigate, col_delete, col_sort, col_quit, col_count] = layout.areas(inner_area);
 
-    let text_delete = color_capital_letter("Delete - 2x,".into(), None, None);
+    let text_delete = match read_only {
+        true => Line::from("Read only,".fg(TEXT_PRE_DELETED_BG)),
+        false => color_capital_letter("Delete - 2x,".into(), None, None),
+    };
     let text_sort = color_capital_letter("Sort,".into(), None, None);
     let text_quit = color_capital_letter("Quit".into(), None, None);
 
-    Paragraph::new(TEXT_HINT_NAVIGATE)
+    let text_navigate = match enter_hint {
+        Some(hint) => format!("{TEXT_HINT_NAVIGATE_FILE}, Enter - {hint}"),
+        None => TEXT_HINT_NAVIGATE.to_string(),
+    };
+    Paragraph::new(text_navigate)
         .left_aligned()
         .render(col_navigate, buf);
     Paragraph::new(text_delete)
@@ -32,4 +53,225 @@ pub fn render_footer(area: Rect, buf: &mut Buffer) {
     Paragraph::new(text_quit)
         .left_aligned()
         .render(col_quit, buf);
+    // Count typed so far, waiting for a motion
+    if let Some(count) = pending_count {
+        Paragraph::new(count.to_string())
+            .fg(TEXT_HIGHLIGHTED)
+            .right_aligned()
+            .render(col_count, buf);
+    }
+}
+
+/// Replaces footer hints while redrawing is paused after idle timeout
+pub fn render_idle_notice(area: Rect, buf: &mut Buffer) {
+    let block = Block::default().padding(Padding::top(1));
+    let inner_area = block.inner(area);
+    Widget::render(block, area, buf);
+
+    Paragraph::new("Idle, press any key to resume")
+        .fg(TEXT_HIGHLIGHTED)
+        .bold()
+        .left_aligned()
+        .render(inner_area, buf);
+}
+
+/// Replaces footer hints while scan root can't be read
+pub fn render_root_missing(area: Rect, buf: &mut Buffer) {
+    let block = Block::default().padding(Padding::top(1));
+    let inner_area = block.inner(area);
+    Widget::render(block, area, buf);
+
+    Paragraph::new(Line::from(vec![
+        Span::from("Scan root no longer available")
+            .fg(TEXT_PRE_DELETED_BG)
+            .bold(),
+        Span::from(" | deleting is disabled, O opens another root, R rescans once it is back")
+            .fg(TEXT_HIGHLIGHTED),
+    ]))
+    .left_aligned()
+    .render(inner_area, buf);
+}
+
+/// Replaces footer hints while a folder is verified against disk in background
+pub fn render_verifying(area: Rect, buf: &mut Buffer, (read, total): (usize, usize)) {
+    let block = Block::default().padding(Padding::top(1));
+    let inner_area = block.inner(area);
+    Widget::render(block, area, buf);
+
+    Paragraph::new(Line::from(vec![
+        Span::from(format!(
+            "Verifying against disk, {read}/{total} folders read"
+        ))
+        .bold(),
+        Span::from(" | V stops").fg(TEXT_HIGHLIGHTED),
+    ]))
+    .left_aligned()
+    .render(inner_area, buf);
+}
+
+/// Replaces footer hints while quit waits for confirmation
+pub fn render_quit_prompt(area: Rect, buf: &mut Buffer, freed: u64, deleted: usize) {
+    let block = Block::default().padding(Padding::top(1));
+    let inner_area = block.inner(area);
+    Widget::render(block, area, buf);
+
+    let items = match deleted {
+        1 => "item",
+        _ => "items",
+    };
+    Paragraph::new(format!(
+        "You freed {} across {} {} this session, press q to confirm exit, any other key to stay",
+        format_file_size(freed),
+        deleted,
+        items
+    ))
+    .fg(TEXT_HIGHLIGHTED)
+    .bold()
+    .left_aligned()
+    .render(inner_area, buf);
+}
+
+/// Replaces footer hints while leaving a folder with marks waits for confirmation
+pub fn render_leave_prompt(area: Rect, buf: &mut Buffer, marked: usize) {
+    let block = Block::default().padding(Padding::top(1));
+    let inner_area = block.inner(area);
+    Widget::render(block, area, buf);
+
+    let items = match marked {
+        1 => "item",
+        _ => "items",
+    };
+    Paragraph::new(format!(
+        "You have {} marked {} — leave and discard marks? (y/N)",
+        marked, items
+    ))
+    .fg(TEXT_HIGHLIGHTED)
+    .bold()
+    .left_aligned()
+    .render(inner_area, buf);
+}
+
+/// How the confirm key is asked for, `d` confirms by being pressed again
+pub fn confirm_hint(key: PromptKey) -> String {
+    match key {
+        PromptKey::Char('d') => "Press d again".to_string(),
+        _ => format!("Press {}", key.label()),
+    }
+}
+
+/// Cancel keys named in prompts, Esc cancels whatever else is set
+fn cancel_hint(key: PromptKey) -> String {
+    match key {
+        PromptKey::Esc => "Esc".to_string(),
+        _ => format!("{} or Esc", key.label()),
+    }
+}
+
+/// Deletion waiting for confirmation, as the footer asks about it
+pub struct DeletionPrompt<'a> {
+    pub targets: &'a [PathBuf],
+    /// Space taken by targets on each filesystem
+    pub freed: &'a [FilesystemUsage],
+    pub warnings: &'a [String],
+    /// Until confirmation expires, when it does
+    pub time_left: Option<Duration>,
+    /// Phrase to type and what is typed so far, when targets are dangerous
+    pub typed: Option<(&'a str, &'a str)>,
+    pub confirm_key: PromptKey,
+    pub cancel_key: PromptKey,
+    /// Targets go to the trash, so nothing is freed until it is emptied
+    pub move_to_trash: bool,
+}
+
+/// Replaces footer hints while a deletion waits for confirmation
+pub fn render_deletion_prompt(area: Rect, buf: &mut Buffer, prompt: &DeletionPrompt) {
+    let block = Block::default().padding(Padding::top(1));
+    let inner_area = block.inner(area);
+    Widget::render(block, area, buf);
+
+    let targets = prompt.targets;
+    let target = match targets {
+        [target] => target.to_string_lossy().to_string(),
+        _ => format!("{} entries ({})", targets.len(), join_paths(targets)),
+    };
+    let action = match (prompt.move_to_trash, freed_summary(prompt.freed)) {
+        (true, Some(summary)) => format!("Move {target} ({summary}) to trash"),
+        (true, None) => format!("Move {target} to trash"),
+        (false, Some(summary)) => format!("Delete {target}, freeing {summary}"),
+        (false, None) => format!("Delete {target}"),
+    };
+    let countdown = match prompt.time_left {
+        // Rounded up so it never shows 0s while still armed
+        Some(time_left) => format!(" ({}s)", time_left.as_millis().div_ceil(1000)),
+        None => String::new(),
+    };
+    let question = match prompt.typed {
+        Some((phrase, input)) => format!(
+            "{}? Type \"{}\" and press Enter to confirm, Esc to cancel{}: {}▏",
+            action, phrase, countdown, input
+        ),
+        None => format!(
+            "{}? {} to confirm, {} to cancel{}",
+            action,
+            confirm_hint(prompt.confirm_key),
+            cancel_hint(prompt.cancel_key),
+            countdown
+        ),
+    };
+    let mut spans = vec![Span::from(question).fg(TEXT_PRE_DELETED_BG).bold()];
+    for warning in prompt.warnings {
+        spans.push(Span::from(format!(" | {}", warning)).fg(TEXT_HIGHLIGHTED));
+    }
+
+    Paragraph::new(Line::from(spans))
+        .left_aligned()
+        .render(inner_area, buf);
+}
+
+/// Space freed on each filesystem, e.g. "2.00 GB on /, 40.00 GB on /data", just the size when
+/// everything is on one. None when nothing is freed.
+pub fn freed_summary(freed: &[FilesystemUsage]) -> Option<String> {
+    match freed {
+        [] => None,
+        [filesystem] if filesystem.size == 0 => None,
+        [filesystem] => Some(format_file_size(filesystem.size)),
+        _ => Some(
+            freed
+                .iter()
+                .map(|filesystem| {
+                    format!(
+                        "{} on {}",
+                        format_file_size(filesystem.size),
+                        filesystem.mount_point.to_string_lossy()
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join(", "),
+        ),
+    }
+}
+
+/// Replaces footer hints while name filter is typed or applied
+pub fn render_filter_prompt(area: Rect, buf: &mut Buffer, config: &UIConfig) {
+    let block = Block::default().padding(Padding::top(1));
+    let inner_area = block.inner(area);
+    Widget::render(block, area, buf);
+
+    let filter = &config.name_filter;
+    let text = match config.filter_editing {
+        true => format!(
+            "/{}▏ [{}] Enter - apply, Esc - clear, Ctrl-g - glob/substring",
+            filter.query,
+            filter.describe()
+        ),
+        false => format!(
+            "Filter: {} [{}] / - edit, Esc - clear",
+            filter.query,
+            filter.describe()
+        ),
+    };
+    Paragraph::new(text)
+        .fg(TEXT_HIGHLIGHTED)
+        .left_aligned()
+        .render(inner_area, buf);
 }
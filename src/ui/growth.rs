@@ -0,0 +1,75 @@
+use crate::ui::constants::{NORMAL_ROW_COLOR, TEXT_COLOR, TEXT_HIGHLIGHTED};
+use crate::ui::utils::{format_duration, format_file_size};
+use ratatui::{prelude::*, widgets::*};
+use std::time::Duration;
+
+/// Rows the growth panel takes when shown below the preview
+pub const GROWTH_HEIGHT: u16 = 8;
+
+/// Renders sizes selected folder had at cached scans as a sparkline, oldest on the left
+pub fn render_growth(
+    area: Rect,
+    buf: &mut Buffer,
+    title: &str,
+    history: &[(u64, u64)],
+    cached: bool,
+) {
+    let block = Block::default()
+        .title(" Growth ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .padding(Padding::horizontal(1))
+        .border_set(symbols::border::PROPORTIONAL_TALL)
+        .fg(TEXT_COLOR)
+        .bg(NORMAL_ROW_COLOR);
+    let inner_area = block.inner(area);
+    Widget::render(block, area, buf);
+
+    let [title_area, chart_area, summary_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Fill(1),
+        Constraint::Length(1),
+    ])
+    .areas(inner_area);
+    Paragraph::new(Span::from(title).bold()).render(title_area, buf);
+
+    let (Some((first_at, first)), Some((last_at, last))) = (history.first(), history.last()) else {
+        let text = match cached {
+            true => "Not seen by earlier scans",
+            false => "Scans are kept with --cache-scan",
+        };
+        Paragraph::new(text).render(chart_area, buf);
+        return;
+    };
+    if history.len() == 1 {
+        Paragraph::new(format!("{}, one scan so far", format_file_size(*last)))
+            .render(chart_area, buf);
+        return;
+    }
+
+    // Bars start a bit below the smallest size, so small changes of big folders still show
+    let min = history.iter().map(|(_, size)| *size).min().unwrap_or(0);
+    let max = history.iter().map(|(_, size)| *size).max().unwrap_or(0);
+    let baseline = match max > min {
+        true => min.saturating_sub((max - min) / 4 + 1),
+        false => 0,
+    };
+    let data: Vec<u64> = history.iter().map(|(_, size)| size - baseline).collect();
+    Sparkline::default()
+        .data(&data)
+        .style(Style::default().fg(TEXT_HIGHLIGHTED))
+        .render(chart_area, buf);
+
+    let delta = *last as i128 - *first as i128;
+    let sign = if delta < 0 { "-" } else { "+" };
+    let span = Duration::from_secs(last_at.saturating_sub(*first_at));
+    Paragraph::new(format!(
+        "{} ({}{}) over {} scans, {}",
+        format_file_size(*last),
+        sign,
+        format_file_size(delta.unsigned_abs() as u64),
+        history.len(),
+        format_duration(span)
+    ))
+    .render(summary_area, buf);
+}
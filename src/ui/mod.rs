@@ -1,35 +1,105 @@
-use crate::fs::DataStore;
+use crate::config::{UIConfig, OVERVIEW_MIN_SHARE};
+use crate::fs::{with_own_sizes, with_sizes, DataStore};
+use crate::logger::resident_memory;
+use crate::views::View;
 use crate::{app::App, fs::DataStoreKey};
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 
+mod colors;
 pub mod constants;
 mod content;
 mod footer;
+mod growth;
+mod overview;
+mod preview;
+mod prompt;
 mod title;
+pub mod treemap;
 mod utils;
+mod views;
+pub use colors::{reduce_color, reduce_colors};
 use constants::TEXT_TITLE;
-pub use content::{render_content, DebugData};
-pub use footer::render_footer;
-pub use title::render_title;
+use content::render_too_small;
+pub use content::{min_table_size, render_content, sort_column_at, DebugData};
+pub use footer::{
+    freed_summary, render_deletion_prompt, render_filter_prompt, render_footer, render_idle_notice,
+    render_leave_prompt, render_quit_prompt, render_root_missing, render_verifying, DeletionPrompt,
+};
+pub use growth::render_growth;
+pub use overview::{overview_segments, render_overview, OverviewSegment};
+pub use preview::render_preview;
+pub use prompt::{render_broad_prompt, render_path_prompt, render_profile_menu, render_sort_menu};
+pub use title::{format_breadcrumb, render_title};
+pub use utils::{
+    format_age, format_count, format_duration, format_file_size, format_percent,
+    format_short_count, format_size, join_paths, truncate_middle,
+};
+pub use views::{
+    render_deep, render_diff, render_errors, render_filesystems, render_names, render_recent,
+    render_trash, render_verify,
+};
 
 use self::constants::{TEXT_COLOR, TEXT_PRE_DELETED_BG};
 
+/// Rows taken by outer border, title and footer
+const OUTER_CHROME_ROWS: u16 = 6;
+/// Columns taken by outer border and its padding
+const OUTER_CHROME_COLUMNS: u16 = 4;
+/// Smallest terminal folder table fits in, with no side panels
+pub fn min_terminal_size(config: &UIConfig) -> (u16, u16) {
+    let (width, height) = min_table_size(config);
+    (width + OUTER_CHROME_COLUMNS, height + OUTER_CHROME_ROWS)
+}
+
 impl<S: DataStore<DataStoreKey>> Widget for &mut App<S> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         self.pre_render();
+        self.table_area = None;
+        self.treemap_cells.clear();
+        let (min_width, min_height) = min_terminal_size(&self.ui_config);
+        if area.width < min_width || area.height < min_height {
+            let text = format!("Terminal too small, need at least {min_width}×{min_height}");
+            render_too_small(area, buf, &text);
+            reduce_colors(buf, self.ui_config.color_depth);
+            return;
+        }
+        let current_path = self.store.get_current_path().clone();
+        let growth = match self.ui_config.growth {
+            true => self.selected_history(),
+            false => None,
+        };
         let maybe_folder = self.store.get_current_folder();
+        let own_size_folder = if self.ui_config.owner_filter.is_some() {
+            maybe_folder.map(|folder| with_sizes(folder, &self.ui_config.owned_sizes))
+        } else if self.ui_config.own_size {
+            maybe_folder.map(|folder| with_own_sizes(&self.store, &current_path, folder))
+        } else {
+            None
+        };
 
         // Helper data
         let fps = self.fps_counter.update();
         let time_taken = self.task_manager.time_taken();
-        let (spin_left, spin_right) = self.spinner.get_icons(time_taken.is_some());
+        let (spin_left, spin_right) = match self.ui_config.no_animations {
+            true => self.spinner.get_still_icons(time_taken.is_some()),
+            false => self.spinner.get_icons(time_taken.is_some()),
+        };
         let debug = DebugData {
             folders: self.store.get_nodes_len(),
+            // Only read while it is shown
+            memory: match self.ui_config.debug_enabled {
+                true => resident_memory(),
+                false => None,
+            },
             time_taken,
             fps: format!("{:.1}", fps),
             skipped_frames: format!("{:.1}", self.fps_counter.skipped_frames),
             spin_symbol: (spin_left, spin_right),
+            scan: match self.ui_config.debug_enabled {
+                true => self.scan_parameters(),
+                false => vec![],
+            },
         };
 
         // Main wrapper
@@ -37,14 +107,11 @@ impl<S: DataStore<DataStoreKey>> Widget for &mut App<S> {
         let mut title = TEXT_TITLE;
         let mut border_color = TEXT_COLOR;
 
-        match maybe_folder {
-            Some(folder) => {
-                if folder.has_error {
-                    title = "Error";
-                    border_color = TEXT_PRE_DELETED_BG;
-                }
+        if let Some(folder) = maybe_folder {
+            if folder.has_error {
+                title = "Error";
+                border_color = TEXT_PRE_DELETED_BG;
             }
-            None => {}
         }
         let block = Block::default()
             .title(format!(" {} {} {} ", spin_left, title, spin_right))
@@ -64,15 +131,142 @@ impl<S: DataStore<DataStoreKey>> Widget for &mut App<S> {
         ]);
         let [header_area, rest_area, footer_area] = vertical.areas(inner_area);
 
-        render_title(header_area, buf, maybe_folder, &self.ui_config);
-        render_content(
-            rest_area,
-            buf,
-            maybe_folder,
-            &self.ui_config,
-            &self.logger,
-            &debug,
-        );
-        render_footer(footer_area, buf);
+        let breadcrumb = match self.ui_config.breadcrumb {
+            true => self.breadcrumb(&current_path),
+            false => vec![],
+        };
+        render_title(header_area, buf, maybe_folder, &breadcrumb, &self.ui_config);
+        match (&self.path_prompt, &self.view) {
+            (Some(prompt), _) => render_path_prompt(rest_area, buf, prompt, "Open folder"),
+            (None, View::Folder) => {
+                let mut content_area = rest_area;
+                let at_root = current_path == self.root_path;
+                if let (true, true, Some(folder)) = (self.ui_config.overview, at_root, maybe_folder)
+                {
+                    let segments = overview_segments(folder, OVERVIEW_MIN_SHARE);
+                    if !segments.is_empty() {
+                        let [overview_area, table_area] = Layout::vertical([
+                            Constraint::Length(overview::OVERVIEW_HEIGHT),
+                            Constraint::Fill(1),
+                        ])
+                        .areas(content_area);
+                        render_overview(overview_area, buf, &segments);
+                        content_area = table_area;
+                    }
+                }
+                if self.ui_config.preview || self.ui_config.growth {
+                    let [table_area, side_area] =
+                        Layout::horizontal([Constraint::Fill(3), Constraint::Fill(2)])
+                            .areas(content_area);
+                    // Growth panel goes below the preview when both are shown
+                    let [preview_area, growth_area] =
+                        match (self.ui_config.preview, self.ui_config.growth) {
+                            (true, true) => Layout::vertical([
+                                Constraint::Fill(1),
+                                Constraint::Length(growth::GROWTH_HEIGHT),
+                            ])
+                            .areas(side_area),
+                            _ => [side_area, side_area],
+                        };
+                    if self.ui_config.preview {
+                        let preview = self.preview.as_ref().map(|(_, preview)| preview);
+                        render_preview(preview_area, buf, preview);
+                    }
+                    if let Some((title, history)) = &growth {
+                        let cached = self.snapshot_path.is_some();
+                        render_growth(growth_area, buf, title, history, cached);
+                    }
+                    content_area = table_area;
+                }
+                let drawn = render_content(
+                    content_area,
+                    buf,
+                    own_size_folder.as_ref().or(maybe_folder),
+                    self.entry_snapshot
+                        .as_ref()
+                        .filter(|(path, _)| *path == current_path)
+                        .map(|(_, folder)| folder),
+                    &self.ui_config,
+                    &self.logger,
+                    &debug,
+                );
+                self.table_area = drawn.table;
+                self.treemap_cells = drawn.treemap;
+            }
+            (None, View::Filesystems(view)) => {
+                render_filesystems(rest_area, buf, view, &self.ui_config)
+            }
+            (None, View::Errors(view)) => {
+                render_errors(rest_area, buf, view, &self.scan_errors, &self.ui_config)
+            }
+            (None, View::Recent(view)) => {
+                render_recent(rest_area, buf, view, &self.root_path, &self.ui_config)
+            }
+            (None, View::Deep(view)) => {
+                render_deep(rest_area, buf, view, &self.root_path, &self.ui_config)
+            }
+            (None, View::Diff(view)) => render_diff(rest_area, buf, view, &self.ui_config),
+            (None, View::Names(view)) => {
+                render_names(rest_area, buf, view, &self.root_path, &self.ui_config)
+            }
+            (None, View::Trash(view)) => render_trash(
+                rest_area,
+                buf,
+                view,
+                &self.session_trash,
+                &self.root_path,
+                &self.ui_config,
+            ),
+            (None, View::Verify(view)) => {
+                render_verify(rest_area, buf, view, &self.root_path, &self.ui_config)
+            }
+        }
+        if let Some(menu) = &self.sort_menu {
+            render_sort_menu(rest_area, buf, menu);
+        }
+        if let Some(menu) = &self.profile_menu {
+            render_profile_menu(rest_area, buf, menu, self.active_profile.as_deref());
+        }
+        if self.idle {
+            render_idle_notice(footer_area, buf);
+        } else if self.ui_config.confirming_quit {
+            render_quit_prompt(footer_area, buf, self.session_freed, self.session_deleted);
+        } else if self.root_missing {
+            render_root_missing(footer_area, buf);
+        } else if let Some((_, marked)) = &self.pending_leave {
+            render_leave_prompt(footer_area, buf, *marked);
+        } else if self.ui_config.confirming_deletion {
+            let prompt = DeletionPrompt {
+                targets: &self.pending_deletion,
+                freed: &self.deletion_freed,
+                warnings: &self.deletion_warnings,
+                time_left: self.confirmation_time_left(),
+                typed: self
+                    .confirm_phrase
+                    .as_deref()
+                    .map(|phrase| (phrase, self.confirm_input.as_str())),
+                confirm_key: self.ui_config.confirm_key,
+                cancel_key: self.ui_config.cancel_key,
+                move_to_trash: self.ui_config.move_to_trash,
+            };
+            render_deletion_prompt(footer_area, buf, &prompt);
+        } else if let Some(verification) = &self.verification {
+            render_verifying(
+                footer_area,
+                buf,
+                (*verification.read.lock().unwrap(), verification.total),
+            );
+        } else if self.ui_config.filter_editing || self.ui_config.name_filter.is_active() {
+            render_filter_prompt(footer_area, buf, &self.ui_config);
+        } else {
+            render_footer(
+                footer_area,
+                buf,
+                self.pending_count,
+                self.enter_hint(),
+                self.ui_config.read_only,
+            );
+        }
+        reduce_colors(buf, self.ui_config.color_depth);
     }
 }
@@ -0,0 +1,89 @@
+use crate::fs::{compare_names, Folder, FolderEntryType};
+use crate::ui::constants::{NORMAL_ROW_COLOR, OVERVIEW_COLORS, TEXT_COLOR, TEXT_OVERVIEW_OTHER};
+use crate::ui::utils::format_percent;
+use ratatui::{prelude::*, widgets::*};
+
+/// Rows taken by the overview above the table
+pub const OVERVIEW_HEIGHT: u16 = 2;
+
+/// Part of the overview bar, entries too small to tell apart are summed up as "other"
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverviewSegment {
+    pub title: String,
+    pub size: u64,
+}
+
+/// Entries of folder by size, biggest first. Entries under `min_share` of the total, and those
+/// past the number of segment colors, collapse into a single "other" segment.
+pub fn overview_segments(folder: &Folder, min_share: f64) -> Vec<OverviewSegment> {
+    let mut entries: Vec<(&str, u64)> = folder
+        .entries()
+        .iter()
+        .filter(|entry| entry.kind != FolderEntryType::Parent)
+        .filter_map(|entry| entry.size.map(|size| (entry.title.as_str(), size)))
+        .filter(|(_, size)| *size > 0)
+        .collect();
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| compare_names(a.0, b.0)));
+    let total: u64 = entries.iter().map(|(_, size)| size).sum();
+
+    let named = entries
+        .iter()
+        .take_while(|(_, size)| *size as f64 >= total as f64 * min_share)
+        .take(OVERVIEW_COLORS.len() - 1)
+        .count();
+    // A single small entry is still shown under its own name
+    let named = match entries.len() - named {
+        1 => entries.len(),
+        _ => named,
+    };
+    let mut segments: Vec<OverviewSegment> = entries[..named]
+        .iter()
+        .map(|(title, size)| OverviewSegment {
+            title: title.to_string(),
+            size: *size,
+        })
+        .collect();
+    if named < entries.len() {
+        segments.push(OverviewSegment {
+            title: TEXT_OVERVIEW_OTHER.to_string(),
+            size: entries[named..].iter().map(|(_, size)| size).sum(),
+        });
+    }
+    segments
+}
+
+/// Renders stacked bar of segments with a legend below it
+pub fn render_overview(area: Rect, buf: &mut Buffer, segments: &[OverviewSegment]) {
+    let total: u64 = segments.iter().map(|segment| segment.size).sum();
+    if total == 0 {
+        return;
+    }
+    let [bar_area, legend_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(area);
+
+    // Rounded ends of segments, so widths always add up to the whole bar
+    let mut bar = vec![];
+    let mut legend = vec![];
+    let mut covered = 0;
+    let mut start = 0;
+    for (segment, color) in segments.iter().zip(OVERVIEW_COLORS.iter().cycle()) {
+        covered += segment.size;
+        let end = (covered as f64 / total as f64 * bar_area.width as f64).round() as usize;
+        bar.push(Span::from("█".repeat(end - start)).fg(*color));
+        start = end;
+
+        legend.push(Span::from("■ ").fg(*color));
+        legend.push(Span::from(format!(
+            "{} {}  ",
+            segment.title,
+            format_percent(segment.size, total)
+        )));
+    }
+
+    Paragraph::new(Line::from(bar))
+        .bg(NORMAL_ROW_COLOR)
+        .render(bar_area, buf);
+    Paragraph::new(Line::from(legend))
+        .fg(TEXT_COLOR)
+        .render(legend_area, buf);
+}
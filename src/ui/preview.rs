@@ -0,0 +1,83 @@
+use crate::fs::{hexdump, ContentKind, Preview};
+use crate::ui::constants::{NORMAL_ROW_COLOR, TEXT_COLOR, TEXT_PRE_DELETED_BG, TEXT_UNKNOWN};
+use crate::ui::utils::format_file_size;
+use ratatui::{prelude::*, widgets::*};
+
+/// Columns a hexdump line of 16 bytes takes
+const WIDE_HEXDUMP: u16 = 76;
+
+/// Renders start of selected file, or a summary of selected folder
+pub fn render_preview(area: Rect, buf: &mut Buffer, preview: Option<&Preview>) {
+    let block = Block::default()
+        .title(" Preview ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .padding(Padding::horizontal(1))
+        .border_set(symbols::border::PROPORTIONAL_TALL)
+        .fg(TEXT_COLOR)
+        .bg(NORMAL_ROW_COLOR);
+    let inner_area = block.inner(area);
+    Widget::render(block, area, buf);
+
+    let height = inner_area.height as usize;
+    let lines: Vec<Line> = match preview {
+        None => vec![Line::from("Nothing to preview")],
+        Some(Preview::Error(err)) => {
+            vec![Line::from(Span::from(err.clone()).fg(TEXT_PRE_DELETED_BG))]
+        }
+        Some(Preview::Folder { children, size }) => {
+            let size = size
+                .map(format_file_size)
+                .unwrap_or(TEXT_UNKNOWN.to_string());
+            match children {
+                Some(children) => vec![
+                    Line::from(Span::from("Folder").bold()),
+                    Line::from(format!("{children} entries")),
+                    Line::from(format!("Size: {size}")),
+                ],
+                None => vec![
+                    Line::from(Span::from("Folder").bold()),
+                    Line::from("Not scanned yet"),
+                ],
+            }
+        }
+        Some(preview @ Preview::File { chunk, len, kind }) => {
+            let size = match preview.is_truncated() {
+                true => format!(
+                    "first {} of {}",
+                    format_file_size(chunk.len() as u64),
+                    format_file_size(*len)
+                ),
+                false => format_file_size(*len),
+            };
+            let header = match kind {
+                ContentKind::Text => format!("Text, {size}"),
+                ContentKind::Binary => format!("Binary, {size}"),
+                ContentKind::Image(image) => format!(
+                    "{} image, {}×{}, {size}",
+                    image.format, image.width, image.height
+                ),
+            };
+            let mut lines = vec![Line::from(Span::from(header).bold())];
+            let rows = height.saturating_sub(1);
+            match kind {
+                ContentKind::Text => lines.extend(
+                    String::from_utf8_lossy(chunk)
+                        .lines()
+                        .take(rows)
+                        .map(|line| Line::from(line.replace('\t', "    ").replace('\r', ""))),
+                ),
+                ContentKind::Binary | ContentKind::Image(_) => {
+                    let per_line = match inner_area.width >= WIDE_HEXDUMP {
+                        true => 16,
+                        false => 8,
+                    };
+                    let shown = &chunk[..chunk.len().min(rows * per_line)];
+                    lines.extend(hexdump(shown, per_line).into_iter().map(Line::from));
+                }
+            }
+            lines
+        }
+    };
+    Paragraph::new(lines).render(inner_area, buf);
+}
@@ -0,0 +1,233 @@
+use crate::fs::SortBy;
+use crate::prompt::PathPrompt;
+use crate::ui::constants::{
+    NORMAL_ROW_COLOR, TEXT_COLOR, TEXT_DELETED_FG, TEXT_HIGHLIGHTED, TEXT_PRE_DELETED_BG,
+};
+use crate::views::{ProfileMenu, SortMenu, SORT_MENU_KEYS};
+use ratatui::{prelude::*, widgets::*};
+use std::path::Path;
+use unicode_width::UnicodeWidthStr;
+
+/// Columns and rows sort menu takes, borders included
+const SORT_MENU_SIZE: (u16, u16) = (34, 12);
+/// Columns profile menu takes at least, wider when a profile name needs it
+const PROFILE_MENU_WIDTH: u16 = 24;
+
+/// Sort keys with the picked one marked and sorting options, drawn over the middle of area
+pub fn render_sort_menu(area: Rect, buf: &mut Buffer, menu: &SortMenu) {
+    let (width, height) = SORT_MENU_SIZE;
+    let [_, column, _] = Layout::horizontal([
+        Constraint::Fill(1),
+        Constraint::Length(width),
+        Constraint::Fill(1),
+    ])
+    .areas(area);
+    let [_, menu_area, _] = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(height),
+        Constraint::Fill(1),
+    ])
+    .areas(column);
+
+    Widget::render(Clear, menu_area, buf);
+    let block = Block::default()
+        .title(" Sort by ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(TEXT_COLOR)
+        .padding(Padding::horizontal(1))
+        .border_set(symbols::border::DOUBLE)
+        .bg(NORMAL_ROW_COLOR);
+    let inner_area = block.inner(menu_area);
+    Widget::render(block, menu_area, buf);
+
+    let check = |checked: bool| if checked { "[x]" } else { "[ ]" };
+    let mut rows: Vec<String> = SORT_MENU_KEYS
+        .iter()
+        .map(|key| {
+            let picked = if *key == menu.sort_by { "(•)" } else { "( )" };
+            format!("{} {}", picked, sort_key_title(*key))
+        })
+        .collect();
+    rows.push(format!("{} Reverse order", check(menu.reversed)));
+    rows.push(format!("{} Folders first", check(menu.folders_first)));
+
+    let mut lines: Vec<Line> = rows
+        .into_iter()
+        .enumerate()
+        .map(|(index, row)| match index == menu.cursor_index {
+            true => Line::from(Span::from(row).fg(TEXT_HIGHLIGHTED).bold()),
+            false => Line::from(row),
+        })
+        .collect();
+    // Options are set apart from keys
+    lines.insert(SORT_MENU_KEYS.len(), Line::from(""));
+    lines.push(Line::from(""));
+    lines.push(Line::from("Space - pick, Enter - apply"));
+    Paragraph::new(lines).fg(TEXT_COLOR).render(inner_area, buf);
+}
+
+/// Profiles with the active one marked, drawn over the middle of area
+pub fn render_profile_menu(area: Rect, buf: &mut Buffer, menu: &ProfileMenu, active: Option<&str>) {
+    let mut rows = vec![(None, "As started".to_string())];
+    rows.extend(
+        menu.names
+            .iter()
+            .map(|name| (Some(name.as_str()), name.clone())),
+    );
+    let width = rows
+        .iter()
+        .map(|(_, title)| title.width() as u16 + 8)
+        .max()
+        .unwrap_or(0)
+        .max(PROFILE_MENU_WIDTH);
+    // Borders, a blank line and the hint below rows
+    let height = rows.len() as u16 + 4;
+    let [_, column, _] = Layout::horizontal([
+        Constraint::Fill(1),
+        Constraint::Length(width),
+        Constraint::Fill(1),
+    ])
+    .areas(area);
+    let [_, menu_area, _] = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(height),
+        Constraint::Fill(1),
+    ])
+    .areas(column);
+
+    Widget::render(Clear, menu_area, buf);
+    let block = Block::default()
+        .title(" Profile ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(TEXT_COLOR)
+        .padding(Padding::horizontal(1))
+        .border_set(symbols::border::DOUBLE)
+        .bg(NORMAL_ROW_COLOR);
+    let inner_area = block.inner(menu_area);
+    Widget::render(block, menu_area, buf);
+
+    let mut lines: Vec<Line> = rows
+        .into_iter()
+        .enumerate()
+        .map(|(index, (name, title))| {
+            let picked = if name == active { "(•)" } else { "( )" };
+            let row = format!("{} {}", picked, title);
+            match index == menu.cursor_index {
+                true => Line::from(Span::from(row).fg(TEXT_HIGHLIGHTED).bold()),
+                false => Line::from(row),
+            }
+        })
+        .collect();
+    lines.push(Line::from(""));
+    lines.push(Line::from("Enter - apply"));
+    Paragraph::new(lines).fg(TEXT_COLOR).render(inner_area, buf);
+}
+
+fn sort_key_title(sort_by: SortBy) -> &'static str {
+    match sort_by {
+        SortBy::Title => "Name",
+        SortBy::Size => "Size",
+        SortBy::Modified => "Modified",
+        SortBy::Count => "Entries",
+    }
+}
+
+/// Asks which folder to scan, on start or in place of current folder
+pub fn render_path_prompt(area: Rect, buf: &mut Buffer, prompt: &PathPrompt, title: &str) {
+    let block = Block::default()
+        .title(format!(" {} ", title))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(TEXT_COLOR)
+        .padding(Padding::uniform(1))
+        .border_set(symbols::border::DOUBLE);
+    let inner_area = block.inner(area);
+    Widget::render(block, area, buf);
+
+    let vertical = Layout::vertical([
+        Constraint::Length(2), // Question
+        Constraint::Length(2), // Input
+        Constraint::Fill(1),   // Completions
+        Constraint::Length(1), // Hint
+    ]);
+    let [question_area, input_area, completions_area, hint_area] = vertical.areas(inner_area);
+
+    Paragraph::new("Folder to scan:")
+        .bold()
+        .render(question_area, buf);
+    Paragraph::new(format!("{}▏", prompt.input))
+        .fg(TEXT_HIGHLIGHTED)
+        .render(input_area, buf);
+
+    let lines: Vec<Line> = match &prompt.error {
+        Some(err) => vec![Line::styled(
+            err.as_str(),
+            Style::default().fg(TEXT_PRE_DELETED_BG),
+        )],
+        None if prompt.completions.is_empty() && !prompt.history.is_empty() => recent_lines(prompt),
+        None => prompt
+            .completions
+            .iter()
+            .map(|name| Line::from(name.as_str()))
+            .collect(),
+    };
+    Paragraph::new(lines)
+        .fg(TEXT_COLOR)
+        .bg(NORMAL_ROW_COLOR)
+        .render(completions_area, buf);
+
+    let hint = match prompt.history.is_empty() {
+        true => "Enter - scan, Tab - complete folder name, Esc - cancel",
+        false => "Enter - scan, Tab - complete folder name, ↑↓ - recent, Esc - cancel",
+    };
+    Paragraph::new(hint).render(hint_area, buf);
+}
+
+/// Recently scanned roots, the one filled in is highlighted and missing ones are dimmed
+fn recent_lines(prompt: &PathPrompt) -> Vec<Line<'_>> {
+    let mut lines = vec![Line::styled("Recent:", Style::default().bold())];
+    for (index, path) in prompt.history.iter().enumerate() {
+        let line = match Path::new(path).exists() {
+            true => Line::from(path.as_str()),
+            false => Line::styled(
+                format!("{} (missing)", path),
+                Style::default().fg(TEXT_DELETED_FG),
+            ),
+        };
+        lines.push(match prompt.history_index == Some(index) {
+            true => line.patch_style(Style::default().fg(TEXT_HIGHLIGHTED)),
+            false => line,
+        });
+    }
+    lines
+}
+
+/// Asks once before scanning a root that may take very long, like `/` or home folder
+pub fn render_broad_prompt(area: Rect, buf: &mut Buffer, root: &Path, title: &str) {
+    let block = Block::default()
+        .title(format!(" {} ", title))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(TEXT_COLOR)
+        .padding(Padding::uniform(1))
+        .border_set(symbols::border::DOUBLE);
+    let inner_area = block.inner(area);
+    Widget::render(block, area, buf);
+
+    let vertical = Layout::vertical([
+        Constraint::Length(2), // Question
+        Constraint::Fill(1),   // Root
+        Constraint::Length(1), // Hint
+    ]);
+    let [question_area, root_area, hint_area] = vertical.areas(inner_area);
+
+    Paragraph::new("This will scan a very large location — continue? (y/N)")
+        .bold()
+        .render(question_area, buf);
+    Paragraph::new(root.to_string_lossy().to_string())
+        .fg(TEXT_HIGHLIGHTED)
+        .render(root_area, buf);
+    Paragraph::new("y - scan, any other key - quit").render(hint_area, buf);
+}
@@ -1,15 +1,53 @@
 use crate::config::UIConfig;
-use crate::fs::Folder;
+use crate::fs::{user_name, Folder};
 use ratatui::{prelude::*, widgets::*};
+use unicode_width::UnicodeWidthStr;
 
-use crate::ui::utils::{format_file_size, value_to_box};
+use crate::ui::constants::{
+    TEXT_ELLIPSIS, TEXT_PRE_DELETED_BG, TEXT_READ_ONLY, TEXT_READ_ONLY_BADGE, TEXT_UNKNOWN,
+};
+use crate::ui::utils::{format_duration, format_file_size, format_short_count, value_to_box};
 
 use super::utils::color_capital_letter;
 
+/// Folders from root down with their sizes and entry counts, e.g.
+/// `home (1.2 TiB, 40) / app (12 GiB, 1.2k)`.
+///
+/// When it is wider than provided width, titles between root and the current folder are
+/// replaced with `…` one by one from the root side, their sizes and counts are kept.
+pub fn format_breadcrumb(crumbs: &[(String, Option<u64>, Option<usize>)], width: usize) -> String {
+    let format = |elided: usize| {
+        crumbs
+            .iter()
+            .enumerate()
+            .map(|(index, (title, size, count))| {
+                let title = match index > 0 && index <= elided && index + 1 < crumbs.len() {
+                    true => TEXT_ELLIPSIS,
+                    false => title.as_str(),
+                };
+                let size = size
+                    .map(format_file_size)
+                    .unwrap_or(TEXT_UNKNOWN.to_string());
+                match count {
+                    Some(count) => format!("{title} ({size}, {})", format_short_count(*count)),
+                    None => format!("{title} ({size})"),
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(" / ")
+    };
+    let middle = crumbs.len().saturating_sub(2);
+    (0..=middle)
+        .map(format)
+        .find(|text| text.width() <= width)
+        .unwrap_or_else(|| format(middle))
+}
+
 pub fn render_title(
     area: Rect,
     buf: &mut Buffer,
     maybe_folder: Option<&Folder>,
+    breadcrumb: &[(String, Option<u64>, Option<usize>)],
     ui_config: &UIConfig,
 ) {
     let horizontal_layout = Layout::horizontal([Constraint::Fill(1), Constraint::Max(23)]);
@@ -17,14 +55,62 @@ pub fn render_title(
 
     // Folder data
     if let Some(folder) = maybe_folder {
-        Paragraph::new(format!(
-            "{} | {}",
-            folder.title,
-            format_file_size(folder.get_size()),
-        ))
-        .bold()
-        .left_aligned()
-        .render(left_col, buf);
+        let mut text = String::new();
+        if folder.read_only {
+            text = format!("{} | {}", text, TEXT_READ_ONLY);
+        }
+        if let Some(uid) = ui_config.owner_filter {
+            let owned: u64 = ui_config.owned_sizes.values().sum();
+            text = format!(
+                "{} | owner: {} ({})",
+                text,
+                user_name(uid),
+                format_file_size(owned)
+            );
+        }
+        if ui_config.errors_only {
+            let count = ui_config.error_names.len();
+            text = format!(
+                "{} | read errors: {} {}",
+                text,
+                count,
+                if count == 1 { "branch" } else { "branches" }
+            );
+        }
+        if !ui_config.only_extensions.is_empty() {
+            text = format!("{} | only: {}", text, ui_config.only_extensions.join(", "));
+        }
+        if let Some(min_size) = ui_config.min_size {
+            text = format!("{} | ≥ {}", text, format_file_size(min_size));
+        }
+        if !folder.marked.is_empty() {
+            let count = folder.marked.len();
+            text = format!(
+                "{} | Marked: {} {}, {}",
+                text,
+                count,
+                if count == 1 { "item" } else { "items" },
+                format_file_size(folder.marked_size())
+            );
+        }
+        if let Some(age) = ui_config.scan_age {
+            text = format!("{} | scanned {} ago", text, format_duration(age));
+        }
+        let location = match breadcrumb.is_empty() {
+            true => format!(
+                "{} | {}",
+                folder.title,
+                format_file_size(folder.total_size())
+            ),
+            false => {
+                let width = (left_col.width as usize).saturating_sub(text.width());
+                format_breadcrumb(breadcrumb, width)
+            }
+        };
+        Paragraph::new(location + &text)
+            .bold()
+            .left_aligned()
+            .render(left_col, buf);
     }
 
     // Settings
@@ -36,15 +122,22 @@ pub fn render_title(
         None,
         Some(value_to_box(&ui_config.colored)),
     );
+    Paragraph::new(text_color)
+        .right_aligned()
+        .render(col_color, buf);
+    // Trash doesn't matter when nothing can be deleted
+    if ui_config.read_only {
+        Paragraph::new(Span::from(TEXT_READ_ONLY_BADGE).bold().reversed())
+            .fg(TEXT_PRE_DELETED_BG)
+            .right_aligned()
+            .render(col_trash, buf);
+        return;
+    }
     let text_trash = color_capital_letter(
         "Trash: ".into(),
         None,
         Some(value_to_box(&ui_config.move_to_trash)),
     );
-
-    Paragraph::new(text_color)
-        .right_aligned()
-        .render(col_color, buf);
     Paragraph::new(text_trash)
         .right_aligned()
         .render(col_trash, buf);
@@ -0,0 +1,198 @@
+use crate::config::UIConfig;
+use crate::fs::{Folder, FolderEntryType};
+use crate::ui::constants::{
+    NORMAL_ROW_COLOR, TEXT_COLOR, TEXT_FLASH_BG, TEXT_PRE_DELETED_BG, TEXT_SELECTED_BG,
+};
+use crate::ui::utils::{format_file_size, size_to_color};
+use ratatui::{prelude::*, widgets::*};
+
+/// Terminal cells are about twice as tall as wide, layout is done in square units
+const CELL_ASPECT: f64 = 2.0;
+
+/// Rectangle of a treemap layout
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TreemapRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl TreemapRect {
+    pub fn area(&self) -> f64 {
+        self.width * self.height
+    }
+}
+
+/// Splits area into rectangles with areas proportional to values, keeping them close to squares.
+///
+/// Values are expected in descending order, rectangles are returned in the same order.
+pub fn squarify(values: &[u64], area: TreemapRect) -> Vec<TreemapRect> {
+    let total: u64 = values.iter().sum();
+    if total == 0 || area.area() <= 0.0 {
+        return vec![];
+    }
+    let scale = area.area() / total as f64;
+    let areas: Vec<f64> = values.iter().map(|value| *value as f64 * scale).collect();
+
+    let mut rects = Vec::with_capacity(areas.len());
+    let mut free = area;
+    let mut row_start = 0;
+
+    for index in 0..areas.len() {
+        let side = free.width.min(free.height);
+        let row = &areas[row_start..index];
+        let with_next = &areas[row_start..=index];
+        if !row.is_empty() && worst_ratio(with_next, side) > worst_ratio(row, side) {
+            free = layout_row(row, free, &mut rects);
+            row_start = index;
+        }
+    }
+    layout_row(&areas[row_start..], free, &mut rects);
+
+    rects
+}
+
+/// Worst aspect ratio among rectangles of a row laid along side of provided length
+fn worst_ratio(row: &[f64], side: f64) -> f64 {
+    let sum: f64 = row.iter().sum();
+    let max = row.iter().cloned().fold(f64::MIN, f64::max);
+    let min = row.iter().cloned().fold(f64::MAX, f64::min);
+    let side = side * side;
+    let sum = sum * sum;
+    (side * max / sum).max(sum / (side * min))
+}
+
+/// Places row along the shorter side of free area, returns area left after it
+fn layout_row(row: &[f64], free: TreemapRect, rects: &mut Vec<TreemapRect>) -> TreemapRect {
+    let sum: f64 = row.iter().sum();
+    if sum <= 0.0 {
+        return free;
+    }
+    if free.width >= free.height {
+        // Column on the left
+        let width = sum / free.height;
+        let mut y = free.y;
+        for area in row {
+            let height = area / width;
+            rects.push(TreemapRect {
+                x: free.x,
+                y,
+                width,
+                height,
+            });
+            y += height;
+        }
+        TreemapRect {
+            x: free.x + width,
+            width: free.width - width,
+            ..free
+        }
+    } else {
+        // Row on the top
+        let height = sum / free.width;
+        let mut x = free.x;
+        for area in row {
+            let width = area / height;
+            rects.push(TreemapRect {
+                x,
+                y: free.y,
+                width,
+                height,
+            });
+            x += width;
+        }
+        TreemapRect {
+            y: free.y + height,
+            height: free.height - height,
+            ..free
+        }
+    }
+}
+
+/// Renders immediate children of folder as nested rectangles sized by bytes, returns index of
+/// each entry drawn with the cell it fills
+pub fn render_treemap(
+    area: Rect,
+    buf: &mut Buffer,
+    folder: &Folder,
+    config: &UIConfig,
+) -> Vec<(usize, Rect)> {
+    let block = Block::default()
+        .title(" Treemap ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_set(symbols::border::PROPORTIONAL_TALL)
+        .fg(TEXT_COLOR)
+        .bg(NORMAL_ROW_COLOR);
+    let inner_area = block.inner(area);
+    Widget::render(block, area, buf);
+
+    let mut entries: Vec<(usize, u64)> = config
+        .visible_entries(folder)
+        .filter(|(_, entry)| entry.kind != FolderEntryType::Parent)
+        .filter_map(|(index, entry)| entry.size.filter(|size| *size > 0).map(|s| (index, s)))
+        .collect();
+    entries.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+
+    let values: Vec<u64> = entries.iter().map(|(_, size)| *size).collect();
+    let rects = squarify(
+        &values,
+        TreemapRect {
+            x: 0.0,
+            y: 0.0,
+            width: inner_area.width as f64,
+            height: inner_area.height as f64 * CELL_ASPECT,
+        },
+    );
+    let max_size = values.first().cloned().unwrap_or(0);
+
+    let mut cells = vec![];
+    for ((index, size), rect) in entries.iter().zip(rects) {
+        let x0 = inner_area.x + rect.x.round() as u16;
+        let x1 = inner_area.x + (rect.x + rect.width).round() as u16;
+        let y0 = inner_area.y + (rect.y / CELL_ASPECT).round() as u16;
+        let y1 = inner_area.y + ((rect.y + rect.height) / CELL_ASPECT).round() as u16;
+        if x1 <= x0 || y1 <= y0 {
+            continue;
+        }
+        let cell = Rect::new(x0, y0, x1 - x0, y1 - y0).intersection(inner_area);
+
+        let bg = if *index != folder.cursor_index {
+            size_to_color(*size, max_size)
+        } else if config.flashing {
+            TEXT_FLASH_BG
+        } else if config.confirming_deletion {
+            TEXT_PRE_DELETED_BG
+        } else {
+            TEXT_SELECTED_BG
+        };
+        // One column gap keeps neighbouring rectangles apart
+        let fill = Rect {
+            width: cell.width.saturating_sub(1).max(1),
+            ..cell
+        };
+        buf.set_style(fill, Style::default().bg(bg).fg(NORMAL_ROW_COLOR));
+        cells.push((*index, fill));
+
+        let entry = &folder.entries()[*index];
+        let mut lines = vec![Line::from(Span::from(entry.title.clone()).bold())];
+        if fill.height > 1 {
+            lines.push(Line::from(format_file_size(*size)));
+        }
+        let mut text_style = Style::default();
+        if *index == folder.cursor_index {
+            text_style = text_style.fg(TEXT_COLOR);
+        }
+        Paragraph::new(lines).style(text_style).render(fill, buf);
+    }
+    cells
+}
+
+/// Entry whose treemap cell holds provided screen position, using cells as last drawn
+pub fn treemap_entry_at(cells: &[(usize, Rect)], x: u16, y: u16) -> Option<usize> {
+    cells
+        .iter()
+        .find(|(_, cell)| x >= cell.x && x < cell.right() && y >= cell.y && y < cell.bottom())
+        .map(|(index, _)| *index)
+}
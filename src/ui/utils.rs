@@ -1,10 +1,80 @@
-use crate::config::UIConfig;
+use crate::config::{Column, SizeUnit, UIConfig};
 use crate::fs::Folder;
 use crate::fs::FolderEntryType;
-use crate::ui::constants::{NORMAL_ROW_COLOR, TABLE_SPACE_WIDTH, TEXT_UNKNOWN};
+use crate::fs::{format_mode, user_name};
+use crate::ui::constants::{NORMAL_ROW_COLOR, TEXT_ELLIPSIS, TEXT_SCANNING, TEXT_UNKNOWN};
 use ratatui::{prelude::*, widgets::*};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
-use super::constants::TEXT_HIGHLIGHTED;
+use super::constants::{
+    TEXT_FLASH_BG, TEXT_HIGHLIGHTED, TEXT_MARKED, TEXT_NONE, TEXT_PRE_DELETED_BG, TEXT_SPARSE,
+};
+
+/// Shortens text wider than provided number of terminal columns by cutting out its middle.
+///
+/// The ".." put in place of the cut counts towards the width. A quarter of the rest is kept
+/// from the start and the remainder from the end, where paths keep their most telling part.
+/// Never splits a grapheme. Below two columns only `…` is left, or nothing at all.
+pub fn truncate_middle(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    match width {
+        0 => return String::new(),
+        1 => return TEXT_ELLIPSIS.to_string(),
+        _ => {}
+    }
+    let kept_width = width - 2;
+    let head_width = kept_width / 4;
+    let tail_width = kept_width - head_width;
+
+    let mut head = String::new();
+    let mut used = 0;
+    for grapheme in text.graphemes(true) {
+        used += grapheme.width();
+        if used > head_width {
+            break;
+        }
+        head.push_str(grapheme);
+    }
+
+    let mut tail = vec![];
+    let mut used = 0;
+    for grapheme in text.graphemes(true).rev() {
+        used += grapheme.width();
+        if used > tail_width {
+            break;
+        }
+        tail.push(grapheme);
+    }
+    tail.reverse();
+
+    format!("{}..{}", head, tail.concat())
+}
+
+/// Formats duration with its biggest whole unit, e.g. "3h" or "2d"
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86399 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86400),
+    }
+}
+
+/// Like [`format_duration`], with months and years for long ones, e.g. "2mo" or "3y"
+pub fn format_age(duration: Duration) -> String {
+    let days = duration.as_secs() / 86400;
+    match days {
+        0..=29 => format_duration(duration),
+        30..=364 => format!("{}mo", days / 30),
+        _ => format!("{}y", days / 365),
+    }
+}
 
 pub fn format_file_size(size: u64) -> String {
     const KB: u64 = 1024;
@@ -25,7 +95,55 @@ pub fn format_file_size(size: u64) -> String {
     }
 }
 
-pub fn calculate_color(percent: u64, _max_entry_size: u64) -> Color {
+/// Size in provided unit, two decimals for fixed units so they line up
+pub fn format_size(size: u64, unit: SizeUnit) -> String {
+    let (divisor, label) = match unit {
+        SizeUnit::Auto => return format_file_size(size),
+        SizeUnit::KB => (1u64 << 10, "KB"),
+        SizeUnit::MB => (1 << 20, "MB"),
+        SizeUnit::GB => (1 << 30, "GB"),
+    };
+    format!("{:.2} {}", size as f64 / divisor as f64, label)
+}
+
+/// Paths separated by commas, invalid UTF-8 is replaced
+pub fn join_paths(paths: &[PathBuf]) -> String {
+    let paths: Vec<_> = paths.iter().map(|path| path.to_string_lossy()).collect();
+    paths.join(", ")
+}
+
+/// Count with thousands separated, e.g. "1,342"
+pub fn format_count(count: usize) -> String {
+    let digits = count.to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
+/// Count shortened to fit next to a size, e.g. "1.2k"
+pub fn format_short_count(count: usize) -> String {
+    match count {
+        0..=999 => count.to_string(),
+        1_000..=999_999 => format!("{:.1}k", count as f64 / 1e3),
+        _ => format!("{:.1}M", count as f64 / 1e6),
+    }
+}
+
+/// Share of total with one decimal, e.g. "23.4%"
+pub fn format_percent(size: u64, total: u64) -> String {
+    match total {
+        0 => String::new(),
+        _ => format!("{:.1}%", size as f64 * 100.0 / total as f64),
+    }
+}
+
+/// Gradient color for size relative to the largest one, green for small ones up to red
+pub fn size_to_color(size: u64, max_size: u64) -> Color {
     let colors = [
         Color::Rgb(0, 128, 0),    // Green
         Color::Rgb(50, 205, 50),  // LimeGreen
@@ -35,10 +153,23 @@ pub fn calculate_color(percent: u64, _max_entry_size: u64) -> Color {
         Color::Rgb(255, 0, 0),    // Red
     ];
 
-    let index =
-        ((percent as f64 / TABLE_SPACE_WIDTH as f64) * (colors.len() - 1) as f64).round() as usize;
+    let fraction = match max_size {
+        0 => 0.0,
+        _ => (size as f64 / max_size as f64).min(1.0),
+    };
+    colors[(fraction * (colors.len() - 1) as f64).round() as usize]
+}
 
-    colors[index]
+/// Returns Space column bar for provided size relative to the largest one, and its color
+pub fn size_to_bar(size: u64, max_size: u64, config: &UIConfig) -> (String, Color) {
+    let width = config.space_width as u64;
+    let filled = (size.saturating_mul(width))
+        .checked_div(max_size)
+        .unwrap_or(0)
+        .min(width);
+    // Color follows the drawn bar, so equal bars never differ in color
+    let color = size_to_color(filled, width);
+    (config.bar_style.symbol().repeat(filled as usize), color)
 }
 
 pub fn value_to_box(value: &bool) -> String {
@@ -53,33 +184,39 @@ pub fn folder_to_rows<'a>(
     config: &'a UIConfig,
     loading_indicator: char,
 ) -> Vec<Row<'a>> {
-    let max_entry_size = folder.get_max_entry_size();
+    let max_entry_size = config
+        .space_total
+        .unwrap_or_else(|| folder.get_max_entry_size());
+    let now = SystemTime::now();
+    let columns = config.visible_columns();
 
-    folder
-        .to_list()
-        .iter()
-        .map(|item| {
+    config
+        .visible_entries(folder)
+        .map(|(index, item)| {
             let (item_size, bar, color) = match item.size {
                 Some(size) => {
-                    let percent = if max_entry_size == 0 {
-                        0
-                    } else {
-                        (size * TABLE_SPACE_WIDTH as u64 / max_entry_size).div_euclid(1)
-                    };
-                    let mut b = String::new();
-                    let color = calculate_color(percent, max_entry_size);
-                    for _ in 0..percent {
-                        b.push('█');
-                    }
-                    (Text::from(format_file_size(size)), Text::from(b), color)
+                    let (b, color) = size_to_bar(size, max_entry_size, config);
+                    (
+                        Text::from(format_size(size, config.size_unit)),
+                        Text::from(b),
+                        color,
+                    )
+                }
+                // Folder waiting for its turn in the scan
+                None if !item.is_loaded => {
+                    (Text::from(TEXT_SCANNING), Text::from(" "), NORMAL_ROW_COLOR)
                 }
                 None => (Text::from(TEXT_UNKNOWN), Text::from(" "), NORMAL_ROW_COLOR),
             };
+            let item_size = match config.align_sizes {
+                true => item_size.alignment(Alignment::Right),
+                false => item_size,
+            };
             let mut prefix = match item.kind == FolderEntryType::Folder {
                 true => Text::from(""),
                 false => Text::from(" "),
             };
-            if item.is_loaded == false {
+            if !item.is_loaded {
                 prefix = Text::from(String::from(loading_indicator));
             }
 
@@ -88,12 +225,70 @@ pub fn folder_to_rows<'a>(
                 bar_style = bar_style.fg(color);
             }
 
-            Row::new(vec![
-                prefix,
-                Text::from(item.title.clone()),
-                item_size,
-                bar.style(bar_style),
-            ])
+            let mut row_style = Style::default();
+            if folder.is_marked(&item.name) {
+                prefix = Text::from(TEXT_MARKED);
+                row_style = row_style.fg(TEXT_HIGHLIGHTED);
+                if config.flashing {
+                    row_style = row_style.bg(TEXT_FLASH_BG).fg(NORMAL_ROW_COLOR);
+                } else if config.confirming_deletion {
+                    row_style = row_style.bg(TEXT_PRE_DELETED_BG);
+                }
+            }
+
+            let mut title = Line::from(item.title.clone());
+            if let Some(ratio) = config.sparse_ratio {
+                if item.is_sparse(ratio) {
+                    title
+                        .spans
+                        .push(Span::from(format!(" {}", TEXT_SPARSE)).fg(TEXT_HIGHLIGHTED));
+                    if index == folder.cursor_index {
+                        let allocated = format_file_size(item.allocated.unwrap_or(0));
+                        title
+                            .spans
+                            .push(Span::from(format!(" {} on disk", allocated)));
+                    }
+                }
+            }
+
+            let other_size = || {
+                let other_size = match config.other_sizes.get(&item.name) {
+                    Some(size) => Text::from(format_size(*size, config.size_unit)),
+                    None if item.kind == FolderEntryType::Parent => Text::from(""),
+                    None => Text::from(TEXT_UNKNOWN),
+                };
+                match config.align_sizes {
+                    true => other_size.alignment(Alignment::Right),
+                    false => other_size,
+                }
+            };
+            let mut cells = vec![prefix];
+            for column in &columns {
+                cells.push(match column {
+                    Column::Name => Text::from(title.clone()),
+                    Column::Size => item_size.clone(),
+                    Column::OtherSize => other_size(),
+                    Column::Owner => Text::from(item.owner.map(user_name).unwrap_or_default()),
+                    Column::Mode => Text::from(item.mode.map(format_mode).unwrap_or_default()),
+                    Column::Age => Text::from(
+                        item.modified
+                            .map(|modified| {
+                                format_age(now.duration_since(modified).unwrap_or_default())
+                            })
+                            .unwrap_or(TEXT_NONE.to_string()),
+                    ),
+                    Column::DiskShare => Text::from(
+                        config
+                            .disk_capacity
+                            .zip(item.size)
+                            .map(|(capacity, size)| format_percent(size, capacity))
+                            .unwrap_or_default(),
+                    ),
+                    Column::Space => bar.clone().style(bar_style),
+                });
+            }
+
+            Row::new(cells).style(row_style)
         })
         .collect()
 }
@@ -127,3 +322,21 @@ pub fn color_capital_letter<'a>(
 
     Line::from(spans)
 }
+
+/// Position of cursor among rows shown with current filters
+pub fn visible_cursor_index(folder: &Folder, config: &UIConfig) -> usize {
+    config
+        .visible_entries(folder)
+        .take_while(|(index, _)| *index < folder.cursor_index)
+        .count()
+}
+
+/// Reserves highlight column on every row so columns don't shift with the cursor,
+/// and drops it entirely when no symbol is configured
+pub fn highlight_spacing(config: &UIConfig) -> HighlightSpacing {
+    if config.highlight_symbol.is_empty() {
+        HighlightSpacing::Never
+    } else {
+        HighlightSpacing::Always
+    }
+}
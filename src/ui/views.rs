@@ -0,0 +1,578 @@
+use crate::config::{PathStyle, UIConfig};
+use crate::fs::{format_path, Discrepancy, ScanError, SortBy, TrashedEntry};
+use crate::ui::constants::{
+    NORMAL_ROW_COLOR, TABLE_HEADER_BG, TABLE_HEADER_FG, TEXT_COLOR, TEXT_HIGHLIGHTED,
+    TEXT_HINT_PERMISSION_DENIED, TEXT_NONE, TEXT_PRE_DELETED_BG, TEXT_SELECTED_BG, TEXT_UNKNOWN,
+};
+use crate::ui::footer::confirm_hint;
+use crate::ui::utils::{
+    format_age, format_duration, format_file_size, highlight_spacing, size_to_bar, truncate_middle,
+};
+use crate::views::{
+    DeepView, DiffView, ErrorsView, FilesystemsView, NamesView, RecentView, TrashView, VerifyView,
+};
+use ratatui::{prelude::*, widgets::*};
+use std::path::Path;
+use std::time::SystemTime;
+use unicode_width::UnicodeWidthStr;
+
+/// Columns left for the filling column of a bordered table next to fixed width ones
+fn fill_width(area: Rect, fixed: &[u16], config: &UIConfig) -> usize {
+    // Borders, padding and one space between each pair of columns
+    let taken = 4 + fixed.iter().sum::<u16>() + fixed.len() as u16;
+    area.width
+        .saturating_sub(taken)
+        .saturating_sub(config.highlight_symbol.width() as u16) as usize
+}
+
+/// Path relative to scan root, shortened in the middle to fit provided width
+fn relative_path(path: &Path, root: &Path, width: usize) -> String {
+    truncate_middle(&format_path(path, root, PathStyle::Relative), width)
+}
+
+pub fn render_filesystems(area: Rect, buf: &mut Buffer, view: &FilesystemsView, config: &UIConfig) {
+    let block = Block::default()
+        .title(" Filesystems ")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .padding(Padding::horizontal(1))
+        .border_set(symbols::border::PROPORTIONAL_TALL)
+        .fg(TEXT_COLOR)
+        .bg(NORMAL_ROW_COLOR);
+
+    let header_style = Style::default().fg(TABLE_HEADER_FG).bg(TABLE_HEADER_BG);
+    let header_titles = match view.sort_by {
+        SortBy::Size => ["Mount point", "Size ↓", "Files", "Space"],
+        _ => ["Mount point ↓", "Size", "Files", "Space"],
+    };
+    let header = header_titles
+        .into_iter()
+        .map(Cell::from)
+        .collect::<Row>()
+        .style(header_style)
+        .height(1);
+
+    let max_size = view.entries.iter().map(|e| e.size).max().unwrap_or(0);
+    let rows: Vec<Row> = view
+        .entries
+        .iter()
+        .map(|filesystem| {
+            let (bar, color) = size_to_bar(filesystem.size, max_size, config);
+            let mut bar_style = Style::default();
+            if config.colored {
+                bar_style = bar_style.fg(color);
+            }
+            Row::new(vec![
+                Text::from(filesystem.mount_point.to_string_lossy().to_string()),
+                Text::from(format_file_size(filesystem.size)),
+                Text::from(filesystem.files.to_string()),
+                Text::from(bar).style(bar_style),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(40),
+            Constraint::Length(20),
+            Constraint::Length(10),
+            Constraint::Length(config.space_width as u16),
+        ],
+    )
+    .block(block)
+    .header(header)
+    .highlight_symbol(config.highlight_symbol.as_str())
+    .highlight_style(Style::default().bg(TEXT_SELECTED_BG))
+    .highlight_spacing(highlight_spacing(config));
+
+    StatefulWidget::render(
+        table,
+        area,
+        buf,
+        &mut TableState::default().with_selected(Some(view.cursor_index)),
+    );
+}
+
+pub fn render_errors(
+    area: Rect,
+    buf: &mut Buffer,
+    view: &ErrorsView,
+    errors: &[ScanError],
+    config: &UIConfig,
+) {
+    let block = Block::default()
+        .title(format!(" Errors ({}) ", errors.len()))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .padding(Padding::horizontal(1))
+        .border_set(symbols::border::PROPORTIONAL_TALL)
+        .fg(TEXT_COLOR)
+        .bg(NORMAL_ROW_COLOR);
+    let inner_area = block.inner(area);
+    Widget::render(block, area, buf);
+
+    let [table_area, hint_area] =
+        Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(inner_area);
+
+    let header_style = Style::default().fg(TABLE_HEADER_FG).bg(TABLE_HEADER_BG);
+    let header = ["Path", "Error"]
+        .into_iter()
+        .map(Cell::from)
+        .collect::<Row>()
+        .style(header_style)
+        .height(1);
+
+    let rows: Vec<Row> = errors
+        .iter()
+        .map(|err| {
+            Row::new(vec![
+                Text::from(err.path.to_string_lossy().to_string()),
+                Text::from(err.kind.to_string()).fg(TEXT_PRE_DELETED_BG),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(rows, [Constraint::Fill(1), Constraint::Length(30)])
+        .header(header)
+        .highlight_symbol(config.highlight_symbol.as_str())
+        .highlight_style(Style::default().bg(TEXT_SELECTED_BG))
+        .highlight_spacing(highlight_spacing(config));
+
+    StatefulWidget::render(
+        table,
+        table_area,
+        buf,
+        &mut TableState::default().with_selected(Some(view.cursor_index)),
+    );
+
+    if errors
+        .get(view.cursor_index)
+        .is_some_and(|err| err.is_permission_denied())
+    {
+        Paragraph::new(TEXT_HINT_PERMISSION_DENIED)
+            .fg(TEXT_HIGHLIGHTED)
+            .render(hint_area, buf);
+    }
+}
+
+pub fn render_recent(
+    area: Rect,
+    buf: &mut Buffer,
+    view: &RecentView,
+    root: &Path,
+    config: &UIConfig,
+) {
+    let files = view.visible();
+    let block = Block::default()
+        .title(format!(
+            " Modified in last {} ({}) ",
+            format_duration(view.window),
+            files.len()
+        ))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .padding(Padding::horizontal(1))
+        .border_set(symbols::border::PROPORTIONAL_TALL)
+        .fg(TEXT_COLOR)
+        .bg(NORMAL_ROW_COLOR);
+
+    let header_style = Style::default().fg(TABLE_HEADER_FG).bg(TABLE_HEADER_BG);
+    let header = ["Path", "Size", "Modified ↓"]
+        .into_iter()
+        .map(Cell::from)
+        .collect::<Row>()
+        .style(header_style)
+        .height(1);
+
+    let now = SystemTime::now();
+    let path_width = fill_width(area, &[12, 12], config);
+    let rows: Vec<Row> = files
+        .iter()
+        .map(|file| {
+            let age = now.duration_since(file.modified).unwrap_or_default();
+            Row::new(vec![
+                Text::from(relative_path(&file.path, root, path_width)),
+                Text::from(format_file_size(file.size)),
+                Text::from(format!("{} ago", format_age(age))),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(1),
+            Constraint::Length(12),
+            Constraint::Length(12),
+        ],
+    )
+    .block(block)
+    .header(header)
+    .highlight_symbol(config.highlight_symbol.as_str())
+    .highlight_style(Style::default().bg(TEXT_SELECTED_BG))
+    .highlight_spacing(highlight_spacing(config));
+
+    StatefulWidget::render(
+        table,
+        area,
+        buf,
+        &mut TableState::default().with_selected(Some(view.cursor_index)),
+    );
+}
+
+pub fn render_deep(area: Rect, buf: &mut Buffer, view: &DeepView, root: &Path, config: &UIConfig) {
+    let folders = view.visible();
+    let block = Block::default()
+        .title(format!(
+            " Folders {} or more levels deep ({}) ",
+            view.min_depth,
+            folders.len()
+        ))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .padding(Padding::horizontal(1))
+        .border_set(symbols::border::PROPORTIONAL_TALL)
+        .fg(TEXT_COLOR)
+        .bg(NORMAL_ROW_COLOR);
+
+    let header_style = Style::default().fg(TABLE_HEADER_FG).bg(TABLE_HEADER_BG);
+    let header = ["Path", "Size", "Depth ↓"]
+        .into_iter()
+        .map(Cell::from)
+        .collect::<Row>()
+        .style(header_style)
+        .height(1);
+
+    let path_width = fill_width(area, &[12, 8], config);
+    let rows: Vec<Row> = folders
+        .iter()
+        .map(|folder| {
+            Row::new(vec![
+                Text::from(relative_path(&folder.path, root, path_width)),
+                Text::from(
+                    folder
+                        .size
+                        .map(format_file_size)
+                        .unwrap_or(TEXT_UNKNOWN.to_string()),
+                ),
+                Text::from(folder.depth.to_string()),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(1),
+            Constraint::Length(12),
+            Constraint::Length(8),
+        ],
+    )
+    .block(block)
+    .header(header)
+    .highlight_symbol(config.highlight_symbol.as_str())
+    .highlight_style(Style::default().bg(TEXT_SELECTED_BG))
+    .highlight_spacing(highlight_spacing(config));
+
+    StatefulWidget::render(
+        table,
+        area,
+        buf,
+        &mut TableState::default().with_selected(Some(view.cursor_index)),
+    );
+}
+
+pub fn render_verify(
+    area: Rect,
+    buf: &mut Buffer,
+    view: &VerifyView,
+    root: &Path,
+    config: &UIConfig,
+) {
+    let block = Block::default()
+        .title(format!(
+            " Differences from disk ({}), R to rescan ",
+            view.mismatches.len()
+        ))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .padding(Padding::horizontal(1))
+        .border_set(symbols::border::PROPORTIONAL_TALL)
+        .fg(TEXT_COLOR)
+        .bg(NORMAL_ROW_COLOR);
+
+    let header_style = Style::default().fg(TABLE_HEADER_FG).bg(TABLE_HEADER_BG);
+    let header = ["Path", "Difference", "Scanned", "On disk"]
+        .into_iter()
+        .map(Cell::from)
+        .collect::<Row>()
+        .style(header_style)
+        .height(1);
+
+    let path_width = fill_width(area, &[14, 12, 12], config);
+    let rows: Vec<Row> = view
+        .mismatches
+        .iter()
+        .map(|mismatch| {
+            let (difference, scanned, disk) = match mismatch.discrepancy {
+                Discrepancy::Unlisted => ("not in scan", TEXT_NONE.to_string(), String::new()),
+                Discrepancy::Missing => ("gone from disk", String::new(), TEXT_NONE.to_string()),
+                Discrepancy::Size { recorded, disk } => (
+                    "size drifted",
+                    format_file_size(recorded),
+                    format_file_size(disk),
+                ),
+            };
+            Row::new(vec![
+                Text::from(relative_path(&mismatch.path, root, path_width)),
+                Text::from(difference),
+                Text::from(scanned),
+                Text::from(disk),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(1),
+            Constraint::Length(14),
+            Constraint::Length(12),
+            Constraint::Length(12),
+        ],
+    )
+    .block(block)
+    .header(header)
+    .highlight_symbol(config.highlight_symbol.as_str())
+    .highlight_style(Style::default().bg(TEXT_SELECTED_BG))
+    .highlight_spacing(highlight_spacing(config));
+
+    StatefulWidget::render(
+        table,
+        area,
+        buf,
+        &mut TableState::default().with_selected(Some(view.cursor_index)),
+    );
+}
+
+pub fn render_diff(area: Rect, buf: &mut Buffer, view: &DiffView, config: &UIConfig) {
+    let changes = view.visible();
+    let hidden = match view.show_all {
+        true => String::new(),
+        false => format!(
+            ", {} under {} hidden",
+            view.changes.len() - changes.len(),
+            format_file_size(view.threshold)
+        ),
+    };
+    let block = Block::default()
+        .title(format!(
+            " Changes since previous scan ({}{}) ",
+            changes.len(),
+            hidden
+        ))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .padding(Padding::horizontal(1))
+        .border_set(symbols::border::PROPORTIONAL_TALL)
+        .fg(TEXT_COLOR)
+        .bg(NORMAL_ROW_COLOR);
+
+    let header_style = Style::default().fg(TABLE_HEADER_FG).bg(TABLE_HEADER_BG);
+    let header = ["Path", "Before", "After", "Change ↓"]
+        .into_iter()
+        .map(Cell::from)
+        .collect::<Row>()
+        .style(header_style)
+        .height(1);
+
+    let size_or_dash = |size: Option<u64>| size.map(format_file_size).unwrap_or("-".to_string());
+    let rows: Vec<Row> = changes
+        .iter()
+        .map(|change| {
+            let delta = change.delta();
+            let sign = if delta < 0 { "-" } else { "+" };
+            let path = match (change.before, change.after) {
+                (None, _) => format!("{} (added)", change.path),
+                (_, None) => format!("{} (removed)", change.path),
+                _ => change.path.clone(),
+            };
+            Row::new(vec![
+                Text::from(path),
+                Text::from(size_or_dash(change.before)),
+                Text::from(size_or_dash(change.after)),
+                Text::from(format!(
+                    "{}{}",
+                    sign,
+                    format_file_size(delta.unsigned_abs() as u64)
+                )),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(1),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(12),
+        ],
+    )
+    .block(block)
+    .header(header)
+    .highlight_symbol(config.highlight_symbol.as_str())
+    .highlight_style(Style::default().bg(TEXT_SELECTED_BG))
+    .highlight_spacing(highlight_spacing(config));
+
+    StatefulWidget::render(
+        table,
+        area,
+        buf,
+        &mut TableState::default().with_selected(Some(view.cursor_index)),
+    );
+}
+
+pub fn render_names(
+    area: Rect,
+    buf: &mut Buffer,
+    view: &NamesView,
+    root: &Path,
+    config: &UIConfig,
+) {
+    let block = Block::default()
+        .title(format!(
+            " Names found in several folders ({}) ",
+            view.groups.len()
+        ))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .padding(Padding::horizontal(1))
+        .border_set(symbols::border::PROPORTIONAL_TALL)
+        .fg(TEXT_COLOR)
+        .bg(NORMAL_ROW_COLOR);
+
+    let header_style = Style::default().fg(TABLE_HEADER_FG).bg(TABLE_HEADER_BG);
+    let header = ["Name", "Path", "Size"]
+        .into_iter()
+        .map(Cell::from)
+        .collect::<Row>()
+        .style(header_style)
+        .height(1);
+
+    // Group name is shown next to its first occurrence only
+    let path_width = fill_width(area, &[30, 12], config);
+    let rows: Vec<Row> = view
+        .groups
+        .iter()
+        .flat_map(|group| {
+            group.files.iter().enumerate().map(move |(index, file)| {
+                let name = match index {
+                    0 => format!("{} ×{}", group.name, group.files.len()),
+                    _ => String::new(),
+                };
+                Row::new(vec![
+                    Text::from(name).fg(TEXT_HIGHLIGHTED),
+                    Text::from(relative_path(&file.path, root, path_width)),
+                    Text::from(format_file_size(file.size)),
+                ])
+            })
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(30),
+            Constraint::Fill(1),
+            Constraint::Length(12),
+        ],
+    )
+    .block(block)
+    .header(header)
+    .highlight_symbol(config.highlight_symbol.as_str())
+    .highlight_style(Style::default().bg(TEXT_SELECTED_BG))
+    .highlight_spacing(highlight_spacing(config));
+
+    StatefulWidget::render(
+        table,
+        area,
+        buf,
+        &mut TableState::default().with_selected(Some(view.cursor_index)),
+    );
+}
+
+pub fn render_trash(
+    area: Rect,
+    buf: &mut Buffer,
+    view: &TrashView,
+    entries: &[TrashedEntry],
+    root: &Path,
+    config: &UIConfig,
+) {
+    let size: u64 = entries.iter().map(|entry| entry.size).sum();
+    let block = Block::default()
+        .title(format!(
+            " Moved to trash this session ({}, {} reclaimable) ",
+            entries.len(),
+            format_file_size(size)
+        ))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .padding(Padding::horizontal(1))
+        .border_set(symbols::border::PROPORTIONAL_TALL)
+        .fg(TEXT_COLOR)
+        .bg(NORMAL_ROW_COLOR);
+    let inner_area = block.inner(area);
+    Widget::render(block, area, buf);
+
+    let [table_area, hint_area] =
+        Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(inner_area);
+
+    let header_style = Style::default().fg(TABLE_HEADER_FG).bg(TABLE_HEADER_BG);
+    let header = ["Path", "Size"]
+        .into_iter()
+        .map(Cell::from)
+        .collect::<Row>()
+        .style(header_style)
+        .height(1);
+
+    let path_width = fill_width(area, &[12], config);
+    let rows: Vec<Row> = entries
+        .iter()
+        .map(|entry| {
+            Row::new(vec![
+                Text::from(relative_path(&entry.path, root, path_width)),
+                Text::from(format_file_size(entry.size)),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(rows, [Constraint::Fill(1), Constraint::Length(12)])
+        .header(header)
+        .highlight_symbol(config.highlight_symbol.as_str())
+        .highlight_style(Style::default().bg(TEXT_SELECTED_BG))
+        .highlight_spacing(highlight_spacing(config));
+
+    StatefulWidget::render(
+        table,
+        table_area,
+        buf,
+        &mut TableState::default().with_selected(Some(view.cursor_index)),
+    );
+
+    let hint = match (entries.is_empty(), view.confirming_purge) {
+        (true, _) => Paragraph::new("Nothing was moved to trash this session"),
+        (false, false) => {
+            Paragraph::new("d - remove these permanently, until then they stay in the trash")
+        }
+        (false, true) => Paragraph::new(format!(
+            "{} to remove {} entries permanently, any other key cancels",
+            confirm_hint(config.confirm_key),
+            entries.len()
+        ))
+        .fg(TEXT_PRE_DELETED_BG),
+    };
+    hint.render(hint_area, buf);
+}
@@ -0,0 +1,434 @@
+use crate::fs::{
+    DeepFolder, FilesystemUsage, Mismatch, NameGroup, NamedFile, RecentFile, SizeChange, SortBy,
+};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// Content shown in place of the current folder table
+#[derive(Debug, Default)]
+pub enum View {
+    #[default]
+    Folder,
+    Filesystems(FilesystemsView),
+    Errors(ErrorsView),
+    Recent(RecentView),
+    Deep(DeepView),
+    Diff(DiffView),
+    Names(NamesView),
+    Trash(TrashView),
+    Verify(VerifyView),
+}
+
+/// Scanned usage grouped by filesystem
+#[derive(Debug)]
+pub struct FilesystemsView {
+    pub entries: Vec<FilesystemUsage>,
+    pub cursor_index: usize,
+    pub sort_by: SortBy,
+}
+
+impl FilesystemsView {
+    pub fn new(entries: Vec<FilesystemUsage>) -> Self {
+        let mut view = FilesystemsView {
+            entries,
+            cursor_index: 0,
+            sort_by: SortBy::Size,
+        };
+        view.sort();
+        view
+    }
+
+    pub fn toggle_sorting(&mut self) {
+        self.sort_by = match self.sort_by {
+            SortBy::Size => SortBy::Title,
+            _ => SortBy::Size,
+        };
+        self.sort();
+    }
+
+    fn sort(&mut self) {
+        // Only name and size are toggled between here
+        match self.sort_by {
+            SortBy::Size => self.entries.sort_by_key(|e| std::cmp::Reverse(e.size)),
+            _ => self
+                .entries
+                .sort_by(|a, b| a.mount_point.cmp(&b.mount_point)),
+        }
+    }
+
+    pub fn cursor_up(&mut self) {
+        self.cursor_index = self.cursor_index.saturating_sub(1);
+    }
+
+    pub fn cursor_down(&mut self) {
+        if self.cursor_index + 1 < self.entries.len() {
+            self.cursor_index += 1;
+        }
+    }
+}
+
+/// Paths that could not be scanned, listed from app state so it fills up while scanning
+#[derive(Debug, Default)]
+pub struct ErrorsView {
+    pub cursor_index: usize,
+}
+
+impl ErrorsView {
+    pub fn cursor_up(&mut self) {
+        self.cursor_index = self.cursor_index.saturating_sub(1);
+    }
+
+    pub fn cursor_down(&mut self, len: usize) {
+        if self.cursor_index + 1 < len {
+            self.cursor_index += 1;
+        }
+    }
+}
+
+/// Window presets cycled through in recent view
+pub const RECENT_WINDOWS: [Duration; 4] = [
+    Duration::from_secs(60 * 60),
+    Duration::from_secs(24 * 60 * 60),
+    Duration::from_secs(7 * 24 * 60 * 60),
+    Duration::from_secs(30 * 24 * 60 * 60),
+];
+
+/// Files modified within a window, taken from scanned data so changing window needs no rescan
+#[derive(Debug)]
+pub struct RecentView {
+    /// Every scanned file with known modification time, most recent first
+    pub files: Vec<RecentFile>,
+    pub window: Duration,
+    pub cursor_index: usize,
+}
+
+impl RecentView {
+    pub fn new(files: Vec<RecentFile>, window: Duration) -> Self {
+        RecentView {
+            files,
+            window,
+            cursor_index: 0,
+        }
+    }
+
+    /// Files modified within current window
+    pub fn visible(&self) -> &[RecentFile] {
+        let Some(since) = SystemTime::now().checked_sub(self.window) else {
+            return &self.files;
+        };
+        let len = self.files.partition_point(|file| file.modified >= since);
+        &self.files[..len]
+    }
+
+    /// Moves to the next bigger preset, wraps around to the smallest one
+    pub fn cycle_window(&mut self) {
+        self.window = RECENT_WINDOWS
+            .into_iter()
+            .find(|window| *window > self.window)
+            .unwrap_or(RECENT_WINDOWS[0]);
+        self.cursor_index = 0;
+    }
+
+    pub fn cursor_up(&mut self) {
+        self.cursor_index = self.cursor_index.saturating_sub(1);
+    }
+
+    pub fn cursor_down(&mut self) {
+        if self.cursor_index + 1 < self.visible().len() {
+            self.cursor_index += 1;
+        }
+    }
+}
+
+/// Folders nested at least `min_depth` levels below scan root, taken from scanned data so
+/// changing depth needs no rescan
+#[derive(Debug)]
+pub struct DeepView {
+    /// Every scanned folder, deepest first
+    pub folders: Vec<DeepFolder>,
+    pub min_depth: usize,
+    pub cursor_index: usize,
+}
+
+impl DeepView {
+    pub fn new(folders: Vec<DeepFolder>, min_depth: usize) -> Self {
+        DeepView {
+            folders,
+            min_depth: min_depth.max(1),
+            cursor_index: 0,
+        }
+    }
+
+    /// Folders at or below current depth
+    pub fn visible(&self) -> &[DeepFolder] {
+        let len = self
+            .folders
+            .partition_point(|folder| folder.depth >= self.min_depth);
+        &self.folders[..len]
+    }
+
+    pub fn selected(&self) -> Option<&DeepFolder> {
+        self.visible().get(self.cursor_index)
+    }
+
+    /// Shows only folders one level deeper, stops at the deepest scanned one
+    pub fn deeper(&mut self) {
+        let deepest = self.folders.first().map_or(1, |folder| folder.depth);
+        if self.min_depth < deepest {
+            self.min_depth += 1;
+            self.clamp_cursor();
+        }
+    }
+
+    pub fn shallower(&mut self) {
+        self.min_depth = self.min_depth.saturating_sub(1).max(1);
+    }
+
+    pub fn cursor_up(&mut self) {
+        self.cursor_index = self.cursor_index.saturating_sub(1);
+    }
+
+    pub fn cursor_down(&mut self) {
+        if self.cursor_index + 1 < self.visible().len() {
+            self.cursor_index += 1;
+        }
+    }
+
+    fn clamp_cursor(&mut self) {
+        self.cursor_index = self
+            .cursor_index
+            .min(self.visible().len().saturating_sub(1));
+    }
+}
+
+/// Where scanned tree below a folder differs from disk
+#[derive(Debug)]
+pub struct VerifyView {
+    pub mismatches: Vec<Mismatch>,
+    /// Folder that was verified, rescanned on resync
+    pub path: PathBuf,
+    pub cursor_index: usize,
+}
+
+impl VerifyView {
+    pub fn new(mismatches: Vec<Mismatch>, path: PathBuf) -> Self {
+        VerifyView {
+            mismatches,
+            path,
+            cursor_index: 0,
+        }
+    }
+
+    pub fn selected(&self) -> Option<&Mismatch> {
+        self.mismatches.get(self.cursor_index)
+    }
+
+    pub fn cursor_up(&mut self) {
+        self.cursor_index = self.cursor_index.saturating_sub(1);
+    }
+
+    pub fn cursor_down(&mut self) {
+        if self.cursor_index + 1 < self.mismatches.len() {
+            self.cursor_index += 1;
+        }
+    }
+}
+
+/// Entries that changed since previous scan of the same root
+#[derive(Debug)]
+pub struct DiffView {
+    /// Every change, biggest first
+    pub changes: Vec<SizeChange>,
+    /// Changes smaller than that many bytes are hidden
+    pub threshold: u64,
+    /// Show changes below threshold too
+    pub show_all: bool,
+    pub cursor_index: usize,
+}
+
+impl DiffView {
+    pub fn new(changes: Vec<SizeChange>, threshold: u64) -> Self {
+        DiffView {
+            changes,
+            threshold,
+            show_all: false,
+            cursor_index: 0,
+        }
+    }
+
+    /// Changes shown with current threshold
+    pub fn visible(&self) -> &[SizeChange] {
+        if self.show_all {
+            return &self.changes;
+        }
+        let len = self
+            .changes
+            .partition_point(|change| change.delta().unsigned_abs() >= self.threshold as u128);
+        &self.changes[..len]
+    }
+
+    pub fn toggle_show_all(&mut self) {
+        self.show_all = !self.show_all;
+        self.cursor_index = 0;
+    }
+
+    pub fn cursor_up(&mut self) {
+        self.cursor_index = self.cursor_index.saturating_sub(1);
+    }
+
+    pub fn cursor_down(&mut self) {
+        if self.cursor_index + 1 < self.visible().len() {
+            self.cursor_index += 1;
+        }
+    }
+}
+
+/// Files whose names repeat across folders, cursor moves over single occurrences
+#[derive(Debug)]
+pub struct NamesView {
+    pub groups: Vec<NameGroup>,
+    /// Index among occurrences of all groups
+    pub cursor_index: usize,
+}
+
+impl NamesView {
+    pub fn new(groups: Vec<NameGroup>) -> Self {
+        NamesView {
+            groups,
+            cursor_index: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.groups.iter().map(|group| group.files.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    pub fn selected(&self) -> Option<&NamedFile> {
+        self.groups
+            .iter()
+            .flat_map(|group| &group.files)
+            .nth(self.cursor_index)
+    }
+
+    pub fn cursor_up(&mut self) {
+        self.cursor_index = self.cursor_index.saturating_sub(1);
+    }
+
+    pub fn cursor_down(&mut self) {
+        if self.cursor_index + 1 < self.len() {
+            self.cursor_index += 1;
+        }
+    }
+}
+
+/// Entries moved to trash this session, listed from app state as they are purged together
+#[derive(Debug, Default)]
+pub struct TrashView {
+    pub cursor_index: usize,
+    /// Purge waits for the key to be pressed again
+    pub confirming_purge: bool,
+}
+
+impl TrashView {
+    pub fn cursor_up(&mut self) {
+        self.cursor_index = self.cursor_index.saturating_sub(1);
+    }
+
+    pub fn cursor_down(&mut self, len: usize) {
+        if self.cursor_index + 1 < len {
+            self.cursor_index += 1;
+        }
+    }
+}
+
+/// Sort keys listed in the sort menu, in the order they are shown
+pub const SORT_MENU_KEYS: [SortBy; 4] =
+    [SortBy::Title, SortBy::Size, SortBy::Modified, SortBy::Count];
+
+/// Profiles to switch to, the first row goes back to options wiper started with
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileMenu {
+    pub names: Vec<String>,
+    pub cursor_index: usize,
+}
+
+impl ProfileMenu {
+    /// Menu with active profile selected
+    pub fn new(names: Vec<String>, active: Option<&str>) -> Self {
+        let cursor_index = active
+            .and_then(|active| names.iter().position(|name| name == active))
+            .map_or(0, |index| index + 1);
+        ProfileMenu {
+            names,
+            cursor_index,
+        }
+    }
+
+    /// Profile under cursor, None for the options wiper started with
+    pub fn selected(&self) -> Option<&str> {
+        self.cursor_index
+            .checked_sub(1)
+            .and_then(|index| self.names.get(index))
+            .map(String::as_str)
+    }
+
+    pub fn cursor_up(&mut self) {
+        self.cursor_index = self.cursor_index.saturating_sub(1);
+    }
+
+    pub fn cursor_down(&mut self) {
+        if self.cursor_index < self.names.len() {
+            self.cursor_index += 1;
+        }
+    }
+}
+
+/// Sorting picked in the sort menu, only applied to the table once confirmed
+#[derive(Debug, Clone, PartialEq)]
+pub struct SortMenu {
+    pub cursor_index: usize,
+    pub sort_by: SortBy,
+    pub reversed: bool,
+    pub folders_first: bool,
+}
+
+impl SortMenu {
+    /// Rows after sort keys, reverse order then folders first
+    pub const ROWS: usize = SORT_MENU_KEYS.len() + 2;
+
+    /// Menu with current sorting picked and its key selected
+    pub fn new(sort_by: SortBy, reversed: bool, folders_first: bool) -> Self {
+        SortMenu {
+            cursor_index: SORT_MENU_KEYS
+                .iter()
+                .position(|key| *key == sort_by)
+                .unwrap_or(0),
+            sort_by,
+            reversed,
+            folders_first,
+        }
+    }
+
+    pub fn cursor_up(&mut self) {
+        self.cursor_index = self.cursor_index.saturating_sub(1);
+    }
+
+    pub fn cursor_down(&mut self) {
+        if self.cursor_index + 1 < Self::ROWS {
+            self.cursor_index += 1;
+        }
+    }
+
+    /// Picks sort key under cursor, or flips the option under it
+    pub fn toggle(&mut self) {
+        match SORT_MENU_KEYS.get(self.cursor_index) {
+            Some(key) => self.sort_by = *key,
+            None if self.cursor_index == SORT_MENU_KEYS.len() => self.reversed = !self.reversed,
+            None => self.folders_first = !self.folders_first,
+        }
+    }
+}
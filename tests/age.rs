@@ -0,0 +1,64 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use std::time::Duration;
+use wiper::app::App;
+use wiper::events::handle_key_events;
+use wiper::fs::DataStoreType;
+use wiper::ui::format_age;
+
+mod age {
+    use super::*;
+
+    const WIDTH: u16 = 140;
+
+    fn render_rows(app: &mut App<DataStoreType>) -> Vec<String> {
+        let area = Rect::new(0, 0, WIDTH, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content
+            .chunks(WIDTH as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn formats_age_with_biggest_unit() {
+        let days = |days: u64| Duration::from_secs(days * 86400);
+        assert_eq!(format_age(Duration::from_secs(90)), "1m");
+        assert_eq!(format_age(days(5)), "5d");
+        assert_eq!(format_age(days(29)), "29d");
+        assert_eq!(format_age(days(65)), "2mo");
+        assert_eq!(format_age(days(364)), "12mo");
+        assert_eq!(format_age(days(3 * 365 + 10)), "3y");
+    }
+
+    #[test]
+    fn toggles_age_column() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        assert!(!render_rows(&mut app).iter().any(|row| row.contains("Age")));
+
+        handle_key_events(
+            KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE),
+            &mut app,
+        )
+        .unwrap();
+        assert!(app.ui_config.show_age);
+
+        let rows = render_rows(&mut app);
+        assert!(rows.iter().any(|row| row.contains("Age")));
+        // Folders have no modified time
+        let file_row = rows
+            .iter()
+            .find(|row| row.contains("a_root_file.txt"))
+            .unwrap();
+        assert!(!file_row.contains("—"));
+        let folder_row = rows.iter().find(|row| row.contains("a_folder")).unwrap();
+        assert!(folder_row.contains("—"));
+    }
+}
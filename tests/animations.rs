@@ -0,0 +1,61 @@
+pub mod common;
+
+use crate::common::*;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use wiper::app::App;
+use wiper::fs::DataStoreType;
+use wiper::spinner::Spinner;
+
+mod animations {
+    use super::*;
+
+    fn title_row(app: &mut App<DataStoreType>) -> String {
+        let area = Rect::new(0, 0, 100, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content[..area.width as usize]
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn still_icons_do_not_move() {
+        let spinner = Spinner::default();
+        assert_eq!(spinner.get_still_icons(false), ('…', '…'));
+        assert_eq!(spinner.get_still_icons(true), (' ', ' '));
+    }
+
+    #[test]
+    fn shows_static_indicator_while_loading() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        app.ui_config.no_animations = true;
+        let first = title_row(&mut app);
+        assert!(first.contains("… Wiper …"));
+        assert_eq!(title_row(&mut app), first);
+
+        handle_tasks_synchronously(&mut app);
+        // Scan is timed as finished on the next tick
+        app.tick();
+        let row = title_row(&mut app);
+        assert!(row.contains("   Wiper   "), "{row}");
+    }
+
+    #[test]
+    fn does_not_flash_on_confirmation() {
+        let postfix = "57";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        app.ui_config.no_animations = true;
+        handle_tasks_synchronously(&mut app);
+
+        app.on_cursor_down();
+        app.on_delete();
+        app.pre_render();
+        assert!(app.ui_config.confirming_deletion);
+        assert!(!app.ui_config.flashing);
+        cleanup_testing_files(postfix);
+    }
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "archives")]
+pub mod common;
+
+use crate::common::*;
+use std::fs::{self, File};
+use std::io::Write;
+use wiper::app::App;
+use wiper::fs::{DataStore, DataStoreType, FolderEntryType};
+
+mod archives {
+    use super::*;
+
+    fn create_zip(path: &str) {
+        let mut writer = zip::ZipWriter::new(File::create(path).unwrap());
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Stored);
+        writer.add_directory("empty/", options).unwrap();
+        writer.start_file("root.txt", options).unwrap();
+        writer.write_all(&[1; 100]).unwrap();
+        writer.start_file("nested/inner.txt", options).unwrap();
+        writer.write_all(&[1; 300]).unwrap();
+        writer.finish().unwrap();
+    }
+
+    fn create_tar(path: &str) {
+        let mut builder = tar::Builder::new(File::create(path).unwrap());
+        let data = [1; 200];
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "nested/inner.txt", &data[..])
+            .unwrap();
+        builder.finish().unwrap();
+    }
+
+    fn setup(postfix: &str, archive: &str) -> App<DataStoreType> {
+        let path = format!("{}_{}", TEST_FILE_PATH_EDIT, postfix);
+        fs::create_dir_all(&path).unwrap();
+        match archive {
+            "archive.zip" => create_zip(&format!("{}/{}", path, archive)),
+            _ => create_tar(&format!("{}/{}", path, archive)),
+        }
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+        app.store
+            .get_current_folder_mut()
+            .unwrap()
+            .select_name(archive.as_ref());
+        app
+    }
+
+    #[test]
+    fn lists_zip_contents_as_read_only_folder() {
+        let postfix = "24";
+        let mut app = setup(postfix, "archive.zip");
+
+        app.on_enter();
+        handle_tasks_synchronously(&mut app);
+
+        assert!(app.store.get_current_path().ends_with("archive.zip"));
+        let folder = get_current_folder(&app).unwrap();
+        assert!(folder.read_only);
+        assert_eq!(folder.total_size(), 400);
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::Folder).len(), 2);
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::File).len(), 1);
+
+        app.store
+            .get_current_folder_mut()
+            .unwrap()
+            .select_name("nested".as_ref());
+        app.on_enter();
+        assert_eq!(get_current_folder(&app).unwrap().total_size(), 300);
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn lists_tar_contents() {
+        let postfix = "25";
+        let mut app = setup(postfix, "archive.tar");
+
+        app.on_enter();
+        handle_tasks_synchronously(&mut app);
+
+        assert!(app.store.get_current_path().ends_with("archive.tar"));
+        assert_eq!(get_current_folder(&app).unwrap().total_size(), 200);
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn does_not_delete_inside_archive() {
+        let postfix = "26";
+        let mut app = setup(postfix, "archive.zip");
+
+        app.on_enter();
+        app.store
+            .get_current_folder_mut()
+            .unwrap()
+            .select_name("root.txt".as_ref());
+        app.on_delete();
+        app.on_delete();
+
+        assert!(!app.ui_config.confirming_deletion);
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::File).len(), 1);
+        cleanup_testing_files(postfix);
+    }
+}
@@ -0,0 +1,154 @@
+pub mod common;
+
+use crate::common::*;
+use std::fs::File;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use wiper::app::App;
+use wiper::config::{InitConfig, PathStyle};
+use wiper::filter::{MatchMode, NameFilter};
+use wiper::fs::{DataStore, DataStoreType};
+use wiper::report::{BatchDeletion, Outcome, EXIT_DELETION_REFUSED};
+
+mod batch {
+    use super::*;
+
+    fn pick(
+        app: &mut App<DataStoreType>,
+        pattern: &str,
+        older_than: Option<Duration>,
+    ) -> BatchDeletion {
+        app.wait_for_tasks();
+        let filter = NameFilter {
+            query: pattern.to_string(),
+            mode: MatchMode::Glob,
+            ..Default::default()
+        };
+        let root = app.store.get_current_path().clone();
+        BatchDeletion::build(
+            &app.store,
+            &root,
+            &filter,
+            older_than,
+            &[],
+            PathStyle::Absolute,
+        )
+    }
+
+    #[test]
+    fn parses_batch_options() {
+        let args = [
+            "wiper",
+            "--scan",
+            "/tmp",
+            "--delete-matching",
+            "*.tmp",
+            "--older-than",
+            "7d",
+            "--yes",
+        ]
+        .map(String::from);
+        let config = InitConfig::build(args.into_iter()).unwrap();
+        assert_eq!(config.file_path.as_deref(), Some("/tmp"));
+        assert_eq!(config.delete_matching.as_deref(), Some("*.tmp"));
+        assert_eq!(
+            config.older_than,
+            Some(Duration::from_secs(7 * 24 * 60 * 60))
+        );
+        assert!(config.yes);
+        assert!(config.is_batch_deletion());
+    }
+
+    #[test]
+    fn lists_matching_files_without_deleting_them() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        let batch = pick(&mut app, "*_file1.txt", None);
+
+        assert!(batch.dry_run);
+        let titles: Vec<&str> = batch
+            .files
+            .iter()
+            .map(|file| Path::new(&file.path).file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(titles.len(), 3);
+        assert!(titles.contains(&"folder1_file1.txt"));
+
+        let mut output = vec![];
+        batch.write(&mut output, false).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("would delete "));
+        assert!(output.contains("3 files, would free "));
+        assert!(output.ends_with("pass --yes to delete them\n"));
+    }
+
+    #[test]
+    fn deletes_only_old_matching_files() {
+        let postfix = "34";
+        create_testing_files(postfix);
+        let root = format!("{}_{}", TEST_FILE_PATH_EDIT, postfix);
+        let old_file = format!("{}/folder_to_delete_1/file_to_delete_2.txt", root);
+        File::options()
+            .write(true)
+            .open(&old_file)
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(10 * 24 * 60 * 60))
+            .unwrap();
+
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        let mut batch = pick(&mut app, "*_2.txt", Some(Duration::from_secs(24 * 60 * 60)));
+        assert_eq!(batch.files.len(), 1);
+
+        batch.delete(&app.ui_config);
+        assert!(!batch.dry_run);
+        assert!(!batch.has_errors());
+        assert!(!Path::new(&old_file).exists());
+        assert!(Path::new(&format!("{}/file_to_delete_2.txt", root)).exists());
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn refuses_files_matching_danger_patterns() {
+        let postfix = "79";
+        create_testing_files(postfix);
+        let root = format!("{}_{}", TEST_FILE_PATH_EDIT, postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        app.wait_for_tasks();
+        let filter = NameFilter {
+            query: "file_to_delete_1.txt".to_string(),
+            mode: MatchMode::Glob,
+            ..Default::default()
+        };
+        let current_path = app.store.get_current_path().clone();
+        let mut batch = BatchDeletion::build(
+            &app.store,
+            &current_path,
+            &filter,
+            None,
+            &["*/folder_to_delete_1/*".to_string()],
+            PathStyle::Absolute,
+        );
+        assert_eq!(batch.files.len(), 3);
+
+        batch.delete(&app.ui_config);
+        assert!(batch.has_refused());
+        assert!(!batch.has_errors());
+        assert!(!Path::new(&format!("{}/file_to_delete_1.txt", root)).exists());
+        assert!(Path::new(&format!("{}/folder_to_delete_1/file_to_delete_1.txt", root)).exists());
+
+        let mut output = vec![];
+        batch.write(&mut output, false).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(
+            output
+                .matches("refused (matches danger pattern */folder_to_delete_1/*)")
+                .count(),
+            2
+        );
+        let outcome = Outcome {
+            deletion_refused: batch.has_refused(),
+            ..Outcome::default()
+        };
+        assert_eq!(outcome.exit_code(), EXIT_DELETION_REFUSED);
+        cleanup_testing_files(postfix);
+    }
+}
@@ -0,0 +1,77 @@
+pub mod common;
+
+use crate::common::*;
+use std::time::Duration;
+use wiper::config::InitConfig;
+use wiper::report::ScanBench;
+
+mod bench {
+    use super::*;
+
+    fn build(args: &[&str]) -> Result<InitConfig, &'static str> {
+        InitConfig::build(
+            std::iter::once("wiper")
+                .chain(args.iter().copied())
+                .map(String::from),
+        )
+    }
+
+    #[test]
+    fn scans_path_every_run() {
+        let bench = ScanBench::run(&InitConfig::default(), TEST_FILE_PATH_VIEW, 3);
+
+        assert_eq!(bench.runs.len(), 3);
+        assert_eq!(bench.files, 11);
+        assert_eq!(bench.size, 15124);
+        assert!(!bench.read_errors);
+        let (min, median, max) = bench.stats();
+        assert!(min <= median && median <= max);
+    }
+
+    #[test]
+    fn takes_median_of_runs() {
+        let bench = ScanBench {
+            path: "/tmp".to_string(),
+            runs: [30, 10, 20].map(Duration::from_millis).to_vec(),
+            files: 100,
+            directories: 2,
+            size: 1 << 20,
+            read_errors: false,
+        };
+
+        let (min, median, max) = bench.stats();
+        assert_eq!(min, Duration::from_millis(10));
+        assert_eq!(median, Duration::from_millis(20));
+        assert_eq!(max, Duration::from_millis(30));
+        let (files_per_sec, mib_per_sec) = bench.throughput();
+        assert_eq!(files_per_sec.round(), 5000.0);
+        assert_eq!(mib_per_sec.round(), 50.0);
+    }
+
+    #[test]
+    fn writes_json() {
+        let bench = ScanBench::run(&InitConfig::default(), TEST_FILE_PATH_VIEW, 2);
+        let mut output = vec![];
+        bench.write(&mut output, true).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+        assert_eq!(json["runs"], 2);
+        assert_eq!(json["files"], 11);
+        assert_eq!(json["size"], 15124);
+        assert!(json["median_ms"].as_f64().unwrap() >= 0.0);
+    }
+
+    #[test]
+    fn parses_bench_options() {
+        let config = build(&["--bench-scan", "/tmp", "--bench-runs", "3"]).unwrap();
+        assert_eq!(config.bench_scan.as_deref(), Some("/tmp"));
+        assert_eq!(config.bench_runs, Some(3));
+
+        assert!(build(&["--bench-scan"]).is_err());
+        assert!(build(&["--bench-scan", "/tmp", "--bench-runs", "0"]).is_err());
+        assert_eq!(
+            build(&["--bench-runs", "3"]).unwrap_err(),
+            "--bench-runs needs --bench-scan"
+        );
+    }
+}
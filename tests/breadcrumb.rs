@@ -0,0 +1,104 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use wiper::app::App;
+use wiper::events::handle_key_events;
+use wiper::fs::{DataStore, DataStoreType};
+use wiper::ui::{format_breadcrumb, format_file_size, format_short_count};
+
+mod breadcrumb {
+    use super::*;
+
+    fn crumbs() -> Vec<(String, Option<u64>, Option<usize>)> {
+        vec![
+            ("home".to_string(), Some(1 << 40), Some(40)),
+            ("Projects".to_string(), Some(340 << 30), Some(212)),
+            ("archive".to_string(), None, None),
+            ("app".to_string(), Some(12 << 30), Some(1234)),
+        ]
+    }
+
+    #[test]
+    fn lists_every_folder_with_its_size_and_count() {
+        let text = format_breadcrumb(&crumbs(), 200);
+        assert_eq!(
+            text,
+            format!(
+                "home ({}, 40) / Projects ({}, 212) / archive (N/A) / app ({}, 1.2k)",
+                format_file_size(1 << 40),
+                format_file_size(340 << 30),
+                format_file_size(12 << 30)
+            )
+        );
+    }
+
+    #[test]
+    fn elides_middle_titles_keeping_sizes() {
+        let full = format_breadcrumb(&crumbs(), 200);
+        let text = format_breadcrumb(&crumbs(), full.chars().count() - 1);
+        assert!(text.starts_with("home ("), "{text}");
+        assert!(
+            text.contains(" / … (") && text.contains(" / archive ("),
+            "{text}"
+        );
+        assert!(
+            text.contains(&format!("{}, 212", format_file_size(340 << 30))),
+            "{text}"
+        );
+
+        // Root and current folder stay even when nothing fits
+        let text = format_breadcrumb(&crumbs(), 10);
+        assert!(text.starts_with("home ("), "{text}");
+        assert!(text.contains(" / … (N/A) / app ("), "{text}");
+    }
+
+    #[test]
+    fn follows_navigation_when_toggled() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        // Entry after ".." is b_folder with the default sort by size
+        app.on_cursor_down();
+        let title = get_current_folder(&app)
+            .unwrap()
+            .get_selected_entry()
+            .title
+            .clone();
+        app.on_enter();
+        handle_tasks_synchronously(&mut app);
+
+        let current = app.store.get_current_path().clone();
+        let breadcrumb = app.breadcrumb(&current);
+        assert_eq!(breadcrumb.len(), 2);
+        assert_eq!(breadcrumb[0], ("view".to_string(), Some(15124), Some(6)));
+        assert_eq!(breadcrumb[1].0, title);
+
+        handle_key_events(
+            KeyEvent::new(KeyCode::Char('W'), KeyModifiers::NONE),
+            &mut app,
+        )
+        .unwrap();
+        assert!(app.ui_config.breadcrumb);
+        let area = Rect::new(0, 0, 120, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        let text: String = buf.content.iter().map(|cell| cell.symbol()).collect();
+        assert!(
+            text.contains(&format!(
+                "view ({}, 6) / {title} (",
+                format_file_size(15124)
+            )),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn shortens_large_counts() {
+        assert_eq!(format_short_count(999), "999");
+        assert_eq!(format_short_count(1234), "1.2k");
+        assert_eq!(format_short_count(3_400_000), "3.4M");
+    }
+}
@@ -0,0 +1,64 @@
+pub mod common;
+
+use crate::common::*;
+use std::path::{Path, PathBuf};
+use wiper::config::InitConfig;
+use wiper::prompt::{default_broad_paths, is_broad_root};
+
+mod broad_root {
+    use super::*;
+
+    fn build(args: &[&str]) -> Result<InitConfig, &'static str> {
+        InitConfig::build(
+            std::iter::once("wiper")
+                .chain(args.iter().copied())
+                .map(String::from),
+        )
+    }
+
+    #[test]
+    fn listed_path_is_broad() {
+        let root = Path::new(TEST_FILE_PATH_VIEW);
+        let listed = [PathBuf::from("./tests/test_files/../test_files/view")];
+        assert!(is_broad_root(root, &listed, usize::MAX));
+        assert!(!is_broad_root(root, &[PathBuf::from("/")], usize::MAX));
+    }
+
+    #[test]
+    fn many_entries_make_root_broad() {
+        // Six entries right below the view root
+        let root = Path::new(TEST_FILE_PATH_VIEW);
+        assert!(is_broad_root(root, &[], 5));
+        assert!(!is_broad_root(root, &[], 6));
+    }
+
+    #[test]
+    fn filesystem_root_is_broad_by_default() {
+        assert!(default_broad_paths().contains(&PathBuf::from("/")));
+        assert!(is_broad_root(
+            Path::new("/"),
+            &default_broad_paths(),
+            usize::MAX
+        ));
+    }
+
+    #[test]
+    fn parses_broad_options() {
+        let config = build(&[
+            "--broad-path",
+            "/data",
+            "--broad-path",
+            "/srv",
+            "--broad-entries",
+            "100",
+            TEST_FILE_PATH_VIEW,
+        ])
+        .unwrap();
+        assert_eq!(config.broad_paths, vec!["/data", "/srv"]);
+        assert_eq!(config.broad_entries, Some(100));
+        assert!(!config.no_broad_check);
+        assert!(build(&["--no-broad-check"]).unwrap().no_broad_check);
+        assert!(build(&["--broad-entries", "0"]).is_err());
+        assert!(build(&["--broad-path"]).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+pub mod common;
+
+use crate::common::*;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{palette::tailwind, Color};
+use ratatui::widgets::Widget;
+use wiper::app::App;
+use wiper::config::{ColorDepth, InitConfig};
+use wiper::fs::DataStoreType;
+use wiper::ui::constants::{TEXT_PRE_DELETED_BG, TEXT_SELECTED_BG};
+use wiper::ui::reduce_color;
+
+mod colors {
+    use super::*;
+
+    fn render_colors(app: &mut App<DataStoreType>) -> Vec<Color> {
+        let area = Rect::new(0, 0, 120, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content
+            .iter()
+            .flat_map(|cell| [cell.fg, cell.bg])
+            .collect()
+    }
+
+    #[test]
+    fn detects_depth_from_environment() {
+        let detect = ColorDepth::detect;
+        assert_eq!(
+            detect(Some("truecolor"), Some("xterm-256color")),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(detect(None, Some("screen-256color")), ColorDepth::Ansi256);
+        assert_eq!(detect(None, Some("linux")), ColorDepth::Ansi16);
+        assert_eq!(detect(None, Some("rxvt-16color")), ColorDepth::Ansi16);
+        assert_eq!(detect(None, Some("xterm")), ColorDepth::TrueColor);
+        assert_eq!(detect(None, None), ColorDepth::TrueColor);
+    }
+
+    #[test]
+    fn parses_colors_option() {
+        let build = |depth: &str| {
+            InitConfig::build(
+                ["wiper", "--colors", depth]
+                    .iter()
+                    .map(|arg| arg.to_string()),
+            )
+        };
+        assert_eq!(build("16").unwrap().color_depth, Some(ColorDepth::Ansi16));
+        assert_eq!(build("256").unwrap().color_depth, Some(ColorDepth::Ansi256));
+        assert_eq!(build("auto").unwrap().color_depth, None);
+        assert!(build("8").is_err());
+    }
+
+    #[test]
+    fn maps_to_nearest_palette_entry() {
+        let ansi256 = |color| reduce_color(color, ColorDepth::Ansi256);
+        assert_eq!(ansi256(Color::Rgb(255, 0, 0)), Color::Indexed(196));
+        assert_eq!(ansi256(Color::Rgb(0, 0, 0)), Color::Indexed(16));
+        assert_eq!(ansi256(Color::Rgb(128, 128, 128)), Color::Indexed(244));
+
+        let ansi16 = |color| reduce_color(color, ColorDepth::Ansi16);
+        assert_eq!(ansi16(tailwind::BLUE.c500), Color::LightBlue);
+        assert_eq!(ansi16(Color::Rgb(250, 250, 250)), Color::White);
+        // Named colors are already within every depth
+        assert_eq!(ansi16(Color::Reset), Color::Reset);
+        assert_eq!(
+            reduce_color(TEXT_SELECTED_BG, ColorDepth::TrueColor),
+            TEXT_SELECTED_BG
+        );
+    }
+
+    #[test]
+    fn keeps_states_apart_with_16_colors() {
+        let ansi16 = |color| reduce_color(color, ColorDepth::Ansi16);
+        assert_eq!(ansi16(TEXT_SELECTED_BG), Color::DarkGray);
+        assert_eq!(ansi16(TEXT_PRE_DELETED_BG), Color::Red);
+    }
+
+    #[test]
+    fn renders_without_rgb_colors_in_reduced_depth() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        app.ui_config.color_depth = ColorDepth::TrueColor;
+        let is_rgb = |color: &Color| matches!(color, Color::Rgb(..));
+        assert!(render_colors(&mut app).iter().any(is_rgb));
+
+        app.ui_config.color_depth = ColorDepth::Ansi256;
+        let colors = render_colors(&mut app);
+        assert!(!colors.iter().any(is_rgb));
+        assert!(colors
+            .iter()
+            .any(|color| matches!(color, Color::Indexed(..))));
+
+        app.ui_config.color_depth = ColorDepth::Ansi16;
+        let colors = render_colors(&mut app);
+        assert!(!colors.iter().any(is_rgb));
+        assert!(colors.contains(&Color::DarkGray));
+    }
+}
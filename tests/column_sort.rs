@@ -0,0 +1,159 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use wiper::app::App;
+use wiper::events::{handle_key_events, handle_mouse_events};
+use wiper::fs::{DataStoreType, SortBy};
+
+mod column_sort {
+    use super::*;
+
+    const WIDTH: u16 = 140;
+
+    fn press(app: &mut App<DataStoreType>, c: char) {
+        handle_key_events(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE), app).unwrap();
+        handle_tasks_synchronously(app);
+    }
+
+    fn render_lines(app: &mut App<DataStoreType>) -> Vec<String> {
+        let area = Rect::new(0, 0, WIDTH, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content
+            .chunks(WIDTH as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect())
+            .collect()
+    }
+
+    fn click(app: &mut App<DataStoreType>, column: u16, row: u16) {
+        let event = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        handle_mouse_events(event, app).unwrap();
+    }
+
+    /// Screen position of text in the table header
+    fn header_position(lines: &[String], text: &str) -> (u16, u16) {
+        let (row, line) = lines
+            .iter()
+            .enumerate()
+            .find(|(_, line)| line.contains("Name"))
+            .unwrap();
+        let column = line[..line.find(text).unwrap()].chars().count();
+        (column as u16, row as u16)
+    }
+
+    fn titles(app: &App<DataStoreType>) -> Vec<String> {
+        get_current_folder(app)
+            .unwrap()
+            .entries()
+            .iter()
+            .map(|entry| entry.title.clone())
+            .collect()
+    }
+
+    #[test]
+    fn sorts_by_size_and_reverses_on_second_press() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        press(&mut app, 'z');
+        assert_root_view_folder_sorted_by_size(&app);
+
+        press(&mut app, 'z');
+        assert!(app.ui_config.sort_reversed);
+        let lines = render_lines(&mut app);
+        assert!(lines.iter().any(|line| line.contains("Size ↑")));
+        assert_eq!(
+            titles(&app),
+            vec![
+                "..",
+                "a_root_file.txt",
+                "z_root_file.txt",
+                "d_root_file.txt",
+                "a_folder",
+                "b_folder",
+                "c_folder",
+            ]
+        );
+    }
+
+    #[test]
+    fn reverses_names() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        press(&mut app, 'n');
+        let lines = render_lines(&mut app);
+        assert!(lines.iter().any(|line| line.contains("Name ↑")));
+        assert_eq!(
+            titles(&app),
+            vec![
+                "..",
+                "c_folder",
+                "b_folder",
+                "a_folder",
+                "z_root_file.txt",
+                "d_root_file.txt",
+                "a_root_file.txt",
+            ]
+        );
+
+        // Sort menu starts from current sorting
+        press(&mut app, 's');
+        let menu = app.sort_menu.as_ref().unwrap();
+        assert_eq!(menu.sort_by, SortBy::Title);
+        assert!(menu.reversed);
+    }
+
+    #[test]
+    fn sorts_by_number_of_entries() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        press(&mut app, '#');
+        let lines = render_lines(&mut app);
+        assert!(lines.iter().any(|line| line.contains("Name (entries ↓)")));
+        assert_eq!(
+            titles(&app),
+            vec![
+                "..",
+                "b_folder",
+                "c_folder",
+                "a_folder",
+                "a_root_file.txt",
+                "d_root_file.txt",
+                "z_root_file.txt",
+            ]
+        );
+    }
+
+    #[test]
+    fn clicking_header_sorts_by_its_column() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        let lines = render_lines(&mut app);
+        let (column, row) = header_position(&lines, "Size");
+        click(&mut app, column + 1, row);
+        assert_eq!(app.ui_config.sort_by, SortBy::Size);
+        assert!(!app.ui_config.sort_reversed);
+
+        let lines = render_lines(&mut app);
+        let (column, row) = header_position(&lines, "Name");
+        click(&mut app, column, row);
+        assert_eq!(app.ui_config.sort_by, SortBy::Title);
+
+        // Rows below the header are not headers
+        click(&mut app, column, row + 1);
+        assert_eq!(app.ui_config.sort_by, SortBy::Title);
+        assert!(!app.ui_config.sort_reversed);
+    }
+}
@@ -0,0 +1,126 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use wiper::app::App;
+use wiper::config::{Column, EnterAction, InitConfig};
+use wiper::events::handle_mouse_events;
+use wiper::fs::{DataStoreType, SortBy};
+
+mod columns {
+    use super::*;
+
+    const WIDTH: u16 = 140;
+
+    fn build(args: &[&str]) -> Result<InitConfig, &'static str> {
+        InitConfig::build(
+            std::iter::once("wiper")
+                .chain(args.iter().copied())
+                .map(String::from),
+        )
+    }
+
+    /// View fixture sorted by name, with provided columns
+    fn setup_app(columns: &[Column]) -> App<DataStoreType> {
+        let config = InitConfig {
+            file_path: Some(TEST_FILE_PATH_VIEW.to_string()),
+            columns: Some(columns.to_vec()),
+            ..Default::default()
+        };
+        let mut app: App<DataStoreType> = App::new(config);
+        app.ui_config.enter_action = EnterAction::Nothing;
+        app.ui_config.sort_by = SortBy::Title;
+        app.init();
+        handle_tasks_synchronously(&mut app);
+        app
+    }
+
+    /// Table header, the line holding "Name"
+    fn render_header(app: &mut App<DataStoreType>) -> (String, u16) {
+        let area = Rect::new(0, 0, WIDTH, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        let lines: Vec<String> = buf
+            .content
+            .chunks(WIDTH as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect())
+            .collect();
+        let row = lines.iter().position(|line| line.contains("Name")).unwrap();
+        (lines[row].clone(), row as u16)
+    }
+
+    /// Screen column of text in header
+    fn position(header: &str, text: &str) -> usize {
+        header[..header.find(text).unwrap()].chars().count()
+    }
+
+    #[test]
+    fn parses_columns() {
+        let config = build(&["--columns", "size, name,age", TEST_FILE_PATH_VIEW]).unwrap();
+        assert_eq!(
+            config.columns,
+            Some(vec![Column::Size, Column::Name, Column::Age])
+        );
+        assert_eq!(build(&[TEST_FILE_PATH_VIEW]).unwrap().columns, None);
+
+        assert_eq!(
+            build(&["--columns", "size,age"]).unwrap_err(),
+            "--columns must include name"
+        );
+        assert!(build(&["--columns", "name,size,name"]).is_err());
+        assert!(build(&["--columns", "name,color"]).is_err());
+        assert!(build(&["--columns"]).is_err());
+    }
+
+    #[test]
+    fn adds_columns_switched_on_by_other_options() {
+        let config = build(&["--columns", "name,space", "--both-sizes"]).unwrap();
+        assert_eq!(
+            config.table_columns(),
+            vec![Column::Name, Column::OtherSize, Column::Space]
+        );
+        assert_eq!(build(&[]).unwrap().table_columns(), Column::DEFAULT);
+    }
+
+    #[test]
+    fn orders_header_by_columns() {
+        let mut app = setup_app(&[Column::Size, Column::Name, Column::Age]);
+        assert!(app.ui_config.show_age);
+
+        let (header, _) = render_header(&mut app);
+        assert!(position(&header, "Size") < position(&header, "Name"));
+        assert!(position(&header, "Name") < position(&header, "Age"));
+        assert!(!header.contains("Space"));
+    }
+
+    #[test]
+    fn toggled_column_joins_before_space_bars() {
+        let mut app = setup_app(&[Column::Name, Column::Size, Column::Space]);
+        assert!(!render_header(&mut app).0.contains("Age"));
+
+        app.on_toggle_age_column();
+        assert_eq!(
+            app.ui_config.columns,
+            vec![Column::Name, Column::Size, Column::Age, Column::Space]
+        );
+        let (header, _) = render_header(&mut app);
+        assert!(position(&header, "Age") < position(&header, "Space"));
+    }
+
+    #[test]
+    fn sorts_by_clicked_column_wherever_it_is() {
+        let mut app = setup_app(&[Column::Size, Column::Name]);
+        let (header, row) = render_header(&mut app);
+        let event = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: position(&header, "Size") as u16,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        handle_mouse_events(event, &mut app).unwrap();
+        assert_eq!(app.ui_config.sort_by, SortBy::Size);
+    }
+}
@@ -1,17 +1,80 @@
+// Lints in helpers kept as they were written
+#![allow(
+    clippy::needless_borrow,
+    clippy::to_string_in_format_args,
+    clippy::useless_format
+)]
+
+use std::fs::{self, File};
+use std::io::Write;
 use std::thread;
 use std::time::Duration;
 use wiper::app::App;
-use wiper::config::InitConfig;
+use wiper::config::{EnterAction, InitConfig};
 use wiper::fs::{DataStore, DataStoreKey, Folder, FolderEntry, FolderEntryType, SortBy};
 
 pub const TEST_FILE_PATH_VIEW: &str = "./tests/test_files/view";
 pub const TEST_FILE_PATH_EDIT: &str = "./tests/test_files/edit";
+pub const TEST_FILE_SIZE: usize = 446;
+
+pub fn generate_lorem_ipsum() -> String {
+    String::from(
+        "Lorem ipsum dolor sit amet, consectetur adipiscing elit. \
+Sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. \
+Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi \
+ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit \
+in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur \
+sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt \
+mollit anim id est laborum.",
+    )
+}
+
+/// - folder_1
+///     - folder_2
+///         - folder_3
+///         - file_1
+///         - file_2
+///         - file_3
+///     - file_1
+///     - file_2
+///     - file_3
+/// - file_1
+/// - file_2
+/// - file_3
+pub fn create_testing_files(postfix: &str) {
+    let custom_folder = format!("{}_{}", TEST_FILE_PATH_EDIT, postfix);
+    fs::create_dir_all(&custom_folder).expect("Failed to create test folder");
+
+    let mut folder_path = format!("{}", custom_folder);
+
+    for folder_index in 1..4 {
+        for file_index in 1..4 {
+            let file_name = format!("file_to_delete_{}.txt", file_index);
+            let file_path = format!("{}/{}", folder_path, file_name);
+            let mut file = File::create(&file_path).expect("Failed to create test file");
+            writeln!(file, "{}", generate_lorem_ipsum()).expect("Failed to write to test file");
+        }
+
+        folder_path = format!("{}/folder_to_delete_{}", folder_path, folder_index);
+
+        fs::create_dir_all(&folder_path).expect("Failed to create test folder");
+    }
+}
+
+pub fn cleanup_testing_files(postfix: &str) {
+    let custom_folder = format!("{}_{}", TEST_FILE_PATH_EDIT, postfix);
+    if let Err(err) = fs::remove_dir_all(custom_folder) {
+        eprintln!("Failed to remove test folder: {}", err);
+    }
+}
+
 pub fn setup_app_view<S: DataStore<DataStoreKey>>() -> App<S> {
     let c = InitConfig {
         file_path: Some(TEST_FILE_PATH_VIEW.to_string()),
+        ..Default::default()
     };
     let mut app: App<S> = App::new(c);
-    app.ui_config.open_file = false;
+    app.ui_config.enter_action = EnterAction::Nothing;
     app.ui_config.sort_by = SortBy::Title;
     app.init();
     app
@@ -20,15 +83,28 @@ pub fn setup_app_view<S: DataStore<DataStoreKey>>() -> App<S> {
 pub fn setup_app_edit<S: DataStore<DataStoreKey>>(postfix: &str) -> App<S> {
     let c = InitConfig {
         file_path: Some(format!("{}_{}", TEST_FILE_PATH_EDIT.to_string(), postfix)),
+        ..Default::default()
     };
     let mut app: App<S> = App::new(c);
-    app.ui_config.open_file = false;
+    app.ui_config.enter_action = EnterAction::Nothing;
     app.ui_config.move_to_trash = false;
     app.ui_config.sort_by = SortBy::Title;
     app.init();
     app
 }
 
+pub fn setup_app_roots<S: DataStore<DataStoreKey>>(paths: &[&str]) -> App<S> {
+    let c = InitConfig {
+        root_paths: paths.iter().map(|path| path.to_string()).collect(),
+        ..Default::default()
+    };
+    let mut app: App<S> = App::new(c);
+    app.ui_config.enter_action = EnterAction::Nothing;
+    app.ui_config.sort_by = SortBy::Title;
+    app.init();
+    app
+}
+
 pub fn handle_tasks_synchronously<S: DataStore<DataStoreKey>>(app: &mut App<S>) {
     while !app.task_manager.is_done() {
         app.tick();
@@ -46,7 +122,7 @@ pub fn assert_item_at_index_is<S: DataStore<DataStoreKey>>(
         app.store
             .get_current_folder()
             .unwrap()
-            .entries
+            .entries()
             .get(index)
             .unwrap()
             .kind,
@@ -63,7 +139,7 @@ pub fn assert_item_at_index_title<S: DataStore<DataStoreKey>>(
         app.store
             .get_current_folder()
             .unwrap()
-            .entries
+            .entries()
             .get(index)
             .unwrap()
             .title,
@@ -80,7 +156,7 @@ pub fn assert_item_at_index_loading_state<S: DataStore<DataStoreKey>>(
         app.store
             .get_current_folder()
             .unwrap()
-            .entries
+            .entries()
             .get(index)
             .unwrap()
             .is_loaded,
@@ -95,7 +171,7 @@ pub fn get_entry_by_kind<S: DataStore<DataStoreKey>>(
     app.store
         .get_current_folder()
         .unwrap()
-        .entries
+        .entries()
         .iter()
         .filter(|e| e.kind == kind)
         .cloned()
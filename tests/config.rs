@@ -0,0 +1,143 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+use wiper::config::{EnterAction, ExportFormat, InitConfig, SizeUnit};
+use wiper::fs::DataStoreType;
+
+mod config {
+    use super::*;
+
+    fn build(args: &[&str]) -> Result<InitConfig, &'static str> {
+        InitConfig::build(
+            std::iter::once("wiper")
+                .chain(args.iter().copied())
+                .map(String::from),
+        )
+    }
+
+    #[test]
+    fn uses_default_highlight_symbol() {
+        let app: App<DataStoreType> = setup_app_view();
+        assert_eq!(app.ui_config.highlight_symbol, "> ");
+    }
+
+    #[test]
+    fn separates_custom_highlight_symbol_from_first_column() {
+        let config = build(&["--highlight-symbol", "→", TEST_FILE_PATH_VIEW]).unwrap();
+        let app: App<DataStoreType> = App::new(config);
+        assert_eq!(app.ui_config.highlight_symbol, "→ ");
+    }
+
+    #[test]
+    fn allows_empty_highlight_symbol() {
+        let config = build(&["--highlight-symbol", "", TEST_FILE_PATH_VIEW]).unwrap();
+        let app: App<DataStoreType> = App::new(config);
+        assert_eq!(app.ui_config.highlight_symbol, "");
+    }
+
+    #[test]
+    fn fails_without_highlight_symbol_value() {
+        assert!(build(&["--highlight-symbol"]).is_err());
+    }
+
+    #[test]
+    fn scans_whole_tree_with_low_priority() {
+        let config = build(&["--low-priority", TEST_FILE_PATH_VIEW]).unwrap();
+        assert!(config.low_priority);
+        let mut app: App<DataStoreType> = App::new(config);
+        app.init();
+        handle_tasks_synchronously(&mut app);
+        assert!(*app.task_manager.low_priority.lock().unwrap());
+        assert_eq!(get_current_folder(&app).unwrap().get_size(), 15124);
+    }
+
+    #[test]
+    fn parses_enter_action() {
+        let config = build(&["--enter-action", "copy", TEST_FILE_PATH_VIEW]).unwrap();
+        let app: App<DataStoreType> = App::new(config);
+        assert_eq!(app.ui_config.enter_action, EnterAction::CopyPath);
+
+        let app: App<DataStoreType> = App::new(build(&[TEST_FILE_PATH_VIEW]).unwrap());
+        assert_eq!(app.ui_config.enter_action, EnterAction::Open);
+        assert!(build(&["--enter-action", "print"]).is_err());
+    }
+
+    #[test]
+    fn parses_size_unit() {
+        let config = build(&["--size-unit", "MiB", "--align-sizes", TEST_FILE_PATH_VIEW]).unwrap();
+        let app: App<DataStoreType> = App::new(config);
+        assert_eq!(app.ui_config.size_unit, SizeUnit::MB);
+        assert!(app.ui_config.align_sizes);
+
+        let app: App<DataStoreType> = App::new(build(&[TEST_FILE_PATH_VIEW]).unwrap());
+        assert_eq!(app.ui_config.size_unit, SizeUnit::Auto);
+        assert!(!app.ui_config.align_sizes);
+        assert!(build(&["--size-unit", "tb"]).is_err());
+    }
+
+    #[test]
+    fn parses_inline() {
+        assert!(build(&["--inline", TEST_FILE_PATH_VIEW]).unwrap().inline);
+        assert!(!build(&[TEST_FILE_PATH_VIEW]).unwrap().inline);
+    }
+
+    #[test]
+    fn parses_wrap_navigation() {
+        assert!(
+            build(&["--wrap-navigation", TEST_FILE_PATH_VIEW])
+                .unwrap()
+                .wrap_navigation
+        );
+        assert!(!build(&[TEST_FILE_PATH_VIEW]).unwrap().wrap_navigation);
+    }
+
+    #[test]
+    fn parses_both_sizes() {
+        assert!(
+            build(&["--both-sizes", TEST_FILE_PATH_VIEW])
+                .unwrap()
+                .both_sizes
+        );
+        assert!(!build(&[TEST_FILE_PATH_VIEW]).unwrap().both_sizes);
+    }
+
+    #[test]
+    fn parses_folders_first() {
+        let config = build(&["--folders-first", TEST_FILE_PATH_VIEW]).unwrap();
+        assert!(config.folders_first);
+        assert!(!build(&[TEST_FILE_PATH_VIEW]).unwrap().folders_first);
+    }
+
+    #[test]
+    fn parses_errors_only() {
+        assert!(
+            build(&["--errors-only", TEST_FILE_PATH_VIEW])
+                .unwrap()
+                .errors_only
+        );
+        assert!(!build(&[TEST_FILE_PATH_VIEW]).unwrap().errors_only);
+    }
+
+    #[test]
+    fn parses_output_format() {
+        let config = build(&["--output-format", "ncdu", "--output", "scan.json", "."]).unwrap();
+        assert_eq!(config.output_format, Some(ExportFormat::Ncdu));
+        assert_eq!(config.output.as_deref(), Some("scan.json"));
+
+        let config = build(&["--output-format", "tree", "."]).unwrap();
+        assert_eq!(config.output_format, Some(ExportFormat::Tree));
+        assert_eq!(config.output, None);
+
+        // Older flag picks CSV
+        let config = build(&["--csv", "-", "."]).unwrap();
+        assert_eq!(config.output_format, Some(ExportFormat::Csv));
+        assert_eq!(config.output.as_deref(), Some("-"));
+
+        assert!(build(&["--output-format", "xml"]).is_err());
+        assert!(build(&["--output", "scan.json"]).is_err());
+
+        let config = build(&["--output-format", "csv", "--export-visible", "."]).unwrap();
+        assert!(config.export_visible);
+        assert!(build(&["--export-visible", "."]).is_err());
+    }
+}
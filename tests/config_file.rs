@@ -0,0 +1,113 @@
+pub mod common;
+use crate::common::*;
+use std::fs::{create_dir_all, remove_dir_all, write};
+use std::path::PathBuf;
+use wiper::config::{config_file_args, InitConfig, SizeUnit};
+
+mod config_file {
+    use super::*;
+
+    fn load(args: &[&str]) -> Result<InitConfig, String> {
+        InitConfig::load(
+            std::iter::once("wiper")
+                .chain(args.iter().copied())
+                .map(String::from),
+        )
+    }
+
+    /// Folder of its own for every test, so they don't share files
+    fn config_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("wiper_config_{name}"));
+        let _ = remove_dir_all(&dir);
+        create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn splits_lines_into_args() {
+        let args = config_file_args(
+            "# Sizes in megabytes\n\n--size-unit mb\n  --wrap-navigation  \n--highlight-symbol > \n",
+        )
+        .unwrap();
+        assert_eq!(
+            args,
+            vec![
+                "--size-unit",
+                "mb",
+                "--wrap-navigation",
+                "--highlight-symbol",
+                ">"
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_lines_that_are_not_options() {
+        let err = config_file_args("--size-unit mb\n/home/me\n").unwrap_err();
+        assert!(err.contains("`/home/me`"), "{err}");
+
+        let dir = config_dir("positional");
+        let path = dir.join("config");
+        write(&path, "size-unit mb\n").unwrap();
+        let err = load(&["--config", path.to_str().unwrap()]).unwrap_err();
+        assert!(err.contains("`size-unit mb`"), "{err}");
+        remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn applies_options_from_provided_file() {
+        let dir = config_dir("provided");
+        let path = dir.join("config");
+        write(&path, "--size-unit mb\n--wrap-navigation\n").unwrap();
+
+        let config = load(&["--config", path.to_str().unwrap(), TEST_FILE_PATH_VIEW]).unwrap();
+        assert_eq!(config.size_unit, Some(SizeUnit::MB));
+        assert!(config.wrap_navigation);
+        assert_eq!(config.file_path.as_deref(), Some(TEST_FILE_PATH_VIEW));
+
+        // Command line wins over the file
+        let config = load(&["--config", path.to_str().unwrap(), "--size-unit", "gb"]).unwrap();
+        assert_eq!(config.size_unit, Some(SizeUnit::GB));
+        remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn reports_unusable_config_files() {
+        let dir = config_dir("unusable");
+        let missing = dir.join("missing");
+        let err = load(&["--config", missing.to_str().unwrap()]).unwrap_err();
+        assert!(err.contains(missing.to_str().unwrap()), "{err}");
+
+        let path = dir.join("config");
+        write(&path, "--size-unit\n").unwrap();
+        let err = load(&["--config", path.to_str().unwrap(), "--wrap-navigation"]).unwrap_err();
+        assert!(err.contains("in config file"), "{err}");
+
+        write(&path, "--no-config\n").unwrap();
+        assert!(load(&["--config", path.to_str().unwrap()]).is_err());
+        remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn refuses_config_with_no_config() {
+        assert!(load(&["--config", "config", "--no-config"]).is_err());
+        assert!(load(&["--config"]).is_err());
+    }
+
+    /// The only test reading the default location, so changing environment doesn't race
+    #[test]
+    fn reads_default_file_unless_disabled() {
+        let dir = config_dir("default");
+        std::env::set_var("XDG_CONFIG_HOME", &dir);
+        assert_eq!(load(&[]).unwrap().size_unit, None);
+
+        create_dir_all(dir.join("wiper")).unwrap();
+        write(dir.join("wiper").join("config"), "--size-unit kb\n").unwrap();
+        assert_eq!(load(&[]).unwrap().size_unit, Some(SizeUnit::KB));
+
+        let config = load(&["--no-config"]).unwrap();
+        assert!(config.no_config);
+        assert_eq!(config.size_unit, None);
+        remove_dir_all(&dir).unwrap();
+    }
+}
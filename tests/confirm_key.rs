@@ -0,0 +1,107 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use wiper::app::App;
+use wiper::config::{InitConfig, PromptKey};
+use wiper::events::handle_key_events;
+use wiper::fs::DataStoreType;
+
+mod confirm_key {
+    use super::*;
+
+    fn press(app: &mut App<DataStoreType>, code: KeyCode) {
+        handle_key_events(KeyEvent::new(code, KeyModifiers::NONE), app).unwrap();
+    }
+
+    fn render(app: &mut App<DataStoreType>) -> String {
+        let area = Rect::new(0, 0, 160, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content.iter().map(|cell| cell.symbol()).collect()
+    }
+
+    fn build(args: &[&str]) -> Result<InitConfig, &'static str> {
+        InitConfig::build(
+            std::iter::once("wiper")
+                .chain(args.iter().copied())
+                .map(String::from),
+        )
+    }
+
+    #[test]
+    fn confirms_with_configured_key_only() {
+        let postfix = "59";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        app.ui_config.confirm_key = PromptKey::Char('y');
+        app.ui_config.cancel_key = PromptKey::Char('n');
+        handle_tasks_synchronously(&mut app);
+        app.on_cursor_down();
+
+        press(&mut app, KeyCode::Char('d'));
+        assert!(app.ui_config.confirming_deletion);
+        assert!(render(&mut app).contains("Press y to confirm, n or Esc to cancel"));
+        // `d` no longer confirms
+        press(&mut app, KeyCode::Char('d'));
+        assert!(app.ui_config.confirming_deletion);
+        assert_eq!(app.session_deleted, 0);
+
+        press(&mut app, KeyCode::Char('n'));
+        assert!(!app.ui_config.confirming_deletion);
+
+        press(&mut app, KeyCode::Char('d'));
+        press(&mut app, KeyCode::Char('y'));
+        assert!(!app.ui_config.confirming_deletion);
+        assert_eq!(app.session_deleted, 1);
+
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn confirms_with_enter() {
+        let postfix = "60";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        app.ui_config.confirm_key = PromptKey::Enter;
+        handle_tasks_synchronously(&mut app);
+        app.on_cursor_down();
+
+        press(&mut app, KeyCode::Char('d'));
+        assert!(render(&mut app).contains("Press Enter to confirm, Esc to cancel"));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.session_deleted, 1);
+
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn keeps_d_again_by_default() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        assert_eq!(app.ui_config.confirm_key, PromptKey::Char('d'));
+        assert_eq!(app.ui_config.cancel_key, PromptKey::Esc);
+
+        app.on_cursor_down();
+        press(&mut app, KeyCode::Char('d'));
+        assert!(render(&mut app).contains("Press d again to confirm, Esc to cancel"));
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.ui_config.confirming_deletion);
+    }
+
+    #[test]
+    fn parses_prompt_keys() {
+        let config = build(&["--confirm-key", "y", "--cancel-key", "n", "."]).unwrap();
+        assert_eq!(config.confirm_key, Some(PromptKey::Char('y')));
+        assert_eq!(config.cancel_key, Some(PromptKey::Char('n')));
+        let config = build(&["--confirm-key", "Enter", "."]).unwrap();
+        assert_eq!(config.confirm_key, Some(PromptKey::Enter));
+
+        assert!(build(&["--confirm-key", "yes"]).is_err());
+        assert!(build(&["--confirm-key", "esc"]).is_err());
+        assert!(build(&["--confirm-key", "y", "--cancel-key", "y"]).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use wiper::app::App;
+use wiper::events::handle_key_events;
+use wiper::fs::DataStoreType;
+
+mod confirm_quit {
+    use super::*;
+
+    fn press(app: &mut App<DataStoreType>, code: KeyCode) {
+        handle_key_events(KeyEvent::new(code, KeyModifiers::NONE), app).unwrap();
+    }
+
+    fn render(app: &mut App<DataStoreType>) -> String {
+        let area = Rect::new(0, 0, 140, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content.iter().map(|cell| cell.symbol()).collect()
+    }
+
+    fn setup_app(postfix: &str) -> App<DataStoreType> {
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        app.ui_config.confirm_quit = true;
+        handle_tasks_synchronously(&mut app);
+        app
+    }
+
+    /// Deletes first file of the edit fixture
+    fn delete_file(app: &mut App<DataStoreType>) {
+        app.on_cursor_down();
+        app.on_cursor_down();
+        app.on_delete();
+        app.on_delete();
+        handle_tasks_synchronously(app);
+    }
+
+    #[test]
+    fn quits_at_once_when_nothing_was_deleted() {
+        let postfix = "48";
+        let mut app = setup_app(postfix);
+
+        press(&mut app, KeyCode::Char('q'));
+        assert!(!app.running);
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn asks_again_after_deletions() {
+        let postfix = "49";
+        let mut app = setup_app(postfix);
+        delete_file(&mut app);
+        assert_eq!(app.session_deleted, 1);
+        assert_eq!(app.session_freed, TEST_FILE_SIZE as u64);
+
+        press(&mut app, KeyCode::Char('q'));
+        assert!(app.running);
+        assert!(render(&mut app)
+            .contains("You freed 446 bytes across 1 item this session, press q to confirm exit"));
+
+        press(&mut app, KeyCode::Char('q'));
+        assert!(!app.running);
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn stays_on_any_other_key() {
+        let postfix = "50";
+        let mut app = setup_app(postfix);
+        delete_file(&mut app);
+
+        press(&mut app, KeyCode::Char('q'));
+        press(&mut app, KeyCode::Char('j'));
+        assert!(app.running);
+        assert!(!app.ui_config.confirming_quit);
+        assert!(!render(&mut app).contains("You freed"));
+
+        // Asked again on next quit
+        press(&mut app, KeyCode::Char('q'));
+        assert!(app.running);
+        assert!(app.ui_config.confirming_quit);
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn quits_at_once_without_the_option() {
+        let postfix = "51";
+        let mut app = setup_app(postfix);
+        app.ui_config.confirm_quit = false;
+        delete_file(&mut app);
+
+        press(&mut app, KeyCode::Char('q'));
+        assert!(!app.running);
+        cleanup_testing_files(postfix);
+    }
+}
@@ -0,0 +1,81 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use wiper::app::{App, Launch};
+use wiper::config::{InitConfig, SizeForm};
+use wiper::events::handle_key_events;
+use wiper::fs::DataStoreType;
+
+mod copy_size {
+    use super::*;
+
+    fn press(app: &mut App<DataStoreType>, c: char, modifiers: KeyModifiers) {
+        handle_key_events(KeyEvent::new(KeyCode::Char(c), modifiers), app).unwrap();
+    }
+
+    fn logged(app: &App<DataStoreType>, message: &str) -> bool {
+        app.logger
+            .messages
+            .iter()
+            .any(|logged| logged.message == message)
+    }
+
+    #[test]
+    fn parses_copy_size_form() {
+        let build = |args: &[&str]| {
+            InitConfig::build(
+                std::iter::once("wiper")
+                    .chain(args.iter().copied())
+                    .map(String::from),
+            )
+        };
+        assert_eq!(
+            build(&["--copy-size", "bytes"]).unwrap().copy_size,
+            Some(SizeForm::Bytes)
+        );
+        assert_eq!(build(&[]).unwrap().copy_size, None);
+        assert!(build(&["--copy-size", "kb"]).is_err());
+        assert!(build(&["--copy-size"]).is_err());
+    }
+
+    /// The only test depending on `TERM`, so changing environment doesn't race
+    #[test]
+    fn copies_size_of_selected_entry() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        std::env::set_var("TERM", "xterm-256color");
+
+        // Parent entry has no size of its own
+        press(&mut app, 'Y', KeyModifiers::SHIFT);
+        assert_eq!(app.launch, None);
+        assert!(logged(&app, "No size to copy"));
+
+        // Folders copy size of their whole subtree
+        app.on_cursor_down();
+        press(&mut app, 'Y', KeyModifiers::SHIFT);
+        assert_eq!(
+            app.launch.take(),
+            Some(Launch::Clipboard("1.34 KB".to_string()))
+        );
+        assert!(logged(&app, "Copied size of a_folder: 1.34 KB"));
+
+        press(&mut app, 'y', KeyModifiers::ALT);
+        assert_eq!(
+            app.launch.take(),
+            Some(Launch::Clipboard("1376".to_string()))
+        );
+
+        app.ui_config.copy_size = SizeForm::Bytes;
+        app.on_copy_size(false);
+        assert_eq!(
+            app.launch.take(),
+            Some(Launch::Clipboard("1376".to_string()))
+        );
+
+        std::env::set_var("TERM", "dumb");
+        app.on_copy_size(true);
+        assert_eq!(app.launch, None);
+        assert!(logged(&app, "Size of a_folder: 1.34 KB"));
+    }
+}
@@ -1,7 +1,11 @@
+#![allow(clippy::unnecessary_mut_passed)]
+
 pub mod common;
 
 use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use wiper::app::App;
+use wiper::events::handle_key_events;
 
 mod cursor {
 
@@ -47,4 +51,69 @@ mod cursor {
         }
         assert_cursor_index(&mut app, 6);
     }
+
+    #[test]
+    fn wraps_cursor_at_edges_when_enabled() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        app.ui_config.wrap_navigation = true;
+        handle_tasks_synchronously(&mut app);
+
+        app.on_cursor_up();
+        assert_cursor_index(&app, 6);
+
+        app.on_cursor_down();
+        assert_cursor_index(&app, 0);
+    }
+
+    fn press(app: &mut App<DataStoreType>, code: KeyCode) {
+        handle_key_events(KeyEvent::new(code, KeyModifiers::NONE), app).unwrap();
+    }
+
+    #[test]
+    fn repeats_motion_by_count_prefix() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        press(&mut app, KeyCode::Char('3'));
+        assert_eq!(app.pending_count, Some(3));
+        press(&mut app, KeyCode::Char('j'));
+        assert_cursor_index(&app, 3);
+        assert_eq!(app.pending_count, None);
+
+        press(&mut app, KeyCode::Char('2'));
+        press(&mut app, KeyCode::Up);
+        assert_cursor_index(&app, 1);
+
+        press(&mut app, KeyCode::Char('1'));
+        press(&mut app, KeyCode::Char('0'));
+        press(&mut app, KeyCode::Down);
+        assert_cursor_index(&app, 6);
+    }
+
+    #[test]
+    fn bounds_and_clears_count_prefix() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        // Zero without count is not a count
+        press(&mut app, KeyCode::Char('0'));
+        assert_eq!(app.pending_count, None);
+
+        for digit in "123456".chars() {
+            press(&mut app, KeyCode::Char(digit));
+        }
+        assert_eq!(app.pending_count, Some(999));
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.pending_count, None);
+        press(&mut app, KeyCode::Char('j'));
+        assert_cursor_index(&app, 1);
+
+        // Descending ignores count
+        press(&mut app, KeyCode::Char('5'));
+        press(&mut app, KeyCode::Char('l'));
+        handle_tasks_synchronously(&mut app);
+        assert_eq!(app.pending_count, None);
+        assert_cursor_index(&app, 0);
+    }
 }
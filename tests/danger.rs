@@ -0,0 +1,162 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use std::fs;
+use std::path::Path;
+use wiper::app::App;
+use wiper::config::InitConfig;
+use wiper::events::handle_key_events;
+use wiper::fs::DataStoreType;
+
+mod danger {
+    use super::*;
+
+    fn press(app: &mut App<DataStoreType>, code: KeyCode) {
+        handle_key_events(KeyEvent::new(code, KeyModifiers::NONE), app).unwrap();
+    }
+
+    fn type_text(app: &mut App<DataStoreType>, text: &str) {
+        for c in text.chars() {
+            press(app, KeyCode::Char(c));
+        }
+    }
+
+    fn render(app: &mut App<DataStoreType>) -> String {
+        let area = Rect::new(0, 0, 160, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content.iter().map(|cell| cell.symbol()).collect()
+    }
+
+    fn root(postfix: &str) -> String {
+        format!("{}_{}", TEST_FILE_PATH_EDIT, postfix)
+    }
+
+    /// Edit fixture with cursor on file_to_delete_1.txt, deletions of paths matching pattern
+    /// need typed confirmation
+    fn setup_app(postfix: &str, pattern: &str) -> App<DataStoreType> {
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        app.ui_config.danger_patterns = vec![pattern.to_string()];
+        handle_tasks_synchronously(&mut app);
+        app.on_cursor_down();
+        app.on_cursor_down();
+        app
+    }
+
+    fn file_exists(postfix: &str) -> bool {
+        Path::new(&root(postfix))
+            .join("file_to_delete_1.txt")
+            .exists()
+    }
+
+    #[test]
+    fn parses_repeated_patterns() {
+        let config = InitConfig::build(
+            ["wiper", "--danger", "*/.config/*", "--danger", "/srv/*"]
+                .into_iter()
+                .map(String::from),
+        )
+        .unwrap();
+        assert_eq!(config.danger_patterns, ["*/.config/*", "/srv/*"]);
+        assert!(InitConfig::build(["wiper", "--danger"].into_iter().map(String::from)).is_err());
+    }
+
+    #[test]
+    fn matching_target_needs_typed_name() {
+        let postfix = "53";
+        let mut app = setup_app(postfix, "*/file_to_delete_1.txt");
+
+        press(&mut app, KeyCode::Char('d'));
+        assert!(app.is_typing_confirmation());
+        assert_eq!(app.confirm_phrase.as_deref(), Some("file_to_delete_1.txt"));
+        assert!(app
+            .deletion_warnings
+            .iter()
+            .any(|warning| warning.contains("matches danger pattern")));
+
+        // Second `d` is typed instead of confirming
+        press(&mut app, KeyCode::Char('d'));
+        assert!(file_exists(postfix));
+        assert_eq!(app.confirm_input, "d");
+        assert!(render(&mut app).contains("Type \"file_to_delete_1.txt\" and press Enter"));
+
+        press(&mut app, KeyCode::Backspace);
+        type_text(&mut app, "file_to_delete_1.txt");
+        press(&mut app, KeyCode::Enter);
+        handle_tasks_synchronously(&mut app);
+
+        assert!(!file_exists(postfix));
+        assert!(!app.ui_config.confirming_deletion);
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn wrong_text_cancels_deletion() {
+        let postfix = "54";
+        let mut app = setup_app(postfix, "*/file_to_delete_*");
+
+        press(&mut app, KeyCode::Char('d'));
+        type_text(&mut app, "file_to_delete_2.txt");
+        press(&mut app, KeyCode::Enter);
+
+        assert!(file_exists(postfix));
+        assert!(!app.ui_config.confirming_deletion);
+        assert!(!app.is_typing_confirmation());
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn other_targets_confirm_with_second_press() {
+        let postfix = "55";
+        let mut app = setup_app(postfix, "*/.config/*");
+
+        press(&mut app, KeyCode::Char('d'));
+        assert!(app.ui_config.confirming_deletion);
+        assert!(!app.is_typing_confirmation());
+        press(&mut app, KeyCode::Char('d'));
+        handle_tasks_synchronously(&mut app);
+
+        assert!(!file_exists(postfix));
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn matches_canonical_paths() {
+        let postfix = "56";
+        create_testing_files(postfix);
+        let canonical = fs::canonicalize(root(postfix)).unwrap();
+        let pattern = format!("{}/*", canonical.to_string_lossy());
+        let mut app = setup_app(postfix, &pattern);
+
+        press(&mut app, KeyCode::Char('d'));
+        assert!(app.is_typing_confirmation());
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.ui_config.confirming_deletion);
+        assert!(file_exists(postfix));
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn sweeping_matching_empty_folder_needs_typed_name() {
+        let postfix = "78";
+        let mut app = setup_app(postfix, "*/folder_to_delete_3");
+        let deepest = "folder_to_delete_1/folder_to_delete_2/folder_to_delete_3";
+
+        press(&mut app, KeyCode::Char('x'));
+        assert!(app.is_typing_confirmation());
+        assert_eq!(app.confirm_phrase.as_deref(), Some(deepest));
+        press(&mut app, KeyCode::Char('x'));
+        assert!(Path::new(&root(postfix)).join(deepest).exists());
+
+        press(&mut app, KeyCode::Backspace);
+        type_text(&mut app, deepest);
+        press(&mut app, KeyCode::Enter);
+        assert!(!Path::new(&root(postfix)).join(deepest).exists());
+        cleanup_testing_files(postfix);
+    }
+}
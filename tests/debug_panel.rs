@@ -0,0 +1,335 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use std::time::Duration;
+use wiper::app::App;
+use wiper::config::InitConfig;
+use wiper::events::handle_key_events;
+use wiper::fs::DataStoreType;
+use wiper::logger::{resident_memory, Logger, MessageLevel};
+use wiper::ui::{format_count, truncate_middle};
+
+mod debug_panel {
+    use super::*;
+
+    fn press(app: &mut App<DataStoreType>, code: KeyCode) {
+        handle_key_events(KeyEvent::new(code, KeyModifiers::NONE), app).unwrap();
+    }
+
+    fn render(app: &mut App<DataStoreType>) -> String {
+        let area = Rect::new(0, 0, 120, 30);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content.iter().map(|cell| cell.symbol()).collect()
+    }
+
+    #[test]
+    fn toggles_debug_panel_with_f12() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        assert!(!render(&mut app).contains("Stats"));
+
+        press(&mut app, KeyCode::F(12));
+        assert!(app.ui_config.debug_enabled);
+        assert!(render(&mut app).contains("Stats"));
+
+        press(&mut app, KeyCode::F(12));
+        assert!(!app.ui_config.debug_enabled);
+    }
+
+    #[test]
+    fn shows_resident_memory_next_to_folder_count() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        press(&mut app, KeyCode::F(12));
+
+        let screen = render(&mut app);
+        assert!(screen.contains("Memory: "));
+        match cfg!(target_os = "linux") {
+            true => {
+                assert!(resident_memory().is_some_and(|bytes| bytes > 0));
+                assert!(!screen.contains("Memory: —"));
+            }
+            false => assert!(screen.contains("Memory: —")),
+        }
+    }
+
+    #[test]
+    fn toggles_debug_panel_while_typing_filter() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        press(&mut app, KeyCode::Char('/'));
+        press(&mut app, KeyCode::F(12));
+        assert!(app.ui_config.debug_enabled);
+        assert!(app.ui_config.filter_editing);
+        assert_eq!(app.ui_config.name_filter.query, "");
+    }
+
+    #[test]
+    fn keeps_short_messages() {
+        assert_eq!(truncate_middle("/home/élodie", 40), "/home/élodie");
+    }
+
+    #[test]
+    fn truncates_on_grapheme_boundaries() {
+        let message = "/home/élodie/Документы/très_long_nom_de_fichier_ünïcödé.txt";
+        let truncated = truncate_middle(message, 20);
+        assert_eq!(truncated, "/hom..er_ünïcödé.txt");
+        assert!(truncated.chars().count() <= 20);
+
+        // Wide characters take two columns each
+        assert_eq!(
+            truncate_middle("日本語のとても長いファイル名", 10),
+            "日..イル名"
+        );
+        // Combining marks stay with their letter
+        assert_eq!(
+            truncate_middle("e\u{301}e\u{301}e\u{301}e\u{301}e\u{301}", 4),
+            "..e\u{301}e\u{301}"
+        );
+    }
+
+    #[test]
+    fn leaves_nothing_at_zero_width() {
+        assert_eq!(truncate_middle("/home/élodie", 0), "");
+        assert_eq!(truncate_middle("", 0), "");
+    }
+
+    #[test]
+    fn leaves_ellipsis_at_one_column() {
+        assert_eq!(truncate_middle("/home/élodie", 1), "…");
+        assert_eq!(truncate_middle("a", 1), "a");
+    }
+
+    #[test]
+    fn renders_long_unicode_log_messages() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        app.logger.log(
+            "/home/élodie/Документы/très_long_nom_de_fichier_ünïcödé.txt".to_string(),
+            MessageLevel::Info,
+        );
+        press(&mut app, KeyCode::F(12));
+        assert!(render(&mut app).contains("Logs"));
+    }
+
+    #[test]
+    fn collapses_repeated_messages() {
+        let mut logger = Logger::default();
+        for index in 0..1342 {
+            logger.log_keyed(
+                format!("/data/{index}: permission denied"),
+                "permission denied".to_string(),
+                MessageLevel::Error,
+            );
+        }
+        logger.log("Scanning /data".to_string(), MessageLevel::Info);
+        logger.log("Scanning /data".to_string(), MessageLevel::Info);
+        // Same key at another level is a separate entry
+        logger.log_keyed(
+            "/data/x: permission denied".to_string(),
+            "permission denied".to_string(),
+            MessageLevel::Info,
+        );
+
+        assert_eq!(logger.messages.len(), 3);
+        let errors = &logger.messages[2];
+        assert_eq!(errors.count, 1342);
+        assert_eq!(errors.message, "/data/1341: permission denied");
+        assert_eq!(logger.messages[1].count, 2);
+        assert_eq!(logger.messages[0].count, 1);
+    }
+
+    #[test]
+    fn repeat_moves_to_the_top() {
+        let mut logger = Logger::default();
+        logger.log("first".to_string(), MessageLevel::Info);
+        logger.log("second".to_string(), MessageLevel::Info);
+        logger.log("first".to_string(), MessageLevel::Info);
+        let messages: Vec<&str> = logger.messages.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["first", "second"]);
+    }
+
+    #[test]
+    fn renders_count_of_repeated_messages() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        for index in 0..1342 {
+            app.logger.log_keyed(
+                format!("/data/{index}: permission denied"),
+                "permission denied".to_string(),
+                MessageLevel::Error,
+            );
+        }
+        press(&mut app, KeyCode::F(12));
+        assert!(render(&mut app).contains("permission denied ×1,342"));
+    }
+
+    #[test]
+    fn separates_thousands() {
+        assert_eq!(format_count(0), "0");
+        assert_eq!(format_count(999), "999");
+        assert_eq!(format_count(1342), "1,342");
+        assert_eq!(format_count(1234567), "1,234,567");
+    }
+
+    fn setup_app(args: &[&str]) -> App<DataStoreType> {
+        let config = InitConfig::build(
+            std::iter::once("wiper")
+                .chain(args.iter().copied())
+                .map(String::from),
+        )
+        .unwrap();
+        let mut app: App<DataStoreType> = App::new(config);
+        app.init();
+        handle_tasks_synchronously(&mut app);
+        app
+    }
+
+    #[test]
+    fn lists_default_scan_parameters() {
+        let app = setup_app(&[TEST_FILE_PATH_VIEW]);
+        let lines = app.scan_parameters();
+
+        assert!(lines[0].starts_with("Root: /") && lines[0].ends_with("tests/test_files/view"));
+        assert_eq!(
+            &lines[1..],
+            ["Files: all", "Exclude: —", "Priority: normal"]
+        );
+    }
+
+    #[test]
+    fn lists_scan_limits_and_roots() {
+        let app = setup_app(&[
+            "--exclude-smaller-than",
+            "1K",
+            "--only",
+            "txt,md",
+            "--exclude",
+            "*.log",
+            "--low-priority",
+            "./tests/test_files/view/a_folder",
+            "./tests/test_files/view/b_folder",
+        ]);
+        let lines = app.scan_parameters();
+
+        assert!(lines[0].contains("view/a_folder, ") && lines[0].ends_with("view/b_folder"));
+        assert_eq!(lines[1], "Files: from 1.00 KB, only txt,md");
+        assert_eq!(lines[2], "Exclude: *.log");
+        assert_eq!(lines[3], "Priority: low");
+    }
+
+    #[test]
+    fn shows_scan_parameters_in_panel() {
+        let mut app = setup_app(&["--exclude", "*.log", TEST_FILE_PATH_VIEW]);
+        press(&mut app, KeyCode::F(12));
+
+        let screen = render(&mut app);
+        assert!(screen.contains(" Scan "));
+        assert!(screen.contains("Exclude: *.log"));
+    }
+
+    fn log_burst(logger: &mut Logger, count: usize) {
+        for index in 0..count {
+            logger.log(format!("Scanned folder {index}"), MessageLevel::Info);
+        }
+    }
+
+    #[test]
+    fn holds_errors_past_newer_messages() {
+        let mut logger = Logger {
+            hold: Duration::from_secs(60),
+            ..Logger::default()
+        };
+        logger.log("/data: permission denied".to_string(), MessageLevel::Error);
+        log_burst(&mut logger, 30);
+
+        // Kept while older messages are dropped, and given a row before newer ones
+        assert_eq!(logger.messages.len(), 10);
+        assert_eq!(logger.messages[9].level, MessageLevel::Error);
+        let visible = logger.visible(3);
+        let messages: Vec<&str> = visible.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Scanned folder 29",
+                "Scanned folder 28",
+                "/data: permission denied"
+            ]
+        );
+    }
+
+    #[test]
+    fn drops_errors_once_held_long_enough() {
+        let mut logger = Logger {
+            hold: Duration::from_secs(60),
+            ..Logger::default()
+        };
+        logger.log("/data: permission denied".to_string(), MessageLevel::Error);
+        logger.messages[0].timestamp -= 61_000;
+        log_burst(&mut logger, 3);
+        assert_eq!(logger.visible(3).len(), 3);
+        assert!(logger
+            .visible(3)
+            .iter()
+            .all(|entry| entry.level == MessageLevel::Info));
+
+        log_burst(&mut logger, 30);
+        assert!(logger
+            .messages
+            .iter()
+            .all(|entry| entry.level == MessageLevel::Info));
+    }
+
+    #[test]
+    fn holds_no_errors_when_disabled() {
+        let mut logger = Logger::default();
+        logger.log("/data: permission denied".to_string(), MessageLevel::Error);
+        log_burst(&mut logger, 10);
+        assert!(logger
+            .messages
+            .iter()
+            .all(|entry| entry.level == MessageLevel::Info));
+    }
+
+    #[test]
+    fn pins_latest_error_until_acknowledged() {
+        let mut app = setup_app(&["--pin-errors", "--log-hold", "0", TEST_FILE_PATH_VIEW]);
+        assert!(app.logger.pin_errors);
+        assert_eq!(app.logger.hold, Duration::ZERO);
+        app.logger
+            .log("/data: permission denied".to_string(), MessageLevel::Error);
+        app.logger
+            .log("/srv: permission denied".to_string(), MessageLevel::Error);
+        log_burst(&mut app.logger, 30);
+        press(&mut app, KeyCode::F(12));
+
+        let pinned = app.logger.pinned.as_ref().unwrap();
+        assert_eq!(pinned.message, "/srv: permission denied");
+        let screen = render(&mut app);
+        assert!(screen.contains("] ! /srv: permission denied"));
+        assert!(!screen.contains("/data: permission denied"));
+
+        press(&mut app, KeyCode::Char('A'));
+        assert!(app.logger.pinned.is_none());
+        assert!(!render(&mut app).contains("permission denied"));
+    }
+
+    #[test]
+    fn holds_errors_by_default() {
+        let app = setup_app(&[TEST_FILE_PATH_VIEW]);
+        assert_eq!(app.logger.hold, Duration::from_secs(5));
+        assert!(!app.logger.pin_errors);
+        assert!(InitConfig::build(
+            ["wiper", "--log-hold", "soon"]
+                .map(String::from)
+                .into_iter()
+        )
+        .is_err());
+    }
+}
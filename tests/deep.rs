@@ -0,0 +1,129 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use std::path::PathBuf;
+use wiper::app::App;
+use wiper::config::InitConfig;
+use wiper::events::handle_key_events;
+use wiper::fs::{deep_folders, DataStore, DataStoreType};
+use wiper::views::View;
+
+mod deep {
+    use super::*;
+
+    fn press(app: &mut App<DataStoreType>, c: char) {
+        handle_key_events(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE), app).unwrap();
+    }
+
+    fn nested(app: &App<DataStoreType>, depth: usize) -> PathBuf {
+        (1..=depth).fold(app.root_path.clone(), |path, level| {
+            path.join(format!("folder_to_delete_{level}"))
+        })
+    }
+
+    fn min_depth(app: &App<DataStoreType>) -> (usize, usize) {
+        let View::Deep(view) = &app.view else {
+            panic!("deep folders view is not open");
+        };
+        (view.min_depth, view.visible().len())
+    }
+
+    #[test]
+    fn lists_folders_deepest_first() {
+        let postfix = "68";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+
+        let folders = deep_folders(&app.store, &app.root_path);
+        let depths: Vec<usize> = folders.iter().map(|folder| folder.depth).collect();
+        assert_eq!(depths, vec![3, 2, 1]);
+        assert_eq!(folders[0].path, nested(&app, 3));
+        assert_eq!(folders[0].size, Some(0));
+        assert!(folders[2].size > folders[1].size);
+
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn adjusts_depth_without_rescanning() {
+        let postfix = "69";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+        app.ui_config.min_depth = 2;
+
+        press(&mut app, 'L');
+        assert_eq!(min_depth(&app), (2, 2));
+        press(&mut app, '+');
+        assert_eq!(min_depth(&app), (3, 1));
+        // Nothing is deeper than the deepest scanned folder
+        press(&mut app, '+');
+        assert_eq!(min_depth(&app), (3, 1));
+        press(&mut app, '-');
+        press(&mut app, '-');
+        press(&mut app, '-');
+        assert_eq!(min_depth(&app), (1, 3));
+
+        // Depth is kept for the next time the view opens
+        press(&mut app, 'L');
+        assert!(matches!(app.view, View::Folder));
+        press(&mut app, 'L');
+        assert_eq!(min_depth(&app), (1, 3));
+
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn reveals_selected_folder() {
+        let postfix = "70";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+        app.ui_config.min_depth = 3;
+
+        press(&mut app, 'L');
+        handle_key_events(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE), &mut app).unwrap();
+        assert!(matches!(app.view, View::Folder));
+        assert_eq!(app.store.get_current_path().clone(), nested(&app, 2));
+        assert_eq!(
+            get_current_folder(&app).unwrap().get_selected_entry().title,
+            "folder_to_delete_3"
+        );
+
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn renders_relative_paths() {
+        let postfix = "71";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+        app.ui_config.min_depth = 3;
+        press(&mut app, 'L');
+
+        let area = Rect::new(0, 0, 120, 30);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        let text: String = buf.content.iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("Folders 3 or more levels deep (1)"));
+        assert!(text.contains("folder_to_delete_1/folder_to_delete_2/folder_to_delete_3"));
+
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn uses_depth_from_options() {
+        let args = ["wiper", "--min-depth", "4", TEST_FILE_PATH_VIEW].map(String::from);
+        let app: App<DataStoreType> = App::new(InitConfig::build(args.into_iter()).unwrap());
+        assert_eq!(app.ui_config.min_depth, 4);
+
+        let args = ["wiper", "--min-depth", "0"].map(String::from);
+        assert!(InitConfig::build(args.into_iter()).is_err());
+    }
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::bool_assert_comparison)]
+
 pub mod common;
 use crate::common::*;
 use wiper::app::App;
@@ -5,64 +7,11 @@ use wiper::fs::FolderEntryType;
 
 mod delete {
 
-    use wiper::fs::DataStoreType;
+    use std::time::{Duration, Instant};
+    use wiper::config::FlashIntensity;
+    use wiper::fs::{DataStore, DataStoreType};
 
     use super::*;
-    use std::fs::{self, File};
-    use std::io::Write;
-
-    const TEST_FILE_SIZE: usize = 446;
-
-    fn generate_lorem_ipsum() -> String {
-        String::from(
-            "Lorem ipsum dolor sit amet, consectetur adipiscing elit. \
-    Sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. \
-    Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi \
-    ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit \
-    in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur \
-    sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt \
-    mollit anim id est laborum.",
-        )
-    }
-
-    /// - folder_1
-    ///     - folder_2
-    ///         - folder_3
-    ///         - file_1
-    ///         - file_2
-    ///         - file_3
-    ///     - file_1
-    ///     - file_2
-    ///     - file_3
-    /// - file_1
-    /// - file_2
-    /// - file_3
-    fn create_testing_files(postfix: &str) {
-        let custom_folder = format!("{}_{}", TEST_FILE_PATH_EDIT, postfix);
-        fs::create_dir_all(&custom_folder).expect("Failed to create test folder");
-
-        let mut folder_path = format!("{}", custom_folder);
-
-        for folder_index in 1..4 {
-            for file_index in 1..4 {
-                let file_name = format!("file_to_delete_{}.txt", file_index);
-                let file_path = format!("{}/{}", folder_path, file_name);
-                let mut file = File::create(&file_path).expect("Failed to create test file");
-                writeln!(file, "{}", generate_lorem_ipsum()).expect("Failed to write to test file");
-            }
-
-            folder_path = format!("{}/folder_to_delete_{}", folder_path, folder_index);
-
-            fs::create_dir_all(&folder_path).expect("Failed to create test folder");
-        }
-    }
-
-    fn cleanup_testing_files(postfix: &str) {
-        let custom_folder = format!("{}_{}", TEST_FILE_PATH_EDIT, postfix);
-        if let Err(err) = fs::remove_dir_all(custom_folder) {
-            eprintln!("Failed to remove test folder: {}", err);
-        }
-    }
 
     #[test]
     fn has_correct_initial_state() {
@@ -357,4 +306,107 @@ mod delete {
 
         cleanup_testing_files(postfix);
     }
+
+    #[test]
+    fn warns_before_deleting_git_repository() {
+        let postfix = "19";
+        create_testing_files(postfix);
+        std::fs::create_dir_all(format!(
+            "{}_{}/folder_to_delete_1/.git",
+            TEST_FILE_PATH_EDIT, postfix
+        ))
+        .unwrap();
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+
+        app.store
+            .get_current_folder_mut()
+            .unwrap()
+            .select_name("folder_to_delete_1".as_ref());
+        app.on_delete();
+
+        assert!(app.ui_config.confirming_deletion);
+        assert_eq!(
+            app.deletion_warnings,
+            vec!["folder_to_delete_1 contains a git repository".to_string()]
+        );
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn does_not_warn_before_deleting_plain_folder() {
+        let postfix = "20";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+
+        app.store
+            .get_current_folder_mut()
+            .unwrap()
+            .select_name("folder_to_delete_1".as_ref());
+        app.on_delete();
+
+        assert!(app.ui_config.confirming_deletion);
+        assert!(app.deletion_warnings.is_empty());
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn flashes_once_when_confirmation_starts() {
+        let postfix = "28";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+
+        app.on_cursor_down();
+        app.on_delete();
+        app.pre_render();
+        assert!(app.ui_config.flashing);
+
+        app.confirmation_started = Some(Instant::now() - Duration::from_secs(1));
+        app.pre_render();
+        assert!(!app.ui_config.flashing);
+        assert!(app.ui_config.confirming_deletion);
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn scales_flash_by_intensity() {
+        let at = Duration::from_millis;
+        assert!(!FlashIntensity::Off.is_lit(at(0)));
+        assert!(FlashIntensity::Subtle.is_lit(at(100)));
+        assert!(!FlashIntensity::Subtle.is_lit(at(300)));
+        assert!(FlashIntensity::Strong.is_lit(at(0)));
+        assert!(!FlashIntensity::Strong.is_lit(at(200)));
+        assert!(FlashIntensity::Strong.is_lit(at(300)));
+        assert!(!FlashIntensity::Strong.is_lit(at(1000)));
+    }
+
+    #[test]
+    fn abandons_confirmation_after_timeout_without_input() {
+        let postfix = "31";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        app.ui_config.confirm_timeout_secs = Some(10);
+        handle_tasks_synchronously(&mut app);
+
+        app.on_cursor_down();
+        app.on_delete();
+        app.tick();
+        assert!(app.ui_config.confirming_deletion);
+        assert!(app.confirmation_time_left().unwrap() > Duration::from_secs(9));
+
+        // Input restarts the countdown
+        app.last_input = Instant::now() - Duration::from_secs(9);
+        app.on_input();
+        app.tick();
+        assert!(app.ui_config.confirming_deletion);
+
+        app.last_input = Instant::now() - Duration::from_secs(11);
+        app.tick();
+        assert!(!app.ui_config.confirming_deletion);
+        assert_eq!(app.confirmation_time_left(), None);
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::File).len(), 3);
+        cleanup_testing_files(postfix);
+    }
 }
@@ -0,0 +1,87 @@
+pub mod common;
+
+use std::path::PathBuf;
+use wiper::app::App;
+use wiper::fs::{DataStoreType, Snapshot};
+use wiper::views::View;
+
+use crate::common::*;
+
+mod diff {
+    use super::*;
+
+    fn scanned_view_app() -> App<DataStoreType> {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        app
+    }
+
+    #[test]
+    fn snapshot_keeps_every_entry_by_relative_path() {
+        let app = scanned_view_app();
+        let snapshot = Snapshot::build(&app.store, &app.root_path);
+        assert_eq!(snapshot.sizes.len(), 14);
+        assert_eq!(snapshot.sizes.get("a_root_file.txt"), Some(&459));
+        assert!(snapshot.sizes.contains_key("b_folder/folder2_file1.txt"));
+        assert!(snapshot.changes_since(&snapshot).is_empty());
+    }
+
+    #[test]
+    fn lists_changes_biggest_first() {
+        let mut app = scanned_view_app();
+        let mut previous = Snapshot::build(&app.store, &app.root_path);
+        previous.sizes.remove("z_root_file.txt");
+        previous.sizes.insert("a_root_file.txt".to_string(), 59);
+        previous.sizes.insert("gone.txt".to_string(), 3 << 20);
+        app.previous_snapshot = Some(previous);
+        app.update_snapshot();
+
+        let changes = app.scan_changes.clone().unwrap();
+        let paths: Vec<&str> = changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, ["gone.txt", "z_root_file.txt", "a_root_file.txt"]);
+        assert_eq!(changes[0].after, None);
+        assert_eq!(changes[0].delta(), -(3 << 20));
+        assert_eq!(changes[1].before, None);
+        assert_eq!(changes[2].delta(), 400);
+    }
+
+    #[test]
+    fn hides_changes_under_threshold() {
+        let mut app = scanned_view_app();
+        let mut previous = Snapshot::build(&app.store, &app.root_path);
+        previous.sizes.insert("gone.txt".to_string(), 3 << 20);
+        previous.sizes.insert("a_root_file.txt".to_string(), 59);
+        app.previous_snapshot = Some(previous);
+        app.update_snapshot();
+
+        app.on_toggle_diff_view();
+        let View::Diff(view) = &mut app.view else {
+            panic!("diff view is not open");
+        };
+        assert_eq!(view.visible().len(), 1);
+        view.toggle_show_all();
+        assert_eq!(view.visible().len(), 2);
+
+        app.on_toggle_diff_view();
+        assert!(matches!(app.view, View::Folder));
+    }
+
+    #[test]
+    fn stays_in_folder_view_without_previous_scan() {
+        let mut app = scanned_view_app();
+        app.on_toggle_diff_view();
+        assert!(matches!(app.view, View::Folder));
+        assert!(app.scan_changes.is_none());
+    }
+
+    #[test]
+    fn saves_and_loads_snapshot() {
+        let postfix = "35";
+        let path = PathBuf::from(format!("{}_{}/snapshot.json", TEST_FILE_PATH_EDIT, postfix));
+        let app = scanned_view_app();
+        let snapshot = Snapshot::build(&app.store, &app.root_path);
+        snapshot.save(&path).unwrap();
+        assert_eq!(Snapshot::load(&path).unwrap(), snapshot);
+        cleanup_testing_files(postfix);
+    }
+}
@@ -0,0 +1,95 @@
+pub mod common;
+
+use crate::common::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use wiper::app::App;
+use wiper::config::InitConfig;
+use wiper::fs::{empty_folders, DataStore, DataStoreType, ScanOptions};
+
+mod sweep {
+    use super::*;
+
+    #[test]
+    fn sweeps_nested_empty_folders_after_confirmation() {
+        let postfix = "33";
+        create_testing_files(postfix);
+        let root = format!("{}_{}", TEST_FILE_PATH_EDIT, postfix);
+        fs::create_dir_all(format!("{}/empty_a/empty_b/empty_c", root)).unwrap();
+        fs::create_dir_all(format!("{}/empty_a/empty_d", root)).unwrap();
+
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+        let current_path = app.store.get_current_path().clone();
+        let deepest = "folder_to_delete_1/folder_to_delete_2/folder_to_delete_3";
+        assert_eq!(
+            empty_folders(&app.store, &current_path),
+            vec![current_path.join("empty_a"), current_path.join(deepest)]
+        );
+
+        app.on_sweep_empty_folders();
+        assert!(app.ui_config.confirming_deletion);
+        assert_eq!(
+            app.pending_deletion,
+            vec![PathBuf::from("empty_a"), PathBuf::from(deepest)]
+        );
+        assert!(Path::new(&format!("{}/empty_a", root)).exists());
+
+        app.on_delete();
+        assert!(!app.ui_config.confirming_deletion);
+        assert!(!Path::new(&format!("{}/empty_a", root)).exists());
+        assert!(!Path::new(&format!("{}/{}", root, deepest)).exists());
+        assert!(Path::new(&format!("{}/folder_to_delete_1/folder_to_delete_2", root)).exists());
+        assert_eq!(get_current_folder(&app).unwrap().entries().len(), 5);
+
+        // Nothing is left to sweep
+        app.on_sweep_empty_folders();
+        assert!(!app.ui_config.confirming_deletion);
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn keeps_files_the_scan_left_out() {
+        let postfix = "82";
+        create_testing_files(postfix);
+        let root = format!("{}_{}", TEST_FILE_PATH_EDIT, postfix);
+        fs::create_dir_all(format!("{}/logs/nested", root)).unwrap();
+        fs::write(format!("{}/logs/nested/app.log", root), "kept").unwrap();
+        fs::create_dir_all(format!("{}/empty", root)).unwrap();
+
+        let mut app: App<DataStoreType> = App::new(InitConfig {
+            file_path: Some(root.clone()),
+            scan_options: ScanOptions {
+                exclude: vec!["*.log".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        app.ui_config.move_to_trash = false;
+        app.init();
+        handle_tasks_synchronously(&mut app);
+
+        // Excluded file is not listed, so its folder looks empty
+        app.on_sweep_empty_folders();
+        assert_eq!(app.pending_deletion.len(), 3);
+        app.on_delete();
+
+        assert!(Path::new(&format!("{}/logs/nested/app.log", root)).exists());
+        assert!(!Path::new(&format!("{}/empty", root)).exists());
+        assert!(get_current_folder(&app)
+            .unwrap()
+            .entries()
+            .iter()
+            .any(|entry| entry.title == "logs"));
+        assert_eq!(app.session_failed_deletions, 1);
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn keeps_folders_with_files() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        let current_path = app.store.get_current_path().clone();
+        assert!(empty_folders(&app.store, &current_path).is_empty());
+    }
+}
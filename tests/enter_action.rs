@@ -0,0 +1,84 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use wiper::app::{App, Launch};
+use wiper::config::EnterAction;
+use wiper::events::handle_key_events;
+use wiper::fs::{DataStore, DataStoreType};
+
+mod enter_action {
+    use super::*;
+
+    fn press(app: &mut App<DataStoreType>, code: KeyCode) {
+        handle_key_events(KeyEvent::new(code, KeyModifiers::NONE), app).unwrap();
+    }
+
+    fn render(app: &mut App<DataStoreType>) -> String {
+        let area = Rect::new(0, 0, 140, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content.iter().map(|cell| cell.symbol()).collect()
+    }
+
+    /// View fixture with cursor on a_root_file.txt
+    fn setup_app(action: EnterAction) -> App<DataStoreType> {
+        let mut app: App<DataStoreType> = setup_app_view();
+        app.ui_config.enter_action = action;
+        handle_tasks_synchronously(&mut app);
+        for _ in 0..4 {
+            app.on_cursor_down();
+        }
+        app
+    }
+
+    #[test]
+    fn copies_path_of_file() {
+        let mut app = setup_app(EnterAction::CopyPath);
+        let current_path = app.store.get_current_path().clone();
+        press(&mut app, KeyCode::Enter);
+
+        let expected = current_path.join("a_root_file.txt");
+        assert_eq!(
+            app.launch,
+            Some(Launch::Clipboard(expected.to_string_lossy().to_string()))
+        );
+        assert_eq!(app.store.get_current_path(), &current_path);
+    }
+
+    #[test]
+    fn opens_file_in_pager() {
+        let mut app = setup_app(EnterAction::Pager);
+        press(&mut app, KeyCode::Enter);
+
+        let Some(Launch::Command(command)) = &app.launch else {
+            panic!("Expected a command, got {:?}", app.launch);
+        };
+        assert!(command.len() >= 2);
+        assert!(command.last().unwrap().ends_with("a_root_file.txt"));
+    }
+
+    #[test]
+    fn does_nothing_when_configured_so() {
+        let mut app = setup_app(EnterAction::Nothing);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.launch, None);
+    }
+
+    #[test]
+    fn footer_shows_action_on_files_only() {
+        let mut app = setup_app(EnterAction::Editor);
+        assert!(render(&mut app).contains("←↓↑→/Backspace - navigate, Enter - edit"));
+
+        app.on_cursor_up();
+        assert!(render(&mut app).contains("←↓↑→/Enter/Backspace - navigate"));
+        assert!(!render(&mut app).contains("Enter - edit"));
+
+        let mut app = setup_app(EnterAction::Nothing);
+        assert!(render(&mut app).contains("←↓↑→/Enter/Backspace - navigate"));
+        assert!(!render(&mut app).contains("Enter -"));
+    }
+}
@@ -0,0 +1,125 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use std::io;
+use wiper::app::App;
+use wiper::events::handle_key_events;
+use wiper::fs::{DataStore, DataStoreType, ScanError};
+
+mod error_entries {
+    use super::*;
+
+    fn press(app: &mut App<DataStoreType>, c: char) {
+        handle_key_events(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE), app).unwrap();
+    }
+
+    /// View fixture with a folder and a file inside another folder that could not be read
+    fn app_with_errors() -> App<DataStoreType> {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        let root = app.store.get_current_path().clone();
+        let denied = io::Error::from(io::ErrorKind::PermissionDenied);
+        app.scan_errors = vec![
+            ScanError::new(root.join("a_folder"), &denied),
+            ScanError::new(root.join("c_folder").join("folder2_file1.txt"), &denied),
+        ];
+        app
+    }
+
+    #[test]
+    fn jumps_between_entries_with_errors() {
+        let mut app = app_with_errors();
+        assert_cursor_index(&app, 0);
+
+        press(&mut app, ']');
+        assert_eq!(selected_title(&app), "a_folder");
+        // Error below the folder counts for it
+        press(&mut app, ']');
+        assert_eq!(selected_title(&app), "c_folder");
+        // Wraps around
+        press(&mut app, ']');
+        assert_eq!(selected_title(&app), "a_folder");
+
+        press(&mut app, '[');
+        assert_eq!(selected_title(&app), "c_folder");
+    }
+
+    #[test]
+    fn stays_put_without_errors() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        app.on_cursor_down();
+
+        press(&mut app, ']');
+        assert_cursor_index(&app, 1);
+        press(&mut app, '[');
+        assert_cursor_index(&app, 1);
+    }
+
+    fn visible_titles(app: &App<DataStoreType>) -> Vec<String> {
+        let folder = get_current_folder(app).unwrap();
+        app.ui_config
+            .visible_entries(folder)
+            .map(|(_, entry)| entry.title.clone())
+            .collect()
+    }
+
+    fn render(app: &mut App<DataStoreType>) -> String {
+        let area = Rect::new(0, 0, 120, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content.iter().map(|cell| cell.symbol()).collect()
+    }
+
+    fn select(app: &mut App<DataStoreType>, title: &str) {
+        let folder = app.store.get_current_folder_mut().unwrap();
+        folder.cursor_index = folder
+            .entries()
+            .iter()
+            .position(|entry| entry.title == title)
+            .unwrap();
+    }
+
+    #[test]
+    fn shows_only_branches_with_errors() {
+        let mut app = app_with_errors();
+        select(&mut app, "a_root_file.txt");
+
+        press(&mut app, 'E');
+        let mut titles = visible_titles(&app);
+        titles.sort();
+        assert_eq!(titles, vec!["..", "a_folder", "c_folder"]);
+        // Selected entry was hidden, first shown one takes its place
+        assert_eq!(selected_title(&app), visible_titles(&app)[1]);
+        assert!(render(&mut app).contains("read errors: 2 branches"));
+
+        select(&mut app, "c_folder");
+        press(&mut app, 'l');
+        app.pre_render();
+        assert_eq!(visible_titles(&app), vec!["..", "folder2_file1.txt"]);
+        press(&mut app, 'h');
+
+        press(&mut app, 'E');
+        assert_eq!(visible_titles(&app).len(), 7);
+        assert!(!render(&mut app).contains("read errors:"));
+    }
+
+    #[test]
+    fn hides_everything_after_clean_scan() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        press(&mut app, 'E');
+        assert_eq!(visible_titles(&app), vec![".."]);
+        assert!(render(&mut app).contains("read errors: 0 branches"));
+    }
+
+    fn selected_title(app: &App<DataStoreType>) -> String {
+        let folder = get_current_folder(app).unwrap();
+        folder.entries()[folder.cursor_index].title.clone()
+    }
+}
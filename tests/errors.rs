@@ -0,0 +1,71 @@
+pub mod common;
+
+use crate::common::*;
+use std::io::ErrorKind;
+use wiper::app::App;
+use wiper::fs::{normalize_path, DataStoreType};
+use wiper::views::View;
+
+mod errors {
+    use super::*;
+
+    #[test]
+    fn collects_unreadable_path() {
+        let postfix = "21";
+        let missing = format!("{}_{}", TEST_FILE_PATH_EDIT, postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(app.scan_errors.len(), 1);
+        assert_eq!(app.scan_errors[0].path, normalize_path(&missing));
+        assert_eq!(app.scan_errors[0].kind, ErrorKind::NotFound);
+        assert!(!app.scan_errors[0].is_permission_denied());
+    }
+
+    #[test]
+    fn collects_unreadable_root_path() {
+        let mut app: App<DataStoreType> =
+            setup_app_roots(&[TEST_FILE_PATH_VIEW, "./tests/test_files/missing"]);
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(app.scan_errors.len(), 1);
+        assert_eq!(
+            app.scan_errors[0].path,
+            normalize_path("./tests/test_files/missing")
+        );
+    }
+
+    #[test]
+    fn drops_errors_fixed_by_rescan() {
+        let postfix = "22";
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+        assert_eq!(app.scan_errors.len(), 1);
+
+        create_testing_files(postfix);
+        app.refresh_current_folder();
+        handle_tasks_synchronously(&mut app);
+
+        assert!(app.scan_errors.is_empty());
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn has_no_errors_for_readable_tree() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        assert!(app.scan_errors.is_empty());
+    }
+
+    #[test]
+    fn toggles_errors_view() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        app.on_toggle_errors_view();
+        assert!(matches!(app.view, View::Errors(_)));
+        app.on_toggle_errors_view();
+        assert!(matches!(app.view, View::Folder));
+    }
+}
@@ -0,0 +1,130 @@
+pub mod common;
+
+use std::fs::{create_dir_all, write};
+use std::path::PathBuf;
+use wiper::app::App;
+use wiper::config::InitConfig;
+use wiper::filter::exclude_patterns;
+use wiper::fs::{scan_folder_with, DataStoreType, HardLinks, MemoryFileSystem, ScanOptions};
+
+use crate::common::*;
+
+mod exclude {
+    use super::*;
+
+    fn build(args: &[&str]) -> Result<InitConfig, &'static str> {
+        InitConfig::build(
+            std::iter::once("wiper")
+                .chain(args.iter().copied())
+                .map(String::from),
+        )
+    }
+
+    /// Exclude file of its own for every test, so they don't share it
+    fn exclude_file(name: &str, contents: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join("wiper_exclude");
+        create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        write(&path, contents).unwrap();
+        path
+    }
+
+    fn setup_app(args: &[&str]) -> App<DataStoreType> {
+        let mut args = args.to_vec();
+        args.push(TEST_FILE_PATH_VIEW);
+        let mut app: App<DataStoreType> = App::new(build(&args).unwrap());
+        app.init();
+        handle_tasks_synchronously(&mut app);
+        app
+    }
+
+    fn titles(app: &App<DataStoreType>) -> Vec<String> {
+        get_current_folder(app)
+            .unwrap()
+            .entries()
+            .iter()
+            .map(|entry| entry.title.clone())
+            .collect()
+    }
+
+    #[test]
+    fn parses_exclude_options() {
+        let config = build(&["--exclude", "*.log", "--exclude", "target"]).unwrap();
+        assert_eq!(config.scan_options.exclude, ["*.log", "target"]);
+
+        let config = build(&["--exclude-from", "/tmp/excludes"]).unwrap();
+        assert_eq!(config.exclude_from, Some(PathBuf::from("/tmp/excludes")));
+
+        assert!(build(&["--exclude"]).is_err());
+        assert!(build(&["--exclude-from"]).is_err());
+    }
+
+    #[test]
+    fn reads_patterns_skipping_comments() {
+        let patterns = exclude_patterns("# Build output\ntarget\n\n  *.log  \n#*.tmp\n");
+        assert_eq!(patterns, ["target", "*.log"]);
+    }
+
+    #[test]
+    fn skips_matching_entries_without_descending() {
+        let mut fs = MemoryFileSystem::new();
+        fs.file("/root/app.log", 100)
+            .file("/root/notes.txt", 10)
+            .file("/root/node_modules/lib.js", 50);
+        let options = ScanOptions {
+            exclude: vec!["*.log".to_string()],
+            exclude_from_file: vec!["node_modules".to_string()],
+            ..ScanOptions::default()
+        };
+        let (folder, errors) = scan_folder_with(
+            &fs,
+            PathBuf::from("/root"),
+            &options,
+            &mut HardLinks::default(),
+        );
+
+        let titles: Vec<&str> = folder.entries().iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, ["..", "notes.txt"]);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn combines_inline_and_file_patterns() {
+        let path = exclude_file("combines", "b_*\n");
+        let app = setup_app(&[
+            "--exclude",
+            "*.txt",
+            "--exclude-from",
+            path.to_str().unwrap(),
+        ]);
+
+        assert_eq!(titles(&app), ["..", "a_folder", "c_folder"]);
+    }
+
+    #[test]
+    fn reads_file_again_on_rescan() {
+        let path = exclude_file("rescan", "b_folder\n");
+        let mut app = setup_app(&["--exclude-from", path.to_str().unwrap()]);
+        assert!(!titles(&app).contains(&"b_folder".to_string()));
+        assert_eq!(get_current_folder(&app).unwrap().total_size(), 15124 - 5957);
+
+        write(&path, "c_folder\n").unwrap();
+        app.on_rescan();
+        handle_tasks_synchronously(&mut app);
+
+        let titles = titles(&app);
+        assert!(titles.contains(&"b_folder".to_string()));
+        assert!(!titles.contains(&"c_folder".to_string()));
+        assert_eq!(get_current_folder(&app).unwrap().total_size(), 15124 - 5957);
+    }
+
+    #[test]
+    fn logs_unreadable_file() {
+        let app = setup_app(&["--exclude-from", "/nonexistent/wiper/excludes"]);
+
+        assert!(app.logger.messages.iter().any(|logged| logged
+            .message
+            .starts_with("Could not read exclude file /nonexistent/wiper/excludes")));
+        assert!(titles(&app).contains(&"b_folder".to_string()));
+    }
+}
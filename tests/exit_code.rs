@@ -0,0 +1,65 @@
+pub mod common;
+
+use crate::common::*;
+use std::fs;
+use wiper::app::App;
+use wiper::fs::DataStoreType;
+use wiper::report::{Outcome, EXIT_DELETION_FAILED, EXIT_READ_ERRORS};
+
+mod exit_code {
+    use super::*;
+
+    #[test]
+    fn maps_outcomes_to_codes() {
+        assert_eq!(Outcome::default().exit_code(), 0);
+        let read_errors = Outcome {
+            read_errors: true,
+            deletion_failed: false,
+            deletion_refused: false,
+        };
+        assert_eq!(read_errors.exit_code(), EXIT_READ_ERRORS);
+        let both = Outcome {
+            read_errors: true,
+            deletion_failed: true,
+            deletion_refused: false,
+        };
+        assert_eq!(both.exit_code(), EXIT_DELETION_FAILED);
+        assert_ne!(EXIT_READ_ERRORS, EXIT_DELETION_FAILED);
+    }
+
+    #[test]
+    fn succeeds_for_readable_tree() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        assert_eq!(app.outcome(), Outcome::default());
+    }
+
+    #[test]
+    fn reports_read_errors() {
+        let mut app: App<DataStoreType> =
+            setup_app_roots(&[TEST_FILE_PATH_VIEW, "./tests/test_files/missing"]);
+        handle_tasks_synchronously(&mut app);
+        assert_eq!(app.outcome().exit_code(), EXIT_READ_ERRORS);
+    }
+
+    #[test]
+    fn reports_failed_deletion() {
+        let postfix = "52";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+
+        // File disappears behind the app's back, so deleting it fails
+        let root = format!("{}_{}", TEST_FILE_PATH_EDIT, postfix);
+        fs::remove_file(format!("{root}/file_to_delete_1.txt")).unwrap();
+        app.on_cursor_down();
+        app.on_cursor_down();
+        app.on_delete();
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(app.session_failed_deletions, 1);
+        assert_eq!(app.outcome().exit_code(), EXIT_DELETION_FAILED);
+        cleanup_testing_files(postfix);
+    }
+}
@@ -0,0 +1,293 @@
+pub mod common;
+
+use crate::common::*;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+use wiper::app::App;
+use wiper::config::{ExportFormat, PathStyle};
+use wiper::fs::{export, DataStore, DataStoreType, Folder, FolderEntryType};
+
+mod export_csv {
+    use super::*;
+
+    fn write_csv(app: &mut App<DataStoreType>) -> String {
+        write_csv_with(app, PathStyle::Absolute)
+    }
+
+    fn write_csv_with(app: &mut App<DataStoreType>, paths: PathStyle) -> String {
+        let root = app.store.get_current_path().clone();
+        let mut output = vec![];
+        export::csv(&app.store, &root, paths, None, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn writes_row_per_file_with_raw_sizes() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        app.wait_for_tasks();
+        let output = write_csv(&mut app);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines[0], "path,name,type,size_bytes,modified,owner");
+        // 11 files, folders are left out
+        assert_eq!(lines.len(), 12);
+        assert!(lines[1..].iter().all(|line| line.contains(",file,")));
+
+        let row = lines
+            .iter()
+            .find(|line| line.contains("/a_root_file.txt,"))
+            .unwrap();
+        let fields: Vec<&str> = row.split(',').collect();
+        assert_eq!(fields[1], "a_root_file.txt");
+        assert_eq!(fields[2], "file");
+        assert_eq!(fields[3], "459");
+        assert!(fields[4].ends_with('Z') && fields[4].contains('T'));
+
+        assert!(!lines.iter().any(|line| line.contains("/b_folder,")));
+
+        let files: u64 = lines[1..]
+            .iter()
+            .filter(|line| line.contains(",file,"))
+            .map(|line| line.split(',').nth(3).unwrap().parse::<u64>().unwrap())
+            .sum();
+        assert_eq!(files, 15124);
+    }
+
+    #[test]
+    fn writes_paths_relative_to_root() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        app.wait_for_tasks();
+        let output = write_csv_with(&mut app, PathStyle::Relative);
+
+        assert!(output.contains("\na_root_file.txt,a_root_file.txt,file,459,"));
+        assert!(output.contains("\nb_folder/folder2_file1.txt,folder2_file1.txt,file,"));
+    }
+
+    #[test]
+    fn quotes_names_with_commas_and_quotes() {
+        let postfix = "38";
+        create_testing_files(postfix);
+        let root = PathBuf::from(format!("{}_{}", TEST_FILE_PATH_EDIT, postfix));
+        fs::write(root.join("a,\"b\".txt"), [0u8; 10]).unwrap();
+
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        app.wait_for_tasks();
+        let output = write_csv(&mut app);
+        cleanup_testing_files(postfix);
+
+        let row = output
+            .lines()
+            .find(|line| line.contains("a,\"\"b\"\".txt"))
+            .unwrap();
+        assert!(row.starts_with('"'));
+        assert!(row.contains(",\"a,\"\"b\"\".txt\",file,10,"));
+    }
+}
+
+mod export_formats {
+    use super::*;
+
+    fn write(format: ExportFormat, paths: PathStyle) -> String {
+        let mut app: App<DataStoreType> = setup_app_view();
+        app.wait_for_tasks();
+        let root = app.store.get_current_path().clone();
+        let mut output = vec![];
+        export::export(format, &app.store, &root, paths, None, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn writes_only_entries_shown_with_filters() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        app.wait_for_tasks();
+        app.ui_config.min_size = Some(1000);
+        let root = app.store.get_current_path().clone();
+
+        for format in [ExportFormat::Csv, ExportFormat::Tree, ExportFormat::Json] {
+            let mut output = vec![];
+            let visible = Some(&app.ui_config);
+            export::export(
+                format,
+                &app.store,
+                &root,
+                PathStyle::Relative,
+                visible,
+                &mut output,
+            )
+            .unwrap();
+            let output = String::from_utf8(output).unwrap();
+            assert!(!output.contains("a_root_file.txt"), "{:?}", format);
+            // Folders shown are written whole, small files below them too
+            assert!(output.contains("b_folder"), "{:?}", format);
+            assert!(output.contains("folder2_file1.txt"), "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn dispatches_csv() {
+        let output = write(ExportFormat::Csv, PathStyle::Relative);
+        assert!(output.starts_with("path,name,type,size_bytes,modified,owner\n"));
+        assert!(output.contains("\na_root_file.txt,a_root_file.txt,file,459,"));
+    }
+
+    #[test]
+    fn nests_entries_in_json() {
+        let output = write(ExportFormat::Json, PathStyle::Relative);
+        let export: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(export["format_version"], export::JSON_FORMAT_VERSION);
+        let root = &export["root"];
+        assert_eq!(root["path"], ".");
+        assert_eq!(root["type"], "directory");
+        assert_eq!(root["bytes"], 15124);
+        assert_eq!(root["error"], false);
+
+        let children = root["children"].as_array().unwrap();
+        let names: Vec<&str> = children
+            .iter()
+            .map(|c| c["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "a_folder",
+                "a_root_file.txt",
+                "b_folder",
+                "c_folder",
+                "d_root_file.txt",
+                "z_root_file.txt"
+            ]
+        );
+        let b_folder = &children[2];
+        assert_eq!(b_folder["bytes"], 5957);
+        assert_eq!(b_folder["children"].as_array().unwrap().len(), 3);
+        assert_eq!(
+            b_folder["children"][0]["path"],
+            "b_folder/folder2_file1.txt"
+        );
+        assert!(children[1].get("children").is_none());
+        // Stable field set, written even when unknown
+        let mut fields: Vec<&String> = children[1].as_object().unwrap().keys().collect();
+        fields.sort();
+        assert_eq!(fields, ["bytes", "error", "mtime", "name", "path", "type"]);
+    }
+
+    /// Entries other than parent entry as (title, kind, size, modified), sorted by title
+    fn entries(folder: &Folder) -> Vec<(String, FolderEntryType, Option<u64>, Option<SystemTime>)> {
+        let mut entries: Vec<_> = folder
+            .entries()
+            .iter()
+            .filter(|entry| entry.kind != FolderEntryType::Parent)
+            .map(|entry| {
+                (
+                    entry.title.clone(),
+                    entry.kind.clone(),
+                    entry.size,
+                    entry.modified,
+                )
+            })
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries
+    }
+
+    #[test]
+    fn reads_json_back_losslessly() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        app.wait_for_tasks();
+        let root = app.store.get_current_path().clone();
+        // Modification times keep their nanoseconds
+        let nanos = SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789);
+        let folder = app.store.get_folder_mut(&root.join("b_folder")).unwrap();
+        folder.update_entry(1, |entry| entry.modified = Some(nanos));
+        folder.has_error = true;
+
+        let mut output = vec![];
+        export::json(&app.store, &root, PathStyle::Absolute, None, &mut output).unwrap();
+        let folders = export::read_json(output.as_slice()).unwrap();
+
+        assert_eq!(folders.len(), 4);
+        for (path, folder) in &folders {
+            let scanned = app.store.get_folder(path).unwrap();
+            assert_eq!(entries(folder), entries(scanned), "{:?}", path);
+            assert_eq!(folder.has_error, scanned.has_error);
+            assert_eq!(folder.total_size(), scanned.total_size());
+        }
+        let b_folder = &folders
+            .iter()
+            .find(|(path, _)| path.ends_with("b_folder"))
+            .unwrap()
+            .1;
+        assert!(b_folder.has_error);
+        assert!(b_folder
+            .entries()
+            .iter()
+            .any(|entry| entry.modified == Some(nanos)));
+    }
+
+    #[test]
+    fn refuses_other_format_versions() {
+        let export = r#"{"format_version": 99, "root": {}}"#;
+        let err = export::read_json(export.as_bytes()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("version 99"));
+
+        let err = export::read_json(r#"{"name": "x"}"#.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("Missing format_version"));
+    }
+
+    #[test]
+    fn refuses_malformed_timestamps() {
+        let export = |mtime: &str| {
+            format!(
+                r#"{{"format_version": {}, "root": {{"name": "root", "path": "root",
+                "type": "directory", "bytes": 1, "mtime": null, "error": false, "children": [
+                {{"name": "a", "path": "root/a", "type": "file", "bytes": 1, "mtime": "{}",
+                "error": false}}]}}}}"#,
+                export::JSON_FORMAT_VERSION,
+                mtime
+            )
+        };
+        assert!(export::read_json(export("2024-02-29T12:00:00Z").as_bytes()).is_ok());
+        for mtime in [
+            "18446744073709551615-01-01T00:00:00Z",
+            "9223372036854775807-12-31T23:59:59Z",
+            "2024-13-01T00:00:00Z",
+            "2024-01-01T25:00:00Z",
+            "yesterday",
+        ] {
+            let err = export::read_json(export(mtime).as_bytes()).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData, "{}", mtime);
+            assert!(err.to_string().contains("Invalid mtime"), "{}", mtime);
+        }
+    }
+
+    #[test]
+    fn writes_ncdu_format() {
+        let output = write(ExportFormat::Ncdu, PathStyle::Relative);
+        let export: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(export[0], 1);
+        assert_eq!(export[2]["progname"], "wiper");
+
+        // Root keeps absolute path, folders are arrays led by their info
+        let root = export[3].as_array().unwrap();
+        assert!(root[0]["name"].as_str().unwrap().starts_with('/'));
+        assert_eq!(root.len(), 7);
+        assert_eq!(root[1][0]["name"], "a_folder");
+        assert_eq!(root[1][1]["asize"], 459);
+        assert_eq!(root[2]["name"], "a_root_file.txt");
+    }
+
+    #[test]
+    fn draws_tree_biggest_first() {
+        let output = write(ExportFormat::Tree, PathStyle::Relative);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], ". (14.77 KB)");
+        assert_eq!(lines[1], "├── b_folder/ (5.82 KB)");
+        assert_eq!(lines[2], "│   ├── folder2_file3.txt (2.68 KB)");
+        assert_eq!(lines.last(), Some(&"└── z_root_file.txt (459 bytes)"));
+        // Every file and folder below root
+        assert_eq!(lines.len(), 15);
+    }
+}
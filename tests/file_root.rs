@@ -0,0 +1,107 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::path::{Path, PathBuf};
+use wiper::app::App;
+use wiper::config::{EnterAction, InitConfig};
+use wiper::events::handle_key_events;
+use wiper::fs::{normalize_path, resolve_root, DataStoreType, FolderEntryType, ScanRoot};
+
+mod file_root {
+    use super::*;
+
+    fn press(app: &mut App<DataStoreType>, code: KeyCode) {
+        handle_key_events(KeyEvent::new(code, KeyModifiers::NONE), app).unwrap();
+    }
+
+    fn setup_app_file(path: &str) -> App<DataStoreType> {
+        let config = InitConfig {
+            file_path: Some(path.to_string()),
+            ..Default::default()
+        };
+        let mut app: App<DataStoreType> = App::new(config);
+        app.ui_config.enter_action = EnterAction::Nothing;
+        app.ui_config.move_to_trash = false;
+        app.init();
+        handle_tasks_synchronously(&mut app);
+        app
+    }
+
+    #[test]
+    fn tells_files_from_folders() {
+        let file = format!("{}/a_root_file.txt", TEST_FILE_PATH_VIEW);
+        assert_eq!(
+            resolve_root(Path::new(&file)),
+            ScanRoot::File(normalize_path(&file))
+        );
+        assert_eq!(
+            resolve_root(Path::new(TEST_FILE_PATH_VIEW)),
+            ScanRoot::Folder(normalize_path(TEST_FILE_PATH_VIEW))
+        );
+    }
+
+    #[test]
+    fn lists_file_alone_with_its_size() {
+        let file = format!("{}/a_root_file.txt", TEST_FILE_PATH_VIEW);
+        let app = setup_app_file(&file);
+
+        assert_eq!(app.file_root, Some(normalize_path(&file)));
+        let folder = get_current_folder(&app).unwrap();
+        let files: Vec<_> = folder
+            .entries()
+            .iter()
+            .filter(|entry| entry.kind == FolderEntryType::File)
+            .collect();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].size, Some(459));
+        assert!(files[0].modified.is_some());
+        assert_eq!(folder.entries().len(), 2);
+        assert!(app.scan_errors.is_empty());
+    }
+
+    #[test]
+    fn opens_parent_folder_when_going_up() {
+        let file = format!("{}/a_root_file.txt", TEST_FILE_PATH_VIEW);
+        let mut app = setup_app_file(&file);
+
+        press(&mut app, KeyCode::Char('h'));
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(app.file_root, None);
+        assert_eq!(app.root_path, normalize_path(TEST_FILE_PATH_VIEW));
+        assert_eq!(get_current_folder(&app).unwrap().total_size(), 15124);
+    }
+
+    #[test]
+    fn deletes_file_root() {
+        let postfix = "43";
+        create_testing_files(postfix);
+        let file = format!("{}_{}/file_to_delete_1.txt", TEST_FILE_PATH_EDIT, postfix);
+        let mut app = setup_app_file(&file);
+
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char('d'));
+        press(&mut app, KeyCode::Char('d'));
+
+        assert!(!PathBuf::from(&file).exists());
+        assert_eq!(get_current_folder(&app).unwrap().entries().len(), 1);
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn opens_file_from_root_prompt() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        press(&mut app, KeyCode::Char('O'));
+        app.path_prompt.as_mut().unwrap().input =
+            format!("{}/d_root_file.txt", TEST_FILE_PATH_VIEW);
+        press(&mut app, KeyCode::Enter);
+        handle_tasks_synchronously(&mut app);
+
+        assert!(app.path_prompt.is_none());
+        assert!(app.file_root.is_some());
+        assert_eq!(get_current_folder(&app).unwrap().total_size(), 916);
+    }
+}
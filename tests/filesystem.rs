@@ -0,0 +1,264 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use wiper::fs::{
+    scan_folder_streaming, scan_folder_with, FileSystem, Folder, FolderEntryType, HardLinks,
+    MemoryFileSystem, ScanError, ScanOptions,
+};
+
+mod filesystem {
+    use super::*;
+
+    fn scan(fs: &MemoryFileSystem, path: &str) -> (Folder, Vec<ScanError>) {
+        scan_folder_with(
+            fs,
+            PathBuf::from(path),
+            &ScanOptions::default(),
+            &mut HardLinks::default(),
+        )
+    }
+
+    /// Kind and size of entry with provided title
+    fn entry(folder: &Folder, title: &str) -> (FolderEntryType, Option<u64>) {
+        let entry = folder
+            .entries()
+            .iter()
+            .find(|entry| entry.title == title)
+            .unwrap_or_else(|| panic!("{} is not listed", title));
+        (entry.kind.clone(), entry.size)
+    }
+
+    #[test]
+    fn lists_files_and_folders() {
+        let mut fs = MemoryFileSystem::new();
+        fs.file("/root/a.txt", 10)
+            .file("/root/sub/b.txt", 20)
+            .folder("/root/empty");
+
+        let (folder, errors) = scan(&fs, "/root");
+        assert!(errors.is_empty());
+        assert!(!folder.has_error);
+        assert_eq!(folder.entries().len(), 4);
+        assert_eq!(entry(&folder, "a.txt"), (FolderEntryType::File, Some(10)));
+        assert_eq!(entry(&folder, "sub").0, FolderEntryType::Folder);
+        assert_eq!(entry(&folder, "empty").0, FolderEntryType::Folder);
+    }
+
+    #[test]
+    fn does_not_descend_into_links_to_parents() {
+        let mut fs = MemoryFileSystem::new();
+        fs.file("/root/sub/a.txt", 10)
+            .symlink("/root/sub/up", "/root")
+            .symlink("/root/sub/itself", ".")
+            .symlink("/root/sub/relative_up", "..");
+
+        let (folder, errors) = scan(&fs, "/root/sub");
+        assert!(errors.is_empty());
+        assert_eq!(entry(&folder, "up").0, FolderEntryType::File);
+        assert_eq!(entry(&folder, "itself").0, FolderEntryType::File);
+        assert_eq!(entry(&folder, "relative_up").0, FolderEntryType::File);
+        // Looping link is not followed through a path either
+        let (looped, _) = scan(&fs, "/root/sub/up/sub");
+        assert_eq!(entry(&looped, "up").0, FolderEntryType::File);
+    }
+
+    #[test]
+    fn follows_links_to_each_other_once() {
+        let mut fs = MemoryFileSystem::new();
+        fs.file("/root/a/a.txt", 10)
+            .file("/root/b/b.txt", 20)
+            .symlink("/root/a/to_b", "/root/b")
+            .symlink("/root/b/to_a", "/root/a");
+
+        let (a, _) = scan(&fs, "/root/a");
+        assert_eq!(entry(&a, "to_b").0, FolderEntryType::Folder);
+        // Back in the folder the walk started from
+        let (b, _) = scan(&fs, "/root/a/to_b");
+        assert_eq!(entry(&b, "to_a").0, FolderEntryType::File);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn follows_links_to_each_other_once_on_disk() {
+        use std::os::unix::fs::symlink;
+        use wiper::fs::RealFileSystem;
+
+        let root = std::env::temp_dir().join(format!("wiper_link_cycle_{}", std::process::id()));
+        std::fs::create_dir_all(root.join("a")).unwrap();
+        std::fs::create_dir_all(root.join("b")).unwrap();
+        symlink(root.join("b"), root.join("a/to_b")).unwrap();
+        symlink(root.join("a"), root.join("b/to_a")).unwrap();
+
+        let scan_real = |path: PathBuf| {
+            scan_folder_with(
+                &RealFileSystem,
+                path,
+                &ScanOptions::default(),
+                &mut HardLinks::default(),
+            )
+            .0
+        };
+        let a = scan_real(root.join("a"));
+        let b = scan_real(root.join("a/to_b"));
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(entry(&a, "to_b").0, FolderEntryType::Folder);
+        assert_eq!(entry(&b, "to_a").0, FolderEntryType::File);
+    }
+
+    #[test]
+    fn descends_into_links_to_other_folders() {
+        let mut fs = MemoryFileSystem::new();
+        fs.file("/data/big.bin", 100)
+            .symlink("/root/data", "/data")
+            .symlink("/root/file_link", "/data/big.bin");
+
+        let (folder, _) = scan(&fs, "/root");
+        assert_eq!(entry(&folder, "data").0, FolderEntryType::Folder);
+        // Links to files take only their own size
+        assert_eq!(
+            entry(&folder, "file_link"),
+            (FolderEntryType::File, Some("/data/big.bin".len() as u64))
+        );
+
+        let (linked, _) = scan(&fs, "/root/data");
+        assert_eq!(
+            entry(&linked, "big.bin"),
+            (FolderEntryType::File, Some(100))
+        );
+    }
+
+    #[test]
+    fn lists_broken_and_looping_links_as_files() {
+        let mut fs = MemoryFileSystem::new();
+        fs.symlink("/root/dangling", "/missing")
+            .symlink("/root/ping", "/root/pong")
+            .symlink("/root/pong", "/root/ping");
+
+        let (folder, errors) = scan(&fs, "/root");
+        assert!(errors.is_empty());
+        assert_eq!(entry(&folder, "dangling").0, FolderEntryType::File);
+        assert_eq!(entry(&folder, "ping").0, FolderEntryType::File);
+        assert!(fs.metadata(Path::new("/root/ping")).is_err());
+    }
+
+    #[test]
+    fn counts_hard_links_once() {
+        let mut fs = MemoryFileSystem::new();
+        fs.file("/root/a/data.bin", 100)
+            .hard_link("/root/b/data.bin", "/root/a/data.bin")
+            .hard_link("/root/b/copy.bin", "/root/a/data.bin");
+        let options = ScanOptions::default();
+        let mut hard_links = HardLinks::default();
+
+        let (a, _) = scan_folder_with(&fs, "/root/a".into(), &options, &mut hard_links);
+        let (b, _) = scan_folder_with(&fs, "/root/b".into(), &options, &mut hard_links);
+        assert_eq!(a.total_size() + b.total_size(), 100);
+        assert_eq!(entry(&a, "data.bin").1, Some(100));
+        assert_eq!(entry(&b, "copy.bin").1, Some(0));
+        assert_eq!(entry(&b, "data.bin").1, Some(0));
+
+        // Rescans count the file at the same path
+        let (a, _) = scan_folder_with(&fs, "/root/a".into(), &options, &mut hard_links);
+        assert_eq!(entry(&a, "data.bin").1, Some(100));
+    }
+
+    #[test]
+    fn counts_remaining_hard_link_once_first_one_is_gone() {
+        let mut fs = MemoryFileSystem::new();
+        fs.file("/root/a/data.bin", 100)
+            .hard_link("/root/b/data.bin", "/root/a/data.bin")
+            .hard_link("/root/c/data.bin", "/root/a/data.bin");
+        let options = ScanOptions::default();
+        let mut hard_links = HardLinks::default();
+        scan_folder_with(&fs, "/root/a".into(), &options, &mut hard_links);
+
+        fs.remove("/root/a/data.bin");
+        let (b, _) = scan_folder_with(&fs, "/root/b".into(), &options, &mut hard_links);
+        let (c, _) = scan_folder_with(&fs, "/root/c".into(), &options, &mut hard_links);
+        assert_eq!(entry(&b, "data.bin").1, Some(100));
+        assert_eq!(entry(&c, "data.bin").1, Some(0));
+    }
+
+    #[test]
+    fn reports_unreadable_folder() {
+        let mut fs = MemoryFileSystem::new();
+        fs.file("/root/locked/secret.txt", 10)
+            .fail_read_dir("/root/locked", io::ErrorKind::PermissionDenied);
+
+        let (folder, errors) = scan(&fs, "/root/locked");
+        assert!(folder.has_error);
+        assert_eq!(folder.entries().len(), 1);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, PathBuf::from("/root/locked"));
+        assert!(errors[0].is_permission_denied());
+
+        // Parent still lists it as a folder
+        let (parent, errors) = scan(&fs, "/root");
+        assert!(errors.is_empty());
+        assert_eq!(entry(&parent, "locked").0, FolderEntryType::Folder);
+    }
+
+    #[test]
+    fn reports_entries_without_metadata() {
+        let mut fs = MemoryFileSystem::new();
+        fs.file("/root/ok.txt", 10)
+            .file("/root/broken.txt", 20)
+            .folder("/root/broken_folder")
+            .fail_metadata("/root/broken.txt", io::ErrorKind::PermissionDenied)
+            .fail_metadata("/root/broken_folder", io::ErrorKind::Other);
+
+        let (folder, errors) = scan(&fs, "/root");
+        assert!(folder.has_error);
+        assert_eq!(entry(&folder, "ok.txt").1, Some(10));
+        assert_eq!(entry(&folder, "broken.txt"), (FolderEntryType::File, None));
+        let paths: Vec<&Path> = errors.iter().map(|err| err.path.as_path()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                Path::new("/root/broken.txt"),
+                Path::new("/root/broken_folder")
+            ]
+        );
+    }
+
+    #[test]
+    fn fails_on_missing_folder() {
+        let fs = MemoryFileSystem::new();
+
+        let (folder, errors) = scan(&fs, "/nowhere");
+        assert!(folder.has_error);
+        assert_eq!(errors[0].kind, io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn hands_out_entries_read_so_far() {
+        let mut fs = MemoryFileSystem::new();
+        fs.file("/root/a.txt", 10)
+            .file("/root/b.txt", 20)
+            .folder("/root/sub");
+
+        let mut partials = vec![];
+        let (folder, _) = scan_folder_streaming(
+            &fs,
+            PathBuf::from("/root"),
+            &ScanOptions::default(),
+            &mut HardLinks::default(),
+            Duration::ZERO,
+            |partial| partials.push((partial.entries().len(), partial.total_size())),
+        );
+        // Parent entry comes first
+        assert_eq!(partials, vec![(2, 10), (3, 30), (4, 30)]);
+        assert_eq!(folder.entries().len(), 4);
+
+        let mut partials = 0;
+        scan_folder_streaming(
+            &fs,
+            PathBuf::from("/root"),
+            &ScanOptions::default(),
+            &mut HardLinks::default(),
+            Duration::MAX,
+            |_| partials += 1,
+        );
+        assert_eq!(partials, 0);
+    }
+}
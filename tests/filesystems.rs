@@ -0,0 +1,114 @@
+pub mod common;
+
+use crate::common::*;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use std::path::PathBuf;
+use wiper::app::App;
+use wiper::fs::{
+    filesystems_usage, freed_by_filesystem, DataStore, DataStoreType, FilesystemUsage, FolderEntry,
+};
+use wiper::ui::freed_summary;
+use wiper::views::View;
+
+mod filesystems {
+    use super::*;
+
+    #[test]
+    fn groups_scanned_files_by_filesystem() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        let root = app.store.get_current_path().clone();
+        let usage = filesystems_usage(&app.store, &root);
+
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].size, 15124);
+        assert_eq!(usage[0].files, 11);
+        assert!(root.starts_with(&usage[0].mount_point));
+    }
+
+    #[test]
+    fn toggles_filesystems_view() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        app.on_toggle_filesystems_view();
+        assert!(matches!(app.view, View::Filesystems(_)));
+
+        app.on_toggle_filesystems_view();
+        assert!(matches!(app.view, View::Folder));
+    }
+
+    #[test]
+    fn sums_freed_space_of_entries_by_filesystem() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        let root = app.store.get_current_path().clone();
+        let folder = get_current_folder(&app).unwrap();
+        let entries: Vec<&FolderEntry> = folder
+            .entries()
+            .iter()
+            .filter(|entry| ["a_folder", "d_root_file.txt", ".."].contains(&entry.title.as_str()))
+            .collect();
+        let freed = freed_by_filesystem(&app.store, &root, &entries);
+
+        assert_eq!(freed.len(), 1);
+        assert_eq!(freed[0].size, 1376 + 916);
+        assert_eq!(freed[0].files, 3);
+    }
+
+    #[test]
+    fn lists_freed_space_per_filesystem_only_when_several() {
+        let usage = |mount_point: &str, size: u64| FilesystemUsage {
+            device: Some(size),
+            mount_point: PathBuf::from(mount_point),
+            size,
+            files: 1,
+        };
+        assert_eq!(freed_summary(&[]), None);
+        assert_eq!(freed_summary(&[usage("/", 0)]), None);
+        assert_eq!(
+            freed_summary(&[usage("/", 2048)]),
+            Some("2.00 KB".to_string())
+        );
+        assert_eq!(
+            freed_summary(&[usage("/data", 4096), usage("/", 2048)]),
+            Some("4.00 KB on /data, 2.00 KB on /".to_string())
+        );
+    }
+
+    #[test]
+    fn shows_freed_space_in_deletion_prompt() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        app.on_cursor_down();
+        app.on_delete();
+
+        let screen = |app: &mut App<DataStoreType>| {
+            let area = Rect::new(0, 0, 160, 20);
+            let mut buf = Buffer::empty(area);
+            app.render(area, &mut buf);
+            buf.content
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect::<String>()
+        };
+        // Trash frees nothing until it is emptied
+        let trashed = screen(&mut app);
+        assert!(
+            trashed.contains("Move a_folder (1.34 KB) to trash?"),
+            "{trashed}"
+        );
+        assert!(!trashed.contains("freeing"));
+
+        app.ui_config.move_to_trash = false;
+        let deleted = screen(&mut app);
+        assert!(
+            deleted.contains("Delete a_folder, freeing 1.34 KB?"),
+            "{deleted}"
+        );
+    }
+}
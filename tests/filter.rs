@@ -0,0 +1,140 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use wiper::app::App;
+use wiper::config::InitConfig;
+use wiper::events::handle_key_events;
+use wiper::filter::{glob_match, CaseMode, MatchMode, NameFilter};
+use wiper::fs::{DataStoreType, FolderEntryType};
+
+mod filter {
+    use super::*;
+
+    fn filter(query: &str, case: CaseMode, mode: MatchMode) -> NameFilter {
+        NameFilter {
+            query: query.to_string(),
+            case,
+            mode,
+        }
+    }
+
+    #[test]
+    fn uses_smart_case_by_default() {
+        let lower = filter("readme", CaseMode::Smart, MatchMode::Substring);
+        assert!(lower.matches("README.md"));
+        assert!(lower.matches("readme.md"));
+
+        let upper = filter("README", CaseMode::Smart, MatchMode::Substring);
+        assert!(upper.matches("README.md"));
+        assert!(!upper.matches("readme.md"));
+    }
+
+    #[test]
+    fn forces_case_behavior() {
+        assert!(!filter("readme", CaseMode::Sensitive, MatchMode::Substring).matches("README"));
+        assert!(filter("README", CaseMode::Insensitive, MatchMode::Substring).matches("readme"));
+    }
+
+    #[test]
+    fn matches_globs_against_whole_title() {
+        assert!(glob_match("*.log", "app.log"));
+        assert!(!glob_match("*.log", "app.log.gz"));
+        assert!(glob_match("a?c*", "abcdef"));
+        assert!(glob_match("*b*b*", "abcabc"));
+        assert!(!glob_match("a?c", "ac"));
+        assert!(filter("*.TXT", CaseMode::Insensitive, MatchMode::Glob).matches("a.txt"));
+    }
+
+    #[test]
+    fn matches_everything_with_empty_query() {
+        assert!(NameFilter::default().matches("anything"));
+    }
+
+    #[test]
+    fn moves_cursor_over_matching_entries_only() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        app.on_start_filter();
+        for c in "root".chars() {
+            app.on_filter_input(c);
+        }
+        assert_cursor_index(&app, 4);
+        app.on_cursor_down();
+        assert_cursor_index(&app, 5);
+        app.on_cursor_up();
+        app.on_cursor_up();
+        assert_cursor_index(&app, 0);
+        app.on_cursor_down();
+        assert_cursor_index(&app, 4);
+    }
+
+    #[test]
+    fn clears_filter_on_escape() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        app.on_start_filter();
+        app.on_filter_input('x');
+        app.on_filter_submit();
+        assert!(!app.ui_config.filter_editing);
+        assert!(app.ui_config.name_filter.is_active());
+
+        app.on_escape();
+        assert!(!app.ui_config.name_filter.is_active());
+    }
+
+    fn visible_titles(app: &App<DataStoreType>) -> Vec<String> {
+        get_current_folder(app)
+            .unwrap()
+            .entries()
+            .iter()
+            .filter(|entry| {
+                entry.kind == FolderEntryType::Folder || entry.kind == FolderEntryType::File
+            })
+            .filter(|entry| app.ui_config.is_visible(entry))
+            .map(|entry| entry.title.clone())
+            .collect()
+    }
+
+    fn press_alt(app: &mut App<DataStoreType>, c: char) {
+        handle_key_events(KeyEvent::new(KeyCode::Char(c), KeyModifiers::ALT), app).unwrap();
+    }
+
+    #[test]
+    fn hides_entries_below_size_preset() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        app.ui_config.size_presets = vec![1000, 5000];
+
+        press_alt(&mut app, '1');
+        assert_eq!(app.ui_config.min_size, Some(1000));
+        assert_eq!(visible_titles(&app), ["a_folder", "b_folder", "c_folder"]);
+        assert_eq!(
+            get_current_folder(&app).unwrap().get_selected_entry().title,
+            "a_folder"
+        );
+
+        press_alt(&mut app, '2');
+        assert_eq!(visible_titles(&app), ["b_folder", "c_folder"]);
+
+        // Missing preset keeps current floor
+        press_alt(&mut app, '5');
+        assert_eq!(app.ui_config.min_size, Some(5000));
+
+        press_alt(&mut app, '0');
+        assert_eq!(app.ui_config.min_size, None);
+        assert_eq!(visible_titles(&app).len(), 6);
+    }
+
+    #[test]
+    fn parses_size_presets() {
+        let args = ["wiper", "--size-presets", "1M,2G"].map(String::from);
+        let config = InitConfig::build(args.into_iter()).unwrap();
+        assert_eq!(config.size_presets, Some(vec![1 << 20, 2 << 30]));
+
+        let args = ["wiper", "--size-presets", "1M,huge"].map(String::from);
+        assert!(InitConfig::build(args.into_iter()).is_err());
+    }
+}
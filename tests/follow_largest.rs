@@ -0,0 +1,76 @@
+pub mod common;
+
+use crate::common::*;
+use std::ffi::OsString;
+use wiper::app::{App, Leave};
+use wiper::fs::{DataStore, DataStoreType};
+
+mod follow_largest {
+    use super::*;
+
+    fn selected_title(app: &App<DataStoreType>) -> String {
+        get_current_folder(app)
+            .unwrap()
+            .get_selected_entry()
+            .title
+            .clone()
+    }
+
+    #[test]
+    fn selects_largest_entry_then_enters_it() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        app.on_follow_largest();
+        assert_eq!(selected_title(&app), "b_folder");
+
+        app.on_follow_largest();
+        assert!(app.store.get_current_path().ends_with("b_folder"));
+    }
+
+    #[test]
+    fn follows_largest_entries_down_to_a_file() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        app.on_follow_largest_all();
+        assert!(app.store.get_current_path().ends_with("b_folder"));
+        assert_eq!(selected_title(&app), "folder2_file3.txt");
+    }
+
+    #[test]
+    fn asks_once_before_leaving_marks_behind() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        app.on_cursor_down();
+        app.on_toggle_mark();
+
+        app.on_follow_largest_all();
+        assert_eq!(app.pending_leave, Some((Leave::LargestAll, 1)));
+        assert!(!app.store.get_current_path().ends_with("b_folder"));
+
+        app.on_confirm_leave();
+        assert_eq!(app.pending_leave, None);
+        assert!(app.store.get_current_path().ends_with("b_folder"));
+        assert_eq!(selected_title(&app), "folder2_file3.txt");
+    }
+
+    #[test]
+    fn asks_again_after_leaving_is_cancelled() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        app.on_cursor_down();
+        app.on_toggle_mark();
+        app.on_follow_largest();
+
+        app.on_follow_largest();
+        assert_eq!(
+            app.pending_leave,
+            Some((Leave::Child(OsString::from("b_folder")), 1))
+        );
+        app.on_cancel_leave();
+        app.on_follow_largest();
+        assert!(app.pending_leave.is_some());
+        assert!(!app.store.get_current_path().ends_with("b_folder"));
+    }
+}
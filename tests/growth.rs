@@ -0,0 +1,113 @@
+pub mod common;
+
+use crate::common::*;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use wiper::app::App;
+use wiper::config::InitConfig;
+use wiper::fs::{DataStoreType, Snapshot};
+
+mod growth {
+    use super::*;
+
+    fn scanned_view_app() -> App<DataStoreType> {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        app
+    }
+
+    fn render(app: &mut App<DataStoreType>) -> String {
+        let area = Rect::new(0, 0, 140, 24);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content.iter().map(|cell| cell.symbol()).collect()
+    }
+
+    #[test]
+    fn keeps_sizes_of_folders_and_root() {
+        let app = scanned_view_app();
+        let snapshot = Snapshot::build(&app.store, &app.root_path);
+        assert_eq!(snapshot.history.len(), 4);
+        assert_eq!(snapshot.history[""][0].1, 15124);
+        assert_eq!(snapshot.history["a_folder"][0].1, 1376);
+        assert!(!snapshot.history.contains_key("a_root_file.txt"));
+    }
+
+    #[test]
+    fn caps_history_depth() {
+        let app = scanned_view_app();
+        let mut previous = Snapshot::build(&app.store, &app.root_path);
+        previous
+            .history
+            .insert("a_folder".to_string(), vec![(1, 100), (2, 200), (3, 300)]);
+        previous
+            .history
+            .insert("gone".to_string(), vec![(1, 100), (2, 200)]);
+
+        let mut snapshot = Snapshot::build(&app.store, &app.root_path);
+        let now = snapshot.history["a_folder"][0].0;
+        snapshot.keep_history(&previous, 3);
+        assert_eq!(
+            snapshot.history["a_folder"],
+            vec![(2, 200), (3, 300), (now, 1376)]
+        );
+        assert_eq!(snapshot.history["b_folder"].len(), 2);
+        assert!(!snapshot.history.contains_key("gone"));
+    }
+
+    #[test]
+    fn shows_history_of_selected_folder() {
+        let mut app = scanned_view_app();
+        let mut previous = Snapshot::build(&app.store, &app.root_path);
+        previous
+            .history
+            .insert("a_folder".to_string(), vec![(0, 1000), (3600, 1200)]);
+        app.previous_snapshot = Some(previous);
+        app.update_snapshot();
+
+        app.on_cursor_down();
+        let (title, history) = app.selected_history().unwrap();
+        assert_eq!(title, "a_folder");
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[2].1, 1376);
+
+        assert!(!render(&mut app).contains("Growth"));
+        app.on_toggle_growth();
+        let screen = render(&mut app);
+        assert!(screen.contains("Growth"));
+        assert!(screen.contains("1.34 KB (+376 bytes) over 3 scans"));
+    }
+
+    #[test]
+    fn files_have_no_history() {
+        let mut app = scanned_view_app();
+        app.update_snapshot();
+        for _ in 0..4 {
+            app.on_cursor_down();
+        }
+        let (title, history) = app.selected_history().unwrap();
+        assert_eq!(title, "a_root_file.txt");
+        assert!(history.is_empty());
+
+        app.on_toggle_growth();
+        assert!(render(&mut app).contains("Scans are kept with --cache-scan"));
+    }
+
+    #[test]
+    fn parses_history_depth() {
+        let build = |args: &[&str]| {
+            InitConfig::build(
+                std::iter::once("wiper")
+                    .chain(args.iter().copied())
+                    .map(String::from),
+            )
+        };
+        assert_eq!(
+            build(&["--history-depth", "5"]).unwrap().history_depth,
+            Some(5)
+        );
+        assert!(build(&["--history-depth", "0"]).is_err());
+        assert!(build(&["--history-depth"]).is_err());
+    }
+}
@@ -2,7 +2,7 @@ pub mod common;
 use crate::common::*;
 
 use wiper::app::App;
-use wiper::fs::DataStoreType;
+use wiper::fs::{DataStore, DataStoreType};
 mod handle_enter {
 
     use super::*;
@@ -38,6 +38,25 @@ mod handle_enter {
         assert_cursor_index(&app, 1);
     }
 
+    #[test]
+    fn jumps_back_to_root_keeping_its_cursor() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        let root_path = app.root_path.clone();
+
+        app.on_cursor_down();
+        app.on_enter();
+        handle_tasks_synchronously(&mut app);
+        assert_parent_folder_a_state(&app);
+
+        app.on_go_to_root();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(app.store.get_current_path(), &root_path);
+        assert_parent_folder_state(&app);
+        assert_cursor_index(&app, 1);
+    }
+
     #[test]
     fn does_nothing_when_tries_to_enter_file() {
         let mut app: App<DataStoreType> = setup_app_view();
@@ -0,0 +1,51 @@
+pub mod common;
+
+use crate::common::*;
+use wiper::app::App;
+use wiper::config::PathStyle;
+use wiper::fs::{DataStore, DataStoreType};
+use wiper::report::HeaviestPath;
+
+mod heaviest_path {
+    use super::*;
+
+    fn build_heaviest_path() -> HeaviestPath {
+        let mut app: App<DataStoreType> = setup_app_view();
+        app.wait_for_tasks();
+        let root = app.store.get_current_path().clone();
+        HeaviestPath::build(&app.store, &root, PathStyle::Absolute)
+    }
+
+    #[test]
+    fn follows_largest_entries_down_to_a_file() {
+        let levels = build_heaviest_path().levels;
+
+        let sizes: Vec<u64> = levels.iter().map(|level| level.size).collect();
+        assert_eq!(sizes, vec![15124, 5957, 2749]);
+        assert!(levels[2].path.ends_with("b_folder/folder2_file3.txt"));
+    }
+
+    #[test]
+    fn writes_paths_relative_to_root() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        app.wait_for_tasks();
+        let root = app.store.get_current_path().clone();
+        let levels = HeaviestPath::build(&app.store, &root, PathStyle::Relative).levels;
+
+        let paths: Vec<&str> = levels.iter().map(|level| level.path.as_str()).collect();
+        assert_eq!(paths, vec![".", "b_folder", "b_folder/folder2_file3.txt"]);
+    }
+
+    #[test]
+    fn writes_indented_lines() {
+        let mut output = vec![];
+        build_heaviest_path().write(&mut output, false).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].ends_with("view 15124 (14.77 KB)"));
+        assert_eq!(lines[1], "  b_folder 5957 (5.82 KB)");
+        assert_eq!(lines[2], "    folder2_file3.txt 2749 (2.68 KB)");
+    }
+}
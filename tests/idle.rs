@@ -0,0 +1,88 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use std::time::{Duration, Instant};
+use wiper::app::App;
+use wiper::config::{IdleAction, InitConfig};
+use wiper::events::handle_key_events;
+use wiper::fs::DataStoreType;
+
+mod idle {
+    use super::*;
+
+    fn setup_app(action: IdleAction) -> App<DataStoreType> {
+        let mut app: App<DataStoreType> = setup_app_view();
+        app.ui_config.idle_timeout_secs = Some(60);
+        app.ui_config.idle_action = action;
+        handle_tasks_synchronously(&mut app);
+        app
+    }
+
+    fn pass_idle_timeout(app: &mut App<DataStoreType>) {
+        app.last_input = Instant::now() - Duration::from_secs(61);
+        app.tick();
+    }
+
+    fn press(app: &mut App<DataStoreType>, code: KeyCode) {
+        handle_key_events(KeyEvent::new(code, KeyModifiers::NONE), app).unwrap();
+    }
+
+    #[test]
+    fn keeps_running_before_timeout() {
+        let mut app = setup_app(IdleAction::Quit);
+        app.last_input = Instant::now() - Duration::from_secs(59);
+        app.tick();
+        assert!(app.running);
+        assert!(!app.idle);
+    }
+
+    #[test]
+    fn quits_after_timeout_cancelling_pending_deletion() {
+        let mut app = setup_app(IdleAction::Quit);
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char('d'));
+        assert!(app.ui_config.confirming_deletion);
+
+        pass_idle_timeout(&mut app);
+        assert!(!app.running);
+        assert!(!app.ui_config.confirming_deletion);
+    }
+
+    #[test]
+    fn sleeps_until_key_which_only_wakes() {
+        let mut app = setup_app(IdleAction::Sleep);
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char('d'));
+        pass_idle_timeout(&mut app);
+        assert!(app.running);
+        assert!(app.idle);
+        assert!(!app.ui_config.confirming_deletion);
+
+        let area = Rect::new(0, 0, 100, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        let content: String = buf.content.iter().map(|cell| cell.symbol()).collect();
+        assert!(content.contains("Idle, press any key to resume"));
+
+        press(&mut app, KeyCode::Char('d'));
+        assert!(!app.idle);
+        assert!(!app.ui_config.confirming_deletion);
+        app.tick();
+        assert!(!app.idle);
+    }
+
+    #[test]
+    fn parses_idle_options() {
+        let args = ["wiper", "--idle-timeout", "600", "--idle-action", "sleep"];
+        let config = InitConfig::build(args.iter().map(|arg| arg.to_string())).unwrap();
+        assert_eq!(config.idle_timeout_secs, Some(600));
+        assert_eq!(config.idle_action, Some(IdleAction::Sleep));
+
+        let args = ["wiper", "--idle-action", "nap"];
+        assert!(InitConfig::build(args.iter().map(|arg| arg.to_string())).is_err());
+    }
+}
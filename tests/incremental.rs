@@ -0,0 +1,165 @@
+pub mod common;
+
+use crate::common::*;
+use std::fs::{create_dir, remove_file, write, File};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use wiper::app::App;
+use wiper::config::InitConfig;
+use wiper::fs::{for_each_folder, DataStoreType, FolderEntryType};
+
+mod incremental {
+    use super::*;
+
+    fn edit_root(postfix: &str) -> PathBuf {
+        PathBuf::from(format!("{}_{}", TEST_FILE_PATH_EDIT, postfix))
+    }
+
+    fn setup_app(root: &Path, incremental_rescan: bool) -> App<DataStoreType> {
+        let mut app: App<DataStoreType> = App::new(InitConfig {
+            file_path: Some(root.to_string_lossy().to_string()),
+            incremental_rescan,
+            ..Default::default()
+        });
+        app.init();
+        handle_tasks_synchronously(&mut app);
+        app
+    }
+
+    /// Moves modification time of every folder a minute back, so scans trust it
+    fn settle_folders(root: &Path) {
+        let settled = SystemTime::now() - Duration::from_secs(60);
+        let mut pending = vec![root.to_path_buf()];
+        while let Some(path) = pending.pop() {
+            File::open(&path).unwrap().set_modified(settled).unwrap();
+            for entry in std::fs::read_dir(&path).unwrap() {
+                let entry = entry.unwrap();
+                if entry.file_type().unwrap().is_dir() {
+                    pending.push(entry.path());
+                }
+            }
+        }
+    }
+
+    /// Stored folders by path relative to root, with titles and sizes of their entries
+    type Tree = Vec<(PathBuf, Vec<(String, Option<u64>)>)>;
+
+    /// Every stored folder, with its entries sorted by name
+    fn tree(app: &App<DataStoreType>, root: &Path) -> Tree {
+        let root = std::fs::canonicalize(root).unwrap();
+        let mut folders = vec![];
+        for_each_folder(&app.store, &root, |path, folder| {
+            let mut entries: Vec<(String, Option<u64>)> = folder
+                .entries()
+                .iter()
+                .filter(|entry| entry.kind != FolderEntryType::Parent)
+                .map(|entry| (entry.title.clone(), entry.size))
+                .collect();
+            entries.sort();
+            let relative = path.strip_prefix(&root).unwrap().to_path_buf();
+            folders.push((relative, entries));
+        });
+        folders.sort();
+        folders
+    }
+
+    #[test]
+    fn matches_full_rescan_after_changes() {
+        let postfix = "65";
+        create_testing_files(postfix);
+        let root = edit_root(postfix);
+        settle_folders(&root);
+        let mut app = setup_app(&root, true);
+
+        // Added, removed and new nested entries, two levels down the untouched root
+        let nested = root.join("folder_to_delete_1");
+        write(nested.join("added.txt"), "twelve bytes").unwrap();
+        remove_file(nested.join("file_to_delete_2.txt")).unwrap();
+        create_dir(nested.join("folder_to_delete_2/new_folder")).unwrap();
+        write(nested.join("folder_to_delete_2/new_folder/new.txt"), "new").unwrap();
+
+        app.on_rescan();
+        handle_tasks_synchronously(&mut app);
+
+        let full = setup_app(&root, false);
+        assert_eq!(tree(&app, &root), tree(&full, &root));
+        // Root and the deepest folder were not touched
+        assert_eq!(app.task_manager.unchanged_tasks, 2);
+        assert_eq!(
+            get_current_folder(&app).unwrap().total_size(),
+            get_current_folder(&full).unwrap().total_size()
+        );
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn matches_full_rescan_after_file_rewritten_in_place() {
+        let postfix = "81";
+        create_testing_files(postfix);
+        let root = edit_root(postfix);
+        settle_folders(&root);
+        let mut app = setup_app(&root, true);
+
+        // Rewriting a file keeps modification time of its folder
+        write(
+            root.join("folder_to_delete_1/file_to_delete_1.txt"),
+            "short",
+        )
+        .unwrap();
+
+        app.on_rescan();
+        handle_tasks_synchronously(&mut app);
+
+        let full = setup_app(&root, false);
+        let stored = tree(&app, &root);
+        assert_eq!(stored, tree(&full, &root));
+        assert_eq!(app.task_manager.unchanged_tasks, stored.len());
+        assert_eq!(
+            get_current_folder(&app).unwrap().total_size(),
+            get_current_folder(&full).unwrap().total_size()
+        );
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn reads_every_folder_again_without_option() {
+        let postfix = "66";
+        create_testing_files(postfix);
+        let root = edit_root(postfix);
+        settle_folders(&root);
+        let mut app = setup_app(&root, false);
+
+        app.on_rescan();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(app.task_manager.unchanged_tasks, 0);
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn reads_recently_changed_folders_again() {
+        let postfix = "67";
+        create_testing_files(postfix);
+        let root = edit_root(postfix);
+        // Folders were just created, their times could still hide a change
+        let mut app = setup_app(&root, true);
+        assert!(get_current_folder(&app).unwrap().modified.is_none());
+
+        app.on_rescan();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(app.task_manager.unchanged_tasks, 0);
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn parses_incremental_option() {
+        let config = InitConfig::build(
+            ["wiper", "--incremental-rescan"]
+                .iter()
+                .map(|arg| arg.to_string()),
+        )
+        .unwrap();
+        assert!(config.incremental_rescan);
+    }
+}
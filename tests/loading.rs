@@ -1,8 +1,12 @@
 pub mod common;
 
 use crate::common::*;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use std::path::PathBuf;
 use wiper::app::App;
-use wiper::fs::DataStoreType;
+use wiper::fs::{scan_folder_with, DataStore, DataStoreType, Folder, HardLinks, MemoryFileSystem};
 
 mod loading {
     use super::*;
@@ -18,4 +22,96 @@ mod loading {
         assert_item_at_index_loading_state(&app, 5, true);
         assert_item_at_index_loading_state(&app, 6, true);
     }
+
+    #[test]
+    fn reports_progress_while_scanning() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        assert_eq!(app.task_manager.progress(), Some(0));
+        assert!(app.window_title().starts_with("wiper — scanning… 0% · "));
+
+        handle_tasks_synchronously(&mut app);
+        assert_eq!(app.task_manager.progress(), None);
+        assert_eq!(
+            app.window_title(),
+            format!("wiper · {}", app.root_path.to_string_lossy())
+        );
+    }
+
+    /// Part of b_folder as if it was still being read, with a subfolder it doesn't really hold
+    fn partial_b_folder() -> Folder {
+        let mut fs = MemoryFileSystem::new();
+        fs.file("/b_folder/folder2_file3.txt", 100)
+            .folder("/b_folder/nested");
+        scan_folder_with(
+            &fs,
+            PathBuf::from("/b_folder"),
+            &Default::default(),
+            &mut HardLinks::default(),
+        )
+        .0
+    }
+
+    fn root_entry(app: &App<DataStoreType>, title: &str) -> (Option<u64>, bool) {
+        let entry = get_current_folder(app)
+            .unwrap()
+            .entries()
+            .iter()
+            .find(|entry| entry.title == title)
+            .unwrap();
+        (entry.size, entry.is_loaded)
+    }
+
+    fn render(app: &mut App<DataStoreType>) -> String {
+        let area = Rect::new(0, 0, 120, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content.iter().map(|cell| cell.symbol()).collect()
+    }
+
+    #[test]
+    fn shows_folders_while_they_are_read() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        let path = app.store.get_current_path().join("b_folder");
+        app.store.remove_path(&path);
+
+        app.task_manager
+            .process_partial(&mut app.store, &path, partial_b_folder());
+        assert_eq!(root_entry(&app, "b_folder"), (Some(100), false));
+
+        // Entries found so far can be browsed, subfolders wait for the folder to be read
+        app.store.set_current_path(&path);
+        app.store
+            .get_current_folder_mut()
+            .unwrap()
+            .select_name("folder2_file3.txt".as_ref());
+        assert!(render(&mut app).contains("scanning…"));
+
+        app.task_manager
+            .process_partial(&mut app.store, &path, partial_b_folder());
+        assert_eq!(app.task_manager.partial_paths.len(), 1);
+
+        app.task_manager.add_task(&path);
+        handle_tasks_synchronously(&mut app);
+        let folder = get_current_folder(&app).unwrap();
+        assert_eq!(folder.get_selected_entry().title, "folder2_file3.txt");
+        assert!(!folder.entries().iter().any(|entry| entry.title == "nested"));
+        assert!(app.task_manager.partial_paths.is_empty());
+
+        app.store
+            .set_current_path(&path.parent().unwrap().to_path_buf());
+        assert_eq!(root_entry(&app, "b_folder"), (Some(5957), true));
+    }
+
+    #[test]
+    fn keeps_complete_folder_while_it_is_rescanned() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        let path = app.store.get_current_path().join("b_folder");
+
+        app.task_manager
+            .process_partial(&mut app.store, &path, partial_b_folder());
+        assert_eq!(root_entry(&app, "b_folder"), (Some(5957), true));
+        assert_eq!(app.store.get_folder(&path).unwrap().entries().len(), 4);
+    }
 }
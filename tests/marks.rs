@@ -0,0 +1,200 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use std::ffi::OsString;
+use wiper::app::{App, Leave};
+use wiper::config::InitConfig;
+use wiper::events::handle_key_events;
+use wiper::fs::{DataStore, DataStoreType, FolderEntryType};
+
+mod marks {
+    use super::*;
+
+    fn marked_titles(app: &App<DataStoreType>) -> Vec<String> {
+        let mut titles: Vec<String> = get_current_folder(app)
+            .unwrap()
+            .marked
+            .iter()
+            .map(|name| name.to_string_lossy().to_string())
+            .collect();
+        titles.sort();
+        titles
+    }
+
+    #[test]
+    fn marks_entry_and_moves_cursor_down() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        app.on_cursor_down();
+        app.on_toggle_mark();
+
+        assert_eq!(marked_titles(&app), vec!["a_folder"]);
+        assert_cursor_index(&app, 2);
+    }
+
+    #[test]
+    fn does_not_mark_parent_entry() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        app.on_toggle_mark();
+
+        assert!(marked_titles(&app).is_empty());
+    }
+
+    #[test]
+    fn keeps_marks_on_same_entries_after_sorting() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        app.on_cursor_down();
+        app.on_toggle_mark();
+        app.on_cursor_down();
+        app.on_cursor_down();
+        app.on_toggle_mark();
+        assert_eq!(marked_titles(&app), vec!["a_folder", "a_root_file.txt"]);
+
+        app.on_toggle_sorting();
+        handle_tasks_synchronously(&mut app);
+        assert_root_view_folder_sorted_by_size(&app);
+
+        let folder = get_current_folder(&app).unwrap();
+        assert!(folder.is_marked(&folder.entries()[3].name));
+        assert!(folder.is_marked(&folder.entries()[5].name));
+        assert_eq!(marked_titles(&app), vec!["a_folder", "a_root_file.txt"]);
+    }
+
+    #[test]
+    fn clears_marks_on_escape_and_navigation() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        app.on_cursor_down();
+        app.on_toggle_mark();
+        app.on_escape();
+        assert!(marked_titles(&app).is_empty());
+
+        app.on_cursor_up();
+        app.on_toggle_mark();
+        app.on_cursor_up();
+        app.on_enter();
+        app.on_confirm_leave();
+        handle_tasks_synchronously(&mut app);
+        app.on_backspace();
+        handle_tasks_synchronously(&mut app);
+        assert!(marked_titles(&app).is_empty());
+    }
+
+    #[test]
+    fn deletes_all_marked_entries() {
+        let postfix = "15";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+
+        app.on_cursor_down();
+        app.on_toggle_mark();
+        app.on_cursor_down();
+        app.on_toggle_mark();
+        app.on_delete();
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::File).len(), 2);
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::Folder).len(), 0);
+        assert_eq!(
+            get_current_folder(&app).unwrap().get_size(),
+            (TEST_FILE_SIZE * 2) as u64
+        );
+        assert!(marked_titles(&app).is_empty());
+        cleanup_testing_files(postfix);
+    }
+
+    fn render(app: &mut App<DataStoreType>) -> String {
+        let area = Rect::new(0, 0, 120, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content.iter().map(|cell| cell.symbol()).collect()
+    }
+
+    #[test]
+    fn shows_marked_total_in_header() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        assert!(!render(&mut app).contains("Marked:"));
+
+        app.on_cursor_down();
+        app.on_toggle_mark();
+        assert_eq!(get_current_folder(&app).unwrap().marked_size(), 1376);
+        assert!(render(&mut app).contains("| Marked: 1 item, 1.34 KB"));
+
+        app.on_toggle_mark();
+        assert_eq!(get_current_folder(&app).unwrap().marked_size(), 1376 + 5957);
+        assert!(render(&mut app).contains("| Marked: 2 items, 7.16 KB"));
+
+        app.on_cursor_up();
+        app.on_toggle_mark();
+        app.on_cursor_up();
+        app.on_cursor_up();
+        app.on_toggle_mark();
+        assert!(!render(&mut app).contains("Marked:"));
+    }
+
+    fn press(app: &mut App<DataStoreType>, c: char) {
+        handle_key_events(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE), app).unwrap();
+    }
+
+    #[test]
+    fn asks_before_leaving_folder_with_marks() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        app.on_cursor_down();
+        app.on_toggle_mark();
+
+        // Cursor moved on to b_folder
+        app.on_enter();
+        assert_eq!(
+            app.pending_leave,
+            Some((Leave::Child(OsString::from("b_folder")), 1))
+        );
+        assert!(
+            render(&mut app).contains("You have 1 marked item — leave and discard marks? (y/N)")
+        );
+
+        press(&mut app, 'n');
+        assert_eq!(app.pending_leave, None);
+        assert_eq!(marked_titles(&app), vec!["a_folder"]);
+
+        app.on_enter();
+        press(&mut app, 'y');
+        handle_tasks_synchronously(&mut app);
+        assert_eq!(app.pending_leave, None);
+        assert!(app.store.get_current_path().ends_with("b_folder"));
+        assert!(!render(&mut app).contains("discard marks"));
+    }
+
+    #[test]
+    fn leaves_without_asking_when_turned_off() {
+        let config = InitConfig::build(
+            ["wiper", "--no-confirm-leave", TEST_FILE_PATH_VIEW]
+                .iter()
+                .map(|arg| arg.to_string()),
+        )
+        .unwrap();
+        let mut app: App<DataStoreType> = App::new(config);
+        app.init();
+        handle_tasks_synchronously(&mut app);
+        app.on_cursor_down();
+        app.on_toggle_mark();
+
+        app.on_enter();
+        handle_tasks_synchronously(&mut app);
+        assert_eq!(app.pending_leave, None);
+        assert!(app.store.get_current_path().ends_with("b_folder"));
+    }
+}
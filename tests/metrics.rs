@@ -0,0 +1,75 @@
+pub mod common;
+
+use crate::common::*;
+use std::fs::read_to_string;
+use std::path::PathBuf;
+use std::time::Duration;
+use wiper::app::App;
+use wiper::fs::{DataStore, DataStoreType};
+use wiper::report::Metrics;
+
+mod metrics {
+    use super::*;
+
+    fn build_metrics() -> Metrics {
+        let mut app: App<DataStoreType> = setup_app_view();
+        app.wait_for_tasks();
+        let root = app.store.get_current_path().clone();
+        Metrics::build(&app.store, &root, Duration::from_millis(1500), 2)
+    }
+
+    fn write_metrics() -> String {
+        let mut output = vec![];
+        build_metrics().write(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    /// Value of the sample whose name and labels start with provided prefix
+    fn sample(output: &str, prefix: &str) -> String {
+        output
+            .lines()
+            .find(|line| line.starts_with(prefix))
+            .and_then(|line| line.rsplit(' ').next())
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    #[test]
+    fn writes_scan_totals() {
+        let output = write_metrics();
+
+        assert_eq!(sample(&output, "wiper_scan_size_bytes{"), "15124");
+        assert_eq!(sample(&output, "wiper_scan_files{"), "11");
+        assert_eq!(sample(&output, "wiper_scan_directories{"), "3");
+        assert_eq!(sample(&output, "wiper_scan_duration_seconds{"), "1.5");
+        assert_eq!(sample(&output, "wiper_scan_errors{"), "2");
+        assert!(output.contains("# TYPE wiper_scan_size_bytes gauge"));
+    }
+
+    #[test]
+    fn writes_top_level_directory_sizes() {
+        let output = write_metrics();
+        let directories: Vec<&str> = output
+            .lines()
+            .filter(|line| line.starts_with("wiper_directory_size_bytes{"))
+            .collect();
+
+        assert_eq!(directories.len(), 3);
+        assert!(directories[0].contains(",directory=\"a_folder\"}"));
+        assert!(directories.iter().all(|line| line.contains("root=\"")));
+    }
+
+    #[test]
+    fn writes_file_in_place() {
+        let postfix = "36";
+        create_testing_files(postfix);
+        let path = PathBuf::from(format!("{}_{}/wiper.prom", TEST_FILE_PATH_EDIT, postfix));
+
+        build_metrics().write_to_file(&path).unwrap();
+        let content = read_to_string(&path).unwrap();
+        assert!(content.contains("wiper_scan_files{"));
+        assert!(!PathBuf::from(format!("{}.tmp", path.to_string_lossy())).exists());
+
+        cleanup_testing_files(postfix);
+    }
+}
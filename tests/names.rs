@@ -0,0 +1,105 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use wiper::app::App;
+use wiper::events::handle_key_events;
+use wiper::fs::{duplicate_names, DataStore, DataStoreType};
+use wiper::views::View;
+
+mod names {
+    use super::*;
+
+    fn press(app: &mut App<DataStoreType>, code: KeyCode) {
+        handle_key_events(KeyEvent::new(code, KeyModifiers::NONE), app).unwrap();
+    }
+
+    #[test]
+    fn groups_files_sharing_a_name() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        let groups = duplicate_names(&app.store, &app.root_path);
+        let names: Vec<&str> = groups.iter().map(|group| group.name.as_str()).collect();
+        assert_eq!(names.len(), 3);
+        assert!(names.contains(&"folder2_file1.txt"));
+        assert!(!names.contains(&"a_root_file.txt"));
+
+        for group in &groups {
+            assert_eq!(group.files.len(), 2);
+            assert!(group.files[0]
+                .path
+                .starts_with(app.root_path.join("b_folder")));
+            assert!(group.files[1]
+                .path
+                .starts_with(app.root_path.join("c_folder")));
+        }
+        // Biggest groups first when counts are equal
+        assert!(groups[0].total_size() >= groups[1].total_size());
+    }
+
+    #[test]
+    fn reveals_selected_occurrence() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        press(&mut app, KeyCode::Char('N'));
+        let View::Names(view) = &app.view else {
+            panic!("names view is not open");
+        };
+        assert_eq!(view.len(), 6);
+        let name = view.groups[0].name.clone();
+
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Enter);
+        assert!(matches!(app.view, View::Folder));
+        assert_eq!(
+            app.store.get_current_path().clone(),
+            app.root_path.join("c_folder")
+        );
+        let folder = get_current_folder(&app).unwrap();
+        assert_eq!(folder.get_selected_entry().title, name);
+    }
+
+    #[test]
+    fn lists_nothing_without_repeated_names() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        let a_folder = app.root_path.join("a_folder");
+
+        assert!(duplicate_names(&app.store, &a_folder).is_empty());
+    }
+
+    fn render(app: &mut App<DataStoreType>, width: u16) -> String {
+        let area = Rect::new(0, 0, width, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content.iter().map(|cell| cell.symbol()).collect()
+    }
+
+    #[test]
+    fn lists_paths_relative_to_root() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        press(&mut app, KeyCode::Char('N'));
+
+        let content = render(&mut app, 140);
+        assert!(content.contains("b_folder/folder2_file1.txt"));
+        assert!(content.contains("c_folder/folder2_file1.txt"));
+        assert!(!content.contains(&app.root_path.to_string_lossy().to_string()));
+    }
+
+    #[test]
+    fn shortens_long_paths_in_the_middle() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        press(&mut app, KeyCode::Char('N'));
+
+        let content = render(&mut app, 70);
+        assert!(!content.contains("b_folder/folder2_file1.txt"));
+        assert!(content.contains("b_f..2_file1.txt"));
+    }
+}
@@ -0,0 +1,132 @@
+#![cfg(unix)]
+pub mod common;
+
+use crate::common::*;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use std::ffi::OsStr;
+use std::fs;
+use std::os::unix::ffi::OsStrExt;
+use std::path::PathBuf;
+use wiper::app::App;
+use wiper::fs::{DataStore, DataStoreType, FolderEntryType};
+
+mod non_utf8 {
+    use super::*;
+
+    const FILE_NAME: &[u8] = b"bad_\xff.txt";
+    const FOLDER_NAME: &[u8] = b"bad_\xfe_folder";
+
+    /// Adds a file and a folder with invalid UTF-8 names, returns their paths
+    fn create_invalid_names(postfix: &str) -> (PathBuf, PathBuf) {
+        let root = PathBuf::from(format!("{}_{}", TEST_FILE_PATH_EDIT, postfix));
+        let file = root.join(OsStr::from_bytes(FILE_NAME));
+        let folder = root.join(OsStr::from_bytes(FOLDER_NAME));
+        fs::write(&file, [0u8; 100]).unwrap();
+        fs::create_dir(&folder).unwrap();
+        fs::write(folder.join("inner.txt"), [0u8; 50]).unwrap();
+        (file, folder)
+    }
+
+    #[test]
+    fn scans_displays_and_deletes_invalid_names() {
+        let postfix = "30";
+        create_testing_files(postfix);
+        let (file, folder) = create_invalid_names(postfix);
+
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+
+        // Scanned with original names, titles are lossy
+        let current = get_current_folder(&app).unwrap();
+        let file_entry = current
+            .entries()
+            .iter()
+            .find(|e| e.name.as_bytes() == FILE_NAME)
+            .unwrap();
+        assert_eq!(file_entry.title, "bad_\u{FFFD}.txt");
+        assert_eq!(file_entry.size, Some(100));
+        let folder_entry = current
+            .entries()
+            .iter()
+            .find(|e| e.name.as_bytes() == FOLDER_NAME)
+            .unwrap();
+        assert_eq!(folder_entry.kind, FolderEntryType::Folder);
+        assert_eq!(folder_entry.size, Some(50));
+        let total_size = current.total_size();
+        let folder_name = folder_entry.name.clone();
+
+        // Rendered with replacement characters
+        let area = Rect::new(0, 0, 100, 30);
+        let mut buf = Buffer::empty(area);
+        app.pre_render();
+        (&mut app).render(area, &mut buf);
+        let content: String = buf.content.iter().map(|cell| cell.symbol()).collect();
+        assert!(content.contains("bad_\u{FFFD}.txt"));
+
+        // Entered by original name
+        app.store
+            .get_current_folder_mut()
+            .unwrap()
+            .select_name(&folder_name);
+        app.on_enter();
+        handle_tasks_synchronously(&mut app);
+        let inner = get_current_folder(&app).unwrap();
+        assert!(inner.entries().iter().any(|e| e.title == "inner.txt"));
+        app.on_backspace();
+        handle_tasks_synchronously(&mut app);
+
+        // Deleted by original name
+        app.store
+            .get_current_folder_mut()
+            .unwrap()
+            .select_name(OsStr::from_bytes(FILE_NAME));
+        app.on_delete();
+        app.on_delete();
+        assert!(!file.exists());
+        assert!(folder.exists());
+        assert_eq!(
+            get_current_folder(&app).unwrap().total_size(),
+            total_size - 100
+        );
+
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn tells_apart_names_with_the_same_lossy_title() {
+        let postfix = "77";
+        create_testing_files(postfix);
+        let root = PathBuf::from(format!("{}_{}", TEST_FILE_PATH_EDIT, postfix));
+        let first = OsStr::from_bytes(b"same_\xff.txt");
+        let second = OsStr::from_bytes(b"same_\xfe.txt");
+        fs::write(root.join(first), [0u8; 10]).unwrap();
+        fs::write(root.join(second), [0u8; 20]).unwrap();
+
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+        let folder = app.store.get_current_folder_mut().unwrap();
+        folder.select_name(second);
+        assert_eq!(folder.get_selected_entry().title, "same_\u{FFFD}.txt");
+
+        // Marking one leaves the other alone
+        app.on_toggle_mark();
+        let folder = app.store.get_current_folder_mut().unwrap();
+        assert!(folder.is_marked(second));
+        assert!(!folder.is_marked(first));
+        assert_eq!(folder.marked_size(), 20);
+        folder.marked.clear();
+
+        folder.select_name(second);
+        app.on_delete();
+        app.on_delete();
+        assert!(root.join(first).exists());
+        assert!(!root.join(second).exists());
+        let folder = get_current_folder(&app).unwrap();
+        assert!(folder.entries().iter().any(|e| e.name == first));
+        assert!(!folder.entries().iter().any(|e| e.name == second));
+
+        cleanup_testing_files(postfix);
+    }
+}
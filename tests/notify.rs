@@ -0,0 +1,43 @@
+pub mod common;
+
+use crate::common::*;
+use wiper::app::App;
+use wiper::config::PathStyle;
+use wiper::fs::DataStoreType;
+use wiper::notify::scan_finished_message;
+use wiper::report::Summary;
+
+mod notify {
+    use super::*;
+
+    #[test]
+    fn describes_finished_scan() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        let summary = Summary::build(&app.store, &app.root_path, PathStyle::Absolute);
+        assert_eq!(
+            scan_finished_message(&summary),
+            format!(
+                "scanned {} — 14.77 KB in 11 files",
+                app.root_path.to_string_lossy()
+            )
+        );
+    }
+
+    #[test]
+    fn groups_file_count_digits() {
+        let summary = Summary {
+            path: "/data".to_string(),
+            total_size: 0,
+            files: 1_234_000,
+            directories: 0,
+            largest_file: None,
+            free_space: None,
+        };
+        assert_eq!(
+            scan_finished_message(&summary),
+            "scanned /data — 0 bytes in 1,234,000 files"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+pub mod common;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use std::path::PathBuf;
+use wiper::app::App;
+use wiper::config::InitConfig;
+use wiper::fs::{scan_folder_with, DataStoreType, HardLinks, MemoryFileSystem, ScanOptions};
+
+use crate::common::*;
+
+mod only_extensions {
+    use super::*;
+
+    fn setup_app(extensions: &str) -> App<DataStoreType> {
+        let mut app: App<DataStoreType> = App::new(
+            InitConfig::build(
+                ["wiper", "--only", extensions, TEST_FILE_PATH_VIEW]
+                    .iter()
+                    .map(|arg| arg.to_string()),
+            )
+            .unwrap(),
+        );
+        app.init();
+        handle_tasks_synchronously(&mut app);
+        app
+    }
+
+    fn visible_titles(app: &App<DataStoreType>) -> Vec<String> {
+        let folder = get_current_folder(app).unwrap();
+        app.ui_config
+            .visible_entries(folder)
+            .map(|(_, entry)| entry.title.clone())
+            .collect()
+    }
+
+    #[test]
+    fn parses_extension_list() {
+        let config = InitConfig::build(
+            ["wiper", "--only", "mp4, .MKV,,mov"]
+                .iter()
+                .map(|arg| arg.to_string()),
+        )
+        .unwrap();
+        assert_eq!(config.scan_options.extensions, ["mp4", "mkv", "mov"]);
+        assert!(InitConfig::build(["wiper", "--only"].iter().map(|arg| arg.to_string())).is_err());
+    }
+
+    #[test]
+    fn records_only_files_with_listed_extensions() {
+        let mut fs = MemoryFileSystem::new();
+        fs.file("/root/movie.mp4", 100)
+            .file("/root/clip.MKV", 50)
+            .file("/root/notes.txt", 10)
+            .file("/root/mp4", 5)
+            .folder("/root/photos");
+        let options = ScanOptions {
+            extensions: vec!["mp4".to_string(), "mkv".to_string()],
+            ..ScanOptions::default()
+        };
+        let (folder, _) = scan_folder_with(
+            &fs,
+            PathBuf::from("/root"),
+            &options,
+            &mut HardLinks::default(),
+        );
+        let mut titles: Vec<&str> = folder
+            .entries()
+            .iter()
+            .map(|entry| entry.title.as_str())
+            .collect();
+        titles.sort();
+        assert_eq!(titles, ["..", "clip.MKV", "movie.mp4", "photos"]);
+        assert_eq!(folder.total_size(), 150);
+    }
+
+    #[test]
+    fn keeps_everything_when_all_match() {
+        let app = setup_app("txt");
+        assert_eq!(get_current_folder(&app).unwrap().total_size(), 15124);
+        assert_eq!(visible_titles(&app).len(), 7);
+    }
+
+    #[test]
+    fn hides_folders_without_matches() {
+        let app = setup_app("mp4");
+        assert_eq!(get_current_folder(&app).unwrap().total_size(), 0);
+        assert_eq!(visible_titles(&app), [".."]);
+    }
+
+    #[test]
+    fn shows_allowlist_in_header() {
+        let mut app = setup_app("mp4,mkv");
+        let area = Rect::new(0, 0, 140, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        let screen: String = buf.content.iter().map(|cell| cell.symbol()).collect();
+        assert!(screen.contains("only: mp4, mkv"));
+
+        app.ui_config.only_extensions.clear();
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        let screen: String = buf.content.iter().map(|cell| cell.symbol()).collect();
+        assert!(!screen.contains("only:"));
+    }
+}
@@ -0,0 +1,73 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use wiper::app::App;
+use wiper::events::handle_key_events;
+use wiper::fs::{normalize_path, DataStore, DataStoreType};
+
+mod open_root {
+    use super::*;
+
+    fn press(app: &mut App<DataStoreType>, code: KeyCode) {
+        handle_key_events(KeyEvent::new(code, KeyModifiers::NONE), app).unwrap();
+    }
+
+    fn type_path(app: &mut App<DataStoreType>, path: &str) {
+        app.path_prompt.as_mut().unwrap().input = path.to_string();
+    }
+
+    #[test]
+    fn scans_new_root_and_remembers_previous_one() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        let first_root = app.root_path.clone();
+
+        press(&mut app, KeyCode::Char('O'));
+        assert!(app.path_prompt.is_some());
+        type_path(&mut app, &format!("{}/b_folder", TEST_FILE_PATH_VIEW));
+        press(&mut app, KeyCode::Enter);
+        handle_tasks_synchronously(&mut app);
+
+        assert!(app.path_prompt.is_none());
+        assert_eq!(
+            app.root_path,
+            normalize_path(format!("{}/b_folder", TEST_FILE_PATH_VIEW))
+        );
+        assert_eq!(get_current_folder(&app).unwrap().title, "b_folder");
+        assert_eq!(app.recent_roots.len(), 1);
+        assert_eq!(app.recent_roots[0], first_root);
+        assert!(!app.store.has_path(&first_root));
+
+        // Previous root comes back from history
+        press(&mut app, KeyCode::Char('O'));
+        press(&mut app, KeyCode::Up);
+        assert_eq!(
+            app.path_prompt.as_ref().unwrap().input,
+            first_root.to_string_lossy()
+        );
+        press(&mut app, KeyCode::Enter);
+        handle_tasks_synchronously(&mut app);
+        assert_eq!(app.root_path, first_root);
+        assert_eq!(get_current_folder(&app).unwrap().total_size(), 15124);
+        assert_eq!(app.recent_roots.len(), 1);
+        assert!(app.recent_roots[0].ends_with("b_folder"));
+    }
+
+    #[test]
+    fn keeps_prompt_open_for_missing_folder() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        let root = app.root_path.clone();
+
+        press(&mut app, KeyCode::Char('O'));
+        type_path(&mut app, &format!("{}/missing", TEST_FILE_PATH_VIEW));
+        press(&mut app, KeyCode::Enter);
+        assert!(app.path_prompt.as_ref().unwrap().error.is_some());
+
+        press(&mut app, KeyCode::Esc);
+        assert!(app.path_prompt.is_none());
+        assert_eq!(app.root_path, root);
+        assert!(app.recent_roots.is_empty());
+    }
+}
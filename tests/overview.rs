@@ -0,0 +1,108 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use wiper::app::App;
+use wiper::events::handle_key_events;
+use wiper::fs::{DataStoreType, Folder, FolderEntry, FolderEntryType};
+use wiper::ui::{overview_segments, OverviewSegment};
+
+mod overview {
+    use super::*;
+
+    fn render(app: &mut App<DataStoreType>) -> String {
+        let area = Rect::new(0, 0, 140, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content.iter().map(|cell| cell.symbol()).collect()
+    }
+
+    fn segment(title: &str, size: u64) -> OverviewSegment {
+        OverviewSegment {
+            title: title.to_string(),
+            size,
+        }
+    }
+
+    fn root_segments(min_share: f64) -> Vec<OverviewSegment> {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        overview_segments(get_current_folder(&app).unwrap(), min_share)
+    }
+
+    #[test]
+    fn lists_entries_by_size() {
+        assert_eq!(
+            root_segments(0.03),
+            vec![
+                segment("b_folder", 5957),
+                segment("c_folder", 5957),
+                segment("a_folder", 1376),
+                segment("d_root_file.txt", 916),
+                segment("a_root_file.txt", 459),
+                segment("z_root_file.txt", 459),
+            ]
+        );
+    }
+
+    #[test]
+    fn collapses_small_entries_into_other() {
+        assert_eq!(
+            root_segments(0.07),
+            vec![
+                segment("b_folder", 5957),
+                segment("c_folder", 5957),
+                segment("a_folder", 1376),
+                segment("other", 1834),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_single_small_entry_by_name() {
+        let mut folder = Folder::new("root".to_string());
+        for (title, size) in [("big", 1000), ("small", 1), ("empty", 0)] {
+            folder.push_entry(FolderEntry {
+                kind: FolderEntryType::File,
+                title: title.to_string(),
+                name: title.into(),
+                size: Some(size),
+                is_loaded: true,
+                device: None,
+                allocated: None,
+                modified: None,
+                owner: None,
+                mode: None,
+            });
+        }
+        assert_eq!(
+            overview_segments(&folder, 0.03),
+            vec![segment("big", 1000), segment("small", 1)]
+        );
+    }
+
+    #[test]
+    fn shows_overview_at_root_only() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        assert!(!render(&mut app).contains("b_folder 39.4%"));
+
+        handle_key_events(
+            KeyEvent::new(KeyCode::Char('B'), KeyModifiers::NONE),
+            &mut app,
+        )
+        .unwrap();
+        assert!(app.ui_config.overview);
+        let screen = render(&mut app);
+        assert!(screen.contains("■ b_folder 39.4%"));
+        assert!(screen.contains("■ z_root_file.txt 3.0%"));
+
+        app.on_cursor_down();
+        app.on_enter();
+        handle_tasks_synchronously(&mut app);
+        assert!(!render(&mut app).contains("39.4%"));
+    }
+}
@@ -0,0 +1,98 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use std::ffi::OsStr;
+use wiper::app::App;
+use wiper::events::handle_key_events;
+use wiper::fs::{with_own_sizes, DataStore, DataStoreType};
+
+mod own_size {
+    use super::*;
+
+    #[test]
+    fn shows_own_size_of_subfolders() {
+        let postfix = "27";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+
+        let path = app.store.get_current_path().clone();
+        let folder = get_current_folder(&app).unwrap();
+        let own = with_own_sizes(&app.store, &path, folder);
+        let title = "folder_to_delete_1";
+        let size_of = |folder: &wiper::fs::Folder| {
+            folder
+                .entries()
+                .iter()
+                .find(|entry| entry.title == title)
+                .unwrap()
+                .size
+        };
+
+        assert_eq!(size_of(folder), Some((TEST_FILE_SIZE * 6) as u64));
+        assert_eq!(size_of(&own), Some((TEST_FILE_SIZE * 3) as u64));
+        assert_eq!(folder.own_size(), (TEST_FILE_SIZE * 3) as u64);
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn toggles_own_size_mode() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        assert!(!app.ui_config.own_size);
+        app.on_toggle_own_size();
+        assert!(app.ui_config.own_size);
+    }
+
+    fn render(app: &mut App<DataStoreType>) -> String {
+        let area = Rect::new(0, 0, 140, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content.iter().map(|cell| cell.symbol()).collect()
+    }
+
+    #[test]
+    fn shows_own_and_total_sizes_side_by_side() {
+        let postfix = "64";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+        let folder_total = (TEST_FILE_SIZE * 6) as u64;
+        let folder_own = (TEST_FILE_SIZE * 3) as u64;
+        let file = TEST_FILE_SIZE as u64;
+
+        let press = KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL);
+        handle_key_events(press, &mut app).unwrap();
+        assert!(app.ui_config.both_sizes);
+        assert!(!app.ui_config.own_size);
+        let screen = render(&mut app);
+        assert!(screen.contains("Own"));
+        let other = &app.ui_config.other_sizes;
+        assert_eq!(
+            other.get(OsStr::new("folder_to_delete_1")),
+            Some(&folder_own)
+        );
+        // Files take the same space either way
+        assert_eq!(other.get(OsStr::new("file_to_delete_1.txt")), Some(&file));
+
+        // Second column shows what the first one doesn't
+        app.on_toggle_own_size();
+        let screen = render(&mut app);
+        assert!(screen.contains("Total"));
+        let other = &app.ui_config.other_sizes;
+        assert_eq!(
+            other.get(OsStr::new("folder_to_delete_1")),
+            Some(&folder_total)
+        );
+        assert_eq!(other.get(OsStr::new("file_to_delete_1.txt")), Some(&file));
+
+        handle_key_events(press, &mut app).unwrap();
+        assert!(!app.ui_config.both_sizes);
+        assert!(!render(&mut app).contains("Total"));
+
+        cleanup_testing_files(postfix);
+    }
+}
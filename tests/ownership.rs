@@ -0,0 +1,127 @@
+#![cfg(unix)]
+pub mod common;
+
+use crate::common::*;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use std::fs;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use wiper::app::App;
+use wiper::config::InitConfig;
+use wiper::fs::{format_mode, user_id, user_name, DataStoreType, FolderEntryType, SortBy};
+
+mod ownership {
+    use super::*;
+
+    #[test]
+    fn formats_permission_bits() {
+        assert_eq!(format_mode(0o755), "rwxr-xr-x");
+        assert_eq!(format_mode(0o640), "rw-r-----");
+        assert_eq!(format_mode(0), "---------");
+    }
+
+    #[test]
+    fn resolves_user_names() {
+        assert_eq!(user_name(0), "root");
+        // Ids without a user are shown as they are
+        assert_eq!(user_name(4_000_000_000), "4000000000");
+    }
+
+    #[test]
+    fn collects_owner_and_mode_while_scanning() {
+        let postfix = "32";
+        create_testing_files(postfix);
+        let file = format!("{}_{}/file_to_delete_1.txt", TEST_FILE_PATH_EDIT, postfix);
+        fs::set_permissions(&file, fs::Permissions::from_mode(0o640)).unwrap();
+        let uid = fs::metadata(&file).unwrap().uid();
+
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+
+        let folder = get_current_folder(&app).unwrap();
+        let entry = folder
+            .entries()
+            .iter()
+            .find(|e| e.title == "file_to_delete_1.txt")
+            .unwrap();
+        assert_eq!(entry.owner, Some(uid));
+        assert_eq!(entry.mode, Some(0o640));
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn shows_columns_only_when_toggled() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        let render = |app: &mut App<DataStoreType>| {
+            let area = Rect::new(0, 0, 140, 20);
+            let mut buf = Buffer::empty(area);
+            app.render(area, &mut buf);
+            buf.content
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect::<String>()
+        };
+
+        let content = render(&mut app);
+        assert!(!content.contains("Owner"));
+        assert!(!content.contains("Mode"));
+
+        app.on_toggle_owner_column();
+        app.on_toggle_permissions_column();
+        let content = render(&mut app);
+        assert!(content.contains("Owner"));
+        assert!(content.contains("Mode"));
+        assert!(content.contains("rw-"));
+    }
+
+    fn setup_owner(owner: &str) -> Result<App<DataStoreType>, &'static str> {
+        let args = ["wiper", "--owner", owner, TEST_FILE_PATH_VIEW].map(String::from);
+        let mut app: App<DataStoreType> = App::new(InitConfig::build(args.into_iter())?);
+        app.init();
+        handle_tasks_synchronously(&mut app);
+        Ok(app)
+    }
+
+    fn visible_titles(app: &App<DataStoreType>) -> Vec<String> {
+        get_current_folder(app)
+            .unwrap()
+            .entries()
+            .iter()
+            .filter(|entry| app.ui_config.is_visible(entry))
+            .map(|entry| entry.title.clone())
+            .collect()
+    }
+
+    #[test]
+    fn resolves_user_ids() {
+        assert_eq!(user_id("root"), Some(0));
+        assert_eq!(user_id("1234"), Some(1234));
+        assert_eq!(user_id("no such user here"), None);
+        assert!(setup_owner("no such user here").is_err());
+    }
+
+    #[test]
+    fn shows_entries_of_filtered_owner() {
+        let uid = fs::metadata(TEST_FILE_PATH_VIEW).unwrap().uid();
+        let mut app = setup_owner(&uid.to_string()).unwrap();
+        assert_eq!(visible_titles(&app).len(), 7);
+        assert_eq!(app.ui_config.owned_sizes.values().sum::<u64>(), 15124);
+
+        // Ranked by owned size
+        app.ui_config.sort_by = SortBy::Size;
+        app.pre_render();
+        let folder = get_current_folder(&app).unwrap();
+        assert_eq!(folder.entries()[0].kind, FolderEntryType::Parent);
+        assert_eq!(folder.entries()[1].title, "b_folder");
+        assert_eq!(folder.entries()[6].title, "z_root_file.txt");
+    }
+
+    #[test]
+    fn hides_entries_of_other_owners() {
+        let app = setup_owner("4000000000").unwrap();
+        assert_eq!(visible_titles(&app), vec![".."]);
+        assert!(app.ui_config.owned_sizes.is_empty());
+    }
+}
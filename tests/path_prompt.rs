@@ -0,0 +1,101 @@
+pub mod common;
+
+use crate::common::*;
+use std::fs::create_dir_all;
+use std::path::PathBuf;
+use wiper::prompt::PathPrompt;
+
+mod path_prompt {
+    use super::*;
+
+    fn with_input(input: &str) -> PathPrompt {
+        PathPrompt {
+            input: input.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn starts_with_current_folder() {
+        let prompt = PathPrompt::new();
+        let current_dir = std::env::current_dir().unwrap();
+        assert!(prompt.input.starts_with(&*current_dir.to_string_lossy()));
+        assert!(prompt.input.ends_with('/'));
+    }
+
+    #[test]
+    fn completes_single_folder() {
+        let mut prompt = with_input(&format!("{}/b", TEST_FILE_PATH_VIEW));
+        prompt.on_complete();
+        assert_eq!(prompt.input, format!("{}/b_folder/", TEST_FILE_PATH_VIEW));
+        assert!(prompt.completions.is_empty());
+    }
+
+    #[test]
+    fn lists_folders_when_several_fit() {
+        let mut prompt = with_input(&format!("{}/", TEST_FILE_PATH_VIEW));
+        prompt.on_complete();
+        assert_eq!(prompt.input, format!("{}/", TEST_FILE_PATH_VIEW));
+        assert_eq!(prompt.completions, ["a_folder", "b_folder", "c_folder"]);
+
+        // Typing drops the list
+        prompt.on_input('c');
+        assert!(prompt.completions.is_empty());
+    }
+
+    #[test]
+    fn completes_common_prefix() {
+        let postfix = "37";
+        create_testing_files(postfix);
+        let root = format!("{}_{}", TEST_FILE_PATH_EDIT, postfix);
+        create_dir_all(format!("{}/shared_one", root)).unwrap();
+        create_dir_all(format!("{}/shared_two", root)).unwrap();
+
+        let mut prompt = with_input(&format!("{}/sh", root));
+        prompt.on_complete();
+        assert_eq!(prompt.input, format!("{}/shared_", root));
+        assert_eq!(prompt.completions, ["shared_one", "shared_two"]);
+
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn rejects_missing_paths() {
+        let mut prompt = with_input(&format!("{}/missing", TEST_FILE_PATH_VIEW));
+        assert_eq!(prompt.submit(), None);
+        assert!(prompt.error.is_some());
+
+        prompt.on_backspace();
+        assert!(prompt.error.is_none());
+
+        let mut prompt = with_input(&format!("{}/a_folder", TEST_FILE_PATH_VIEW));
+        assert!(prompt.submit().is_some());
+        let mut prompt = with_input(&format!("{}/a_root_file.txt", TEST_FILE_PATH_VIEW));
+        assert!(prompt.submit().is_some());
+    }
+
+    #[test]
+    fn expands_home_folder() {
+        let Some(home) = std::env::var_os("HOME") else {
+            return;
+        };
+        let mut prompt = with_input("~");
+        assert_eq!(prompt.submit(), Some(PathBuf::from(home)));
+    }
+
+    #[test]
+    fn goes_through_history() {
+        let history = [PathBuf::from("/newer"), PathBuf::from("/older")];
+        let mut prompt = PathPrompt::with_history(&PathBuf::from("/current"), &history);
+        assert_eq!(prompt.input, "/current/");
+
+        prompt.on_history_up();
+        prompt.on_history_up();
+        prompt.on_history_up();
+        assert_eq!(prompt.input, "/older");
+        prompt.on_history_down();
+        assert_eq!(prompt.input, "/newer");
+        prompt.on_history_down();
+        assert_eq!(prompt.input, "/newer");
+    }
+}
@@ -0,0 +1,172 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use std::path::Path;
+use wiper::app::App;
+use wiper::events::handle_key_events;
+use wiper::fs::{content_kind, hexdump, image_info, ContentKind, DataStoreType, Preview};
+
+mod preview {
+    use super::*;
+
+    fn render(app: &mut App<DataStoreType>) -> String {
+        let area = Rect::new(0, 0, 140, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content.iter().map(|cell| cell.symbol()).collect()
+    }
+
+    fn setup_app() -> App<DataStoreType> {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        handle_key_events(
+            KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE),
+            &mut app,
+        )
+        .unwrap();
+        app
+    }
+
+    #[test]
+    fn reads_only_start_of_file() {
+        let path = Path::new(TEST_FILE_PATH_VIEW).join("a_root_file.txt");
+        let preview = Preview::read_file(&path, 100);
+        let Preview::File { chunk, len, kind } = &preview else {
+            panic!("Expected file preview, got {preview:?}");
+        };
+        assert_eq!(chunk.len(), 100);
+        assert_eq!(*len, 459);
+        assert_eq!(*kind, ContentKind::Text);
+        assert!(preview.is_truncated());
+        assert!(!Preview::read_file(&path, 1000).is_truncated());
+    }
+
+    #[test]
+    fn reports_unreadable_file() {
+        let path = Path::new(TEST_FILE_PATH_VIEW).join("missing.txt");
+        assert!(matches!(Preview::read_file(&path, 100), Preview::Error(_)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn does_not_open_fifos_or_devices() {
+        let dir = std::env::temp_dir().join("wiper_preview_fifo");
+        std::fs::create_dir_all(&dir).unwrap();
+        let fifo = dir.join("pipe");
+        let _ = std::fs::remove_file(&fifo);
+        let c_path = std::ffi::CString::new(fifo.to_str().unwrap()).unwrap();
+        assert_eq!(unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) }, 0);
+
+        // Opening the FIFO for reading would wait for a writer
+        assert!(matches!(Preview::read_file(&fifo, 100), Preview::Error(_)));
+        assert!(matches!(
+            Preview::read_file(Path::new("/dev/zero"), 100),
+            Preview::Error(_)
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn tells_text_from_binary() {
+        assert_eq!(
+            content_kind(b"plain text\nline two\t", false),
+            ContentKind::Text
+        );
+        assert_eq!(content_kind(b"", false), ContentKind::Text);
+        assert_eq!(content_kind(b"nul\0inside", false), ContentKind::Binary);
+        assert_eq!(
+            content_kind(&[0xc3, 0x28, 0x41], false),
+            ContentKind::Binary
+        );
+        assert_eq!(
+            content_kind(&[1, 2, 3, 4, b'a'], false),
+            ContentKind::Binary
+        );
+        // Multi-byte character cut by the read limit
+        let text = "héllo".as_bytes();
+        assert_eq!(content_kind(&text[..2], true), ContentKind::Text);
+        assert_eq!(content_kind(&text[..2], false), ContentKind::Binary);
+    }
+
+    #[test]
+    fn reads_image_dimensions() {
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+        png.extend(640u32.to_be_bytes());
+        png.extend(480u32.to_be_bytes());
+        let image = image_info(&png).unwrap();
+        assert_eq!((image.format, image.width, image.height), ("PNG", 640, 480));
+        assert_eq!(content_kind(&png, false), ContentKind::Image(image));
+
+        let gif = b"GIF89a\x20\x03\x58\x02";
+        let image = image_info(gif).unwrap();
+        assert_eq!((image.format, image.width, image.height), ("GIF", 800, 600));
+
+        let mut bmp = b"BM".to_vec();
+        bmp.resize(18, 0);
+        bmp.extend(100i32.to_le_bytes());
+        bmp.extend((-50i32).to_le_bytes());
+        let image = image_info(&bmp).unwrap();
+        assert_eq!((image.format, image.width, image.height), ("BMP", 100, 50));
+
+        // APP0 segment, then start of frame with height 200 and width 300
+        let jpeg = [
+            0xff, 0xd8, 0xff, 0xe0, 0x00, 0x04, 0x00, 0x00, 0xff, 0xc0, 0x00, 0x11, 0x08, 0x00,
+            0xc8, 0x01, 0x2c,
+        ];
+        let image = image_info(&jpeg).unwrap();
+        assert_eq!(
+            (image.format, image.width, image.height),
+            ("JPEG", 300, 200)
+        );
+
+        assert_eq!(image_info(b"\x89PNG\r\n\x1a\n"), None);
+        assert_eq!(image_info(b"plain text"), None);
+    }
+
+    #[test]
+    fn dumps_bytes_as_hex() {
+        let lines = hexdump(b"abc\x00\x01defghijklmnopq", 8);
+        assert_eq!(
+            lines,
+            vec![
+                "00000000  61 62 63 00 01 64 65 66  |abc..def|",
+                "00000008  67 68 69 6a 6b 6c 6d 6e  |ghijklmn|",
+                "00000010  6f 70 71                 |opq|",
+            ]
+        );
+    }
+
+    #[test]
+    fn shows_start_of_selected_file() {
+        let mut app = setup_app();
+        for _ in 0..4 {
+            app.on_cursor_down();
+        }
+        let screen = render(&mut app);
+        assert!(screen.contains(" Preview "));
+        assert!(screen.contains("Text, 459 bytes"));
+        assert!(screen.contains("Lorem ipsum"));
+    }
+
+    #[test]
+    fn sums_up_selected_folder() {
+        let mut app = setup_app();
+        app.on_cursor_down();
+        let screen = render(&mut app);
+        assert!(screen.contains("2 entries"));
+        assert!(screen.contains("Size: 1.34 KB"));
+    }
+
+    #[test]
+    fn hides_pane_when_toggled_off() {
+        let mut app = setup_app();
+        assert!(render(&mut app).contains("Nothing to preview"));
+        app.on_toggle_preview();
+        assert!(!render(&mut app).contains(" Preview "));
+        assert_eq!(app.preview, None);
+    }
+}
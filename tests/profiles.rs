@@ -0,0 +1,157 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::fs::{create_dir_all, remove_dir_all, write};
+use std::path::PathBuf;
+use wiper::app::App;
+use wiper::config::{
+    builtin_profiles, config_file_args, config_file_profiles, Column, InitConfig, Profile,
+};
+use wiper::events::handle_key_events;
+use wiper::filter::MatchMode;
+use wiper::fs::{DataStoreType, SortBy};
+
+mod profiles {
+    use super::*;
+
+    fn load(args: &[&str]) -> Result<InitConfig, String> {
+        InitConfig::load(
+            std::iter::once("wiper")
+                .chain(args.iter().copied())
+                .map(String::from),
+        )
+    }
+
+    fn config_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("wiper_profiles_{name}"));
+        let _ = remove_dir_all(&dir);
+        create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn press(app: &mut App<DataStoreType>, code: KeyCode) {
+        handle_key_events(KeyEvent::new(code, KeyModifiers::NONE), app).unwrap();
+    }
+
+    fn visible_titles(app: &App<DataStoreType>) -> Vec<String> {
+        app.ui_config
+            .visible_entries(get_current_folder(app).unwrap())
+            .map(|(_, entry)| entry.title.clone())
+            .collect()
+    }
+
+    #[test]
+    fn reads_profiles_from_sections() {
+        let contents = "--size-unit mb\n\n[big]\n# Only what matters\n--sort size\n--min-size 1M\n[ logs ]\n--filter *.log\n";
+        assert_eq!(
+            config_file_args(contents).unwrap(),
+            vec!["--size-unit", "mb"]
+        );
+        assert_eq!(
+            config_file_profiles(contents).unwrap(),
+            vec![
+                Profile::new("big", &["--sort", "size", "--min-size", "1M"]),
+                Profile::new("logs", &["--filter", "*.log"]),
+            ]
+        );
+        for profile in builtin_profiles() {
+            assert!(profile.config().is_ok(), "{}", profile.name);
+        }
+    }
+
+    #[test]
+    fn picks_profile_at_start() {
+        let dir = config_dir("start");
+        let path = dir.join("config");
+        write(&path, "[big]\n--sort size\n--min-size 1K\n").unwrap();
+        let config = load(&["--config", path.to_str().unwrap(), "--profile", "big"]).unwrap();
+        assert_eq!(config.profile.as_deref(), Some("big"));
+        assert_eq!(config.profiles.len(), 1);
+
+        let config = InitConfig {
+            file_path: Some(TEST_FILE_PATH_VIEW.to_string()),
+            ..config
+        };
+        let mut app: App<DataStoreType> = App::new(config);
+        app.init();
+        handle_tasks_synchronously(&mut app);
+        assert_eq!(app.ui_config.sort_by, SortBy::Size);
+        assert_eq!(app.ui_config.min_size, Some(1024));
+        assert_eq!(app.active_profile.as_deref(), Some("big"));
+
+        // Built-in profiles are there without a config file
+        assert!(load(&["--no-config", "--profile", "media"]).is_ok());
+        let err = load(&["--no-config", "--profile", "missing"]).unwrap_err();
+        assert!(err.contains("Unknown profile missing"), "{err}");
+        remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn refuses_options_profiles_can_not_switch() {
+        let dir = config_dir("refused");
+        let path = dir.join("config");
+        write(&path, "[scan]\n--only log\n").unwrap();
+        let err = load(&["--config", path.to_str().unwrap()]).unwrap_err();
+        assert!(
+            err.contains("--only can't be used in profile scan"),
+            "{err}"
+        );
+
+        write(&path, "[sizes]\n--min-size lots\n").unwrap();
+        let err = load(&["--config", path.to_str().unwrap()]).unwrap_err();
+        assert!(err.contains("in profile sizes"), "{err}");
+        remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn switches_profiles_without_rescanning() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        app.profiles = vec![
+            Profile::new(
+                "big",
+                &[
+                    "--sort",
+                    "size",
+                    "--min-size",
+                    "1K",
+                    "--columns",
+                    "name,size,age",
+                ],
+            ),
+            Profile::new("text", &["--filter", "z_*", "--glob"]),
+        ];
+
+        app.apply_profile(Some("big"));
+        assert_eq!(app.ui_config.sort_by, SortBy::Size);
+        assert!(app.ui_config.show_age);
+        assert_eq!(
+            app.ui_config.visible_columns(),
+            vec![Column::Name, Column::Size, Column::Age]
+        );
+        assert_eq!(
+            visible_titles(&app),
+            vec!["..", "a_folder", "b_folder", "c_folder"]
+        );
+        assert!(app.task_manager.is_done());
+
+        // Nothing of the first profile is kept by the second one
+        app.apply_profile(Some("text"));
+        assert_eq!(app.ui_config.sort_by, SortBy::Title);
+        assert_eq!(app.ui_config.min_size, None);
+        assert!(!app.ui_config.show_age);
+        assert_eq!(app.ui_config.name_filter.mode, MatchMode::Glob);
+        assert_eq!(visible_titles(&app), vec!["..", "z_root_file.txt"]);
+
+        // First menu row goes back to options wiper started with
+        press(&mut app, KeyCode::Char('P'));
+        assert_eq!(app.profile_menu.as_ref().unwrap().cursor_index, 2);
+        press(&mut app, KeyCode::Char('k'));
+        press(&mut app, KeyCode::Char('k'));
+        press(&mut app, KeyCode::Enter);
+        assert!(app.profile_menu.is_none());
+        assert_eq!(app.active_profile, None);
+        assert_eq!(visible_titles(&app).len(), 7);
+    }
+}
@@ -0,0 +1,80 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use wiper::app::App;
+use wiper::config::InitConfig;
+use wiper::events::handle_key_events;
+use wiper::fs::{DataStoreType, FolderEntryType};
+use wiper::logger::MessageLevel;
+
+mod read_only {
+    use super::*;
+
+    fn press(app: &mut App<DataStoreType>, c: char) {
+        handle_key_events(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE), app).unwrap();
+    }
+
+    #[test]
+    fn refuses_to_delete_anything() {
+        let postfix = "74";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+        app.ui_config.read_only = true;
+
+        app.on_cursor_down();
+        app.on_cursor_down();
+        press(&mut app, 'd');
+        press(&mut app, 'd');
+        assert!(!app.ui_config.confirming_deletion);
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::File).len(), 3);
+        assert!(app
+            .logger
+            .messages
+            .iter()
+            .any(|entry| entry.level == MessageLevel::Info
+                && entry.message.starts_with("Read-only mode")));
+
+        app.on_sweep_empty_folders();
+        app.on_redo();
+        assert!(!app.ui_config.confirming_deletion);
+        assert_delete_folder_state(&app);
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn shows_badge() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        app.ui_config.read_only = true;
+
+        let area = Rect::new(0, 0, 120, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        let text: String = buf.content.iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("READ ONLY"));
+        assert!(text.contains("Read only,"));
+        assert!(!text.contains("Trash:"));
+    }
+
+    #[test]
+    fn is_set_from_options() {
+        let args = ["wiper", "--read-only", TEST_FILE_PATH_VIEW].map(String::from);
+        let app: App<DataStoreType> = App::new(InitConfig::build(args.into_iter()).unwrap());
+        assert!(app.ui_config.read_only);
+
+        let args = [
+            "wiper",
+            "--read-only",
+            "--delete-matching",
+            "*.log",
+            "--yes",
+        ]
+        .map(String::from);
+        assert!(InitConfig::build(args.into_iter()).is_err());
+    }
+}
@@ -0,0 +1,76 @@
+pub mod common;
+
+use std::fs::File;
+use std::time::{Duration, SystemTime};
+use wiper::app::App;
+use wiper::config::{parse_duration, InitConfig};
+use wiper::fs::DataStoreType;
+use wiper::views::View;
+
+use crate::common::*;
+
+mod recent {
+    use super::*;
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn visible_len(app: &App<DataStoreType>) -> usize {
+        match &app.view {
+            View::Recent(view) => view.visible().len(),
+            _ => panic!("recent view is not open"),
+        }
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("90"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("30m"), Some(Duration::from_secs(30 * 60)));
+        assert_eq!(parse_duration("7d"), Some(7 * DAY));
+        assert_eq!(parse_duration("2w"), Some(14 * DAY));
+        assert_eq!(parse_duration("1y"), None);
+        assert_eq!(parse_duration(""), None);
+    }
+
+    #[test]
+    fn lists_files_modified_within_window() {
+        let postfix = "29";
+        create_testing_files(postfix);
+        let old_file = format!("{}_{}/file_to_delete_1.txt", TEST_FILE_PATH_EDIT, postfix);
+        File::options()
+            .write(true)
+            .open(&old_file)
+            .unwrap()
+            .set_modified(SystemTime::now() - 3 * DAY)
+            .unwrap();
+
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+        app.on_toggle_recent_view();
+        assert_eq!(visible_len(&app), 8);
+
+        // 24h -> 7d
+        app.on_cycle_recent_window();
+        assert_eq!(app.ui_config.recent_window, 7 * DAY);
+        assert_eq!(visible_len(&app), 9);
+        if let View::Recent(view) = &app.view {
+            assert!(view.visible()[8].path.ends_with("file_to_delete_1.txt"));
+        }
+
+        // 7d -> 30d -> 1h
+        app.on_cycle_recent_window();
+        app.on_cycle_recent_window();
+        assert_eq!(app.ui_config.recent_window, Duration::from_secs(60 * 60));
+        assert_eq!(visible_len(&app), 8);
+
+        app.on_toggle_recent_view();
+        assert!(matches!(app.view, View::Folder));
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn uses_window_from_options() {
+        let args = ["wiper", "--recent-window", "7d", TEST_FILE_PATH_VIEW].map(String::from);
+        let app: App<DataStoreType> = App::new(InitConfig::build(args.into_iter()).unwrap());
+        assert_eq!(app.ui_config.recent_window, 7 * DAY);
+    }
+}
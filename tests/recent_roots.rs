@@ -0,0 +1,91 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::path::{Path, PathBuf};
+use wiper::app::App;
+use wiper::config::InitConfig;
+use wiper::events::handle_key_events;
+use wiper::fs::{normalize_path, DataStoreType};
+use wiper::prompt::{load_history, save_history};
+
+mod recent_roots {
+    use super::*;
+
+    fn press(app: &mut App<DataStoreType>, code: KeyCode) {
+        handle_key_events(KeyEvent::new(code, KeyModifiers::NONE), app).unwrap();
+    }
+
+    fn history_file(postfix: &str) -> PathBuf {
+        PathBuf::from(format!("{}_{}", TEST_FILE_PATH_EDIT, postfix))
+            .join("state")
+            .join("recent_roots.json")
+    }
+
+    fn setup_app_with_history(path: &str, history_file: &Path) -> App<DataStoreType> {
+        let config = InitConfig {
+            file_path: Some(path.to_string()),
+            history_file: Some(history_file.to_path_buf()),
+            ..Default::default()
+        };
+        let mut app: App<DataStoreType> = App::new(config);
+        app.init();
+        handle_tasks_synchronously(&mut app);
+        app
+    }
+
+    #[test]
+    fn keeps_roots_between_runs_most_recent_first() {
+        let postfix = "44";
+        create_testing_files(postfix);
+        let file = history_file(postfix);
+        let view = normalize_path(TEST_FILE_PATH_VIEW);
+        let b_folder = normalize_path(format!("{}/b_folder", TEST_FILE_PATH_VIEW));
+        assert!(load_history(&file).is_empty());
+
+        let mut app = setup_app_with_history(TEST_FILE_PATH_VIEW, &file);
+        assert!(app.recent_roots.is_empty());
+        assert_eq!(load_history(&file), vec![view.clone()]);
+
+        app.open_root(b_folder.clone());
+        assert_eq!(load_history(&file), vec![b_folder.clone(), view.clone()]);
+
+        // Next run lists previous roots, current one comes first again
+        let app = setup_app_with_history(TEST_FILE_PATH_VIEW, &file);
+        assert_eq!(app.recent_roots, vec![b_folder.clone()]);
+        assert_eq!(load_history(&file), vec![view, b_folder]);
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn drops_missing_root_when_picked() {
+        let postfix = "45";
+        create_testing_files(postfix);
+        let file = history_file(postfix);
+        let view = normalize_path(TEST_FILE_PATH_VIEW);
+        let missing = PathBuf::from("/surely/missing/root");
+        let a_folder = normalize_path(format!("{}/a_folder", TEST_FILE_PATH_VIEW));
+        save_history(&file, &[missing.clone(), a_folder.clone()]).unwrap();
+
+        let mut app = setup_app_with_history(TEST_FILE_PATH_VIEW, &file);
+        assert_eq!(app.recent_roots, vec![missing.clone(), a_folder.clone()]);
+
+        press(&mut app, KeyCode::Char('O'));
+        press(&mut app, KeyCode::Up);
+        assert_eq!(
+            app.path_prompt.as_ref().unwrap().input,
+            "/surely/missing/root"
+        );
+        press(&mut app, KeyCode::Enter);
+
+        let prompt = app.path_prompt.as_ref().unwrap();
+        assert_eq!(
+            prompt.error.as_deref(),
+            Some("No longer exists: /surely/missing/root")
+        );
+        assert_eq!(prompt.history.len(), 1);
+        assert_eq!(app.recent_roots, vec![a_folder.clone()]);
+        assert_eq!(load_history(&file), vec![view, a_folder]);
+        cleanup_testing_files(postfix);
+    }
+}
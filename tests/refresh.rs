@@ -0,0 +1,75 @@
+pub mod common;
+
+use crate::common::*;
+use std::fs;
+use wiper::app::App;
+use wiper::config::InitConfig;
+use wiper::fs::DataStoreType;
+
+mod refresh {
+    use super::*;
+
+    #[test]
+    fn picks_up_changes_and_keeps_cursor_on_same_entry() {
+        let postfix = "16";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+
+        app.on_cursor_down();
+        app.on_cursor_down();
+        assert_item_at_index_title(&app, 2, "file_to_delete_1.txt".to_string());
+
+        let folder_path = format!("{}_{}", TEST_FILE_PATH_EDIT, postfix);
+        fs::write(format!("{}/a_new_file.txt", folder_path), "0123456789").unwrap();
+        fs::write(
+            format!("{}/folder_to_delete_1/file_to_delete_1.txt", folder_path),
+            "",
+        )
+        .unwrap();
+
+        app.refresh_current_folder();
+        handle_tasks_synchronously(&mut app);
+
+        let folder = get_current_folder(&app).unwrap();
+        assert_eq!(folder.entries().len(), 6);
+        assert_eq!(folder.get_selected_entry().title, "file_to_delete_1.txt");
+        assert_eq!(folder.get_size(), (TEST_FILE_SIZE * 8 + 10) as u64);
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn rescans_on_tick_when_auto_refresh_is_due() {
+        let postfix = "17";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+
+        let folder_path = format!("{}_{}", TEST_FILE_PATH_EDIT, postfix);
+        fs::write(format!("{}/a_new_file.txt", folder_path), "0123456789").unwrap();
+        // Only the current folder is read again, its subfolders keep their sizes
+        fs::write(
+            format!("{}/folder_to_delete_1/file_to_delete_1.txt", folder_path),
+            "",
+        )
+        .unwrap();
+
+        app.ui_config.auto_refresh_secs = Some(0);
+        app.tick();
+        app.ui_config.auto_refresh_secs = None;
+        handle_tasks_synchronously(&mut app);
+
+        let folder = get_current_folder(&app).unwrap();
+        assert_eq!(folder.get_size(), (TEST_FILE_SIZE * 9 + 10) as u64);
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn refuses_zero_seconds_for_auto_refresh() {
+        let args = ["wiper", "--auto-refresh", "0"].map(String::from);
+        assert!(InitConfig::build(args.into_iter()).is_err());
+        let args = ["wiper", "--auto-refresh", "5"].map(String::from);
+        let config = InitConfig::build(args.into_iter()).unwrap();
+        assert_eq!(config.auto_refresh_secs, Some(5));
+    }
+}
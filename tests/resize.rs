@@ -0,0 +1,95 @@
+pub mod common;
+
+use crate::common::*;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use wiper::app::App;
+use wiper::fs::DataStoreType;
+use wiper::ui::min_terminal_size;
+
+mod resize {
+    use super::*;
+
+    /// Rendered lines of a terminal of provided height
+    fn render(app: &mut App<DataStoreType>, height: u16) -> Vec<String> {
+        render_sized(app, 120, height)
+    }
+
+    fn render_sized(app: &mut App<DataStoreType>, width: u16, height: u16) -> Vec<String> {
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content
+            .chunks(area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn table_follows_terminal_height() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        render(&mut app, 30);
+        let tall = app.table_area.unwrap();
+        render(&mut app, 12);
+        let short = app.table_area.unwrap();
+        assert_eq!(tall.height - short.height, 18);
+        assert_eq!(tall.width, short.width);
+    }
+
+    #[test]
+    fn keeps_cursor_visible_after_shrink() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        for _ in 0..6 {
+            app.on_cursor_down();
+        }
+        let lines = render(&mut app, 30);
+        assert!(lines
+            .iter()
+            .any(|line| line.contains(">   z_root_file.txt")));
+
+        let lines = render(&mut app, 12);
+        assert!(lines
+            .iter()
+            .any(|line| line.contains(">   z_root_file.txt")));
+        // Frame is closed at its new bottom
+        assert!(lines[11].starts_with('╚'));
+    }
+
+    #[test]
+    fn shows_message_in_tiny_terminal() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        let (width, height) = min_terminal_size(&app.ui_config);
+        assert_eq!((width, height), (43, 10));
+
+        let text = render_sized(&mut app, 40, 8).concat();
+        assert!(text.contains("Terminal too small, need at least 43×10"));
+        assert!(!text.contains("a_folder"));
+        assert!(app.table_area.is_none());
+
+        // Table comes back once the terminal grows
+        let lines = render_sized(&mut app, width, height);
+        assert!(!lines.concat().contains("too small"));
+        assert!(lines.iter().any(|line| line.contains("Name")));
+        assert!(app.table_area.is_some());
+    }
+
+    #[test]
+    fn shows_message_when_panels_squeeze_table() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        app.ui_config.preview = true;
+
+        let text = render_sized(&mut app, 60, 20).concat();
+        assert!(text.contains("Too small for the table"));
+        assert!(app.table_area.is_none());
+
+        let text = render_sized(&mut app, 120, 20).concat();
+        assert!(!text.contains("too small"));
+        assert!(app.table_area.is_some());
+    }
+}
@@ -0,0 +1,122 @@
+pub mod common;
+
+use crate::common::*;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
+use wiper::app::App;
+use wiper::fs::{root_available, root_device, DataStoreType, FolderEntryType};
+use wiper::logger::MessageLevel;
+
+mod root_missing {
+    use super::*;
+
+    fn moved_away(app: &App<DataStoreType>) -> PathBuf {
+        let mut path = app.root_path.clone().into_os_string();
+        path.push("_moved");
+        PathBuf::from(path)
+    }
+
+    fn errors_about_root(app: &App<DataStoreType>) -> usize {
+        app.logger
+            .messages
+            .iter()
+            .filter(|entry| entry.level == MessageLevel::Error)
+            .filter(|entry| entry.message.contains("is no longer available"))
+            .map(|entry| entry.count)
+            .sum()
+    }
+
+    #[test]
+    fn notices_root_going_away_on_tick() {
+        let postfix = "72";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+        let moved = moved_away(&app);
+
+        fs::rename(&app.root_path, &moved).unwrap();
+        app.tick();
+        assert!(!app.root_missing, "checked only every few seconds");
+        app.last_root_check = Instant::now() - Duration::from_secs(10);
+        app.tick();
+        assert!(app.root_check.is_some(), "checked in background");
+        while app.root_check.is_some() {
+            thread::sleep(Duration::from_millis(1));
+            app.tick();
+        }
+        assert!(app.root_missing);
+        app.check_root();
+        assert_eq!(errors_about_root(&app), 1);
+
+        let area = Rect::new(0, 0, 120, 30);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        let text: String = buf.content.iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("Scan root no longer available"));
+
+        fs::rename(&moved, &app.root_path).unwrap();
+        assert!(app.check_root());
+        assert!(!app.root_missing);
+        assert!(app
+            .logger
+            .messages
+            .iter()
+            .any(|entry| entry.message.contains("is available again")));
+        cleanup_testing_files(postfix);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn treats_root_on_another_device_as_missing() {
+        let postfix = "83";
+        create_testing_files(postfix);
+        let app: App<DataStoreType> = setup_app_edit(postfix);
+        let device = root_device(&app.root_path).unwrap();
+
+        assert!(root_available(&app.root_path, Some(device)));
+        // Empty mount point left behind by an unmounted drive is on the parent's device
+        assert!(!root_available(
+            &app.root_path,
+            Some(device.wrapping_add(1))
+        ));
+        assert!(root_available(&app.root_path, None));
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn refuses_to_delete_until_root_is_back() {
+        let postfix = "73";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+        let moved = moved_away(&app);
+
+        // Folder first, the file after it
+        app.on_cursor_down();
+        app.on_cursor_down();
+        app.on_delete();
+        assert!(app.ui_config.confirming_deletion);
+        fs::rename(&app.root_path, &moved).unwrap();
+        app.on_delete();
+        assert!(!app.ui_config.confirming_deletion);
+        assert!(app.root_missing);
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::File).len(), 3);
+
+        // Rescan keeps the scanned tree instead of turning it into errors
+        app.on_rescan();
+        assert!(app.task_manager.is_done());
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::File).len(), 3);
+
+        fs::rename(&moved, &app.root_path).unwrap();
+        app.on_delete();
+        app.on_delete();
+        assert!(!app.root_missing);
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::File).len(), 2);
+        cleanup_testing_files(postfix);
+    }
+}
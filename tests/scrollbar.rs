@@ -0,0 +1,47 @@
+pub mod common;
+
+use crate::common::*;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use wiper::app::App;
+use wiper::fs::DataStoreType;
+
+mod scrollbar {
+    use super::*;
+
+    /// Symbols in the right padding column of the table, next to its rows
+    fn scrollbar_column(app: &mut App<DataStoreType>, height: u16) -> Vec<String> {
+        let area = Rect::new(0, 0, 120, height);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        // Table starts below the header, its rows below own border and column titles
+        (5..height - 4)
+            .map(|y| buf.get(116, y).symbol().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn hides_scrollbar_when_entries_fit() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        let column = scrollbar_column(&mut app, 30);
+        assert!(column.iter().all(|symbol| symbol == " "));
+    }
+
+    #[test]
+    fn follows_cursor_when_entries_overflow() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        let column = scrollbar_column(&mut app, 12);
+        assert_eq!(column, vec!["█", "║", "║"]);
+
+        for _ in 0..6 {
+            app.on_cursor_down();
+        }
+        let column = scrollbar_column(&mut app, 12);
+        assert_eq!(column, vec!["║", "║", "█"]);
+    }
+}
@@ -0,0 +1,112 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use std::path::PathBuf;
+use wiper::app::App;
+use wiper::events::handle_key_events;
+use wiper::fs::{DataStoreType, TrashedEntry};
+use wiper::views::View;
+
+mod session_trash {
+    use super::*;
+
+    fn press(app: &mut App<DataStoreType>, c: char) {
+        handle_key_events(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE), app).unwrap();
+    }
+
+    fn render(app: &mut App<DataStoreType>) -> String {
+        let area = Rect::new(0, 0, 120, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content.iter().map(|cell| cell.symbol()).collect()
+    }
+
+    fn confirming_purge(app: &App<DataStoreType>) -> bool {
+        matches!(&app.view, View::Trash(view) if view.confirming_purge)
+    }
+
+    #[test]
+    fn keeps_permanent_deletions_out_of_session_trash() {
+        let postfix = "58";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+
+        app.on_cursor_down();
+        app.on_delete();
+        app.on_delete();
+        assert_eq!(app.session_deleted, 1);
+        assert!(app.session_trash.is_empty());
+
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn lists_trashed_entries_with_reclaimable_size() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        let root = app.root_path.clone();
+        app.session_trash = vec![
+            TrashedEntry::new(root.join("old_logs"), 2048),
+            TrashedEntry::new(root.join("cache.bin"), 1024),
+        ];
+
+        press(&mut app, 'T');
+        assert!(matches!(app.view, View::Trash(_)));
+        let screen = render(&mut app);
+        assert!(screen.contains("Moved to trash this session (2, 3.00 KB reclaimable)"));
+        assert!(screen.contains("old_logs"));
+        assert!(screen.contains("cache.bin"));
+
+        press(&mut app, 'T');
+        assert!(matches!(app.view, View::Folder));
+    }
+
+    #[test]
+    fn purges_only_after_second_press() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        // Never trashed, so nothing in the trash is touched
+        let path = PathBuf::from("/nonexistent/wiper_session_trash_test");
+        app.session_trash = vec![TrashedEntry::new(path, 10)];
+
+        press(&mut app, 'T');
+        press(&mut app, 'd');
+        assert!(confirming_purge(&app));
+        assert!(render(&mut app).contains("Press d again"));
+
+        press(&mut app, 'x');
+        assert!(!confirming_purge(&app));
+        assert_eq!(app.session_trash.len(), 1);
+
+        press(&mut app, 'd');
+        press(&mut app, 'd');
+        assert!(!confirming_purge(&app));
+        if cfg!(not(target_os = "macos")) {
+            assert!(app.session_trash.is_empty());
+        }
+    }
+
+    #[test]
+    fn finds_trash_item_deleted_closest_to_trashing() {
+        let path = PathBuf::from("/data/old_logs");
+        let other = PathBuf::from("/data/cache.bin");
+        let mut entry = TrashedEntry::new(path.clone(), 10);
+        entry.trashed_at = 1_000;
+        let items = [
+            (other.as_path(), 1_000),
+            (path.as_path(), 998),
+            (path.as_path(), 1_001),
+            // Trashed again later from outside the session
+            (path.as_path(), 1_600),
+        ];
+
+        assert_eq!(entry.find_in(items.into_iter()), Some(2));
+        assert_eq!(entry.find_in(items[3..].iter().copied()), None);
+        assert_eq!(entry.find_in(items[..1].iter().copied()), None);
+    }
+}
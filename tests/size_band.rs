@@ -0,0 +1,67 @@
+pub mod common;
+
+use wiper::app::App;
+use wiper::config::{parse_size, InitConfig};
+use wiper::fs::{DataStore, DataStoreType};
+
+use crate::common::*;
+
+mod size_band {
+    use super::*;
+
+    fn scanned_size(args: &[&str]) -> u64 {
+        let args = ["wiper"]
+            .iter()
+            .chain(args.iter())
+            .chain([TEST_FILE_PATH_VIEW].iter())
+            .map(|arg| arg.to_string());
+        let mut app: App<DataStoreType> = App::new(InitConfig::build(args).unwrap());
+        app.init();
+        handle_tasks_synchronously(&mut app);
+        get_current_folder(&app).unwrap().total_size()
+    }
+
+    #[test]
+    fn parses_sizes_with_binary_units() {
+        assert_eq!(parse_size("512"), Some(512));
+        assert_eq!(parse_size("1K"), Some(1024));
+        assert_eq!(parse_size("100MiB"), Some(100 * 1024 * 1024));
+        assert_eq!(parse_size("1.5g"), Some(1536 * 1024 * 1024));
+        assert_eq!(parse_size("10 parsecs"), None);
+        assert_eq!(parse_size(""), None);
+    }
+
+    #[test]
+    fn records_only_files_within_band() {
+        assert_eq!(scanned_size(&["--exclude-smaller-than", "1K"]), 11914);
+        assert_eq!(scanned_size(&["--exclude-larger-than", "2K"]), 9626);
+        assert_eq!(
+            scanned_size(&[
+                "--exclude-smaller-than",
+                "1K",
+                "--exclude-larger-than",
+                "2K"
+            ]),
+            6416
+        );
+    }
+
+    #[test]
+    fn still_descends_into_folders() {
+        let mut app: App<DataStoreType> = App::new(
+            InitConfig::build(
+                ["wiper", "--exclude-smaller-than", "1K", TEST_FILE_PATH_VIEW]
+                    .iter()
+                    .map(|arg| arg.to_string()),
+            )
+            .unwrap(),
+        );
+        app.init();
+        handle_tasks_synchronously(&mut app);
+
+        let path = app.store.get_current_path().join("a_folder");
+        let folder = app.store.get_folder(&path).unwrap();
+        assert_eq!(folder.entries().len(), 1);
+        assert_eq!(folder.total_size(), 0);
+    }
+}
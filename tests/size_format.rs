@@ -0,0 +1,76 @@
+pub mod common;
+
+use crate::common::*;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use wiper::app::App;
+use wiper::config::SizeUnit;
+use wiper::fs::DataStoreType;
+use wiper::ui::format_size;
+
+mod size_format {
+    use super::*;
+
+    const WIDTH: u16 = 140;
+
+    fn render_lines(app: &mut App<DataStoreType>) -> Vec<String> {
+        let area = Rect::new(0, 0, WIDTH, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content
+            .chunks(WIDTH as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect())
+            .collect()
+    }
+
+    /// Column right after the size shown on the line of entry
+    fn size_end(lines: &[String], title: &str, size: &str) -> usize {
+        let line = lines.iter().find(|line| line.contains(title)).unwrap();
+        line[..line.find(size).unwrap() + size.len()]
+            .chars()
+            .count()
+    }
+
+    #[test]
+    fn formats_in_fixed_unit() {
+        assert_eq!(format_size(459, SizeUnit::KB), "0.45 KB");
+        assert_eq!(format_size(5 << 20, SizeUnit::MB), "5.00 MB");
+        assert_eq!(format_size(1 << 20, SizeUnit::GB), "0.00 GB");
+        assert_eq!(format_size(459, SizeUnit::Auto), "459 bytes");
+    }
+
+    #[test]
+    fn shows_sizes_in_chosen_unit() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        app.ui_config.size_unit = SizeUnit::KB;
+        handle_tasks_synchronously(&mut app);
+        let screen = render_lines(&mut app).concat();
+        assert!(screen.contains("0.45 KB"));
+        assert!(screen.contains("5.82 KB"));
+        assert!(!screen.contains("bytes"));
+    }
+
+    #[test]
+    fn right_aligns_sizes() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        let lines = render_lines(&mut app);
+        assert_ne!(
+            size_end(&lines, "a_root_file.txt", "459 bytes"),
+            size_end(&lines, "b_folder", "5.82 KB")
+        );
+
+        app.ui_config.size_unit = SizeUnit::KB;
+        app.ui_config.align_sizes = true;
+        let lines = render_lines(&mut app);
+        assert_eq!(
+            size_end(&lines, "a_root_file.txt", "0.45 KB"),
+            size_end(&lines, "b_folder", "5.82 KB")
+        );
+        assert_eq!(
+            size_end(&lines, "a_root_file.txt", "0.45 KB"),
+            size_end(&lines, "Name", "Size")
+        );
+    }
+}
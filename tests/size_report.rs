@@ -0,0 +1,118 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use wiper::app::{App, Launch};
+use wiper::config::SizeUnit;
+use wiper::events::handle_key_events;
+use wiper::fs::{DataStore, DataStoreType};
+use wiper::report::SelectionReport;
+
+mod size_report {
+    use super::*;
+
+    /// View fixture with its three folders marked, folders are listed first
+    fn setup_marked_app() -> App<DataStoreType> {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        app.on_cursor_down();
+        app.on_toggle_mark();
+        app.on_toggle_mark();
+        app.on_toggle_mark();
+        app
+    }
+
+    fn build_report(app: &mut App<DataStoreType>) -> SelectionReport {
+        let path = app.store.get_current_path().clone();
+        SelectionReport::build(get_current_folder(app).unwrap(), &path)
+    }
+
+    #[test]
+    fn lists_marked_entries_biggest_first() {
+        let mut app = setup_marked_app();
+        let report = build_report(&mut app);
+
+        assert_eq!(
+            report.entries,
+            vec![
+                ("b_folder/".to_string(), Some(5957)),
+                ("c_folder/".to_string(), Some(5957)),
+                ("a_folder/".to_string(), Some(1376)),
+            ]
+        );
+        assert_eq!(report.total(), 13290);
+    }
+
+    #[test]
+    fn reports_selected_entry_without_marks() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        app.on_cursor_down();
+
+        assert_eq!(
+            build_report(&mut app).entries,
+            vec![("a_folder/".to_string(), Some(1376))]
+        );
+
+        // Parent entry has nothing to report
+        app.on_cursor_up();
+        assert!(build_report(&mut app).entries.is_empty());
+    }
+
+    #[test]
+    fn aligns_table_columns() {
+        let mut app = setup_marked_app();
+        let table = build_report(&mut app).to_table(SizeUnit::Auto);
+        let lines: Vec<&str> = table.lines().collect();
+
+        let path = app.store.get_current_path().to_string_lossy().to_string();
+        assert_eq!(lines[0], path);
+        assert_eq!(
+            &lines[2..],
+            [
+                "| Name      |     Size |",
+                "|-----------|---------:|",
+                "| b_folder/ |  5.82 KB |",
+                "| c_folder/ |  5.82 KB |",
+                "| a_folder/ |  1.34 KB |",
+                "| Total (3) | 12.98 KB |",
+            ]
+        );
+    }
+
+    #[test]
+    fn uses_configured_size_unit() {
+        let mut app = setup_marked_app();
+        let line = build_report(&mut app).to_line(SizeUnit::KB);
+        assert!(
+            line.ends_with(
+                ": b_folder/ 5.82 KB, c_folder/ 5.82 KB, a_folder/ 1.34 KB, total 12.98 KB"
+            ),
+            "{line}"
+        );
+    }
+
+    /// The only test depending on `TERM`, so changing environment doesn't race
+    #[test]
+    fn copies_report_or_logs_it_without_clipboard() {
+        let mut app = setup_marked_app();
+        std::env::set_var("TERM", "xterm-256color");
+        handle_key_events(
+            KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE),
+            &mut app,
+        )
+        .unwrap();
+        assert_eq!(
+            app.launch.take(),
+            Some(Launch::Clipboard(
+                build_report(&mut app).to_table(SizeUnit::Auto)
+            ))
+        );
+
+        std::env::set_var("TERM", "dumb");
+        app.on_copy_size_report();
+        assert_eq!(app.launch, None);
+        let logged = &app.logger.messages.front().unwrap().message;
+        assert!(logged.contains("total 12.98 KB"), "{logged}");
+    }
+}
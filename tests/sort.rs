@@ -0,0 +1,195 @@
+use std::cmp::Ordering;
+use std::time::{Duration, UNIX_EPOCH};
+use wiper::fs::{compare_names, sort_entries, FolderEntry, FolderEntryType, SortBy};
+
+mod sort {
+    use super::*;
+
+    fn entry(kind: FolderEntryType, title: &str, size: Option<u64>) -> FolderEntry {
+        FolderEntry {
+            kind,
+            title: title.to_string(),
+            name: title.into(),
+            size,
+            is_loaded: true,
+            device: None,
+            allocated: None,
+            modified: None,
+            owner: None,
+            mode: None,
+        }
+    }
+
+    fn file(title: &str, size: u64) -> FolderEntry {
+        entry(FolderEntryType::File, title, Some(size))
+    }
+
+    fn folder(title: &str, size: u64) -> FolderEntry {
+        entry(FolderEntryType::Folder, title, Some(size))
+    }
+
+    fn parent() -> FolderEntry {
+        entry(FolderEntryType::Parent, "..", None)
+    }
+
+    fn sample() -> Vec<FolderEntry> {
+        vec![
+            file("b.txt", 10),
+            folder("c", 30),
+            parent(),
+            file("a.txt", 50),
+            folder("d", 20),
+        ]
+    }
+
+    fn sorted(
+        mut entries: Vec<FolderEntry>,
+        sort_by: SortBy,
+        descending: bool,
+        dirs_first: bool,
+    ) -> Vec<String> {
+        sort_entries(&mut entries, sort_by, descending, dirs_first);
+        entries.into_iter().map(|entry| entry.title).collect()
+    }
+
+    #[test]
+    fn sorts_by_title_with_folders_first() {
+        assert_eq!(
+            sorted(sample(), SortBy::Title, false, true),
+            vec!["..", "c", "d", "a.txt", "b.txt"]
+        );
+    }
+
+    #[test]
+    fn sorts_by_title_descending_keeping_groups() {
+        assert_eq!(
+            sorted(sample(), SortBy::Title, true, true),
+            vec!["..", "d", "c", "b.txt", "a.txt"]
+        );
+    }
+
+    #[test]
+    fn mixes_folders_and_files_unless_grouped() {
+        assert_eq!(
+            sorted(sample(), SortBy::Title, false, false),
+            vec!["..", "a.txt", "b.txt", "c", "d"]
+        );
+    }
+
+    #[test]
+    fn sorts_by_size_in_both_directions() {
+        assert_eq!(
+            sorted(sample(), SortBy::Size, true, false),
+            vec!["..", "a.txt", "c", "d", "b.txt"]
+        );
+        assert_eq!(
+            sorted(sample(), SortBy::Size, false, false),
+            vec!["..", "b.txt", "d", "c", "a.txt"]
+        );
+    }
+
+    #[test]
+    fn sorts_by_size_with_folders_first() {
+        assert_eq!(
+            sorted(sample(), SortBy::Size, true, true),
+            vec!["..", "c", "d", "a.txt", "b.txt"]
+        );
+    }
+
+    #[test]
+    fn puts_unknown_sizes_before_known_ones() {
+        let entries = vec![
+            file("known", 5),
+            entry(FolderEntryType::Folder, "loading_b", None),
+            entry(FolderEntryType::Folder, "loading_a", None),
+        ];
+        assert_eq!(
+            sorted(entries.clone(), SortBy::Size, true, false),
+            vec!["loading_a", "loading_b", "known"]
+        );
+        assert_eq!(
+            sorted(entries, SortBy::Size, false, false),
+            vec!["loading_a", "loading_b", "known"]
+        );
+    }
+
+    #[test]
+    fn breaks_size_ties_by_name() {
+        let entries = vec![file("b", 7), file("c", 7), file("a", 7)];
+        assert_eq!(
+            sorted(entries.clone(), SortBy::Size, true, false),
+            vec!["a", "b", "c"]
+        );
+        assert_eq!(
+            sorted(entries, SortBy::Size, false, false),
+            vec!["a", "b", "c"]
+        );
+    }
+
+    #[test]
+    fn sorts_by_modification_time_with_unknown_first() {
+        let at = |title: &str, secs: u64| FolderEntry {
+            modified: Some(UNIX_EPOCH + Duration::from_secs(secs)),
+            ..file(title, 1)
+        };
+        let entries = vec![at("old", 10), folder("d", 20), parent(), at("new", 30)];
+        assert_eq!(
+            sorted(entries.clone(), SortBy::Modified, true, false),
+            vec!["..", "d", "new", "old"]
+        );
+        assert_eq!(
+            sorted(entries, SortBy::Modified, false, false),
+            vec!["..", "d", "old", "new"]
+        );
+    }
+
+    #[test]
+    fn does_not_depend_on_input_order() {
+        for sort_by in [SortBy::Title, SortBy::Size, SortBy::Modified] {
+            for descending in [false, true] {
+                for dirs_first in [false, true] {
+                    let mut reversed = sample();
+                    reversed.reverse();
+                    assert_eq!(
+                        sorted(sample(), sort_by, descending, dirs_first),
+                        sorted(reversed, sort_by, descending, dirs_first)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn compares_numbers_in_names_by_value() {
+        let entries = vec![file("file10", 1), file("file2", 1), file("file1", 1)];
+        assert_eq!(
+            sorted(entries, SortBy::Title, false, true),
+            vec!["file1", "file2", "file10"]
+        );
+        assert_eq!(compare_names("v1.10", "v1.9"), Ordering::Greater);
+        assert_eq!(compare_names("file007", "file8"), Ordering::Less);
+    }
+
+    #[test]
+    fn ignores_letter_case_of_names() {
+        let entries = vec![file("cherry", 1), file("Banana", 1), file("apple", 1)];
+        assert_eq!(
+            sorted(entries, SortBy::Title, false, true),
+            vec!["apple", "Banana", "cherry"]
+        );
+    }
+
+    #[test]
+    fn tells_apart_names_equal_but_for_case_or_zeros() {
+        assert_eq!(compare_names("a", "A"), "a".cmp("A"));
+        assert_eq!(compare_names("A", "a"), "A".cmp("a"));
+        assert_eq!(compare_names("file01", "file1"), Ordering::Less);
+        assert_eq!(compare_names("same", "same"), Ordering::Equal);
+    }
+
+    #[test]
+    fn orders_shorter_prefix_first() {
+        assert_eq!(compare_names("abc", "abcd"), Ordering::Less);
+        assert_eq!(compare_names("", "a"), Ordering::Less);
+    }
+}
@@ -0,0 +1,110 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use wiper::app::App;
+use wiper::events::handle_key_events;
+use wiper::fs::{DataStoreType, FolderEntryType, SortBy};
+
+mod sort_menu {
+    use super::*;
+
+    fn press(app: &mut App<DataStoreType>, code: KeyCode) {
+        handle_key_events(KeyEvent::new(code, KeyModifiers::NONE), app).unwrap();
+    }
+
+    fn render(app: &mut App<DataStoreType>) -> String {
+        let area = Rect::new(0, 0, 120, 24);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content.iter().map(|cell| cell.symbol()).collect()
+    }
+
+    fn kinds(app: &App<DataStoreType>) -> Vec<FolderEntryType> {
+        get_current_folder(app)
+            .unwrap()
+            .entries()
+            .iter()
+            .map(|entry| entry.kind.clone())
+            .collect()
+    }
+
+    #[test]
+    fn lists_sort_keys_with_current_one_picked() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        press(&mut app, KeyCode::Char('s'));
+        let screen = render(&mut app);
+        assert!(screen.contains(" Sort by "));
+        assert!(screen.contains("(•) Name"));
+        assert!(screen.contains("( ) Size"));
+        assert!(screen.contains("( ) Entries"));
+        assert!(screen.contains("[ ] Reverse order"));
+        assert!(screen.contains("[ ] Folders first"));
+
+        // Closes without changing anything
+        press(&mut app, KeyCode::Char('z'));
+        press(&mut app, KeyCode::Esc);
+        assert!(app.sort_menu.is_none());
+        assert_eq!(app.ui_config.sort_by, SortBy::Title);
+    }
+
+    #[test]
+    fn applies_key_under_cursor_on_enter() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        press(&mut app, KeyCode::Char('s'));
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Enter);
+        assert!(app.sort_menu.is_none());
+        assert_eq!(app.ui_config.sort_by, SortBy::Size);
+        assert!(!app.ui_config.sort_reversed);
+    }
+
+    #[test]
+    fn toggles_direction_and_grouping() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        press(&mut app, KeyCode::Char('s'));
+        // Pick size, then both options below the keys
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Char(' '));
+        for _ in 0..3 {
+            press(&mut app, KeyCode::Down);
+        }
+        press(&mut app, KeyCode::Char(' '));
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Char(' '));
+        // Cursor can't move past the last option
+        press(&mut app, KeyCode::Down);
+        assert!(render(&mut app).contains("[x] Folders first"));
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.ui_config.sort_by, SortBy::Size);
+        assert!(app.ui_config.sort_reversed);
+        assert!(app.ui_config.folders_first);
+        render(&mut app);
+        // Smallest first, yet every folder comes before the files
+        assert_eq!(
+            kinds(&app),
+            vec![
+                FolderEntryType::Parent,
+                FolderEntryType::Folder,
+                FolderEntryType::Folder,
+                FolderEntryType::Folder,
+                FolderEntryType::File,
+                FolderEntryType::File,
+                FolderEntryType::File,
+            ]
+        );
+        let folder = get_current_folder(&app).unwrap();
+        assert_eq!(folder.entries()[1].title, "a_folder");
+        assert_eq!(folder.entries()[4].title, "a_root_file.txt");
+    }
+}
@@ -0,0 +1,143 @@
+pub mod common;
+
+use crate::common::*;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use wiper::app::App;
+use wiper::config::{BarStyle, InitConfig, SpaceScale};
+use wiper::fs::DataStoreType;
+use wiper::ui::format_percent;
+
+mod space_scale {
+    use super::*;
+
+    fn render(app: &mut App<DataStoreType>) -> String {
+        app.pre_render();
+        let area = Rect::new(0, 0, 140, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content.iter().map(|cell| cell.symbol()).collect()
+    }
+
+    #[test]
+    fn cycles_through_parent_root_and_disk() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        assert_eq!(app.ui_config.space_scale, SpaceScale::Parent);
+
+        app.on_cycle_space_scale();
+        app.pre_render();
+        assert_eq!(app.ui_config.space_scale, SpaceScale::Root);
+        assert_eq!(app.ui_config.space_total, Some(15124));
+
+        app.on_cycle_space_scale();
+        app.pre_render();
+        assert_eq!(app.ui_config.space_scale, SpaceScale::Disk);
+        if cfg!(unix) {
+            assert!(app.ui_config.space_total.unwrap() > 15124);
+        }
+
+        app.on_cycle_space_scale();
+        app.pre_render();
+        assert_eq!(app.ui_config.space_scale, SpaceScale::Parent);
+        assert_eq!(app.ui_config.space_total, None);
+    }
+
+    #[test]
+    fn scales_bars_to_root_inside_subfolder() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        app.on_cursor_down();
+        app.on_enter();
+        handle_tasks_synchronously(&mut app);
+
+        let content = render(&mut app);
+        assert!(content.contains("Space (of parent)"));
+        let parent_bars = content.matches('█').count();
+
+        app.on_cycle_space_scale();
+        let content = render(&mut app);
+        assert!(content.contains("Space (of root)"));
+        let root_bars = content.matches('█').count();
+        assert!(root_bars < parent_bars);
+    }
+
+    #[test]
+    fn draws_bars_of_configured_width_and_style() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        app.ui_config.space_width = 10;
+        let content = render(&mut app);
+        assert!(content.contains(&"█".repeat(10)));
+        assert!(!content.contains(&"█".repeat(11)));
+
+        app.ui_config.bar_style = BarStyle::Ascii;
+        let content = render(&mut app);
+        assert!(content.contains(&"#".repeat(10)));
+        // Borders are drawn with single blocks
+        assert!(!content.contains("██"));
+    }
+
+    #[test]
+    fn hides_space_column() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        app.ui_config.space_width = 0;
+        let content = render(&mut app);
+        assert!(!content.contains("Space (of"));
+        assert!(!content.contains("██"));
+    }
+
+    #[test]
+    fn parses_space_column_options() {
+        let build = |args: &[&str]| {
+            let args = ["wiper"].iter().chain(args).map(|arg| arg.to_string());
+            InitConfig::build(args.collect::<Vec<_>>().into_iter())
+        };
+        let config = build(&["--space-width", "20", "--bar-style", "ascii"]).unwrap();
+        assert_eq!(config.space_width, Some(20));
+        assert_eq!(config.bar_style, Some(BarStyle::Ascii));
+        assert_eq!(build(&["--no-space-column"]).unwrap().space_width, Some(0));
+        assert!(build(&["--bar-style", "dots"]).is_err());
+        assert!(build(&["--disk-percent"]).unwrap().disk_percent);
+    }
+
+    #[test]
+    fn shows_share_of_disk_only_when_asked() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        assert!(!render(&mut app).contains("% of disk"));
+        assert_eq!(app.ui_config.disk_capacity, None);
+
+        app.ui_config.show_disk_percent = true;
+        let content = render(&mut app);
+        if cfg!(unix) {
+            let capacity = app.ui_config.disk_capacity.unwrap();
+            assert!(content.contains("% of disk"));
+            assert!(content.contains(&format_percent(5957, capacity)));
+        }
+    }
+
+    #[test]
+    fn hides_share_of_disk_without_capacity() {
+        let mut app: App<DataStoreType> = setup_app_roots(&[
+            TEST_FILE_PATH_VIEW,
+            &format!("{}/a_folder", TEST_FILE_PATH_VIEW),
+        ]);
+        handle_tasks_synchronously(&mut app);
+        app.ui_config.show_disk_percent = true;
+
+        assert!(!render(&mut app).contains("% of disk"));
+        assert_eq!(app.ui_config.disk_capacity, None);
+    }
+
+    #[test]
+    fn formats_share_with_one_decimal() {
+        assert_eq!(format_percent(1, 4), "25.0%");
+        assert_eq!(format_percent(1, 3), "33.3%");
+        assert_eq!(format_percent(5, 0), "");
+    }
+}
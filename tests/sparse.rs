@@ -0,0 +1,62 @@
+pub mod common;
+
+use crate::common::*;
+use std::fs::{self, File};
+use std::io::Write;
+use wiper::app::App;
+use wiper::config::InitConfig;
+use wiper::fs::{DataStore, DataStoreType, FolderEntry};
+
+mod sparse {
+    use super::*;
+
+    const SIZE: u64 = 4 * 1024 * 1024;
+
+    fn get_entry(app: &App<DataStoreType>, title: &str) -> FolderEntry {
+        app.store
+            .get_current_folder()
+            .unwrap()
+            .entries()
+            .iter()
+            .find(|entry| entry.title == title)
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn detects_sparse_files_only() {
+        let postfix = "23";
+        let path = format!("{}_{}", TEST_FILE_PATH_EDIT, postfix);
+        fs::create_dir_all(&path).unwrap();
+        File::create(format!("{}/sparse.img", path))
+            .unwrap()
+            .set_len(SIZE)
+            .unwrap();
+        File::create(format!("{}/dense.bin", path))
+            .unwrap()
+            .write_all(&vec![1; SIZE as usize])
+            .unwrap();
+
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+
+        let sparse = get_entry(&app, "sparse.img");
+        let dense = get_entry(&app, "dense.bin");
+        assert_eq!(sparse.size, Some(SIZE));
+        assert!(sparse.allocated.unwrap() < SIZE);
+        assert!(sparse.is_sparse(app.ui_config.sparse_ratio.unwrap()));
+        assert!(!dense.is_sparse(app.ui_config.sparse_ratio.unwrap()));
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn configures_sparse_marker() {
+        let args = ["wiper", "--sparse-ratio", "10"].map(String::from);
+        let app: App<DataStoreType> = App::new(InitConfig::build(args.into_iter()).unwrap());
+        assert_eq!(app.ui_config.sparse_ratio, Some(10.0));
+
+        let args = ["wiper", "--no-sparse-marker"].map(String::from);
+        let app: App<DataStoreType> = App::new(InitConfig::build(args.into_iter()).unwrap());
+        assert_eq!(app.ui_config.sparse_ratio, None);
+    }
+}
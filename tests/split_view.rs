@@ -0,0 +1,95 @@
+pub mod common;
+
+use crate::common::*;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Modifier;
+use ratatui::widgets::Widget;
+use wiper::app::App;
+use wiper::fs::DataStoreType;
+use wiper::ui::format_file_size;
+
+mod split_view {
+    use super::*;
+
+    const WIDTH: u16 = 160;
+
+    fn render(app: &mut App<DataStoreType>) -> Buffer {
+        let area = Rect::new(0, 0, WIDTH, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf
+    }
+
+    /// Line of buffer and column where text starts in the left half, the Before pane
+    fn find_in_before_pane(buf: &Buffer, text: &str) -> Option<(u16, u16)> {
+        (0..buf.area.height).find_map(|y| {
+            let line: String = (0..WIDTH / 2).map(|x| buf.get(x, y).symbol()).collect();
+            line.find(text).map(|start| {
+                let x = line[..start].chars().count() as u16;
+                (x, y)
+            })
+        })
+    }
+
+    #[test]
+    fn captures_folder_on_entry() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        let root = app.store.current_path.clone();
+        assert_eq!(app.entry_snapshot.as_ref().unwrap().0, root);
+        assert_eq!(app.entry_snapshot.as_ref().unwrap().1.total_size(), 15124);
+
+        app.on_cursor_down();
+        app.on_enter();
+        app.pre_render();
+        let (path, snapshot) = app.entry_snapshot.as_ref().unwrap();
+        assert_eq!(*path, root.join("a_folder"));
+        assert_eq!(snapshot.total_size(), 1376);
+    }
+
+    #[test]
+    fn shows_deleted_entries_struck_through_next_to_current_folder() {
+        let postfix = "42";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+        app.on_toggle_split_view();
+
+        app.on_cursor_down();
+        app.on_cursor_down();
+        let folder = get_current_folder(&app).unwrap();
+        let deleted = folder.get_selected_entry().clone();
+        app.on_delete();
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+        assert!(get_current_folder(&app)
+            .unwrap()
+            .entries()
+            .iter()
+            .all(|entry| entry.name != deleted.name));
+
+        let buf = render(&mut app);
+        let freed = format!("Before | freed {}", format_file_size(deleted.size.unwrap()));
+        assert!(find_in_before_pane(&buf, &freed).is_some());
+        let (x, y) = find_in_before_pane(&buf, &deleted.title).unwrap();
+        assert!(buf.get(x, y).modifier.contains(Modifier::CROSSED_OUT));
+
+        let kept = get_current_folder(&app).unwrap().entries()[2].title.clone();
+        let (x, y) = find_in_before_pane(&buf, &kept).unwrap();
+        assert!(!buf.get(x, y).modifier.contains(Modifier::CROSSED_OUT));
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn is_hidden_by_default() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        let buf = render(&mut app);
+        assert!(find_in_before_pane(&buf, "Before").is_none());
+
+        app.on_toggle_split_view();
+        let buf = render(&mut app);
+        assert!(find_in_before_pane(&buf, "Before | freed 0 bytes").is_some());
+    }
+}
@@ -0,0 +1,155 @@
+pub mod common;
+
+use crate::common::*;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
+use wiper::app::App;
+use wiper::fs::{DataStore, DataStoreType};
+
+mod stale {
+    use super::*;
+
+    fn edit_root(postfix: &str) -> PathBuf {
+        PathBuf::from(format!("{}_{}", TEST_FILE_PATH_EDIT, postfix))
+    }
+
+    /// Asks for deletion and waits for targets to be compared with disk
+    fn delete(app: &mut App<DataStoreType>) {
+        app.on_delete();
+        while app.change_check.is_some() {
+            thread::sleep(Duration::from_millis(1));
+            app.tick();
+        }
+    }
+
+    #[test]
+    fn warns_when_file_changed_since_scan() {
+        let postfix = "39";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+        app.pre_render();
+
+        fs::write(edit_root(postfix).join("file_to_delete_1.txt"), "shorter").unwrap();
+        app.on_cursor_down();
+        app.on_cursor_down();
+        delete(&mut app);
+
+        assert_eq!(
+            app.deletion_warnings,
+            vec!["file_to_delete_1.txt changed on disk since scan, rescan recommended (R)"]
+        );
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn warns_when_entry_is_gone_or_folder_contents_changed() {
+        let postfix = "40";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+        app.pre_render();
+
+        fs::write(
+            edit_root(postfix).join("folder_to_delete_1/new_file.txt"),
+            "new",
+        )
+        .unwrap();
+        fs::remove_file(edit_root(postfix).join("file_to_delete_2.txt")).unwrap();
+        app.on_cursor_down();
+        app.on_toggle_mark();
+        app.on_cursor_down();
+        app.on_toggle_mark();
+        delete(&mut app);
+
+        assert_eq!(
+            app.deletion_warnings,
+            vec![
+                "file_to_delete_2.txt no longer exists on disk",
+                "folder_to_delete_1 changed on disk since scan, rescan recommended (R)",
+            ]
+        );
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn does_not_warn_about_untouched_entries_or_after_rescan() {
+        let postfix = "41";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+        app.pre_render();
+
+        app.on_cursor_down();
+        delete(&mut app);
+        assert!(app.deletion_warnings.is_empty());
+
+        fs::write(
+            edit_root(postfix).join("folder_to_delete_1/new_file.txt"),
+            "new",
+        )
+        .unwrap();
+        app.on_rescan();
+        assert!(!app.ui_config.confirming_deletion);
+        handle_tasks_synchronously(&mut app);
+        delete(&mut app);
+        assert!(app.deletion_warnings.is_empty());
+        cleanup_testing_files(postfix);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn does_not_warn_about_hard_links_counted_elsewhere() {
+        let postfix = "80";
+        create_testing_files(postfix);
+        let root = edit_root(postfix);
+        fs::hard_link(
+            root.join("file_to_delete_1.txt"),
+            root.join("folder_to_delete_1/linked.txt"),
+        )
+        .unwrap();
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+        app.pre_render();
+
+        // Whichever path was counted first, the other one shows 0 bytes
+        let root = app.root_path.clone();
+        for name in ["file_to_delete_1.txt", "folder_to_delete_1"] {
+            let folder = app.store.get_folder_mut(&root).unwrap();
+            folder.select_name(name.as_ref());
+            app.on_toggle_mark();
+        }
+        delete(&mut app);
+
+        assert!(
+            app.deletion_warnings.is_empty(),
+            "{:?}",
+            app.deletion_warnings
+        );
+        cleanup_testing_files(postfix);
+    }
+
+    fn render(app: &mut App<DataStoreType>) -> String {
+        app.pre_render();
+        let area = Rect::new(0, 0, 120, 20);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        buf.content.iter().map(|cell| cell.symbol()).collect()
+    }
+
+    #[test]
+    fn marks_old_scan_in_header() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        app.wait_for_tasks();
+        assert!(app.scan_finished_at.is_some());
+        assert!(!render(&mut app).contains("scanned"));
+
+        app.scan_finished_at = Instant::now().checked_sub(Duration::from_secs(2 * 60 * 60));
+        assert!(render(&mut app).contains("| scanned 2h ago"));
+    }
+}
@@ -0,0 +1,72 @@
+pub mod common;
+
+use crate::common::*;
+use wiper::app::App;
+use wiper::config::PathStyle;
+use wiper::fs::{DataStore, DataStoreType};
+use wiper::report::Summary;
+
+mod summary {
+    use super::*;
+
+    fn build_summary() -> Summary {
+        build_summary_with(PathStyle::Absolute)
+    }
+
+    fn build_summary_with(paths: PathStyle) -> Summary {
+        let mut app: App<DataStoreType> = setup_app_view();
+        app.wait_for_tasks();
+        let root = app.store.get_current_path().clone();
+        Summary::build(&app.store, &root, paths)
+    }
+
+    #[test]
+    fn counts_whole_tree() {
+        let summary = build_summary();
+
+        assert_eq!(summary.total_size, 15124);
+        assert_eq!(summary.files, 11);
+        assert_eq!(summary.directories, 3);
+    }
+
+    #[test]
+    fn finds_largest_file() {
+        let summary = build_summary();
+
+        let largest = summary.largest_file.unwrap();
+        assert_eq!(largest.size, 2749);
+        assert!(largest.path.ends_with("b_folder/folder2_file3.txt"));
+    }
+
+    #[test]
+    fn writes_paths_relative_to_root() {
+        let summary = build_summary_with(PathStyle::Relative);
+
+        assert_eq!(summary.path, ".");
+        assert_eq!(
+            summary.largest_file.unwrap().path,
+            "b_folder/folder2_file3.txt"
+        );
+    }
+
+    #[test]
+    fn writes_parseable_lines() {
+        let mut output = vec![];
+        build_summary().write(&mut output, false).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.contains("total_size: 15124 (14.77 KB)\n"));
+        assert!(output.contains("files: 11\n"));
+        assert!(output.contains("directories: 3\n"));
+    }
+
+    #[test]
+    fn writes_json_object() {
+        let mut output = vec![];
+        build_summary().write(&mut output, true).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+        assert_eq!(value["total_size"], 15124);
+        assert_eq!(value["files"], 11);
+    }
+}
@@ -0,0 +1,100 @@
+pub mod common;
+
+use crate::common::*;
+use std::fs;
+use wiper::app::App;
+use wiper::fs::{DataStoreType, Folder, FolderEntry, FolderEntryType};
+
+mod total_size {
+    use super::*;
+
+    fn assert_cached_sizes_are_fresh(app: &App<DataStoreType>) {
+        for (path, folder) in app.store.store.iter() {
+            assert_eq!(
+                folder.total_size(),
+                folder.get_size(),
+                "stale size for {}",
+                path.to_string_lossy()
+            );
+        }
+    }
+
+    #[test]
+    fn matches_recomputation_after_scan() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        assert_cached_sizes_are_fresh(&app);
+        assert_eq!(get_current_folder(&app).unwrap().total_size(), 15124);
+    }
+
+    #[test]
+    fn matches_recomputation_after_mutations() {
+        let postfix = "18";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+
+        // Delete a file two levels down
+        app.on_cursor_down();
+        app.on_enter();
+        handle_tasks_synchronously(&mut app);
+        app.on_cursor_down();
+        app.on_enter();
+        handle_tasks_synchronously(&mut app);
+        app.on_cursor_down();
+        app.on_cursor_down();
+        app.on_delete();
+        app.on_delete();
+        assert_cached_sizes_are_fresh(&app);
+
+        // Delete marked entries one level up
+        app.on_backspace();
+        handle_tasks_synchronously(&mut app);
+        app.on_cursor_down();
+        app.on_cursor_down();
+        app.on_toggle_mark();
+        app.on_toggle_mark();
+        app.on_delete();
+        app.on_delete();
+        assert_cached_sizes_are_fresh(&app);
+
+        // Rescan after an outside change
+        let folder_path = format!("{}_{}", TEST_FILE_PATH_EDIT, postfix);
+        fs::write(format!("{}/a_new_file.txt", folder_path), "0123456789").unwrap();
+        app.on_backspace();
+        handle_tasks_synchronously(&mut app);
+        app.refresh_current_folder();
+        handle_tasks_synchronously(&mut app);
+        assert_cached_sizes_are_fresh(&app);
+
+        assert_eq!(
+            get_current_folder(&app).unwrap().total_size(),
+            (TEST_FILE_SIZE * 6 + 10) as u64
+        );
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn removes_every_row_down_to_none() {
+        let mut folder = Folder::new("rows".to_string());
+        let mut entry = folder.entries()[0].clone();
+        entry.kind = FolderEntryType::File;
+        entry.size = Some(10);
+        folder.push_entry(FolderEntry {
+            title: "file".to_string(),
+            name: "file".into(),
+            ..entry
+        });
+
+        folder.set_entry_size(1, Some(20));
+        assert_eq!(folder.total_size(), 20);
+        folder.remove_entry(1);
+        assert_eq!(folder.total_size(), 0);
+
+        // Folder left without any row, not even the parent one
+        folder.remove_entry(0);
+        assert!(folder.entries().is_empty());
+        assert_eq!(folder.cursor_index, 0);
+    }
+}
@@ -0,0 +1,95 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use wiper::app::App;
+use wiper::events::handle_mouse_events;
+use wiper::fs::DataStoreType;
+use wiper::ui::treemap::{render_treemap, squarify, TreemapRect};
+
+mod treemap {
+    use super::*;
+
+    const AREA: TreemapRect = TreemapRect {
+        x: 0.0,
+        y: 0.0,
+        width: 60.0,
+        height: 40.0,
+    };
+
+    #[test]
+    fn sizes_rectangles_by_value() {
+        let values = [600, 300, 200, 100, 100, 50, 50];
+        let total: u64 = values.iter().sum();
+        let rects = squarify(&values, AREA);
+
+        assert_eq!(rects.len(), values.len());
+        for (value, rect) in values.iter().zip(&rects) {
+            let expected = *value as f64 / total as f64 * AREA.area();
+            assert!((rect.area() - expected).abs() < 1e-6);
+            assert!(rect.x >= 0.0 && rect.x + rect.width <= AREA.width + 1e-6);
+            assert!(rect.y >= 0.0 && rect.y + rect.height <= AREA.height + 1e-6);
+        }
+    }
+
+    #[test]
+    fn keeps_rectangles_close_to_squares() {
+        let rects = squarify(&[100, 100, 100, 100], AREA);
+        for rect in rects {
+            let ratio = rect.width.max(rect.height) / rect.width.min(rect.height);
+            assert!(ratio < 2.0);
+        }
+    }
+
+    #[test]
+    fn lays_out_nothing_for_empty_values() {
+        assert!(squarify(&[], AREA).is_empty());
+        assert!(squarify(&[0, 0], AREA).is_empty());
+    }
+
+    #[test]
+    fn renders_entry_titles() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        let area = Rect::new(0, 0, 80, 20);
+        let mut buf = Buffer::empty(area);
+        let folder = get_current_folder(&app).unwrap();
+        render_treemap(area, &mut buf, folder, &app.ui_config);
+
+        let content: String = buf.content.iter().map(|cell| cell.symbol()).collect();
+        assert!(content.contains("b_folder"));
+        assert!(content.contains("a_folder"));
+    }
+
+    #[test]
+    fn selects_clicked_rectangle() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        app.on_toggle_treemap();
+
+        let area = Rect::new(0, 0, 100, 30);
+        let mut buf = Buffer::empty(area);
+        (&mut app).render(area, &mut buf);
+        let (x, y) = (0..area.height)
+            .find_map(|y| {
+                let line: String = (0..area.width).map(|x| buf.get(x, y).symbol()).collect();
+                let start = line.find("c_folder")?;
+                Some((line[..start].chars().count() as u16, y))
+            })
+            .expect("c_folder is drawn");
+
+        let click = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: x + 1,
+            row: y,
+            modifiers: KeyModifiers::NONE,
+        };
+        handle_mouse_events(click, &mut app).unwrap();
+        let folder = get_current_folder(&app).unwrap();
+        assert_eq!(folder.get_selected_entry().title, "c_folder");
+    }
+}
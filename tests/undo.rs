@@ -0,0 +1,165 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::path::{Path, PathBuf};
+use wiper::app::App;
+use wiper::events::handle_key_events;
+use wiper::fs::{DataStoreType, DeletionStep, TrashedEntry};
+
+mod undo {
+    use super::*;
+
+    fn press(app: &mut App<DataStoreType>, code: KeyCode, modifiers: KeyModifiers) {
+        handle_key_events(KeyEvent::new(code, modifiers), app).unwrap();
+    }
+
+    fn logged(app: &App<DataStoreType>, text: &str) -> bool {
+        app.logger
+            .messages
+            .iter()
+            .any(|message| message.message.contains(text))
+    }
+
+    fn current_titles(app: &App<DataStoreType>) -> Vec<String> {
+        get_current_folder(app)
+            .unwrap()
+            .entries()
+            .iter()
+            .map(|entry| entry.title.clone())
+            .collect()
+    }
+
+    fn selected_title(app: &App<DataStoreType>) -> String {
+        let folder = get_current_folder(app).unwrap();
+        folder.entries()[folder.cursor_index].title.clone()
+    }
+
+    fn delete_selected(app: &mut App<DataStoreType>) {
+        app.on_delete();
+        app.on_delete();
+    }
+
+    #[test]
+    fn undoes_and_redoes_trashed_deletions() {
+        let postfix = "61";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        app.ui_config.move_to_trash = true;
+        handle_tasks_synchronously(&mut app);
+        let root = app.root_path.clone();
+        let total = get_current_folder(&app).unwrap().total_size();
+
+        // Two deletions, the second entry takes place of the first one
+        app.on_cursor_down();
+        let first = selected_title(&app);
+        delete_selected(&mut app);
+        let second = selected_title(&app);
+        delete_selected(&mut app);
+        assert_eq!(app.undo_history.len(), 2);
+        assert!(!root.join(&first).exists());
+        assert!(!root.join(&second).exists());
+
+        press(&mut app, KeyCode::Char('U'), KeyModifiers::SHIFT);
+        handle_tasks_synchronously(&mut app);
+        assert!(root.join(&second).exists());
+        assert!(!root.join(&first).exists());
+        assert_eq!((app.undo_history.len(), app.redo_history.len()), (1, 1));
+        assert_eq!(app.session_deleted, 1);
+        assert_eq!(app.session_trash.len(), 1);
+
+        press(&mut app, KeyCode::Char('<'), KeyModifiers::NONE);
+        handle_tasks_synchronously(&mut app);
+        assert!(root.join(&first).exists());
+        assert!(app.undo_history.is_empty());
+        assert_eq!(app.redo_history.len(), 2);
+        assert_eq!(app.session_freed, 0);
+        assert_eq!(get_current_folder(&app).unwrap().total_size(), total);
+        assert!(current_titles(&app).contains(&first));
+
+        press(&mut app, KeyCode::Char('r'), KeyModifiers::CONTROL);
+        assert!(!root.join(&first).exists());
+        assert!(!current_titles(&app).contains(&first));
+        assert_eq!((app.undo_history.len(), app.redo_history.len()), (1, 1));
+
+        press(&mut app, KeyCode::Char('>'), KeyModifiers::NONE);
+        assert!(!root.join(&second).exists());
+        assert!(app.redo_history.is_empty());
+        assert_eq!(app.session_deleted, 2);
+
+        // Puts everything back, so the trash is left as it was
+        app.on_undo_all();
+        handle_tasks_synchronously(&mut app);
+        assert!(app.undo_history.is_empty());
+        assert!(root.join(&first).exists());
+        assert!(root.join(&second).exists());
+
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn new_deletion_drops_redo_history() {
+        let postfix = "62";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+        app.redo_history = vec![DeletionStep {
+            entries: vec![TrashedEntry::new(PathBuf::from("/nonexistent/a"), 1)],
+            permanent: false,
+        }];
+
+        app.on_cursor_down();
+        delete_selected(&mut app);
+        assert!(app.redo_history.is_empty());
+        assert_eq!(app.undo_history.len(), 1);
+        assert!(app.undo_history[0].permanent);
+
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn permanent_deletion_halts_undo() {
+        let postfix = "63";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+        app.on_cursor_down();
+        let path = Path::new(&app.root_path).join(selected_title(&app));
+        delete_selected(&mut app);
+        app.on_undo_all();
+        assert!(logged(&app, "Can't undo deletion"));
+        assert!(logged(&app, "was deleted permanently"));
+        assert!(!path.exists());
+        assert_eq!(app.undo_history.len(), 1);
+        assert!(app.redo_history.is_empty());
+
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn halts_when_entry_left_the_trash() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        let gone = TrashedEntry::new(PathBuf::from("/nonexistent/wiper_undo_test"), 10);
+        app.undo_history = vec![DeletionStep {
+            entries: vec![gone],
+            permanent: false,
+        }];
+
+        app.on_undo();
+        assert!(logged(&app, "Can't undo deletion"));
+        assert_eq!(app.undo_history.len(), 1);
+        assert!(app.redo_history.is_empty());
+    }
+
+    #[test]
+    fn reports_empty_history() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        app.on_undo();
+        assert!(logged(&app, "Nothing to undo"));
+        press(&mut app, KeyCode::Char('>'), KeyModifiers::NONE);
+        assert!(logged(&app, "Nothing to redo"));
+    }
+}
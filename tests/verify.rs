@@ -0,0 +1,248 @@
+pub mod common;
+
+use crate::common::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+use wiper::app::App;
+use wiper::events::handle_key_events;
+use wiper::fs::{
+    scan_folder_with, verify_tree_with, DataStore, DataStoreType, Discrepancy, HardLinks,
+    MemoryFileSystem, Mismatch, ScanOptions,
+};
+use wiper::views::View;
+
+mod verify {
+    use super::*;
+
+    fn press(app: &mut App<DataStoreType>, c: char) {
+        let modifiers = match c.is_uppercase() {
+            true => KeyModifiers::SHIFT,
+            false => KeyModifiers::NONE,
+        };
+        handle_key_events(KeyEvent::new(KeyCode::Char(c), modifiers), app).unwrap();
+    }
+
+    /// Starts verification and waits for it to read every folder
+    fn verify(app: &mut App<DataStoreType>) {
+        press(app, 'V');
+        assert!(app.verification.is_some());
+        while app.verification.is_some() {
+            thread::sleep(Duration::from_millis(1));
+            app.tick();
+        }
+    }
+
+    fn mismatches(app: &App<DataStoreType>) -> Vec<Mismatch> {
+        match &app.view {
+            View::Verify(view) => view.mismatches.clone(),
+            _ => panic!("verify view is not open"),
+        }
+    }
+
+    fn find<'a>(mismatches: &'a [Mismatch], path: &str) -> Option<&'a Mismatch> {
+        mismatches
+            .iter()
+            .find(|mismatch| mismatch.path.ends_with(path))
+    }
+
+    #[test]
+    fn finds_nothing_right_after_scan() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        verify(&mut app);
+        assert!(mismatches(&app).is_empty());
+        assert!(app.logger.messages[0].message.ends_with("matches disk"));
+
+        let area = Rect::new(0, 0, 120, 30);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        let text: String = buf.content.iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("Differences from disk (0)"));
+
+        press(&mut app, 'V');
+        assert!(matches!(app.view, View::Folder));
+    }
+
+    #[test]
+    fn stops_verifying_on_second_press() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        press(&mut app, 'V');
+        press(&mut app, 'V');
+        assert!(app.verification.is_none());
+        assert!(app.logger.messages[0]
+            .message
+            .starts_with("Stopped verifying"));
+        thread::sleep(Duration::from_millis(50));
+        app.tick();
+        assert!(matches!(app.view, View::Folder));
+    }
+
+    #[test]
+    fn shows_deletion_prompt_while_verifying() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        press(&mut app, 'V');
+        app.on_cursor_down();
+        app.on_delete();
+        assert!(app.ui_config.confirming_deletion);
+
+        let area = Rect::new(0, 0, 160, 30);
+        let mut buf = Buffer::empty(area);
+        app.render(area, &mut buf);
+        let text: String = buf.content.iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("Move a_folder"));
+        assert!(!text.contains("Verifying against disk"));
+        app.on_escape();
+    }
+
+    #[test]
+    fn keeps_differences_until_back_in_folder_view() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        press(&mut app, 'V');
+        app.on_toggle_errors_view();
+        let total = app.verification.as_ref().unwrap().total;
+        while *app.verification.as_ref().unwrap().read.lock().unwrap() < total {
+            thread::sleep(Duration::from_millis(1));
+        }
+        thread::sleep(Duration::from_millis(50));
+        app.tick();
+        assert!(matches!(app.view, View::Errors(_)));
+        assert!(app.verification.is_some());
+
+        app.on_toggle_errors_view();
+        app.tick();
+        assert!(mismatches(&app).is_empty());
+    }
+
+    #[test]
+    fn takes_sizes_of_hard_links_as_scanned() {
+        let mut fs = MemoryFileSystem::new();
+        fs.file("/root/a/data.bin", 100)
+            .hard_link("/root/b/data.bin", "/root/a/data.bin")
+            .file("/root/b/other.bin", 10);
+        let options = ScanOptions::default();
+        let mut hard_links = HardLinks::default();
+        scan_folder_with(&fs, "/root/a".into(), &options, &mut hard_links);
+        let (b, _) = scan_folder_with(&fs, "/root/b".into(), &options, &mut hard_links);
+        assert_eq!(b.total_size(), 10);
+
+        // Link in b was counted in a, verifying b alone reads it first
+        let scanned = HashMap::from([(PathBuf::from("/root/b"), b)]);
+        let found = verify_tree_with(&scanned, &fs, "/root/b".as_ref(), &options, || true);
+        assert!(found.is_empty(), "{:?}", found);
+    }
+
+    #[test]
+    fn finds_entries_changed_on_disk_and_resyncs() {
+        let postfix = "75";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+
+        let root = app.root_path.clone();
+        fs::write(root.join("new_file.txt"), "new").unwrap();
+        fs::remove_file(root.join("folder_to_delete_1/file_to_delete_1.txt")).unwrap();
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(root.join("file_to_delete_2.txt"))
+            .unwrap();
+        writeln!(file, "more").unwrap();
+
+        verify(&mut app);
+        let found = mismatches(&app);
+        assert_eq!(found.len(), 3);
+        assert_eq!(
+            find(&found, "new_file.txt").unwrap().discrepancy,
+            Discrepancy::Unlisted
+        );
+        assert_eq!(
+            find(&found, "folder_to_delete_1/file_to_delete_1.txt")
+                .unwrap()
+                .discrepancy,
+            Discrepancy::Missing
+        );
+        let size = TEST_FILE_SIZE as u64;
+        assert_eq!(
+            find(&found, "file_to_delete_2.txt").unwrap().discrepancy,
+            Discrepancy::Size {
+                recorded: size,
+                disk: size + 5
+            }
+        );
+
+        press(&mut app, 'R');
+        assert!(matches!(app.view, View::Folder));
+        handle_tasks_synchronously(&mut app);
+        verify(&mut app);
+        assert!(mismatches(&app).is_empty());
+
+        cleanup_testing_files(postfix);
+    }
+
+    #[test]
+    fn finds_folder_sizes_drifting_in_memory() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+
+        let root = app.root_path.clone();
+        let folder = app.store.get_folder_mut(&root).unwrap();
+        let index = folder
+            .entries()
+            .iter()
+            .position(|entry| entry.title == "a_folder")
+            .unwrap();
+        let size = folder.entries()[index].size.unwrap();
+        folder.set_entry_size(index, Some(size + 100));
+
+        verify(&mut app);
+        let found = mismatches(&app);
+        assert_eq!(
+            found,
+            [Mismatch {
+                path: root,
+                discrepancy: Discrepancy::Size {
+                    recorded: 15124 + 100,
+                    disk: 15124
+                }
+            }]
+        );
+    }
+
+    #[test]
+    fn reveals_selected_difference() {
+        let postfix = "76";
+        create_testing_files(postfix);
+        let mut app: App<DataStoreType> = setup_app_edit(postfix);
+        handle_tasks_synchronously(&mut app);
+
+        let root = app.root_path.clone();
+        fs::remove_file(root.join("folder_to_delete_1/file_to_delete_3.txt")).unwrap();
+        verify(&mut app);
+        assert_eq!(mismatches(&app).len(), 1);
+
+        handle_key_events(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE), &mut app).unwrap();
+        assert!(matches!(app.view, View::Folder));
+        assert_eq!(
+            app.store.get_current_path(),
+            &root.join("folder_to_delete_1")
+        );
+        let folder = get_current_folder(&app).unwrap();
+        assert_eq!(folder.get_selected_entry().title, "file_to_delete_3.txt");
+
+        cleanup_testing_files(postfix);
+    }
+}
@@ -0,0 +1,80 @@
+pub mod common;
+
+use crate::common::*;
+use wiper::app::App;
+use wiper::config::InitConfig;
+use wiper::fs::{DataStoreType, FolderEntryType};
+
+mod virtual_root {
+    use super::*;
+
+    fn setup() -> App<DataStoreType> {
+        setup_app_roots(&[
+            &format!("{}/b_folder", TEST_FILE_PATH_VIEW),
+            &format!("{}/a_folder", TEST_FILE_PATH_VIEW),
+            &format!("{}/a_root_file.txt", TEST_FILE_PATH_VIEW),
+            &format!("{}/missing_file.txt", TEST_FILE_PATH_VIEW),
+        ])
+    }
+
+    #[test]
+    fn parses_several_positional_paths_as_roots() {
+        let args = ["wiper", "one", "two"].iter().map(|arg| arg.to_string());
+        let config = InitConfig::build(args).unwrap();
+
+        assert_eq!(config.file_path, None);
+        assert_eq!(config.root_paths, vec!["one", "two"]);
+    }
+
+    #[test]
+    fn lists_valid_paths_and_skips_invalid_ones() {
+        let mut app = setup();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::Folder).len(), 2);
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::File).len(), 1);
+        assert!(get_current_folder(&app).unwrap().has_error);
+    }
+
+    #[test]
+    fn aggregates_sizes_of_scanned_roots() {
+        let mut app = setup();
+        handle_tasks_synchronously(&mut app);
+
+        let folder = get_current_folder(&app).unwrap();
+        assert_eq!(folder.get_size(), 1376 + 5957 + 459);
+        assert!(folder.entries().iter().all(|entry| entry.is_loaded));
+    }
+
+    #[test]
+    fn navigates_into_root_and_back_to_virtual_root() {
+        let mut app = setup();
+        handle_tasks_synchronously(&mut app);
+
+        app.on_cursor_down();
+        app.on_enter();
+        handle_tasks_synchronously(&mut app);
+        assert_parent_folder_a_state(&app);
+
+        app.on_backspace();
+        handle_tasks_synchronously(&mut app);
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::Folder).len(), 2);
+        assert_cursor_index(&app, 1);
+    }
+
+    #[test]
+    fn jumps_back_to_virtual_root() {
+        let mut app = setup();
+        handle_tasks_synchronously(&mut app);
+
+        app.on_cursor_down();
+        app.on_enter();
+        handle_tasks_synchronously(&mut app);
+        assert_parent_folder_a_state(&app);
+
+        app.on_go_to_root();
+        handle_tasks_synchronously(&mut app);
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::Folder).len(), 2);
+        assert_cursor_index(&app, 1);
+    }
+}